@@ -0,0 +1,106 @@
+//! Shared confidence clamping policy
+//!
+//! Components scale confidences by decay factors and heuristics, which can
+//! push values outside [0, 1] or drive them towards zero. Every confidence
+//! a component emits passes through [`clamp_confidence`] with the policy
+//! from its configuration, so downstream threshold checks always see a
+//! finite value inside the configured band.
+
+/// Default lower bound for emitted confidences
+pub const DEFAULT_CONFIDENCE_FLOOR: f32 = 0.01;
+
+/// Default upper bound for emitted confidences
+pub const DEFAULT_CONFIDENCE_CEILING: f32 = 1.0;
+
+/// Floor/ceiling band applied to every emitted confidence
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ConfidencePolicy {
+    pub floor: f32,
+    pub ceiling: f32,
+}
+
+impl Default for ConfidencePolicy {
+    fn default() -> Self {
+        Self {
+            floor: DEFAULT_CONFIDENCE_FLOOR,
+            ceiling: DEFAULT_CONFIDENCE_CEILING,
+        }
+    }
+}
+
+impl ConfidencePolicy {
+    /// Create a policy, rejecting bands outside [0, 1] or with floor > ceiling
+    pub fn new(floor: f32, ceiling: f32) -> Result<Self, String> {
+        if !(0.0..=1.0).contains(&floor) || !(0.0..=1.0).contains(&ceiling) {
+            return Err(format!(
+                "Invalid confidence band [{}, {}] (bounds must be 0.0-1.0)",
+                floor, ceiling
+            ));
+        }
+        if floor > ceiling {
+            return Err(format!(
+                "Invalid confidence band [{}, {}] (floor exceeds ceiling)",
+                floor, ceiling
+            ));
+        }
+        Ok(Self { floor, ceiling })
+    }
+
+    /// Clamp a confidence into the band
+    pub fn clamp(&self, confidence: f32) -> f32 {
+        clamp_confidence(confidence, self)
+    }
+}
+
+/// Clamp a confidence into the policy band; NaN maps to the floor
+pub fn clamp_confidence(confidence: f32, policy: &ConfidencePolicy) -> f32 {
+    if confidence.is_nan() {
+        return policy.floor;
+    }
+    confidence.clamp(policy.floor, policy.ceiling)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_out_of_range_values_are_clamped() {
+        let policy = ConfidencePolicy::default();
+        assert_eq!(policy.clamp(1.7), 1.0);
+        assert_eq!(policy.clamp(-0.2), DEFAULT_CONFIDENCE_FLOOR);
+        assert_eq!(policy.clamp(f32::NAN), DEFAULT_CONFIDENCE_FLOOR);
+        assert_eq!(policy.clamp(f32::INFINITY), 1.0);
+        assert_eq!(policy.clamp(0.42), 0.42);
+    }
+
+    #[test]
+    fn test_long_horizon_decay_stops_at_floor() {
+        // 10 Hz prediction over a 60 s horizon with 5% decay per step
+        let policy = ConfidencePolicy::new(0.05, 0.95).unwrap();
+        let mut confidence = policy.clamp(0.9);
+        for _ in 0..600 {
+            confidence = policy.clamp(confidence * 0.95);
+            assert!(confidence >= policy.floor && confidence <= policy.ceiling);
+        }
+        assert_eq!(confidence, 0.05);
+    }
+
+    #[test]
+    fn test_compounding_gain_stops_at_ceiling() {
+        let policy = ConfidencePolicy::default();
+        let mut confidence = 0.5;
+        for _ in 0..100 {
+            confidence = policy.clamp(confidence * 1.3 + 0.1);
+        }
+        assert_eq!(confidence, 1.0);
+    }
+
+    #[test]
+    fn test_invalid_bands_are_rejected() {
+        assert!(ConfidencePolicy::new(0.6, 0.4).is_err());
+        assert!(ConfidencePolicy::new(-0.1, 1.0).is_err());
+        assert!(ConfidencePolicy::new(0.0, 1.5).is_err());
+        assert!(ConfidencePolicy::new(0.5, 0.5).is_ok());
+    }
+}
@@ -0,0 +1,226 @@
+//! Shared component lifecycle state machine
+//!
+//! Every start/stop component routes its `initialize`, `start` and `stop`
+//! calls through [`Lifecycle::apply`] so the orchestrator sees the same
+//! transitions everywhere. Repeated `start` on an active component and
+//! `stop` on a component that is not running are defined no-ops; every
+//! other disallowed transition is an [`InvalidTransition`] error.
+//!
+//! The tracker also records when the component last did real work so the
+//! orchestrator can poll it as a heartbeat, independent of the health the
+//! component reports about itself.
+
+use std::fmt;
+
+/// Lifecycle state of a component
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum LifecycleState {
+    /// Not yet configured; `initialize` has not succeeded
+    Uninitialized,
+    /// Configured and ready to start
+    Inactive,
+    /// Running and accepting work
+    Active,
+    /// Faulted; `initialize` is required to recover
+    Error,
+}
+
+/// Lifecycle request issued by the host
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum LifecycleEvent {
+    Initialize,
+    Start,
+    Stop,
+    Fault,
+}
+
+/// Outcome of a permitted lifecycle request
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Transition {
+    /// The component moved to a new state
+    Changed(LifecycleState),
+    /// The component was already in the requested state
+    Unchanged,
+}
+
+/// A lifecycle request that is not allowed from the current state
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InvalidTransition {
+    pub component: &'static str,
+    pub from: LifecycleState,
+    pub event: LifecycleEvent,
+}
+
+impl fmt::Display for InvalidTransition {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "InvalidTransition: {} cannot {:?} while {:?}",
+            self.component, self.event, self.from
+        )
+    }
+}
+
+impl std::error::Error for InvalidTransition {}
+
+impl From<InvalidTransition> for String {
+    fn from(err: InvalidTransition) -> Self {
+        err.to_string()
+    }
+}
+
+/// Transition table shared by all components
+///
+/// | from          | initialize | start     | stop      | fault |
+/// |---------------|------------|-----------|-----------|-------|
+/// | Uninitialized | Inactive   | invalid   | no-op     | Error |
+/// | Inactive      | Inactive   | Active    | no-op     | Error |
+/// | Active        | invalid    | no-op     | Inactive  | Error |
+/// | Error         | Inactive   | invalid   | no-op     | no-op |
+pub fn next_state(from: LifecycleState, event: LifecycleEvent) -> Option<Transition> {
+    use LifecycleEvent::*;
+    use LifecycleState::*;
+
+    let to = match (from, event) {
+        (Active, Initialize) => return None,
+        (_, Initialize) => Inactive,
+        (Inactive, Start) => Active,
+        (Active, Start) => Active,
+        (Uninitialized | Error, Start) => return None,
+        (Active, Stop) => Inactive,
+        (_, Stop) => from,
+        (_, Fault) => Error,
+    };
+
+    Some(if to == from && event != Initialize {
+        Transition::Unchanged
+    } else {
+        Transition::Changed(to)
+    })
+}
+
+/// Lifecycle tracker embedded in each component's state
+#[derive(Debug, Clone)]
+pub struct Lifecycle {
+    component: &'static str,
+    state: LifecycleState,
+    last_activity_ms: Option<u64>,
+}
+
+impl Lifecycle {
+    /// Create a tracker for an uninitialized component
+    pub fn new(component: &'static str) -> Self {
+        Self {
+            component,
+            state: LifecycleState::Uninitialized,
+            last_activity_ms: None,
+        }
+    }
+
+    /// Current state
+    pub fn state(&self) -> LifecycleState {
+        self.state
+    }
+
+    /// Whether the component is running
+    pub fn is_active(&self) -> bool {
+        self.state == LifecycleState::Active
+    }
+
+    /// Record a processing call at `now_ms`
+    ///
+    /// Call this from every data-processing entry point, not from status or
+    /// health queries, so a stalled component stops advancing its heartbeat.
+    pub fn record_activity(&mut self, now_ms: u64) {
+        self.last_activity_ms = Some(self.last_activity_ms.map_or(now_ms, |last| last.max(now_ms)));
+    }
+
+    /// Timestamp of the last processing call, `None` before the first one
+    pub fn last_activity_ms(&self) -> Option<u64> {
+        self.last_activity_ms
+    }
+
+    /// Check a request against the transition table without applying it
+    pub fn check(&self, event: LifecycleEvent) -> Result<Transition, InvalidTransition> {
+        next_state(self.state, event).ok_or(InvalidTransition {
+            component: self.component,
+            from: self.state,
+            event,
+        })
+    }
+
+    /// Apply a request, returning whether the state changed
+    pub fn apply(&mut self, event: LifecycleEvent) -> Result<Transition, InvalidTransition> {
+        let transition = self.check(event)?;
+        if let Transition::Changed(to) = transition {
+            self.state = to;
+        }
+        Ok(transition)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_start_and_stop_are_idempotent() {
+        let mut lifecycle = Lifecycle::new("test");
+        lifecycle.apply(LifecycleEvent::Initialize).unwrap();
+
+        assert_eq!(lifecycle.apply(LifecycleEvent::Stop), Ok(Transition::Unchanged));
+        assert_eq!(
+            lifecycle.apply(LifecycleEvent::Start),
+            Ok(Transition::Changed(LifecycleState::Active))
+        );
+        assert_eq!(lifecycle.apply(LifecycleEvent::Start), Ok(Transition::Unchanged));
+        assert_eq!(
+            lifecycle.apply(LifecycleEvent::Stop),
+            Ok(Transition::Changed(LifecycleState::Inactive))
+        );
+        assert_eq!(lifecycle.apply(LifecycleEvent::Stop), Ok(Transition::Unchanged));
+    }
+
+    #[test]
+    fn test_invalid_transitions_are_reported() {
+        let mut lifecycle = Lifecycle::new("test");
+        let err = lifecycle.apply(LifecycleEvent::Start).unwrap_err();
+        assert_eq!(err.from, LifecycleState::Uninitialized);
+        assert!(err.to_string().starts_with("InvalidTransition"));
+
+        lifecycle.apply(LifecycleEvent::Initialize).unwrap();
+        lifecycle.apply(LifecycleEvent::Start).unwrap();
+        assert!(lifecycle.apply(LifecycleEvent::Initialize).is_err());
+        assert!(lifecycle.is_active());
+    }
+
+    #[test]
+    fn test_activity_timestamp_only_advances() {
+        let mut lifecycle = Lifecycle::new("test");
+        assert_eq!(lifecycle.last_activity_ms(), None);
+
+        lifecycle.record_activity(1_000);
+        lifecycle.record_activity(900);
+        assert_eq!(lifecycle.last_activity_ms(), Some(1_000));
+
+        lifecycle.apply(LifecycleEvent::Initialize).unwrap();
+        lifecycle.record_activity(1_200);
+        assert_eq!(lifecycle.last_activity_ms(), Some(1_200));
+    }
+
+    #[test]
+    fn test_fault_requires_reinitialize() {
+        let mut lifecycle = Lifecycle::new("test");
+        lifecycle.apply(LifecycleEvent::Initialize).unwrap();
+        lifecycle.apply(LifecycleEvent::Start).unwrap();
+        lifecycle.apply(LifecycleEvent::Fault).unwrap();
+
+        assert!(lifecycle.apply(LifecycleEvent::Start).is_err());
+        assert_eq!(lifecycle.apply(LifecycleEvent::Stop), Ok(Transition::Unchanged));
+        lifecycle.apply(LifecycleEvent::Initialize).unwrap();
+        assert_eq!(
+            lifecycle.apply(LifecycleEvent::Start),
+            Ok(Transition::Changed(LifecycleState::Active))
+        );
+    }
+}
@@ -0,0 +1,311 @@
+//! Shared pixel-format conversion for ADAS video consumers
+//!
+//! Producers may emit frames in any of the formats declared by the data-flow
+//! WIT interfaces. Every consumer normalizes incoming frames through this
+//! crate instead of assuming a layout, so a producer switching to RGBA or YUV
+//! is either converted correctly or rejected with an error - never silently
+//! misread.
+
+/// Pixel layouts understood by ADAS frame consumers
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum PixelFormat {
+    /// Packed 8-bit R, G, B
+    Rgb24,
+    /// Packed 8-bit B, G, R
+    Bgr24,
+    /// Packed 8-bit R, G, B, A
+    Rgba8,
+    /// Single 8-bit luma channel
+    Gray8,
+    /// Planar Y, then U and V at quarter resolution (I420)
+    Yuv420p,
+    /// Planar Y, then interleaved UV at quarter resolution
+    Nv12,
+    /// Packed Y0 U Y1 V, chroma at half horizontal resolution
+    Yuyv422,
+}
+
+impl PixelFormat {
+    /// All supported formats
+    pub const ALL: [PixelFormat; 7] = [
+        PixelFormat::Rgb24,
+        PixelFormat::Bgr24,
+        PixelFormat::Rgba8,
+        PixelFormat::Gray8,
+        PixelFormat::Yuv420p,
+        PixelFormat::Nv12,
+        PixelFormat::Yuyv422,
+    ];
+
+    /// Number of bytes in a frame of the given size
+    pub fn frame_size(&self, width: u32, height: u32) -> usize {
+        let (w, h) = (width as usize, height as usize);
+        let chroma = chroma_width(width) * chroma_height(height);
+        match self {
+            PixelFormat::Rgb24 | PixelFormat::Bgr24 => w * h * 3,
+            PixelFormat::Rgba8 => w * h * 4,
+            PixelFormat::Gray8 => w * h,
+            PixelFormat::Yuv420p | PixelFormat::Nv12 => w * h + 2 * chroma,
+            PixelFormat::Yuyv422 => chroma_width(width) * 4 * h,
+        }
+    }
+}
+
+fn chroma_width(width: u32) -> usize {
+    (width as usize).div_ceil(2)
+}
+
+fn chroma_height(height: u32) -> usize {
+    (height as usize).div_ceil(2)
+}
+
+/// Check that `data` holds exactly one frame of `format`
+pub fn validate_frame(data: &[u8], width: u32, height: u32, format: PixelFormat) -> Result<(), String> {
+    let expected = format.frame_size(width, height);
+    if data.len() != expected {
+        return Err(format!(
+            "Frame size mismatch for {:?} {}x{}: expected {} bytes, got {}",
+            format, width, height, expected, data.len()
+        ));
+    }
+    Ok(())
+}
+
+/// Convert a frame between pixel formats
+pub fn convert(data: &[u8], width: u32, height: u32, from: PixelFormat, to: PixelFormat) -> Result<Vec<u8>, String> {
+    validate_frame(data, width, height, from)?;
+
+    if from == to {
+        return Ok(data.to_vec());
+    }
+
+    let rgb = to_rgb24(data, width, height, from)?;
+    Ok(from_rgb24(&rgb, width, height, to))
+}
+
+/// Normalize any supported format to packed RGB24
+pub fn to_rgb24(data: &[u8], width: u32, height: u32, format: PixelFormat) -> Result<Vec<u8>, String> {
+    validate_frame(data, width, height, format)?;
+
+    let (w, h) = (width as usize, height as usize);
+    let mut rgb = Vec::with_capacity(w * h * 3);
+
+    match format {
+        PixelFormat::Rgb24 => rgb.extend_from_slice(data),
+        PixelFormat::Bgr24 => {
+            for px in data.chunks_exact(3) {
+                rgb.extend_from_slice(&[px[2], px[1], px[0]]);
+            }
+        }
+        PixelFormat::Rgba8 => {
+            for px in data.chunks_exact(4) {
+                rgb.extend_from_slice(&px[..3]);
+            }
+        }
+        PixelFormat::Gray8 => {
+            for &y in data {
+                rgb.extend_from_slice(&[y, y, y]);
+            }
+        }
+        PixelFormat::Yuv420p | PixelFormat::Nv12 => {
+            let (cw, ch) = (chroma_width(width), chroma_height(height));
+            let (y_plane, chroma) = data.split_at(w * h);
+            for row in 0..h {
+                for col in 0..w {
+                    let c = (row / 2) * cw + col / 2;
+                    let (u, v) = if format == PixelFormat::Yuv420p {
+                        (chroma[c], chroma[cw * ch + c])
+                    } else {
+                        (chroma[2 * c], chroma[2 * c + 1])
+                    };
+                    rgb.extend_from_slice(&yuv_to_rgb(y_plane[row * w + col], u, v));
+                }
+            }
+        }
+        PixelFormat::Yuyv422 => {
+            let stride = chroma_width(width) * 4;
+            for row in 0..h {
+                for col in 0..w {
+                    let pair = row * stride + (col / 2) * 4;
+                    let y = data[pair + if col % 2 == 0 { 0 } else { 2 }];
+                    rgb.extend_from_slice(&yuv_to_rgb(y, data[pair + 1], data[pair + 3]));
+                }
+            }
+        }
+    }
+
+    Ok(rgb)
+}
+
+/// Encode a packed RGB24 frame into another format
+pub fn from_rgb24(rgb: &[u8], width: u32, height: u32, format: PixelFormat) -> Vec<u8> {
+    let (w, h) = (width as usize, height as usize);
+    let pixel = |row: usize, col: usize| {
+        let i = (row * w + col) * 3;
+        [rgb[i], rgb[i + 1], rgb[i + 2]]
+    };
+
+    match format {
+        PixelFormat::Rgb24 => rgb.to_vec(),
+        PixelFormat::Bgr24 => rgb.chunks_exact(3).flat_map(|px| [px[2], px[1], px[0]]).collect(),
+        PixelFormat::Rgba8 => rgb.chunks_exact(3).flat_map(|px| [px[0], px[1], px[2], 255]).collect(),
+        PixelFormat::Gray8 => rgb.chunks_exact(3).map(|px| rgb_to_yuv(px[0], px[1], px[2])[0]).collect(),
+        PixelFormat::Yuv420p | PixelFormat::Nv12 => {
+            let (cw, ch) = (chroma_width(width), chroma_height(height));
+            let mut y_plane = Vec::with_capacity(w * h);
+            let mut u_plane = vec![0u8; cw * ch];
+            let mut v_plane = vec![0u8; cw * ch];
+
+            for row in 0..h {
+                for col in 0..w {
+                    let [r, g, b] = pixel(row, col);
+                    y_plane.push(rgb_to_yuv(r, g, b)[0]);
+                }
+            }
+
+            // Chroma is the average of each 2x2 block
+            for crow in 0..ch {
+                for ccol in 0..cw {
+                    let (mut u_sum, mut v_sum, mut n) = (0u32, 0u32, 0u32);
+                    for row in (crow * 2)..((crow * 2 + 2).min(h)) {
+                        for col in (ccol * 2)..((ccol * 2 + 2).min(w)) {
+                            let [r, g, b] = pixel(row, col);
+                            let [_, u, v] = rgb_to_yuv(r, g, b);
+                            u_sum += u as u32;
+                            v_sum += v as u32;
+                            n += 1;
+                        }
+                    }
+                    u_plane[crow * cw + ccol] = (u_sum / n) as u8;
+                    v_plane[crow * cw + ccol] = (v_sum / n) as u8;
+                }
+            }
+
+            let mut out = y_plane;
+            if format == PixelFormat::Yuv420p {
+                out.extend_from_slice(&u_plane);
+                out.extend_from_slice(&v_plane);
+            } else {
+                out.extend(u_plane.iter().zip(&v_plane).flat_map(|(&u, &v)| [u, v]));
+            }
+            out
+        }
+        PixelFormat::Yuyv422 => {
+            let mut out = Vec::with_capacity(format.frame_size(width, height));
+            for row in 0..h {
+                for pair in 0..chroma_width(width) {
+                    let col0 = pair * 2;
+                    let col1 = (col0 + 1).min(w - 1);
+                    let [r0, g0, b0] = pixel(row, col0);
+                    let [r1, g1, b1] = pixel(row, col1);
+                    let [y0, u0, v0] = rgb_to_yuv(r0, g0, b0);
+                    let [y1, u1, v1] = rgb_to_yuv(r1, g1, b1);
+                    let u = ((u0 as u16 + u1 as u16) / 2) as u8;
+                    let v = ((v0 as u16 + v1 as u16) / 2) as u8;
+                    out.extend_from_slice(&[y0, u, y1, v]);
+                }
+            }
+            out
+        }
+    }
+}
+
+/// BT.601 full-range RGB -> YUV
+fn rgb_to_yuv(r: u8, g: u8, b: u8) -> [u8; 3] {
+    let (r, g, b) = (r as f32, g as f32, b as f32);
+    let y = 0.299 * r + 0.587 * g + 0.114 * b;
+    let u = -0.168_736 * r - 0.331_264 * g + 0.5 * b + 128.0;
+    let v = 0.5 * r - 0.418_688 * g - 0.081_312 * b + 128.0;
+    [clamp_u8(y), clamp_u8(u), clamp_u8(v)]
+}
+
+/// BT.601 full-range YUV -> RGB
+fn yuv_to_rgb(y: u8, u: u8, v: u8) -> [u8; 3] {
+    let (y, u, v) = (y as f32, u as f32 - 128.0, v as f32 - 128.0);
+    [
+        clamp_u8(y + 1.402 * v),
+        clamp_u8(y - 0.344_136 * u - 0.714_136 * v),
+        clamp_u8(y + 1.772 * u),
+    ]
+}
+
+fn clamp_u8(value: f32) -> u8 {
+    value.round().clamp(0.0, 255.0) as u8
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Formats consumers normalize to: detector (RGB24) and renderer (RGBA8)
+    const CONSUMER_FORMATS: [PixelFormat; 2] = [PixelFormat::Rgb24, PixelFormat::Rgba8];
+
+    fn solid_rgb(width: u32, height: u32, color: [u8; 3]) -> Vec<u8> {
+        (0..width * height).flat_map(|_| color).collect()
+    }
+
+    #[test]
+    fn test_conformance_matrix() {
+        let color = [200, 60, 30];
+        for (width, height) in [(4, 4), (5, 3)] {
+            let rgb = solid_rgb(width, height, color);
+
+            for source in PixelFormat::ALL {
+                let frame = from_rgb24(&rgb, width, height, source);
+                assert_eq!(frame.len(), source.frame_size(width, height), "{:?}", source);
+
+                for consumer in CONSUMER_FORMATS {
+                    let out = convert(&frame, width, height, source, consumer).unwrap();
+                    assert_eq!(out.len(), consumer.frame_size(width, height));
+
+                    let channels = if consumer == PixelFormat::Rgba8 { 4 } else { 3 };
+                    // Gray loses chroma; compare against its luma instead
+                    let expected = if source == PixelFormat::Gray8 {
+                        let y = rgb_to_yuv(color[0], color[1], color[2])[0];
+                        [y, y, y]
+                    } else {
+                        color
+                    };
+                    for px in out.chunks_exact(channels) {
+                        for c in 0..3 {
+                            let diff = (px[c] as i16 - expected[c] as i16).abs();
+                            assert!(diff <= 3, "{:?} -> {:?}: {:?} vs {:?}", source, consumer, &px[..3], expected);
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_solid_color_plane_values() {
+        // BT.601 full range: Y = 98, U = 89, V = 200 for this colour
+        let rgb = solid_rgb(4, 2, [200, 60, 30]);
+
+        assert_eq!(from_rgb24(&rgb, 4, 2, PixelFormat::Gray8), vec![98; 8]);
+
+        let nv12 = from_rgb24(&rgb, 4, 2, PixelFormat::Nv12);
+        let (y_plane, uv_plane) = nv12.split_at(8);
+        assert_eq!(y_plane, &[98; 8]);
+        assert_eq!(uv_plane, &[89, 200, 89, 200]);
+
+        // Gray and white carry no chroma
+        assert_eq!(from_rgb24(&solid_rgb(2, 2, [255; 3]), 2, 2, PixelFormat::Nv12), vec![255, 255, 255, 255, 128, 128]);
+    }
+
+    #[test]
+    fn test_size_mismatch_is_error_not_panic() {
+        let data = vec![0u8; 10];
+        for format in PixelFormat::ALL {
+            for consumer in CONSUMER_FORMATS {
+                assert!(convert(&data, 4, 4, format, consumer).is_err());
+            }
+        }
+    }
+
+    #[test]
+    fn test_bgr_channel_order() {
+        let bgr = vec![10, 20, 30];
+        assert_eq!(to_rgb24(&bgr, 1, 1, PixelFormat::Bgr24).unwrap(), vec![30, 20, 10]);
+    }
+}
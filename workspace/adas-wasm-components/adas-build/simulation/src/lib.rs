@@ -0,0 +1,98 @@
+//! Seeded pseudo-random generation for simulated sensor data
+//!
+//! Components without real hardware behind them fabricate their readings.
+//! All of that variation comes from a [`SimRng`] seeded from the component
+//! configuration, so a given seed produces the same sequence of readings
+//! on every machine and every run, which golden-file tests rely on.
+
+/// Seed used when a configuration does not choose one
+pub const DEFAULT_SIMULATION_SEED: u64 = 0x5EED_ADA5;
+
+/// SplitMix64 generator; small, fast and identical on every platform
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SimRng {
+    state: u64,
+}
+
+impl SimRng {
+    pub fn new(seed: u64) -> Self {
+        Self { state: seed }
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+
+    /// Uniform value in [0, 1)
+    pub fn next_f32(&mut self) -> f32 {
+        // The top 24 bits fill the f32 mantissa exactly
+        (self.next_u64() >> 40) as f32 / (1u64 << 24) as f32
+    }
+
+    /// Uniform value in [low, high)
+    pub fn range(&mut self, low: f32, high: f32) -> f32 {
+        low + (high - low) * self.next_f32()
+    }
+
+    /// Uniform value within `amplitude` of `center`
+    pub fn jitter(&mut self, center: f32, amplitude: f32) -> f32 {
+        self.range(center - amplitude, center + amplitude)
+    }
+
+    /// Uniform integer in [0, n); 0 when `n` is 0
+    pub fn below(&mut self, n: u64) -> u64 {
+        if n == 0 {
+            0
+        } else {
+            self.next_u64() % n
+        }
+    }
+
+    /// True with probability `p`
+    pub fn chance(&mut self, p: f32) -> bool {
+        self.next_f32() < p
+    }
+}
+
+impl Default for SimRng {
+    fn default() -> Self {
+        Self::new(DEFAULT_SIMULATION_SEED)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_same_seed_same_sequence() {
+        let mut a = SimRng::new(42);
+        let mut b = SimRng::new(42);
+        let mut c = SimRng::new(43);
+        let sequence: Vec<u64> = (0..8).map(|_| a.next_u64()).collect();
+
+        assert_eq!(sequence, (0..8).map(|_| b.next_u64()).collect::<Vec<_>>());
+        assert_ne!(sequence, (0..8).map(|_| c.next_u64()).collect::<Vec<_>>());
+        // Pinned so the sequence cannot change silently across releases
+        assert_eq!(SimRng::new(0).next_u64(), 0xE220_A839_7B1D_CDAF);
+    }
+
+    #[test]
+    fn test_values_stay_in_range() {
+        let mut rng = SimRng::default();
+        for _ in 0..10_000 {
+            let unit = rng.next_f32();
+            assert!((0.0..1.0).contains(&unit));
+            let value = rng.jitter(50.0, 10.0);
+            assert!((40.0..60.0).contains(&value));
+            assert!(rng.below(5) < 5);
+        }
+        assert_eq!(rng.below(0), 0);
+        assert!(!rng.chance(0.0));
+        assert!(rng.chance(1.0));
+    }
+}
@@ -0,0 +1,93 @@
+//! ISO 26262 Automotive Safety Integrity Levels
+//!
+//! Components declare the level they are qualified for in
+//! `[package.metadata.adas]`; a composition is only as safe as the lowest
+//! level it instantiates.
+
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// Safety integrity level of a component, ordered from least to most stringent
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub enum AsilLevel {
+    /// Quality managed; no safety qualification
+    Qm,
+    A,
+    B,
+    C,
+    D,
+}
+
+impl std::str::FromStr for AsilLevel {
+    type Err = anyhow::Error;
+
+    /// Accepts `QM`, `B`, `ASIL-B`, `ASIL B` or `ASIL_B`, in any case
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let normalized = s.trim().to_uppercase();
+        let level = normalized
+            .strip_prefix("ASIL")
+            .map(|rest| rest.trim_start_matches(['-', '_', ' ']))
+            .unwrap_or(&normalized);
+        match level {
+            "QM" => Ok(AsilLevel::Qm),
+            "A" => Ok(AsilLevel::A),
+            "B" => Ok(AsilLevel::B),
+            "C" => Ok(AsilLevel::C),
+            "D" => Ok(AsilLevel::D),
+            _ => anyhow::bail!("Unknown ASIL level: {} (expected QM or ASIL-A to ASIL-D)", s),
+        }
+    }
+}
+
+impl fmt::Display for AsilLevel {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AsilLevel::Qm => write!(f, "QM"),
+            AsilLevel::A => write!(f, "ASIL-A"),
+            AsilLevel::B => write!(f, "ASIL-B"),
+            AsilLevel::C => write!(f, "ASIL-C"),
+            AsilLevel::D => write!(f, "ASIL-D"),
+        }
+    }
+}
+
+impl TryFrom<String> for AsilLevel {
+    type Error = anyhow::Error;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        value.parse()
+    }
+}
+
+impl From<AsilLevel> for String {
+    fn from(level: AsilLevel) -> Self {
+        level.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_and_order() {
+        for (text, level) in [
+            ("QM", AsilLevel::Qm),
+            ("qm", AsilLevel::Qm),
+            ("B", AsilLevel::B),
+            ("ASIL-B", AsilLevel::B),
+            ("asil d", AsilLevel::D),
+            ("ASIL_C", AsilLevel::C),
+        ] {
+            assert_eq!(text.parse::<AsilLevel>().unwrap(), level, "{}", text);
+        }
+        assert!("ASIL-E".parse::<AsilLevel>().is_err());
+        assert!("".parse::<AsilLevel>().is_err());
+
+        assert!(AsilLevel::Qm < AsilLevel::A);
+        assert!(AsilLevel::C < AsilLevel::D);
+        assert_eq!(AsilLevel::B.to_string(), "ASIL-B");
+        assert_eq!(AsilLevel::B.to_string().parse::<AsilLevel>().unwrap(), AsilLevel::B);
+    }
+}
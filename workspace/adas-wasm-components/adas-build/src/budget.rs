@@ -0,0 +1,201 @@
+//! Per-component resource budgets for embedded deployment
+//!
+//! Components declare a maximum artifact size and a maximum declared linear
+//! memory in `[package.metadata.adas]`. Built artifacts are measured against
+//! those budgets after the build and again before composition.
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Deserializer, Serialize};
+use std::collections::HashMap;
+use std::path::Path;
+use wasmparser::{Parser, Payload};
+
+use crate::component::ComponentMetadata;
+
+/// Default wasm page size (64 KiB)
+const DEFAULT_PAGE_SIZE_LOG2: u32 = 16;
+
+/// What happens when a component exceeds its budget
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum BudgetPolicy {
+    /// Log a warning and keep going
+    #[default]
+    Warn,
+    /// Fail the component build or the composition
+    Fail,
+}
+
+/// Measured resources of one built component against its budget
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ResourceUsage {
+    /// Artifact size in bytes
+    pub artifact_size: u64,
+
+    /// Declared artifact size budget in bytes
+    pub max_artifact_size: Option<u64>,
+
+    /// Linear memory declared by the artifact's modules, in bytes
+    pub linear_memory: u64,
+
+    /// Declared linear memory budget in bytes
+    pub max_linear_memory: Option<u64>,
+}
+
+impl ResourceUsage {
+    /// Measure an artifact against the budgets in `metadata`
+    pub fn measure(artifact: &Path, metadata: &ComponentMetadata) -> Result<Self> {
+        let bytes = std::fs::read(artifact).with_context(|| format!("Failed to read {}", artifact.display()))?;
+        let linear_memory = declared_linear_memory(&bytes)
+            .with_context(|| format!("Failed to parse {}", artifact.display()))?;
+
+        Ok(Self {
+            artifact_size: bytes.len() as u64,
+            max_artifact_size: metadata.max_artifact_size,
+            linear_memory,
+            max_linear_memory: metadata.max_linear_memory,
+        })
+    }
+
+    /// Human-readable description of every exceeded budget
+    pub fn violations(&self) -> Vec<String> {
+        let mut violations = Vec::new();
+
+        if let Some(max) = self.max_artifact_size.filter(|max| self.artifact_size > *max) {
+            violations.push(format!("artifact is {} bytes, budget {} bytes", self.artifact_size, max));
+        }
+        if let Some(max) = self.max_linear_memory.filter(|max| self.linear_memory > *max) {
+            violations.push(format!("declares {} bytes of linear memory, budget {} bytes", self.linear_memory, max));
+        }
+
+        violations
+    }
+
+    /// Whether every declared budget is met
+    pub fn within_budget(&self) -> bool {
+        self.violations().is_empty()
+    }
+}
+
+/// Total linear memory declared by the memories a binary defines
+///
+/// Core modules nested in a component are included. Each memory counts at
+/// its declared maximum, or at its initial size when it has no maximum.
+/// Imported memories are defined elsewhere and not counted twice.
+pub fn declared_linear_memory(bytes: &[u8]) -> Result<u64> {
+    let mut total = 0u64;
+
+    for payload in Parser::new(0).parse_all(bytes) {
+        if let Payload::MemorySection(reader) = payload? {
+            for memory in reader {
+                let memory = memory?;
+                let page_size = 1u64 << memory.page_size_log2.unwrap_or(DEFAULT_PAGE_SIZE_LOG2);
+                let pages = memory.maximum.unwrap_or(memory.initial);
+                total = total.saturating_add(pages.saturating_mul(page_size));
+            }
+        }
+    }
+
+    Ok(total)
+}
+
+/// Parse a size such as `4194304`, `"512KiB"`, `"64MiB"` or `"1GiB"`
+pub fn parse_size(value: &toml::Value) -> Result<u64> {
+    if let Some(bytes) = value.as_integer() {
+        return u64::try_from(bytes).context("Size must not be negative");
+    }
+
+    let text = value.as_str().context("Size must be an integer or a string")?.trim();
+    let split = text.find(|c: char| !c.is_ascii_digit()).unwrap_or(text.len());
+    let (number, unit) = text.split_at(split);
+    let number: u64 = number.parse().with_context(|| format!("Invalid size: {}", text))?;
+
+    let multiplier = match unit.trim() {
+        "" | "B" => 1,
+        "KiB" => 1 << 10,
+        "MiB" => 1 << 20,
+        "GiB" => 1 << 30,
+        other => anyhow::bail!("Unknown size unit '{}' in {}", other, text),
+    };
+
+    number.checked_mul(multiplier).with_context(|| format!("Size overflows: {}", text))
+}
+
+/// Deserialize a map of sizes, each given as accepted by [`parse_size`]
+pub(crate) fn deserialize_size_map<'de, D>(deserializer: D) -> Result<HashMap<String, u64>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    HashMap::<String, toml::Value>::deserialize(deserializer)?
+        .into_iter()
+        .map(|(name, value)| {
+            parse_size(&value)
+                .map(|size| (name, size))
+                .map_err(|e| serde::de::Error::custom(format!("{:#}", e)))
+        })
+        .collect()
+}
+
+/// Deserialize an optional size given as accepted by [`parse_size`]
+pub(crate) fn deserialize_optional_size<'de, D>(deserializer: D) -> Result<Option<u64>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    Option::<toml::Value>::deserialize(deserializer)?
+        .map(|value| parse_size(&value).map_err(|e| serde::de::Error::custom(format!("{:#}", e))))
+        .transpose()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use wasm_encoder::{MemorySection, MemoryType, Module};
+
+    fn module_with_memory(minimum: u64, maximum: Option<u64>) -> Vec<u8> {
+        let mut memories = MemorySection::new();
+        memories.memory(MemoryType {
+            minimum,
+            maximum,
+            memory64: false,
+            shared: false,
+            page_size_log2: None,
+        });
+
+        let mut module = Module::new();
+        module.section(&memories);
+        module.finish()
+    }
+
+    #[test]
+    fn test_declared_linear_memory() {
+        assert_eq!(declared_linear_memory(&module_with_memory(2, Some(16))).unwrap(), 16 * 65536);
+        assert_eq!(declared_linear_memory(&module_with_memory(17, None)).unwrap(), 17 * 65536);
+        assert_eq!(declared_linear_memory(&Module::new().finish()).unwrap(), 0);
+    }
+
+    #[test]
+    fn test_violations() {
+        let usage = ResourceUsage {
+            artifact_size: 2048,
+            max_artifact_size: Some(1024),
+            linear_memory: 65536,
+            max_linear_memory: Some(65536),
+        };
+        assert_eq!(usage.violations(), vec!["artifact is 2048 bytes, budget 1024 bytes".to_string()]);
+
+        let unbounded = ResourceUsage {
+            max_artifact_size: None,
+            ..usage
+        };
+        assert!(unbounded.within_budget());
+    }
+
+    #[test]
+    fn test_parse_size() {
+        assert_eq!(parse_size(&toml::Value::Integer(4096)).unwrap(), 4096);
+        assert_eq!(parse_size(&toml::Value::String("512KiB".into())).unwrap(), 512 * 1024);
+        assert_eq!(parse_size(&toml::Value::String("64 MiB".into())).unwrap(), 64 << 20);
+        assert!(parse_size(&toml::Value::String("64MB".into())).is_err());
+        assert!(parse_size(&toml::Value::Integer(-1)).is_err());
+    }
+}
@@ -0,0 +1,534 @@
+//! Component discovery and metadata
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use tracing::{debug, info, warn};
+
+use crate::asil::AsilLevel;
+use crate::budget::parse_size;
+use crate::config::BuildConfig;
+use crate::inventory::{strip_version, world_items, Direction};
+
+/// Component selection manifest at the workspace root
+pub const COMPONENTS_MANIFEST: &str = "adas-components.toml";
+
+/// Which component directories discovery considers (`adas-components.toml`)
+///
+/// Patterns are globs over directories relative to `components/`, e.g.
+/// `sensors/*` or `ai/experimental-*`.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ComponentFilter {
+    /// Directories to discover; empty means all
+    #[serde(default)]
+    pub include: Vec<String>,
+
+    /// Directories never discovered, even when included
+    #[serde(default)]
+    pub exclude: Vec<String>,
+}
+
+impl ComponentFilter {
+    /// Load the filter of a workspace; without a manifest every component is discovered
+    pub fn load(workspace_root: impl AsRef<Path>) -> Result<Self> {
+        let path = workspace_root.as_ref().join(COMPONENTS_MANIFEST);
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+
+        debug!("Loading component filter from {}", path.display());
+        let content = std::fs::read_to_string(&path).with_context(|| format!("Failed to read {}", path.display()))?;
+        toml::from_str(&content).with_context(|| format!("Failed to parse {}", path.display()))
+    }
+}
+
+/// Why discovery passed over a component directory
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Filtered {
+    NotIncluded,
+    Excluded,
+}
+
+/// Compiled [`ComponentFilter`]
+struct FilterPatterns {
+    include: Vec<glob::Pattern>,
+    exclude: Vec<glob::Pattern>,
+}
+
+impl FilterPatterns {
+    fn compile(filter: &ComponentFilter) -> Result<Self> {
+        let compile = |patterns: &[String]| -> Result<Vec<glob::Pattern>> {
+            patterns
+                .iter()
+                .map(|p| glob::Pattern::new(p).with_context(|| format!("Invalid component pattern: {}", p)))
+                .collect()
+        };
+        Ok(Self {
+            include: compile(&filter.include)?,
+            exclude: compile(&filter.exclude)?,
+        })
+    }
+
+    /// Reason to skip the directory at `relative` (below `components/`), if any
+    fn check(&self, relative: &str) -> Option<Filtered> {
+        if self.exclude.iter().any(|p| p.matches(relative)) {
+            Some(Filtered::Excluded)
+        } else if !self.include.is_empty() && !self.include.iter().any(|p| p.matches(relative)) {
+            Some(Filtered::NotIncluded)
+        } else {
+            None
+        }
+    }
+}
+
+/// Functional category of an ADAS component, derived from its directory
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ComponentCategory {
+    Sensors,
+    Ai,
+    Perception,
+    Fusion,
+    Control,
+    Input,
+    Integration,
+    Graphics,
+    System,
+    Orchestrator,
+}
+
+impl ComponentCategory {
+    /// All categories in pipeline order
+    pub const ALL: [ComponentCategory; 10] = [
+        ComponentCategory::Sensors,
+        ComponentCategory::Input,
+        ComponentCategory::Ai,
+        ComponentCategory::Perception,
+        ComponentCategory::Fusion,
+        ComponentCategory::Control,
+        ComponentCategory::Integration,
+        ComponentCategory::Graphics,
+        ComponentCategory::System,
+        ComponentCategory::Orchestrator,
+    ];
+
+    /// Directory name under `components/`
+    pub fn dir_name(&self) -> &'static str {
+        match self {
+            ComponentCategory::Sensors => "sensors",
+            ComponentCategory::Ai => "ai",
+            ComponentCategory::Perception => "perception",
+            ComponentCategory::Fusion => "fusion",
+            ComponentCategory::Control => "control",
+            ComponentCategory::Input => "input",
+            ComponentCategory::Integration => "integration",
+            ComponentCategory::Graphics => "graphics",
+            ComponentCategory::System => "system",
+            ComponentCategory::Orchestrator => "orchestrator",
+        }
+    }
+
+    /// Look up a category from its directory name
+    pub fn from_dir_name(name: &str) -> Option<Self> {
+        Self::ALL.iter().copied().find(|c| c.dir_name() == name)
+    }
+}
+
+/// Metadata read from a component's Cargo.toml and compiled WIT world
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ComponentMetadata {
+    /// Crate package name
+    pub package_name: String,
+
+    /// Crate version
+    pub version: String,
+
+    /// Crate description
+    pub description: Option<String>,
+
+    /// ASIL safety level (`[package.metadata.adas] safety-level`)
+    pub safety_level: Option<String>,
+
+    /// Parsed ASIL level (`[package.metadata.adas] asil-level`, else `safety-level`);
+    /// `None` for components that declare no level
+    #[serde(default)]
+    pub asil_level: Option<AsilLevel>,
+
+    /// Artifact size budget in bytes (`[package.metadata.adas] max-artifact-size`)
+    pub max_artifact_size: Option<u64>,
+
+    /// Declared linear memory budget in bytes (`[package.metadata.adas] max-linear-memory`)
+    pub max_linear_memory: Option<u64>,
+
+    /// Average latency budget for the post-build smoke test (`[package.metadata.adas] max-latency-ms`)
+    #[serde(default)]
+    pub max_latency_ms: Option<f32>,
+
+    /// Version of each exported interface (`[package.metadata.adas.interface-versions.exports]`)
+    #[serde(default)]
+    pub export_versions: BTreeMap<String, semver::Version>,
+
+    /// Version range required of each imported interface (`[package.metadata.adas.interface-versions.imports]`)
+    #[serde(default)]
+    pub import_requirements: BTreeMap<String, semver::VersionReq>,
+
+    /// WIT interfaces the compiled world imports, without version
+    #[serde(default)]
+    pub imports: Vec<String>,
+
+    /// WIT interfaces the compiled world exports, without version
+    #[serde(default)]
+    pub exports: Vec<String>,
+}
+
+/// A buildable ADAS component
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Component {
+    /// Component name (directory name, e.g. `sensor-fusion`)
+    pub name: String,
+
+    /// Component category
+    pub category: ComponentCategory,
+
+    /// Component crate directory
+    pub path: PathBuf,
+
+    /// Cargo metadata
+    pub metadata: ComponentMetadata,
+
+    /// Crate dependencies declared in Cargo.toml
+    pub dependencies: Vec<String>,
+
+    /// Whether the crate has a `tests/` directory or any unit tests
+    pub has_tests: bool,
+}
+
+impl Component {
+    /// Load a component from its crate directory
+    pub fn load(path: impl AsRef<Path>, category: ComponentCategory) -> Result<Self> {
+        let path = path.as_ref();
+        let manifest_path = path.join("Cargo.toml");
+
+        let content = std::fs::read_to_string(&manifest_path)
+            .with_context(|| format!("Failed to read {}", manifest_path.display()))?;
+        let manifest: toml::Value = toml::from_str(&content)
+            .with_context(|| format!("Failed to parse {}", manifest_path.display()))?;
+
+        let package = manifest.get("package");
+        let package_str = |key: &str| {
+            package
+                .and_then(|p| p.get(key))
+                .and_then(|v| v.as_str())
+                .map(str::to_string)
+        };
+
+        let adas = package
+            .and_then(|p| p.get("metadata"))
+            .and_then(|m| m.get("adas"));
+
+        let safety_level = adas
+            .and_then(|a| a.get("safety-level"))
+            .and_then(|v| v.as_str())
+            .map(str::to_string);
+
+        let asil_level = adas
+            .and_then(|a| a.get("asil-level").or_else(|| a.get("safety-level")))
+            .and_then(|v| v.as_str())
+            .map(|v| v.parse().with_context(|| format!("Invalid ASIL level in {}", manifest_path.display())))
+            .transpose()?;
+
+        let budget = |key: &str| -> Result<Option<u64>> {
+            adas.and_then(|a| a.get(key))
+                .map(|v| parse_size(v).with_context(|| format!("Invalid {} in {}", key, manifest_path.display())))
+                .transpose()
+        };
+
+        let max_latency_ms = adas
+            .and_then(|a| a.get("max-latency-ms"))
+            .map(|v| {
+                v.as_float()
+                    .or_else(|| v.as_integer().map(|ms| ms as f64))
+                    .map(|ms| ms as f32)
+                    .with_context(|| format!("Invalid max-latency-ms in {}", manifest_path.display()))
+            })
+            .transpose()?;
+
+        let interface_versions = adas.and_then(|a| a.get("interface-versions"));
+        let export_versions =
+            declared_versions(interface_versions, "exports", &manifest_path, semver::Version::parse)?;
+        let import_requirements =
+            declared_versions(interface_versions, "imports", &manifest_path, semver::VersionReq::parse)?;
+
+        let dependencies = manifest
+            .get("dependencies")
+            .and_then(|d| d.as_table())
+            .map(|table| table.keys().cloned().collect())
+            .unwrap_or_default();
+
+        let (mut imports, mut exports) = (Vec::new(), Vec::new());
+        for (direction, interface) in world_items(path)? {
+            match direction {
+                Direction::Import => imports.push(interface),
+                Direction::Export => exports.push(interface),
+            }
+        }
+
+        let name = path
+            .file_name()
+            .map(|n| n.to_string_lossy().to_string())
+            .unwrap_or_default();
+
+        Ok(Self {
+            name,
+            category,
+            path: path.to_path_buf(),
+            metadata: ComponentMetadata {
+                package_name: package_str("name").unwrap_or_default(),
+                version: package_str("version").unwrap_or_else(|| "0.0.0".to_string()),
+                description: package_str("description"),
+                safety_level,
+                asil_level,
+                max_artifact_size: budget("max-artifact-size")?,
+                max_linear_memory: budget("max-linear-memory")?,
+                max_latency_ms,
+                export_versions,
+                import_requirements,
+                imports,
+                exports,
+            },
+            dependencies,
+            has_tests: detect_tests(path),
+        })
+    }
+
+    /// Directory holding the component's WIT files
+    pub fn wit_dir(&self) -> PathBuf {
+        self.path.join("wit")
+    }
+}
+
+/// Interface versions declared in one direction of `[package.metadata.adas.interface-versions]`
+///
+/// Keys are interface names; a version suffix on a key is ignored.
+fn declared_versions<T>(
+    interface_versions: Option<&toml::Value>,
+    direction: &str,
+    manifest_path: &Path,
+    parse: impl Fn(&str) -> std::result::Result<T, semver::Error>,
+) -> Result<BTreeMap<String, T>> {
+    let Some(table) = interface_versions.and_then(|v| v.get(direction)) else {
+        return Ok(BTreeMap::new());
+    };
+    let table = table
+        .as_table()
+        .with_context(|| format!("interface-versions.{} in {} is not a table", direction, manifest_path.display()))?;
+
+    table
+        .iter()
+        .map(|(interface, version)| {
+            let version = version.as_str().and_then(|v| parse(v).ok()).with_context(|| {
+                format!("Invalid version of {} in {}: {}", interface, manifest_path.display(), version)
+            })?;
+            Ok((strip_version(interface).to_string(), version))
+        })
+        .collect()
+}
+
+/// Whether a crate has integration tests or `#[cfg(test)]`/`#[test]` items
+fn detect_tests(path: &Path) -> bool {
+    if path.join("tests").is_dir() {
+        return true;
+    }
+
+    walkdir::WalkDir::new(path.join("src"))
+        .into_iter()
+        .filter_map(Result::ok)
+        .filter(|entry| entry.path().extension().map_or(false, |ext| ext == "rs"))
+        .any(|entry| {
+            std::fs::read_to_string(entry.path())
+                .map(|source| source.contains("#[cfg(test)]") || source.contains("#[test]"))
+                .unwrap_or(false)
+        })
+}
+
+/// Discover all components under the configured components directory
+///
+/// Components live either directly in a category directory
+/// (`components/orchestrator`) or one level below it
+/// (`components/sensors/camera-front`). Directories filtered out by the
+/// workspace's [`ComponentFilter`] are not loaded at all.
+pub fn discover_components(config: &BuildConfig) -> Result<Vec<Component>> {
+    let workspace_root = config.workspace_root.as_path();
+    let components_dir = config.components_path();
+    if !components_dir.is_dir() {
+        anyhow::bail!("Components directory not found: {}", components_dir.display());
+    }
+
+    let filter = FilterPatterns::compile(&ComponentFilter::load(workspace_root)?)?;
+    let (mut excluded, mut not_included) = (0, 0);
+    let mut admit = |path: &Path| {
+        let relative = path.strip_prefix(&components_dir).unwrap_or(path);
+        let relative = relative.to_string_lossy().replace('\\', "/");
+        match filter.check(&relative) {
+            Some(reason) => {
+                debug!("Filtered out {} ({:?})", relative, reason);
+                match reason {
+                    Filtered::Excluded => excluded += 1,
+                    Filtered::NotIncluded => not_included += 1,
+                }
+                false
+            }
+            None => true,
+        }
+    };
+
+    let mut components = Vec::new();
+
+    for category in ComponentCategory::ALL {
+        let category_dir = components_dir.join(category.dir_name());
+        if !category_dir.is_dir() {
+            continue;
+        }
+
+        if category_dir.join("Cargo.toml").exists() {
+            if admit(&category_dir) {
+                components.push(Component::load(&category_dir, category)?);
+            }
+            continue;
+        }
+
+        let mut entries: Vec<PathBuf> = std::fs::read_dir(&category_dir)
+            .with_context(|| format!("Failed to read {}", category_dir.display()))?
+            .filter_map(|entry| entry.ok().map(|e| e.path()))
+            .filter(|path| path.is_dir())
+            .collect();
+        entries.sort();
+
+        for entry in entries {
+            if !admit(&entry) {
+                continue;
+            }
+            if !entry.join("Cargo.toml").exists() {
+                warn!("Skipping {}: no Cargo.toml", entry.display());
+                continue;
+            }
+            debug!("Discovered component at {}", entry.display());
+            components.push(Component::load(&entry, category)?);
+        }
+    }
+
+    if excluded + not_included > 0 {
+        info!(
+            "Filtered out {} components per {}: {} excluded, {} not included",
+            excluded + not_included,
+            COMPONENTS_MANIFEST,
+            excluded,
+            not_included
+        );
+    }
+
+    Ok(components)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn config_for(root: &Path) -> BuildConfig {
+        BuildConfig { workspace_root: root.to_path_buf(), ..BuildConfig::default() }
+    }
+
+    fn write_component(root: &Path, relative: &str) {
+        let path = root.join("components").join(relative);
+        std::fs::create_dir_all(&path).unwrap();
+        let name = relative.rsplit('/').next().unwrap();
+        std::fs::write(path.join("Cargo.toml"), format!("[package]\nname = \"{}\"\n", name)).unwrap();
+    }
+
+    #[test]
+    fn test_discovery_honors_component_filter() {
+        let temp_dir = TempDir::new().unwrap();
+        let root = temp_dir.path();
+        write_component(root, "sensors/camera-front");
+        write_component(root, "sensors/radar-front");
+        write_component(root, "ai/object-detection");
+        write_component(root, "ai/experimental-planner");
+        // Excluded directories are never loaded, so a broken one is harmless
+        std::fs::create_dir_all(root.join("components/ai/experimental-broken")).unwrap();
+        std::fs::write(root.join("components/ai/experimental-broken/Cargo.toml"), "not toml [").unwrap();
+
+        std::fs::write(
+            root.join(COMPONENTS_MANIFEST),
+            "include = [\"sensors/*\", \"ai/*\"]\nexclude = [\"ai/experimental-*\", \"sensors/radar-*\"]\n",
+        )
+        .unwrap();
+        let names: Vec<String> = discover_components(&config_for(root)).unwrap().into_iter().map(|c| c.name).collect();
+        assert_eq!(names, vec!["camera-front", "object-detection"]);
+
+        std::fs::write(root.join(COMPONENTS_MANIFEST), "include = [\"sensors/*\"]\n").unwrap();
+        let names: Vec<String> = discover_components(&config_for(root)).unwrap().into_iter().map(|c| c.name).collect();
+        assert_eq!(names, vec!["camera-front", "radar-front"]);
+    }
+
+    #[test]
+    fn test_discovery_uses_configured_components_dir() {
+        let temp_dir = TempDir::new().unwrap();
+        let root = temp_dir.path();
+        write_component(root, "sensors/camera-front");
+        std::fs::rename(root.join("components"), root.join("modules")).unwrap();
+        write_component(root, "sensors/radar-front");
+        let lane_detection = root.join("modules/perception/lane-detection");
+        std::fs::create_dir_all(&lane_detection).unwrap();
+        std::fs::write(lane_detection.join("Cargo.toml"), "[package]\nname = \"lane-detection\"\n").unwrap();
+
+        let config = BuildConfig { components_dir: PathBuf::from("modules"), ..config_for(root) };
+        let components = discover_components(&config).unwrap();
+        let names: Vec<&str> = components.iter().map(|c| c.name.as_str()).collect();
+        assert_eq!(names, vec!["camera-front", "lane-detection"]);
+        assert_eq!(components[1].category, ComponentCategory::Perception);
+    }
+
+    #[test]
+    fn test_load_interface_versions() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("planning");
+        std::fs::create_dir_all(&path).unwrap();
+        std::fs::write(
+            path.join("Cargo.toml"),
+            r#"[package]
+name = "adas-planning"
+
+[package.metadata.adas.interface-versions.exports]
+"adas:control/planning@1.1.0" = "1.1.0"
+
+[package.metadata.adas.interface-versions.imports]
+"adas:data/perception-data" = "^2.0"
+"#,
+        )
+        .unwrap();
+
+        let component = Component::load(&path, ComponentCategory::Control).unwrap();
+        assert_eq!(
+            component.metadata.export_versions.get("adas:control/planning"),
+            Some(&semver::Version::new(1, 1, 0))
+        );
+        let required = &component.metadata.import_requirements["adas:data/perception-data"];
+        assert!(required.matches(&semver::Version::new(2, 3, 0)));
+        assert!(!required.matches(&semver::Version::new(1, 9, 0)));
+
+        std::fs::write(
+            path.join("Cargo.toml"),
+            r#"[package]
+name = "adas-planning"
+
+[package.metadata.adas.interface-versions.imports]
+"adas:data/perception-data" = "two"
+"#,
+        )
+        .unwrap();
+        let err = Component::load(&path, ComponentCategory::Control).unwrap_err();
+        assert!(format!("{:#}", err).contains("Invalid version of adas:data/perception-data"));
+    }
+}
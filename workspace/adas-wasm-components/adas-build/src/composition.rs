@@ -0,0 +1,1281 @@
+//! WAC composition of built components into a single system component
+//!
+//! The composer generates a WAC script instantiating every built component,
+//! then runs `wac compose` with each component artifact registered as a
+//! `<name>:component` package, mirroring the hand-written `adas-*.wac` files.
+//! The effective WIT world of the result is decoded from the composed binary
+//! and written next to it, optionally along with a manifest recording which
+//! artifacts and wiring went into it.
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::{Path, PathBuf};
+use tracing::{debug, info, info_span, instrument, warn, Instrument};
+use wit_component::{DecodedWasm, WitPrinter};
+
+use crate::budget::{BudgetPolicy, ResourceUsage};
+use crate::component::Component;
+use crate::config::{BuildConfig, BuildProfile};
+use crate::inventory;
+use crate::pipeline::{artifact_path, check_budget, DEFAULT_TARGET};
+use crate::schedule::topological_order;
+
+/// Default package name of the composed system
+const DEFAULT_PACKAGE: &str = "adas:composed-system@0.1.0";
+
+/// Default manifest of named compositions, relative to the workspace root
+pub const COMPOSITION_MANIFEST: &str = "compositions.toml";
+
+/// Name and version of this tool, recorded in component-graph manifests
+const TOOL_NAME: &str = env!("CARGO_PKG_NAME");
+const TOOL_VERSION: &str = env!("CARGO_PKG_VERSION");
+
+/// Default configuration supplied to one component at instantiation
+///
+/// The provider is a pre-built component exporting the configuration
+/// interfaces with the shipped defaults baked in, e.g. a tuned
+/// `adas:object-detection/config`. Each listed interface the component's
+/// world imports is wired to the provider's export of the same name.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ComponentDefaults {
+    /// Provider component artifact, relative to the workspace root
+    pub provider: PathBuf,
+
+    /// Configuration interfaces the provider exports
+    pub interfaces: Vec<String>,
+}
+
+/// An import of a composed component that nothing in the composition satisfies
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct UnsatisfiedImport {
+    /// Importing component
+    pub component: String,
+
+    /// Imported interface, without version
+    pub interface: String,
+}
+
+/// Composition wiring that would leave component imports unsatisfied
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+#[error("Unsatisfied imports in composition: {}", describe_unsatisfied(.0))]
+pub struct UnsatisfiedImports(pub Vec<UnsatisfiedImport>);
+
+fn describe_unsatisfied(imports: &[UnsatisfiedImport]) -> String {
+    imports
+        .iter()
+        .map(|i| format!("{} imports {}", i.component, i.interface))
+        .collect::<Vec<_>>()
+        .join("; ")
+}
+
+/// An import wired to an exporter whose interface version it does not accept
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct VersionMismatch {
+    /// Importing component
+    pub importer: String,
+
+    /// Exporting component the import is wired to
+    pub exporter: String,
+
+    /// Interface, without version
+    pub interface: String,
+
+    /// Version range the importer requires
+    pub required: String,
+
+    /// Version the exporter provides
+    pub provided: String,
+}
+
+/// Composition wiring that would connect components with incompatible interface versions
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+#[error("Incompatible interface versions in composition: {}", describe_mismatches(.0))]
+pub struct IncompatibleInterfaces(pub Vec<VersionMismatch>);
+
+fn describe_mismatches(mismatches: &[VersionMismatch]) -> String {
+    mismatches
+        .iter()
+        .map(|m| {
+            format!(
+                "{} requires {} {} but {} exports {}",
+                m.importer, m.interface, m.required, m.exporter, m.provided
+            )
+        })
+        .collect::<Vec<_>>()
+        .join("; ")
+}
+
+/// A named subset of components composed together, e.g. `highway` or `urban`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct NamedComposition {
+    /// Package name of the composed component; defaults to the workspace package
+    #[serde(default)]
+    pub package: Option<String>,
+
+    /// Components to compose
+    pub components: Vec<String>,
+
+    /// Default configuration wired into the instances, overriding the workspace defaults
+    #[serde(default)]
+    pub defaults: BTreeMap<String, ComponentDefaults>,
+}
+
+/// Composition settings (`[composition]` in `adas-build.toml`)
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CompositionConfig {
+    /// Package name of the composed component
+    #[serde(default = "default_package")]
+    pub package: String,
+
+    /// Components to compose; empty composes every component with an artifact
+    #[serde(default)]
+    pub components: Vec<String>,
+
+    /// Profile whose artifacts are composed
+    #[serde(default = "default_profile")]
+    pub profile: BuildProfile,
+
+    /// Per-component default configuration, keyed by component name
+    #[serde(default)]
+    pub defaults: BTreeMap<String, ComponentDefaults>,
+
+    /// Interface prefixes provided by the host runtime rather than a component
+    #[serde(default = "default_host_interfaces")]
+    pub host_interfaces: Vec<String>,
+
+    /// Manifest of named compositions, relative to the workspace root
+    #[serde(default = "default_manifest")]
+    pub manifest: PathBuf,
+
+    /// Named compositions, read from the manifest
+    #[serde(skip)]
+    pub named: BTreeMap<String, NamedComposition>,
+
+    /// Write a component-graph manifest next to the output, see [`CompositionManifest`]
+    #[serde(default)]
+    pub write_manifest: bool,
+}
+
+fn default_package() -> String {
+    DEFAULT_PACKAGE.to_string()
+}
+
+fn default_profile() -> BuildProfile {
+    BuildProfile::Release
+}
+
+fn default_host_interfaces() -> Vec<String> {
+    vec!["wasi:".to_string(), "adas:orchestration/".to_string()]
+}
+
+fn default_manifest() -> PathBuf {
+    PathBuf::from(COMPOSITION_MANIFEST)
+}
+
+impl Default for CompositionConfig {
+    fn default() -> Self {
+        Self {
+            package: default_package(),
+            components: Vec::new(),
+            profile: default_profile(),
+            defaults: BTreeMap::new(),
+            host_interfaces: default_host_interfaces(),
+            manifest: default_manifest(),
+            named: BTreeMap::new(),
+            write_manifest: false,
+        }
+    }
+}
+
+impl CompositionConfig {
+    /// Composition settings from the workspace build configuration
+    ///
+    /// Named compositions are read from the manifest when it exists; each
+    /// top-level table of the manifest is one composition.
+    pub fn from_workspace(config: &BuildConfig) -> Result<Self> {
+        let mut composition = config.composition.clone();
+        let manifest = config.workspace_root.join(&composition.manifest);
+        if manifest.exists() {
+            debug!("Loading named compositions from {}", manifest.display());
+            let content = std::fs::read_to_string(&manifest)
+                .with_context(|| format!("Failed to read {}", manifest.display()))?;
+            composition.named =
+                toml::from_str(&content).with_context(|| format!("Failed to parse {}", manifest.display()))?;
+        }
+        Ok(composition)
+    }
+
+    /// Settings composing only the named composition `name`
+    ///
+    /// Fails with the list of available names if `name` is not defined.
+    pub fn named(&self, name: &str) -> Result<Self> {
+        let Some(named) = self.named.get(name) else {
+            let available: Vec<&str> = self.named.keys().map(String::as_str).collect();
+            anyhow::bail!(
+                "Unknown composition `{}`; available: {}",
+                name,
+                if available.is_empty() { "none".to_string() } else { available.join(", ") }
+            );
+        };
+
+        let mut defaults = self.defaults.clone();
+        defaults.extend(named.defaults.clone());
+        Ok(Self {
+            package: named.package.clone().unwrap_or_else(|| self.package.clone()),
+            components: named.components.clone(),
+            profile: self.profile,
+            defaults,
+            host_interfaces: self.host_interfaces.clone(),
+            manifest: self.manifest.clone(),
+            named: BTreeMap::new(),
+            write_manifest: self.write_manifest,
+        })
+    }
+}
+
+/// Outcome of a composition request
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum CompositionStatus {
+    /// `wac` ran and wrote a new output
+    Composed,
+    /// Inputs were unchanged and the existing output was kept
+    Cached,
+}
+
+/// Integration surface of a composed component
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ComposedWorld {
+    /// Imports left unsatisfied by the composition, e.g. `wasi:io/streams@0.2.0`
+    pub imports: Vec<String>,
+
+    /// Exports presented by the composed component
+    pub exports: Vec<String>,
+
+    /// The world and every package it references, as WIT source
+    pub wit: String,
+}
+
+impl ComposedWorld {
+    /// Decode the world of a component binary
+    pub fn from_component(bytes: &[u8]) -> Result<Self> {
+        let decoded = wit_component::decode(bytes).context("Failed to decode component")?;
+        let DecodedWasm::Component(resolve, world_id) = &decoded else {
+            anyhow::bail!("Binary is a WIT package, not a component");
+        };
+
+        let world = &resolve.worlds[*world_id];
+        let names = |items: &wit_parser::WorldItems| -> Vec<String> {
+            items.keys().map(|key| resolve.name_world_key(key)).collect()
+        };
+
+        let package = decoded.package();
+        let nested: Vec<_> = resolve.packages.iter().map(|(id, _)| id).filter(|id| *id != package).collect();
+        let wit = WitPrinter::default()
+            .print(resolve, package, &nested)
+            .context("Failed to print composed world")?;
+
+        Ok(Self {
+            imports: names(&world.imports),
+            exports: names(&world.exports),
+            wit,
+        })
+    }
+
+    /// Decode the world of a component file
+    pub fn from_file(path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref();
+        let bytes = std::fs::read(path).with_context(|| format!("Failed to read {}", path.display()))?;
+        Self::from_component(&bytes).with_context(|| format!("Failed to extract world from {}", path.display()))
+    }
+}
+
+/// What satisfies an import of a composed component
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "lowercase")]
+pub enum ImportSource {
+    /// Provided by the host runtime
+    Host,
+    /// The component's default-config provider
+    Defaults { provider: PathBuf },
+    /// Exported by another included component
+    Component { name: String },
+}
+
+/// One resolved import of a composed component
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct WiringLink {
+    /// Importing component
+    pub component: String,
+
+    /// Imported interface, without version
+    pub interface: String,
+
+    pub source: ImportSource,
+}
+
+/// An input artifact of a composition and its SHA-256
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ArtifactDigest {
+    /// Artifact path, relative to the workspace root when below it
+    pub path: PathBuf,
+
+    pub sha256: String,
+}
+
+/// A component included in a composition
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ManifestComponent {
+    pub name: String,
+
+    /// Crate version
+    pub version: String,
+
+    pub artifact: ArtifactDigest,
+
+    /// Default-config provider wired into the instance
+    pub defaults: Option<ArtifactDigest>,
+
+    /// Interfaces the component's world imports, without version
+    pub imports: Vec<String>,
+
+    /// Interfaces the component's world exports, without version
+    pub exports: Vec<String>,
+}
+
+/// Component graph of a composed artifact, written beside it as `.manifest.json`
+///
+/// Records the tool versions, every included artifact with its hash and the
+/// resolved import wiring, so two composed artifacts can be traced back to
+/// their inputs and diffed across builds.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CompositionManifest {
+    /// Tool that produced the composition and its version
+    pub tool: String,
+    pub tool_version: String,
+
+    /// `wac --version` output, when it could be determined
+    pub wac_version: Option<String>,
+
+    /// Package name of the composed component
+    pub package: String,
+
+    pub profile: BuildProfile,
+
+    pub target: String,
+
+    /// Fingerprint of the composition inputs, as in the `.fingerprint` file
+    pub fingerprint: String,
+
+    pub components: Vec<ManifestComponent>,
+
+    pub wiring: Vec<WiringLink>,
+}
+
+/// One component instance in the generated WAC script
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Instance {
+    /// Component name, also the WAC variable and export name
+    name: String,
+
+    /// Component artifact
+    artifact: PathBuf,
+
+    /// Default-config provider and the imports it satisfies
+    defaults: Option<(PathBuf, Vec<String>)>,
+}
+
+/// Composes built components with the `wac` CLI
+#[derive(Debug)]
+pub struct WacComposer {
+    /// Workspace root, used to resolve artifacts and providers
+    workspace_root: PathBuf,
+
+    /// Composition settings
+    config: CompositionConfig,
+
+    /// Reaction to a component exceeding its resource budget
+    budget_policy: BudgetPolicy,
+
+    /// Target triple whose artifacts are composed (the first configured target)
+    target: String,
+
+    /// Path of the `wac` executable
+    wac: PathBuf,
+}
+
+impl WacComposer {
+    /// Create a composer, failing if `wac` is not installed
+    pub fn new(build_config: &BuildConfig, config: CompositionConfig) -> Result<Self> {
+        let wac = which::which("wac").context("wac not found in PATH (cargo install wac-cli)")?;
+
+        Ok(Self {
+            workspace_root: build_config.workspace_root.clone(),
+            config,
+            budget_policy: build_config.budget_policy,
+            target: build_config.targets.first().cloned().unwrap_or_else(|| DEFAULT_TARGET.to_string()),
+            wac,
+        })
+    }
+
+    /// Compose `components` into a single component at `output_path`
+    ///
+    /// The generated script is written next to the output with a `.wac`
+    /// extension so the composition can be inspected or replayed. When the
+    /// output exists and neither the input artifacts nor the composition
+    /// settings changed since it was produced, `wac` is not run again and
+    /// [`CompositionStatus::Cached`] is returned; `force` always recomposes.
+    /// The composed world is written with a `.wit` extension, see
+    /// [`ComposedWorld`].
+    pub async fn compose(
+        &self,
+        components: &[Component],
+        output_path: impl AsRef<Path>,
+        force: bool,
+    ) -> Result<CompositionStatus> {
+        self.compose_with(&self.config, components, output_path.as_ref(), force).await
+    }
+
+    /// Compose the named composition `name` into `output_path`
+    ///
+    /// Behaves like [`compose`](Self::compose) restricted to the components
+    /// and instantiation defaults of the composition. Fails with the list of
+    /// available names if `name` is not defined.
+    pub async fn compose_named(
+        &self,
+        name: &str,
+        components: &[Component],
+        output_path: impl AsRef<Path>,
+        force: bool,
+    ) -> Result<CompositionStatus> {
+        let config = self.config.named(name)?;
+        info!("Composing `{}`: {}", name, config.components.join(", "));
+        self.compose_with(&config, components, output_path.as_ref(), force).await
+    }
+
+    #[instrument(name = "wac", skip_all, fields(package = %config.package, components = tracing::field::Empty))]
+    async fn compose_with(
+        &self,
+        config: &CompositionConfig,
+        components: &[Component],
+        output_path: &Path,
+        force: bool,
+    ) -> Result<CompositionStatus> {
+        let instances = self.instances(config, components)?;
+        if instances.is_empty() {
+            anyhow::bail!("No built components to compose");
+        }
+        let names: Vec<&str> = instances.iter().map(|i| i.name.as_str()).collect();
+        tracing::Span::current().record("components", tracing::field::display(names.join(",")));
+        let wiring = info_span!("resolve_wiring").in_scope(|| resolve_wiring(config, components, &instances))?;
+        check_interface_versions(components, &wiring)?;
+
+        let script = generate_wac(&config.package, &instances, &wiring)?;
+        let fingerprint = fingerprint(config, &script, &instances)?;
+        let fingerprint_path = output_path.with_extension("fingerprint");
+
+        if !force && output_path.exists() {
+            let previous = tokio::fs::read_to_string(&fingerprint_path).await.unwrap_or_default();
+            if previous.trim() == fingerprint {
+                info!("Composition inputs unchanged, keeping {}", output_path.display());
+                if !output_path.with_extension("wit").exists() {
+                    write_world(output_path).await?;
+                }
+                if config.write_manifest && !manifest_path(output_path).exists() {
+                    self.write_manifest(config, components, &instances, wiring, &fingerprint, output_path)
+                        .await?;
+                }
+                return Ok(CompositionStatus::Cached);
+            }
+        }
+
+        let script_path = output_path.with_extension("wac");
+        tokio::fs::write(&script_path, script)
+            .await
+            .with_context(|| format!("Failed to write {}", script_path.display()))?;
+
+        let mut command = tokio::process::Command::new(&self.wac);
+        command.arg("compose");
+        for instance in &instances {
+            command.arg("--dep").arg(dep_arg(&package_of(&instance.name), &instance.artifact));
+            if let Some((provider, _)) = &instance.defaults {
+                command.arg("--dep").arg(dep_arg(&defaults_package_of(&instance.name), provider));
+            }
+        }
+        command.arg("-o").arg(output_path).arg(&script_path);
+
+        debug!("Running {:?}", command);
+        let output = command
+            .output()
+            .instrument(info_span!("wac_compose"))
+            .await
+            .context("Failed to run wac compose")?;
+        if !output.status.success() {
+            anyhow::bail!("wac compose failed: {}", String::from_utf8_lossy(&output.stderr));
+        }
+
+        tokio::fs::write(&fingerprint_path, &fingerprint)
+            .await
+            .with_context(|| format!("Failed to write {}", fingerprint_path.display()))?;
+
+        let world = write_world(output_path).await?;
+        if config.write_manifest {
+            self.write_manifest(config, components, &instances, wiring, &fingerprint, output_path)
+                .await?;
+        }
+        info!(
+            "Composed {} components into {} ({} imports, {} exports)",
+            instances.len(),
+            output_path.display(),
+            world.imports.len(),
+            world.exports.len()
+        );
+        Ok(CompositionStatus::Composed)
+    }
+
+    /// Resolve the instances to compose and their default-config wiring
+    fn instances(&self, config: &CompositionConfig, components: &[Component]) -> Result<Vec<Instance>> {
+        let selected: Vec<&Component> = if config.components.is_empty() {
+            components.iter().collect()
+        } else {
+            config
+                .components
+                .iter()
+                .map(|name| {
+                    components
+                        .iter()
+                        .find(|c| &c.name == name)
+                        .with_context(|| format!("Unknown component in composition: {}", name))
+                })
+                .collect::<Result<_>>()?
+        };
+
+        let mut instances = Vec::new();
+        for component in selected {
+            let artifact = artifact_path(&self.workspace_root, component, &self.target, config.profile);
+            if !artifact.exists() {
+                if config.components.is_empty() {
+                    warn!("Skipping {}: no artifact at {}", component.name, artifact.display());
+                    continue;
+                }
+                anyhow::bail!("Component {} has not been built: {} missing", component.name, artifact.display());
+            }
+
+            let usage = ResourceUsage::measure(&artifact, &component.metadata)?;
+            if let Some(failure) = check_budget(&component.name, &usage, self.budget_policy) {
+                anyhow::bail!("Refusing to compose: {}", failure);
+            }
+
+            let defaults = match config.defaults.get(&component.name) {
+                Some(defaults) => {
+                    let imports: BTreeSet<String> = component.metadata.imports.iter().cloned().collect();
+                    let wired = supported_defaults(&component.name, defaults, &imports);
+                    (!wired.is_empty()).then(|| (self.workspace_root.join(&defaults.provider), wired))
+                }
+                None => None,
+            };
+
+            instances.push(Instance {
+                name: component.name.clone(),
+                artifact,
+                defaults,
+            });
+        }
+
+        Ok(instances)
+    }
+
+    /// Write the component-graph manifest of a composition beside its output
+    async fn write_manifest(
+        &self,
+        config: &CompositionConfig,
+        components: &[Component],
+        instances: &[Instance],
+        wiring: Vec<WiringLink>,
+        fingerprint: &str,
+        output_path: &Path,
+    ) -> Result<()> {
+        let mut manifest = CompositionManifest::new(
+            &self.workspace_root,
+            config,
+            &self.target,
+            components,
+            instances,
+            wiring,
+            fingerprint,
+        )?;
+        manifest.wac_version = self.wac_version().await;
+
+        let path = manifest_path(output_path);
+        let json = serde_json::to_string_pretty(&manifest).context("Failed to serialize composition manifest")?;
+        tokio::fs::write(&path, json)
+            .await
+            .with_context(|| format!("Failed to write {}", path.display()))?;
+        debug!("Wrote composition manifest {}", path.display());
+        Ok(())
+    }
+
+    /// Version reported by `wac --version`, if it runs
+    async fn wac_version(&self) -> Option<String> {
+        let output = tokio::process::Command::new(&self.wac).arg("--version").output().await.ok()?;
+        if !output.status.success() {
+            return None;
+        }
+        let version = String::from_utf8_lossy(&output.stdout).trim().to_string();
+        (!version.is_empty()).then_some(version)
+    }
+}
+
+impl CompositionManifest {
+    /// Manifest of the given instances, hashing every input artifact
+    ///
+    /// The wac version is left unset; the composer fills it in.
+    fn new(
+        workspace_root: &Path,
+        config: &CompositionConfig,
+        target: &str,
+        components: &[Component],
+        instances: &[Instance],
+        wiring: Vec<WiringLink>,
+        fingerprint: &str,
+    ) -> Result<Self> {
+        let components = instances
+            .iter()
+            .map(|instance| {
+                let component = components.iter().find(|c| c.name == instance.name);
+                Ok(ManifestComponent {
+                    name: instance.name.clone(),
+                    version: component.map(|c| c.metadata.version.clone()).unwrap_or_default(),
+                    artifact: ArtifactDigest::of(workspace_root, &instance.artifact)?,
+                    defaults: instance
+                        .defaults
+                        .as_ref()
+                        .map(|(provider, _)| ArtifactDigest::of(workspace_root, provider))
+                        .transpose()?,
+                    imports: component.map(|c| c.metadata.imports.clone()).unwrap_or_default(),
+                    exports: component.map(|c| c.metadata.exports.clone()).unwrap_or_default(),
+                })
+            })
+            .collect::<Result<_>>()?;
+
+        Ok(Self {
+            tool: TOOL_NAME.to_string(),
+            tool_version: TOOL_VERSION.to_string(),
+            wac_version: None,
+            package: config.package.clone(),
+            profile: config.profile,
+            target: target.to_string(),
+            fingerprint: fingerprint.to_string(),
+            components,
+            wiring,
+        })
+    }
+}
+
+impl ArtifactDigest {
+    fn of(workspace_root: &Path, path: &Path) -> Result<Self> {
+        let bytes = std::fs::read(path).with_context(|| format!("Failed to read {}", path.display()))?;
+        Ok(Self {
+            path: path.strip_prefix(workspace_root).unwrap_or(path).to_path_buf(),
+            sha256: format!("{:x}", Sha256::digest(&bytes)),
+        })
+    }
+}
+
+/// Component-graph manifest path of a composed output
+fn manifest_path(output_path: &Path) -> PathBuf {
+    output_path.with_extension("manifest.json")
+}
+
+/// Resolve every import of the included components before running `wac`
+///
+/// An import is satisfied by the host when it matches one of the configured
+/// host interface prefixes, by the component's default-config provider, or
+/// by another included component exporting it, checked in that order.
+fn resolve_wiring(
+    config: &CompositionConfig,
+    components: &[Component],
+    instances: &[Instance],
+) -> std::result::Result<Vec<WiringLink>, UnsatisfiedImports> {
+    let included: Vec<&Component> = instances
+        .iter()
+        .filter_map(|instance| components.iter().find(|c| c.name == instance.name))
+        .collect();
+
+    let mut links = Vec::new();
+    let mut unsatisfied = Vec::new();
+    for instance in instances {
+        let Some(component) = included.iter().find(|c| c.name == instance.name) else {
+            continue;
+        };
+        let from_defaults: BTreeSet<&str> = instance
+            .defaults
+            .iter()
+            .flat_map(|(_, interfaces)| interfaces.iter().map(|i| inventory::strip_version(i)))
+            .collect();
+
+        for interface in &component.metadata.imports {
+            let source = if config.host_interfaces.iter().any(|prefix| interface.starts_with(prefix.as_str())) {
+                Some(ImportSource::Host)
+            } else if from_defaults.contains(interface.as_str()) {
+                instance.defaults.as_ref().map(|(provider, _)| ImportSource::Defaults {
+                    provider: provider.clone(),
+                })
+            } else {
+                included
+                    .iter()
+                    .find(|other| other.name != component.name && other.metadata.exports.contains(interface))
+                    .map(|other| ImportSource::Component {
+                        name: other.name.clone(),
+                    })
+            };
+            match source {
+                Some(source) => links.push(WiringLink {
+                    component: component.name.clone(),
+                    interface: interface.clone(),
+                    source,
+                }),
+                None => unsatisfied.push(UnsatisfiedImport {
+                    component: component.name.clone(),
+                    interface: interface.clone(),
+                }),
+            }
+        }
+    }
+
+    if unsatisfied.is_empty() {
+        Ok(links)
+    } else {
+        Err(UnsatisfiedImports(unsatisfied))
+    }
+}
+
+/// Check every component-to-component link against the declared interface versions
+///
+/// A link is checked when the importer declares a version range for the
+/// interface; an exporter that declares no version for it is accepted with a
+/// warning. Host and default-config imports are not versioned here.
+fn check_interface_versions(
+    components: &[Component],
+    links: &[WiringLink],
+) -> std::result::Result<(), IncompatibleInterfaces> {
+    let mut mismatches = Vec::new();
+    for link in links {
+        let ImportSource::Component { name: exporter } = &link.source else {
+            continue;
+        };
+        let Some(importer) = components.iter().find(|c| c.name == link.component) else {
+            continue;
+        };
+        let Some(required) = importer.metadata.import_requirements.get(&link.interface) else {
+            continue;
+        };
+        let provided = components
+            .iter()
+            .find(|c| &c.name == exporter)
+            .and_then(|c| c.metadata.export_versions.get(&link.interface));
+        let Some(provided) = provided else {
+            warn!(
+                "{} requires {} {} but {} declares no version for it",
+                importer.name, link.interface, required, exporter
+            );
+            continue;
+        };
+
+        if !required.matches(provided) {
+            mismatches.push(VersionMismatch {
+                importer: importer.name.clone(),
+                exporter: exporter.clone(),
+                interface: link.interface.clone(),
+                required: required.to_string(),
+                provided: provided.to_string(),
+            });
+        }
+    }
+
+    if mismatches.is_empty() {
+        Ok(())
+    } else {
+        Err(IncompatibleInterfaces(mismatches))
+    }
+}
+
+/// Extract the world of a composed component and write it beside the output
+async fn write_world(output_path: &Path) -> Result<ComposedWorld> {
+    let world = ComposedWorld::from_file(output_path)?;
+    let world_path = output_path.with_extension("wit");
+    tokio::fs::write(&world_path, &world.wit)
+        .await
+        .with_context(|| format!("Failed to write {}", world_path.display()))?;
+    Ok(world)
+}
+
+/// Default-config interfaces the component's world actually imports
+///
+/// Components whose world lacks a configured interface cannot take it as an
+/// instantiation argument; those defaults are skipped with a warning.
+fn supported_defaults(component: &str, defaults: &ComponentDefaults, imports: &BTreeSet<String>) -> Vec<String> {
+    defaults
+        .interfaces
+        .iter()
+        .filter(|interface| {
+            let supported = imports.contains(inventory::strip_version(interface));
+            if !supported {
+                warn!("{} does not import {}; default configuration not wired", component, interface);
+            }
+            supported
+        })
+        .cloned()
+        .collect()
+}
+
+/// SHA-256 over the composition settings, generated script and every input artifact
+fn fingerprint(config: &CompositionConfig, script: &str, instances: &[Instance]) -> Result<String> {
+    let mut hasher = Sha256::new();
+    hasher.update(serde_json::to_vec(config).context("Failed to serialize composition config")?);
+    hasher.update(script.as_bytes());
+
+    for instance in instances {
+        let inputs = std::iter::once(&instance.artifact).chain(instance.defaults.as_ref().map(|(provider, _)| provider));
+        for path in inputs {
+            let bytes = std::fs::read(path).with_context(|| format!("Failed to read {}", path.display()))?;
+            hasher.update(path.to_string_lossy().as_bytes());
+            hasher.update((bytes.len() as u64).to_le_bytes());
+            hasher.update(&bytes);
+        }
+    }
+
+    Ok(format!("{:x}", hasher.finalize()))
+}
+
+/// WAC package name under which a component artifact is registered
+fn package_of(component: &str) -> String {
+    format!("{}:component", component)
+}
+
+/// WAC package name of a component's default-config provider
+fn defaults_package_of(component: &str) -> String {
+    format!("{}-defaults:component", component)
+}
+
+fn dep_arg(package: &str, path: &Path) -> String {
+    format!("{}={}", package, path.display())
+}
+
+/// Generate the WAC script for the given instances and their resolved wiring
+///
+/// Imports satisfied by another included component are passed that
+/// component's export, so producers are instantiated before their consumers.
+/// Components that import from each other cannot be instantiated and fail.
+fn generate_wac(package: &str, instances: &[Instance], wiring: &[WiringLink]) -> Result<String> {
+    let producers = |instance: &Instance| -> Vec<(&str, &str)> {
+        wiring
+            .iter()
+            .filter(|link| link.component == instance.name)
+            .filter_map(|link| match &link.source {
+                ImportSource::Component { name } => Some((link.interface.as_str(), name.as_str())),
+                _ => None,
+            })
+            .collect()
+    };
+    let dependencies: Vec<Vec<usize>> = instances
+        .iter()
+        .map(|instance| {
+            producers(instance)
+                .into_iter()
+                .filter_map(|(_, producer)| instances.iter().position(|i| i.name == producer))
+                .collect()
+        })
+        .collect();
+    let order = topological_order(&dependencies).map_err(|cycle| {
+        let names: Vec<&str> = cycle.iter().chain(cycle.first()).map(|&i| instances[i].name.as_str()).collect();
+        anyhow::anyhow!("Components import from each other and cannot be composed: {}", names.join(" -> "))
+    })?;
+
+    let mut script = format!("package {};\n\n", package);
+
+    for instance in order.into_iter().map(|i| &instances[i]) {
+        let mut args: Vec<String> = Vec::new();
+        if let Some((_, interfaces)) = &instance.defaults {
+            let provider = format!("{}-defaults", instance.name);
+            script.push_str(&format!("let {} = new {} {{ ... }};\n", provider, defaults_package_of(&instance.name)));
+            args.extend(interfaces.iter().map(|interface| format!("\"{0}\": {1}[\"{0}\"]", interface, provider)));
+        }
+        args.extend(
+            producers(instance)
+                .into_iter()
+                .map(|(interface, producer)| format!("\"{0}\": {1}[\"{0}\"]", interface, producer)),
+        );
+        args.push("...".to_string());
+
+        script.push_str(&format!(
+            "let {} = new {} {{ {} }};\n",
+            instance.name,
+            package_of(&instance.name),
+            args.join(", ")
+        ));
+    }
+
+    script.push('\n');
+    for instance in instances {
+        script.push_str(&format!("export {0} as {0};\n", instance.name));
+    }
+
+    Ok(script)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn instance(name: &str, defaults: Option<Vec<&str>>) -> Instance {
+        Instance {
+            name: name.to_string(),
+            artifact: PathBuf::from(format!("{}.wasm", name)),
+            defaults: defaults.map(|interfaces| {
+                (
+                    PathBuf::from(format!("{}-defaults.wasm", name)),
+                    interfaces.into_iter().map(str::to_string).collect(),
+                )
+            }),
+        }
+    }
+
+    #[test]
+    fn test_generate_wac_wires_defaults() {
+        let instances = vec![
+            instance("camera-front", None),
+            instance("object-detection", Some(vec!["adas:object-detection/config"])),
+        ];
+
+        let script = generate_wac("adas:test-system@0.1.0", &instances, &[]).unwrap();
+
+        assert!(script.starts_with("package adas:test-system@0.1.0;\n"));
+        assert!(script.contains("let camera-front = new camera-front:component { ... };"));
+        assert!(script.contains("let object-detection-defaults = new object-detection-defaults:component { ... };"));
+        assert!(script.contains(
+            "let object-detection = new object-detection:component { \"adas:object-detection/config\": object-detection-defaults[\"adas:object-detection/config\"], ... };"
+        ));
+        assert!(script.contains("export object-detection as object-detection;"));
+    }
+
+    #[test]
+    fn test_generate_wac_wires_component_links() {
+        let link = |component: &str, interface: &str, producer: &str| WiringLink {
+            component: component.to_string(),
+            interface: interface.to_string(),
+            source: ImportSource::Component {
+                name: producer.to_string(),
+            },
+        };
+        let instances = vec![instance("sensor-fusion", None), instance("camera-front", None)];
+        let wiring = vec![
+            link("sensor-fusion", "adas:data/sensor-data", "camera-front"),
+            WiringLink {
+                component: "camera-front".to_string(),
+                interface: "wasi:clocks/monotonic-clock".to_string(),
+                source: ImportSource::Host,
+            },
+        ];
+
+        let script = generate_wac("adas:test-system@0.1.0", &instances, &wiring).unwrap();
+
+        let producer = script.find("let camera-front = new camera-front:component { ... };").unwrap();
+        let consumer = script
+            .find("let sensor-fusion = new sensor-fusion:component { \"adas:data/sensor-data\": camera-front[\"adas:data/sensor-data\"], ... };")
+            .unwrap();
+        assert!(producer < consumer);
+        assert!(script.ends_with("export sensor-fusion as sensor-fusion;\nexport camera-front as camera-front;\n"));
+
+        // Components feeding each other have no instantiation order
+        let mut wiring = wiring;
+        wiring.push(link("camera-front", "adas:data/perception-data", "sensor-fusion"));
+        let err = generate_wac("adas:test-system@0.1.0", &instances, &wiring).unwrap_err();
+        assert!(err.to_string().contains("sensor-fusion -> camera-front -> sensor-fusion"));
+    }
+
+    fn wired_component(name: &str, imports: &[&str], exports: &[&str]) -> Component {
+        Component {
+            name: name.to_string(),
+            category: crate::component::ComponentCategory::Fusion,
+            path: PathBuf::from(name),
+            metadata: crate::component::ComponentMetadata {
+                imports: imports.iter().map(|i| i.to_string()).collect(),
+                exports: exports.iter().map(|e| e.to_string()).collect(),
+                ..Default::default()
+            },
+            dependencies: Vec::new(),
+            has_tests: false,
+        }
+    }
+
+    #[test]
+    fn test_resolve_wiring_reports_unsatisfied_imports() {
+        let components = vec![
+            wired_component("camera-front", &["wasi:clocks/monotonic-clock"], &["adas:data/sensor-data"]),
+            wired_component(
+                "perception-fusion",
+                &["adas:data/sensor-data", "adas:data/perception-data", "adas:orchestration/execution-control"],
+                &[],
+            ),
+            wired_component("object-detection", &["adas:object-detection/config"], &[]),
+        ];
+        let instances = vec![
+            instance("camera-front", None),
+            instance("perception-fusion", None),
+            instance("object-detection", Some(vec!["adas:object-detection/config@0.1.0"])),
+        ];
+
+        let err = resolve_wiring(&CompositionConfig::default(), &components, &instances).unwrap_err();
+        assert_eq!(
+            err.0,
+            vec![UnsatisfiedImport {
+                component: "perception-fusion".to_string(),
+                interface: "adas:data/perception-data".to_string(),
+            }]
+        );
+        assert!(err.to_string().contains("perception-fusion imports adas:data/perception-data"));
+
+        // Once a provider is included the wiring resolves
+        let mut components = components;
+        components.push(wired_component("sensor-fusion", &[], &["adas:data/perception-data"]));
+        let mut instances = instances;
+        instances.push(instance("sensor-fusion", None));
+        let links = resolve_wiring(&CompositionConfig::default(), &components, &instances).unwrap();
+        let source_of = |component: &str, interface: &str| {
+            links
+                .iter()
+                .find(|l| l.component == component && l.interface == interface)
+                .map(|l| l.source.clone())
+        };
+        assert_eq!(source_of("camera-front", "wasi:clocks/monotonic-clock"), Some(ImportSource::Host));
+        assert_eq!(
+            source_of("object-detection", "adas:object-detection/config"),
+            Some(ImportSource::Defaults {
+                provider: PathBuf::from("object-detection-defaults.wasm")
+            })
+        );
+        assert_eq!(
+            source_of("perception-fusion", "adas:data/perception-data"),
+            Some(ImportSource::Component {
+                name: "sensor-fusion".to_string()
+            })
+        );
+        assert_eq!(links.len(), 5);
+    }
+
+    #[test]
+    fn test_interface_version_mismatch_fails_composition() {
+        let mut producer = wired_component("sensor-fusion", &[], &["adas:data/perception-data"]);
+        producer
+            .metadata
+            .export_versions
+            .insert("adas:data/perception-data".to_string(), semver::Version::new(1, 4, 0));
+        let mut consumer = wired_component("planning", &["adas:data/perception-data"], &[]);
+        consumer
+            .metadata
+            .import_requirements
+            .insert("adas:data/perception-data".to_string(), semver::VersionReq::parse("^1.2").unwrap());
+        let instances = vec![instance("sensor-fusion", None), instance("planning", None)];
+
+        let components = vec![producer.clone(), consumer.clone()];
+        let links = resolve_wiring(&CompositionConfig::default(), &components, &instances).unwrap();
+        assert!(check_interface_versions(&components, &links).is_ok());
+
+        // A v2 consumer must not be wired to the v1 producer
+        consumer
+            .metadata
+            .import_requirements
+            .insert("adas:data/perception-data".to_string(), semver::VersionReq::parse("^2.0").unwrap());
+        let components = vec![producer.clone(), consumer];
+        let err = check_interface_versions(&components, &links).unwrap_err();
+        assert_eq!(
+            err.0,
+            vec![VersionMismatch {
+                importer: "planning".to_string(),
+                exporter: "sensor-fusion".to_string(),
+                interface: "adas:data/perception-data".to_string(),
+                required: "^2.0".to_string(),
+                provided: "1.4.0".to_string(),
+            }]
+        );
+        assert!(err
+            .to_string()
+            .contains("planning requires adas:data/perception-data ^2.0 but sensor-fusion exports 1.4.0"));
+
+        // Exporters without a declared version are not rejected
+        producer.metadata.export_versions.clear();
+        let components = vec![producer, components[1].clone()];
+        assert!(check_interface_versions(&components, &links).is_ok());
+    }
+
+    #[test]
+    fn test_manifest_records_artifacts_and_wiring() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let artifact = temp_dir.path().join("target/camera-front.wasm");
+        std::fs::create_dir_all(artifact.parent().unwrap()).unwrap();
+        std::fs::write(&artifact, b"\0asm v1").unwrap();
+
+        let mut component =
+            wired_component("camera-front", &["wasi:clocks/monotonic-clock"], &["adas:data/sensor-data"]);
+        component.metadata.version = "0.2.0".to_string();
+        let instances = vec![Instance {
+            name: "camera-front".to_string(),
+            artifact,
+            defaults: None,
+        }];
+        let config = CompositionConfig {
+            write_manifest: true,
+            ..CompositionConfig::default()
+        };
+        let wiring = resolve_wiring(&config, std::slice::from_ref(&component), &instances).unwrap();
+
+        let manifest = CompositionManifest::new(
+            temp_dir.path(),
+            &config,
+            DEFAULT_TARGET,
+            &[component],
+            &instances,
+            wiring,
+            "abc123",
+        )
+        .unwrap();
+
+        assert_eq!(manifest.tool_version, env!("CARGO_PKG_VERSION"));
+        assert_eq!(manifest.package, DEFAULT_PACKAGE);
+        let included = &manifest.components[0];
+        assert_eq!(included.version, "0.2.0");
+        assert_eq!(included.artifact.path, PathBuf::from("target/camera-front.wasm"));
+        assert_eq!(included.artifact.sha256, format!("{:x}", Sha256::digest(b"\0asm v1")));
+        assert_eq!(included.exports, vec!["adas:data/sensor-data".to_string()]);
+        assert_eq!(manifest.wiring[0].source, ImportSource::Host);
+
+        let json = serde_json::to_string(&manifest).unwrap();
+        assert!(json.contains("\"kind\":\"host\""));
+        assert_eq!(serde_json::from_str::<CompositionManifest>(&json).unwrap(), manifest);
+        assert_eq!(
+            manifest_path(Path::new("out/system.wasm")),
+            PathBuf::from("out/system.manifest.json")
+        );
+    }
+
+    #[test]
+    fn test_fingerprint_tracks_inputs() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let artifact = temp_dir.path().join("camera-front.wasm");
+        std::fs::write(&artifact, b"\0asm v1").unwrap();
+
+        let instances = vec![Instance {
+            name: "camera-front".to_string(),
+            artifact: artifact.clone(),
+            defaults: None,
+        }];
+        let config = CompositionConfig::default();
+        let script = generate_wac(&config.package, &instances, &[]).unwrap();
+
+        let original = fingerprint(&config, &script, &instances).unwrap();
+        assert_eq!(fingerprint(&config, &script, &instances).unwrap(), original);
+
+        let renamed = CompositionConfig {
+            package: "adas:other-system@0.1.0".to_string(),
+            ..config.clone()
+        };
+        assert_ne!(fingerprint(&renamed, &script, &instances).unwrap(), original);
+
+        std::fs::write(&artifact, b"\0asm v2").unwrap();
+        assert_ne!(fingerprint(&config, &script, &instances).unwrap(), original);
+    }
+
+    #[test]
+    fn test_composed_world_of_empty_component() {
+        let bytes = wasm_encoder::Component::new().finish();
+        let world = ComposedWorld::from_component(&bytes).unwrap();
+
+        assert!(world.imports.is_empty());
+        assert!(world.exports.is_empty());
+        assert!(world.wit.contains("world "));
+        assert!(ComposedWorld::from_component(b"not wasm").is_err());
+    }
+
+    #[test]
+    fn test_unsupported_defaults_skipped() {
+        let defaults = ComponentDefaults {
+            provider: PathBuf::from("defaults.wasm"),
+            interfaces: vec![
+                "adas:object-detection/config@0.1.0".to_string(),
+                "adas:object-detection/thresholds".to_string(),
+            ],
+        };
+        let imports: BTreeSet<String> = ["adas:object-detection/config".to_string()].into();
+
+        assert_eq!(
+            supported_defaults("object-detection", &defaults, &imports),
+            vec!["adas:object-detection/config@0.1.0".to_string()]
+        );
+        assert!(supported_defaults("object-detection", &defaults, &BTreeSet::new()).is_empty());
+    }
+
+    #[test]
+    fn test_config_from_toml() {
+        let config: CompositionConfig = toml::from_str(
+            r#"
+            components = ["camera-front", "object-detection"]
+
+            [defaults.object-detection]
+            provider = "defaults/object-detection.wasm"
+            interfaces = ["adas:object-detection/config"]
+            "#,
+        )
+        .unwrap();
+
+        assert_eq!(config.package, DEFAULT_PACKAGE);
+        assert_eq!(config.profile, BuildProfile::Release);
+        assert_eq!(config.defaults["object-detection"].interfaces.len(), 1);
+    }
+
+    #[test]
+    fn test_named_compositions_from_manifest() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        std::fs::write(
+            temp_dir.path().join(COMPOSITION_MANIFEST),
+            r#"
+            [highway]
+            components = ["camera-front", "radar-front", "sensor-fusion"]
+
+            [urban]
+            package = "adas:urban-system@0.1.0"
+            components = ["camera-front", "object-detection"]
+
+            [urban.defaults.object-detection]
+            provider = "defaults/urban-detection.wasm"
+            interfaces = ["adas:object-detection/config"]
+            "#,
+        )
+        .unwrap();
+        let mut build_config = BuildConfig {
+            workspace_root: temp_dir.path().to_path_buf(),
+            ..BuildConfig::default()
+        };
+        build_config.composition.defaults.insert(
+            "object-detection".to_string(),
+            ComponentDefaults {
+                provider: PathBuf::from("defaults/object-detection.wasm"),
+                interfaces: vec!["adas:object-detection/config".to_string()],
+            },
+        );
+        let config = CompositionConfig::from_workspace(&build_config).unwrap();
+
+        let highway = config.named("highway").unwrap();
+        assert_eq!(highway.package, DEFAULT_PACKAGE);
+        assert_eq!(highway.components.len(), 3);
+
+        let urban = config.named("urban").unwrap();
+        assert_eq!(urban.package, "adas:urban-system@0.1.0");
+        assert_eq!(urban.defaults["object-detection"].provider, PathBuf::from("defaults/urban-detection.wasm"));
+
+        let err = config.named("rural").unwrap_err();
+        assert!(err.to_string().contains("available: highway, urban"));
+    }
+}
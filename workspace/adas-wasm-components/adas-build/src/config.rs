@@ -0,0 +1,506 @@
+//! Build configuration for the ADAS workspace
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use std::path::{Path, PathBuf};
+use tracing::debug;
+
+use crate::asil::AsilLevel;
+use crate::budget::{deserialize_optional_size, deserialize_size_map, BudgetPolicy};
+use crate::composition::CompositionConfig;
+use crate::pipeline::DEFAULT_TARGET;
+use crate::smoke::DEFAULT_SMOKE_TEST_LATENCY_BUDGET_MS;
+
+/// Name of the optional build configuration file at the workspace root, in its default format
+pub const CONFIG_FILE_NAME: &str = "adas-build.toml";
+
+/// File name of the build configuration without extension; any extension
+/// known to [`ConfigFormat`] is accepted at the workspace root
+pub const CONFIG_FILE_STEM: &str = "adas-build";
+
+/// Serialization format of a build configuration file
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ConfigFormat {
+    #[default]
+    Toml,
+    Yaml,
+    Json,
+}
+
+impl ConfigFormat {
+    /// Every format, in the order workspace configuration files are looked up
+    pub const ALL: [ConfigFormat; 3] = [ConfigFormat::Toml, ConfigFormat::Yaml, ConfigFormat::Json];
+
+    /// File extensions recognised for this format
+    pub fn extensions(self) -> &'static [&'static str] {
+        match self {
+            ConfigFormat::Toml => &["toml"],
+            ConfigFormat::Yaml => &["yaml", "yml"],
+            ConfigFormat::Json => &["json"],
+        }
+    }
+
+    /// Format of a configuration file, detected from its extension
+    ///
+    /// Paths without an extension are TOML.
+    pub fn from_path(path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref();
+        let Some(extension) = path.extension() else {
+            return Ok(ConfigFormat::Toml);
+        };
+        let extension = extension.to_string_lossy().to_lowercase();
+        Self::ALL
+            .into_iter()
+            .find(|format| format.extensions().contains(&extension.as_str()))
+            .with_context(|| {
+                format!(
+                    "Unsupported configuration format '.{}' for {} (expected .toml, .yaml, .yml or .json)",
+                    extension,
+                    path.display()
+                )
+            })
+    }
+}
+
+impl std::fmt::Display for ConfigFormat {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            ConfigFormat::Toml => "TOML",
+            ConfigFormat::Yaml => "YAML",
+            ConfigFormat::Json => "JSON",
+        })
+    }
+}
+
+/// Build profile
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum BuildProfile {
+    Debug,
+    Release,
+}
+
+impl std::str::FromStr for BuildProfile {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "debug" | "dev" => Ok(BuildProfile::Debug),
+            "release" => Ok(BuildProfile::Release),
+            _ => anyhow::bail!("Unknown build profile: {}", s),
+        }
+    }
+}
+
+/// Cargo features selected for a build profile
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ProfileFeatures {
+    /// Features passed with `--features`
+    #[serde(default)]
+    pub features: Vec<String>,
+
+    /// Pass `--no-default-features`
+    #[serde(default)]
+    pub no_default_features: bool,
+}
+
+impl ProfileFeatures {
+    /// Cargo arguments selecting these features
+    pub fn cargo_args(&self) -> Vec<String> {
+        let mut args = Vec::new();
+        if self.no_default_features {
+            args.push("--no-default-features".to_string());
+        }
+        if !self.features.is_empty() {
+            args.push("--features".to_string());
+            args.push(self.features.join(","));
+        }
+        args
+    }
+}
+
+/// Per-component adjustment of the profile features
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ComponentFeatures {
+    /// Profile features this component is built without
+    #[serde(default)]
+    pub disable: Vec<String>,
+}
+
+/// Workspace-wide build configuration
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BuildConfig {
+    /// Root of the ADAS workspace
+    #[serde(skip)]
+    pub workspace_root: PathBuf,
+
+    /// Directory containing component crates, relative to the workspace root
+    #[serde(default = "default_components_dir")]
+    pub components_dir: PathBuf,
+
+    /// Directory containing shared WIT interfaces, relative to the workspace root
+    #[serde(default = "default_wit_dir")]
+    pub wit_dir: PathBuf,
+
+    /// How many times a component is rebuilt after a transient failure
+    #[serde(default = "default_max_retries")]
+    pub max_retries: u32,
+
+    /// Delay before the first retry; doubled on every further attempt
+    #[serde(default = "default_retry_base_delay_ms")]
+    pub retry_base_delay_ms: u64,
+
+    /// Kill a component's cargo invocation, with its rustc children, once it
+    /// has run this many seconds; unset means no limit
+    #[serde(default)]
+    pub component_timeout_secs: Option<u64>,
+
+    /// Report components without any tests as a validation warning
+    #[serde(default)]
+    pub warn_on_missing_tests: bool,
+
+    /// Fail validation for components that do not export the health-monitoring interface
+    ///
+    /// Opt-in: most components in this workspace do not export it yet.
+    #[serde(default)]
+    pub require_health_monitoring: bool,
+
+    /// Lowest ASIL level a composed component may have; components below it,
+    /// or without a declared level, fail composition validation
+    #[serde(default)]
+    pub min_asil_level: Option<AsilLevel>,
+
+    /// Whether exceeding a component resource budget warns or fails
+    #[serde(default)]
+    pub budget_policy: BudgetPolicy,
+
+    /// Flash size budget per component, and for the composed artifact under
+    /// [`COMPOSED_SIZE_BUDGET`](crate::validation::COMPOSED_SIZE_BUDGET)
+    #[serde(default, deserialize_with = "deserialize_size_map")]
+    pub size_budgets: HashMap<String, u64>,
+
+    /// Flash size budget for components without their own entry
+    #[serde(default, deserialize_with = "deserialize_optional_size")]
+    pub default_size_budget: Option<u64>,
+
+    /// Cargo features per build profile, e.g. `[profile_features.release]`
+    #[serde(default)]
+    pub profile_features: BTreeMap<BuildProfile, ProfileFeatures>,
+
+    /// Feature opt-outs per component name
+    #[serde(default)]
+    pub component_features: HashMap<String, ComponentFeatures>,
+
+    /// Target triples every component is built for
+    #[serde(default = "default_targets")]
+    pub targets: Vec<String>,
+
+    /// Maximum number of components built concurrently
+    #[serde(default = "default_max_parallel_jobs")]
+    pub max_parallel_jobs: usize,
+
+    /// Cancel the remaining builds as soon as one component fails
+    #[serde(default)]
+    pub fail_fast: bool,
+
+    /// Rebuild every component even when its inputs are unchanged
+    #[serde(default)]
+    pub ignore_cache: bool,
+
+    /// Plan builds without running cargo
+    #[serde(default)]
+    pub dry_run: bool,
+
+    /// Smoke test every freshly built component in a wasmtime host
+    #[serde(default)]
+    pub run_smoke_tests: bool,
+
+    /// Smoke test latency budget for components that do not declare `max-latency-ms`
+    #[serde(default = "default_smoke_test_latency_budget_ms")]
+    pub smoke_test_latency_budget_ms: f32,
+
+    /// WAC composition settings
+    #[serde(default)]
+    pub composition: CompositionConfig,
+}
+
+fn default_components_dir() -> PathBuf {
+    PathBuf::from("components")
+}
+
+fn default_wit_dir() -> PathBuf {
+    PathBuf::from("wit")
+}
+
+fn default_max_retries() -> u32 {
+    2
+}
+
+fn default_retry_base_delay_ms() -> u64 {
+    500
+}
+
+fn default_smoke_test_latency_budget_ms() -> f32 {
+    DEFAULT_SMOKE_TEST_LATENCY_BUDGET_MS
+}
+
+fn default_targets() -> Vec<String> {
+    vec![DEFAULT_TARGET.to_string()]
+}
+
+fn default_max_parallel_jobs() -> usize {
+    std::thread::available_parallelism().map_or(1, |n| n.get())
+}
+
+impl BuildConfig {
+    /// Load the build configuration for a workspace
+    ///
+    /// Settings are read from `adas-build.toml`, `adas-build.yaml`,
+    /// `adas-build.yml` or `adas-build.json` when present; otherwise the
+    /// defaults are used. Fails if `workspace_root` is not a Cargo workspace or
+    /// holds more than one of these files.
+    pub fn load(workspace_root: impl AsRef<Path>) -> Result<Self> {
+        let workspace_root = workspace_root.as_ref();
+
+        if !workspace_root.join("Cargo.toml").exists() {
+            anyhow::bail!("No Cargo.toml found in workspace: {}", workspace_root.display());
+        }
+
+        let candidates: Vec<PathBuf> = ConfigFormat::ALL
+            .iter()
+            .flat_map(|format| format.extensions())
+            .map(|extension| workspace_root.join(format!("{}.{}", CONFIG_FILE_STEM, extension)))
+            .filter(|path| path.exists())
+            .collect();
+
+        let mut config = match candidates.as_slice() {
+            [] => Self::default(),
+            [config_path] => Self::load_file(config_path)?,
+            _ => anyhow::bail!(
+                "Found several build configuration files, keep only one: {}",
+                candidates
+                    .iter()
+                    .map(|p| p.display().to_string())
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ),
+        };
+
+        config.workspace_root = workspace_root.to_path_buf();
+        Ok(config)
+    }
+
+    /// Read a configuration file, detecting its format from the extension
+    ///
+    /// Paths without an extension are read as TOML. The workspace root is
+    /// left at its default; [`BuildConfig::load`] sets it.
+    pub fn load_file(path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref();
+        let format = ConfigFormat::from_path(path)?;
+        debug!("Loading {} build configuration from {}", format, path.display());
+        let content = std::fs::read_to_string(path).with_context(|| format!("Failed to read {}", path.display()))?;
+        Self::from_str(&content, format).with_context(|| format!("Failed to parse {}", path.display()))
+    }
+
+    /// Parse a configuration held in memory
+    ///
+    /// Parse errors name the line and column of the offending input.
+    pub fn from_str(content: &str, format: ConfigFormat) -> Result<Self> {
+        match format {
+            ConfigFormat::Toml => toml::from_str(content).map_err(|e| {
+                let location = e.span().map(|span| line_column(content, span.start));
+                parse_error(format, location, e.message())
+            }),
+            ConfigFormat::Yaml => serde_yaml::from_str(content).map_err(|e| {
+                let location = e.location().map(|l| (l.line(), l.column()));
+                parse_error(format, location, &strip_location(&e.to_string()))
+            }),
+            ConfigFormat::Json => serde_json::from_str(content).map_err(|e| {
+                let location = Some((e.line(), e.column())).filter(|(line, _)| *line > 0);
+                parse_error(format, location, &strip_location(&e.to_string()))
+            }),
+        }
+    }
+
+    /// Serialize the configuration; [`BuildConfig::from_str`] reads it back
+    pub fn to_string(&self, format: ConfigFormat) -> Result<String> {
+        let serialized = match format {
+            ConfigFormat::Toml => toml::to_string_pretty(self).map_err(anyhow::Error::from),
+            ConfigFormat::Yaml => serde_yaml::to_string(self).map_err(anyhow::Error::from),
+            ConfigFormat::Json => serde_json::to_string_pretty(self).map_err(anyhow::Error::from),
+        };
+        serialized.with_context(|| format!("Failed to serialize build configuration as {}", format))
+    }
+
+    /// Features to build `component` with under `profile`
+    ///
+    /// The profile's features minus those the component disables.
+    pub fn features_for(&self, component: &str, profile: BuildProfile) -> ProfileFeatures {
+        let mut features = self.profile_features.get(&profile).cloned().unwrap_or_default();
+        if let Some(overrides) = self.component_features.get(component) {
+            features.features.retain(|f| !overrides.disable.contains(f));
+        }
+        features
+    }
+
+    /// Absolute path of the components directory
+    pub fn components_path(&self) -> PathBuf {
+        self.workspace_root.join(&self.components_dir)
+    }
+
+    /// Absolute path of the shared WIT directory
+    pub fn wit_path(&self) -> PathBuf {
+        self.workspace_root.join(&self.wit_dir)
+    }
+}
+
+/// 1-based line and column of a byte offset
+fn line_column(content: &str, offset: usize) -> (usize, usize) {
+    let before = &content[..offset.min(content.len())];
+    let line = before.matches('\n').count() + 1;
+    let column = before
+        .rfind('\n')
+        .map_or(before, |start| &before[start + 1..])
+        .chars()
+        .count()
+        + 1;
+    (line, column)
+}
+
+/// Drop the trailing " at line L column C" serde_json and serde_yaml append
+fn strip_location(message: &str) -> String {
+    match message.rfind(" at line ") {
+        Some(index) => message[..index].to_string(),
+        None => message.to_string(),
+    }
+}
+
+fn parse_error(format: ConfigFormat, location: Option<(usize, usize)>, message: &str) -> anyhow::Error {
+    let message = message.trim();
+    match location {
+        Some((line, column)) => anyhow::anyhow!("Invalid {} at line {}, column {}: {}", format, line, column, message),
+        None => anyhow::anyhow!("Invalid {}: {}", format, message),
+    }
+}
+
+impl Default for BuildConfig {
+    fn default() -> Self {
+        Self {
+            workspace_root: PathBuf::from("."),
+            components_dir: default_components_dir(),
+            wit_dir: default_wit_dir(),
+            max_retries: default_max_retries(),
+            retry_base_delay_ms: default_retry_base_delay_ms(),
+            component_timeout_secs: None,
+            warn_on_missing_tests: false,
+            require_health_monitoring: false,
+            min_asil_level: None,
+            budget_policy: BudgetPolicy::default(),
+            size_budgets: HashMap::new(),
+            default_size_budget: None,
+            profile_features: BTreeMap::new(),
+            component_features: HashMap::new(),
+            targets: default_targets(),
+            max_parallel_jobs: default_max_parallel_jobs(),
+            fail_fast: false,
+            ignore_cache: false,
+            dry_run: false,
+            run_smoke_tests: false,
+            smoke_test_latency_budget_ms: default_smoke_test_latency_budget_ms(),
+            composition: CompositionConfig::default(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_format_from_extension() {
+        assert_eq!(ConfigFormat::from_path("adas-build.toml").unwrap(), ConfigFormat::Toml);
+        assert_eq!(
+            ConfigFormat::from_path("ci/adas-build.YML").unwrap(),
+            ConfigFormat::Yaml
+        );
+        assert_eq!(ConfigFormat::from_path("adas-build.yaml").unwrap(), ConfigFormat::Yaml);
+        assert_eq!(ConfigFormat::from_path("adas-build.json").unwrap(), ConfigFormat::Json);
+        assert_eq!(ConfigFormat::from_path("adas-build").unwrap(), ConfigFormat::Toml);
+        let err = ConfigFormat::from_path("adas-build.ini").unwrap_err();
+        assert!(err.to_string().contains("'.ini'"));
+    }
+
+    #[test]
+    fn test_round_trip_every_format() {
+        let mut config = BuildConfig {
+            max_retries: 5,
+            min_asil_level: Some(AsilLevel::B),
+            targets: vec!["wasm32-wasip2".to_string(), "wasm32-unknown-unknown".to_string()],
+            ..BuildConfig::default()
+        };
+        config.size_budgets.insert("object-detection".to_string(), 4 << 20);
+        config.profile_features.insert(
+            BuildProfile::Release,
+            ProfileFeatures {
+                features: vec!["simd".to_string()],
+                no_default_features: true,
+            },
+        );
+
+        for format in ConfigFormat::ALL {
+            let text = config.to_string(format).unwrap();
+            let parsed = BuildConfig::from_str(&text, format).unwrap();
+            assert_eq!(parsed.max_retries, 5, "{}", format);
+            assert_eq!(parsed.min_asil_level, Some(AsilLevel::B), "{}", format);
+            assert_eq!(parsed.targets, config.targets, "{}", format);
+            assert_eq!(parsed.size_budgets, config.size_budgets, "{}", format);
+            assert_eq!(parsed.profile_features, config.profile_features, "{}", format);
+        }
+    }
+
+    #[test]
+    fn test_parse_errors_carry_location() {
+        let err = BuildConfig::from_str("max_retries = 1\nfail_fast = \"yes\"\n", ConfigFormat::Toml).unwrap_err();
+        assert!(
+            err.to_string().starts_with("Invalid TOML at line 2, column 13:"),
+            "{}",
+            err
+        );
+
+        let err = BuildConfig::from_str("max_retries: 1\ntargets: 3\n", ConfigFormat::Yaml).unwrap_err();
+        assert!(
+            err.to_string().starts_with("Invalid YAML at line 2, column 10:"),
+            "{}",
+            err
+        );
+
+        let err = BuildConfig::from_str("{\n  \"max_retries\": -1\n}", ConfigFormat::Json).unwrap_err();
+        assert!(err.to_string().starts_with("Invalid JSON at line 2, column"), "{}", err);
+    }
+
+    #[test]
+    fn test_load_detects_workspace_config() {
+        let dir = TempDir::new().unwrap();
+        std::fs::write(dir.path().join("Cargo.toml"), "[workspace]\n").unwrap();
+        assert_eq!(
+            BuildConfig::load(dir.path()).unwrap().max_retries,
+            default_max_retries()
+        );
+
+        std::fs::write(dir.path().join("adas-build.yml"), "max_retries: 7\nfail_fast: true\n").unwrap();
+        let config = BuildConfig::load(dir.path()).unwrap();
+        assert_eq!(config.max_retries, 7);
+        assert!(config.fail_fast);
+        assert_eq!(config.workspace_root, dir.path());
+
+        std::fs::write(dir.path().join("adas-build.toml"), "max_retries = 1\n").unwrap();
+        let err = BuildConfig::load(dir.path()).unwrap_err();
+        assert!(err.to_string().contains("several build configuration files"));
+
+        let extensionless = dir.path().join("ci-config");
+        std::fs::write(&extensionless, "max_retries = 3\n").unwrap();
+        assert_eq!(BuildConfig::load_file(&extensionless).unwrap().max_retries, 3);
+    }
+}
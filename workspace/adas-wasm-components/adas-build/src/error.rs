@@ -0,0 +1,105 @@
+//! Errors returned by the public build system API
+//!
+//! Internally everything is `anyhow`; at the [`crate::AdasBuildSystem`]
+//! boundary failures are sorted by the stage that produced them so that
+//! embedding tools can react to them without matching on message text.
+
+/// Failure of a build system operation, by the stage it happened in
+#[derive(Debug, thiserror::Error)]
+pub enum BuildError {
+    /// Components could not be discovered in the workspace
+    #[error("Component discovery failed: {0:#}")]
+    Discovery(#[source] anyhow::Error),
+
+    /// The workspace configuration or toolchain setup is unusable
+    #[error("Invalid build configuration: {0:#}")]
+    Config(#[source] anyhow::Error),
+
+    /// A single component failed to compile
+    #[error("Component {component} failed to compile: {message}")]
+    Compile { component: String, message: String },
+
+    /// Components could not be composed into a single artifact
+    #[error("Composition failed: {0:#}")]
+    Composition(#[source] anyhow::Error),
+
+    /// Components or artifacts did not pass validation
+    #[error("Validation failed: {0:#}")]
+    Validation(#[source] anyhow::Error),
+}
+
+impl BuildError {
+    /// Component the error is about, if it concerns a single one
+    pub fn component(&self) -> Option<&str> {
+        match self {
+            BuildError::Compile { component, .. } => Some(component),
+            _ => None,
+        }
+    }
+
+    /// Sort an error out of the build pipeline by the stage that raised it
+    ///
+    /// Errors tagged with a [`CompileFailure`] concern one component's
+    /// compiler run; anything else means the workspace or toolchain setup
+    /// was unusable.
+    pub(crate) fn from_pipeline(error: anyhow::Error) -> Self {
+        match error.downcast::<CompileFailure>() {
+            Ok(failure) => failure.into(),
+            Err(error) => BuildError::Config(error),
+        }
+    }
+}
+
+/// Internal tag for an error that stopped one component's compiler run
+///
+/// Raised inside the pipeline as an `anyhow::Error` and turned into
+/// [`BuildError::Compile`] at the public API.
+#[derive(Debug, thiserror::Error)]
+#[error("Component {component} failed to compile: {message}")]
+pub(crate) struct CompileFailure {
+    pub component: String,
+    pub message: String,
+}
+
+impl CompileFailure {
+    pub fn new(component: &str, error: anyhow::Error) -> Self {
+        Self {
+            component: component.to_string(),
+            message: format!("{:#}", error),
+        }
+    }
+}
+
+impl From<CompileFailure> for BuildError {
+    fn from(failure: CompileFailure) -> Self {
+        BuildError::Compile {
+            component: failure.component,
+            message: failure.message,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pipeline::{BuildResult, ComponentStatus};
+
+    #[test]
+    fn test_compile_error_names_component() {
+        let mut result = BuildResult::default();
+        result.record("camera-front", ComponentStatus::Succeeded);
+        assert!(result.compile_error().is_none());
+
+        result.record("object-detection", ComponentStatus::Failed("error[E0308]".to_string()));
+        result.record("lidar", ComponentStatus::Failed("error[E0425]".to_string()));
+        let error = result.compile_error().unwrap();
+        assert!(matches!(&error, BuildError::Compile { component, message }
+            if component == "object-detection" && message == "error[E0308]"));
+        assert_eq!(error.component(), Some("object-detection"));
+
+        let error = BuildError::Config(anyhow::anyhow!("No build targets configured"));
+        assert_eq!(error.to_string(), "Invalid build configuration: No build targets configured");
+        assert!(std::error::Error::source(&error).is_some());
+        assert_eq!(error.component(), None);
+    }
+}
@@ -0,0 +1,209 @@
+//! Component dependency graph rendered as Graphviz DOT or Mermaid
+//!
+//! Nodes are components grouped by category; an edge runs from a component
+//! that imports an interface to each component that exports it.
+
+use anyhow::Result;
+use std::collections::{BTreeMap, BTreeSet};
+
+use crate::component::{Component, ComponentCategory};
+use crate::inventory::InterfaceInventory;
+
+/// Output format of [`DependencyGraph::render`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GraphFormat {
+    Dot,
+    Mermaid,
+}
+
+impl std::str::FromStr for GraphFormat {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "dot" | "graphviz" => Ok(GraphFormat::Dot),
+            "mermaid" | "mmd" => Ok(GraphFormat::Mermaid),
+            _ => anyhow::bail!("Unknown graph format: {}", s),
+        }
+    }
+}
+
+/// An importer depending on an exporter through one interface
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub struct InterfaceLink {
+    pub importer: String,
+    pub exporter: String,
+    pub interface: String,
+}
+
+/// Components and the interface links between them
+#[derive(Debug, Clone, Default)]
+pub struct DependencyGraph {
+    /// Component names per category
+    pub nodes: BTreeMap<ComponentCategory, BTreeSet<String>>,
+
+    /// Import -> export links, sorted for stable output
+    pub links: BTreeSet<InterfaceLink>,
+}
+
+impl DependencyGraph {
+    /// Resolve every imported interface to the components exporting it
+    ///
+    /// Interfaces nobody in the workspace exports (host or WASI imports) do
+    /// not produce edges.
+    pub fn build(components: &[Component]) -> Result<Self> {
+        let mut graph = Self::default();
+        for component in components {
+            graph
+                .nodes
+                .entry(component.category)
+                .or_default()
+                .insert(component.name.clone());
+        }
+
+        let inventory = InterfaceInventory::build(components)?;
+        for (interface, usage) in &inventory.interfaces {
+            for importer in &usage.importers {
+                for exporter in usage.exporters.iter().filter(|e| *e != importer) {
+                    graph.links.insert(InterfaceLink {
+                        importer: importer.clone(),
+                        exporter: exporter.clone(),
+                        interface: interface.clone(),
+                    });
+                }
+            }
+        }
+
+        Ok(graph)
+    }
+
+    /// Render the graph in `format`
+    pub fn render(&self, format: GraphFormat) -> String {
+        match format {
+            GraphFormat::Dot => self.to_dot(),
+            GraphFormat::Mermaid => self.to_mermaid(),
+        }
+    }
+
+    fn to_dot(&self) -> String {
+        let mut dot = String::from("digraph adas_components {\n    rankdir=LR;\n    node [shape=box];\n");
+
+        for (category, names) in &self.nodes {
+            let category = category.dir_name();
+            dot.push_str(&format!("\n    subgraph cluster_{} {{\n        label=\"{}\";\n", category, category));
+            for name in names {
+                dot.push_str(&format!("        \"{}\";\n", name));
+            }
+            dot.push_str("    }\n");
+        }
+
+        dot.push('\n');
+        for link in &self.links {
+            dot.push_str(&format!(
+                "    \"{}\" -> \"{}\" [label=\"{}\"];\n",
+                link.importer, link.exporter, link.interface
+            ));
+        }
+
+        dot.push_str("}\n");
+        dot
+    }
+
+    fn to_mermaid(&self) -> String {
+        let mut mermaid = String::from("flowchart LR\n");
+
+        for (category, names) in &self.nodes {
+            // Prefixed so a category never shares an id with a component of the same name
+            let category = category.dir_name();
+            mermaid.push_str(&format!("    subgraph category_{}[\"{}\"]\n", category, category));
+            for name in names {
+                mermaid.push_str(&format!("        {}[\"{}\"]\n", mermaid_id(name), name));
+            }
+            mermaid.push_str("    end\n");
+        }
+
+        for link in &self.links {
+            mermaid.push_str(&format!(
+                "    {} -->|\"{}\"| {}\n",
+                mermaid_id(&link.importer),
+                link.interface,
+                mermaid_id(&link.exporter)
+            ));
+        }
+
+        mermaid
+    }
+}
+
+/// Mermaid node identifier; hyphens would be read as part of an arrow
+fn mermaid_id(name: &str) -> String {
+    name.chars().map(|c| if c.is_ascii_alphanumeric() { c } else { '_' }).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::component::ComponentMetadata;
+    use std::path::PathBuf;
+
+    fn component(name: &str, category: ComponentCategory, imports: &[&str], exports: &[&str]) -> Component {
+        Component {
+            name: name.to_string(),
+            category,
+            path: PathBuf::from(name),
+            metadata: ComponentMetadata {
+                imports: imports.iter().map(|i| i.to_string()).collect(),
+                exports: exports.iter().map(|e| e.to_string()).collect(),
+                ..Default::default()
+            },
+            dependencies: Vec::new(),
+            has_tests: false,
+        }
+    }
+
+    fn components() -> Vec<Component> {
+        vec![
+            component("camera-front", ComponentCategory::Sensors, &["wasi:clocks/monotonic-clock"], &["adas:data/sensor-data"]),
+            component(
+                "sensor-fusion",
+                ComponentCategory::Fusion,
+                &["adas:data/sensor-data", "adas:data/perception-data"],
+                &["adas:data/perception-data"],
+            ),
+            component("planner", ComponentCategory::Control, &["adas:data/perception-data"], &[]),
+        ]
+    }
+
+    #[test]
+    fn test_links_resolve_imports_to_exporters() {
+        let graph = DependencyGraph::build(&components()).unwrap();
+        let links: Vec<(&str, &str)> = graph
+            .links
+            .iter()
+            .map(|l| (l.importer.as_str(), l.exporter.as_str()))
+            .collect();
+
+        // No self-edge for sensor-fusion, no edge for the unresolved WASI import
+        assert_eq!(links, vec![("planner", "sensor-fusion"), ("sensor-fusion", "camera-front")]);
+        assert_eq!(graph.nodes[&ComponentCategory::Sensors].len(), 1);
+    }
+
+    #[test]
+    fn test_render_formats() {
+        let graph = DependencyGraph::build(&components()).unwrap();
+
+        let dot = graph.render(GraphFormat::Dot);
+        assert!(dot.starts_with("digraph adas_components {"));
+        assert!(dot.contains("subgraph cluster_sensors {"));
+        assert!(dot.contains("\"sensor-fusion\" -> \"camera-front\" [label=\"adas:data/sensor-data\"];"));
+
+        let mermaid = graph.render(GraphFormat::Mermaid);
+        assert!(mermaid.starts_with("flowchart LR\n"));
+        assert!(mermaid.contains("subgraph category_sensors[\"sensors\"]"));
+        assert!(mermaid.contains("camera_front[\"camera-front\"]"));
+        assert!(mermaid.contains("planner -->|\"adas:data/perception-data\"| sensor_fusion"));
+
+        assert_eq!("graphviz".parse::<GraphFormat>().unwrap(), GraphFormat::Dot);
+        assert!("svg".parse::<GraphFormat>().is_err());
+    }
+}
@@ -0,0 +1,398 @@
+//! Inventory of which components export and import each WIT interface
+
+use anyhow::{bail, Context, Result};
+use serde::Serialize;
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::{Path, PathBuf};
+
+use crate::component::Component;
+
+/// Components on each side of one interface
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct InterfaceUsage {
+    /// Components that export (implement) the interface
+    pub exporters: BTreeSet<String>,
+
+    /// Components that import (consume) the interface
+    pub importers: BTreeSet<String>,
+}
+
+/// Interface name -> exporters and importers across the workspace
+///
+/// Interfaces are keyed by their fully qualified name without version, e.g.
+/// `adas:diagnostics/health-monitoring`. Interfaces declared inside a
+/// component's own package are qualified with that package.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct InterfaceInventory {
+    pub interfaces: BTreeMap<String, InterfaceUsage>,
+}
+
+impl InterfaceInventory {
+    /// Collect the interfaces recorded on every component at discovery
+    pub fn build(components: &[Component]) -> Result<Self> {
+        let mut inventory = Self::default();
+
+        for component in components {
+            for interface in &component.metadata.exports {
+                let usage = inventory.interfaces.entry(interface.clone()).or_default();
+                usage.exporters.insert(component.name.clone());
+            }
+            for interface in &component.metadata.imports {
+                let usage = inventory.interfaces.entry(interface.clone()).or_default();
+                usage.importers.insert(component.name.clone());
+            }
+        }
+
+        Ok(inventory)
+    }
+
+    /// Interfaces matching `query` by full name or by interface name alone
+    ///
+    /// `health_monitoring`, `health-monitoring` and
+    /// `adas:diagnostics/health-monitoring` all match the same interface.
+    pub fn find(&self, query: &str) -> Vec<(&str, &InterfaceUsage)> {
+        let query = query.replace('_', "-");
+        self.interfaces
+            .iter()
+            .filter(|(name, _)| {
+                name.as_str() == query || name.rsplit('/').next() == Some(query.as_str())
+            })
+            .map(|(name, usage)| (name.as_str(), usage))
+            .collect()
+    }
+
+    /// Render as a Markdown table
+    pub fn to_table(&self) -> String {
+        let mut table = String::new();
+        table.push_str("| Interface | Exporters | Importers |\n");
+        table.push_str("|-----------|-----------|-----------|\n");
+
+        let join = |names: &BTreeSet<String>| {
+            if names.is_empty() {
+                "-".to_string()
+            } else {
+                names.iter().cloned().collect::<Vec<_>>().join(", ")
+            }
+        };
+
+        for (name, usage) in &self.interfaces {
+            table.push_str(&format!(
+                "| {} | {} | {} |\n",
+                name,
+                join(&usage.exporters),
+                join(&usage.importers)
+            ));
+        }
+
+        table
+    }
+
+    /// Serialize as pretty-printed JSON
+    pub fn to_json(&self) -> Result<String> {
+        serde_json::to_string_pretty(self).context("Failed to serialize interface inventory")
+    }
+}
+
+/// Whether a world exports or imports an interface
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Direction {
+    Export,
+    Import,
+}
+
+/// WIT file wit-bindgen compiles when `generate!` names no world
+const DEFAULT_WORLD_FILE: &str = "world.wit";
+
+/// Interface exports and imports of the world a component compiles
+///
+/// That is the world named in the component's `wit_bindgen::generate!`
+/// invocation, else the world in `wit/world.wit`. Other worlds in the WIT
+/// directory, such as leftover `component.wit` templates, are ignored.
+pub(crate) fn world_items(component_dir: &Path) -> Result<Vec<(Direction, String)>> {
+    let wit_dir = component_dir.join("wit");
+    let Some(world) = generated_world(&component_dir.join("src"))? else {
+        let path = wit_dir.join(DEFAULT_WORLD_FILE);
+        if !path.is_file() {
+            return Ok(Vec::new());
+        }
+        return Ok(parse_world_items(&read_wit(&path)?, None));
+    };
+
+    for path in wit_files(&wit_dir)? {
+        let source = read_wit(&path)?;
+        if source
+            .lines()
+            .any(|line| world_name(line) == Some(world.as_str()))
+        {
+            return Ok(parse_world_items(&source, Some(&world)));
+        }
+    }
+    bail!(
+        "World `{}` named in wit_bindgen::generate! is not declared in {}",
+        world,
+        wit_dir.display()
+    )
+}
+
+/// World named by a `wit_bindgen::generate!` invocation in a crate's sources
+fn generated_world(src_dir: &Path) -> Result<Option<String>> {
+    if !src_dir.is_dir() {
+        return Ok(None);
+    }
+
+    for entry in std::fs::read_dir(src_dir)
+        .with_context(|| format!("Failed to read {}", src_dir.display()))?
+    {
+        let path = entry?.path();
+        if path.extension().map_or(true, |ext| ext != "rs") {
+            continue;
+        }
+        let source = std::fs::read_to_string(&path)
+            .with_context(|| format!("Failed to read {}", path.display()))?;
+        let Some(start) = source.find("generate!(") else {
+            continue;
+        };
+
+        // Either `generate!("world")` or `generate!({ world: "world", ... })`
+        let invocation = &source[start + "generate!(".len()..];
+        let invocation = &invocation[..invocation.find(");").unwrap_or(invocation.len())];
+        let value = match invocation.trim_start() {
+            quoted if quoted.starts_with('"') => Some(quoted),
+            _ => invocation
+                .find("world:")
+                .map(|i| invocation[i + "world:".len()..].trim_start()),
+        };
+        if let Some(world) = value
+            .and_then(|v| v.strip_prefix('"'))
+            .and_then(|v| v.split('"').next())
+        {
+            return Ok(Some(world.to_string()));
+        }
+    }
+
+    Ok(None)
+}
+
+/// `.wit` files directly inside a directory, sorted by name
+fn wit_files(wit_dir: &Path) -> Result<Vec<PathBuf>> {
+    let mut files = Vec::new();
+    if !wit_dir.is_dir() {
+        return Ok(files);
+    }
+    for entry in std::fs::read_dir(wit_dir)
+        .with_context(|| format!("Failed to read {}", wit_dir.display()))?
+    {
+        let path = entry?.path();
+        if path.extension().is_some_and(|ext| ext == "wit") {
+            files.push(path);
+        }
+    }
+    files.sort();
+    Ok(files)
+}
+
+fn read_wit(path: &Path) -> Result<String> {
+    std::fs::read_to_string(path).with_context(|| format!("Failed to read {}", path.display()))
+}
+
+/// Name of the world a `world name {` line opens
+fn world_name(line: &str) -> Option<&str> {
+    let rest = line
+        .split("//")
+        .next()
+        .unwrap_or("")
+        .trim()
+        .strip_prefix("world ")?;
+    rest.trim_start()
+        .split(|c: char| c == '{' || c.is_whitespace())
+        .next()
+        .filter(|name| !name.is_empty())
+}
+
+/// Parse `export`/`import` interface references of the worlds in WIT source
+///
+/// Only items directly inside `world` (or every world when `None`) are
+/// collected. Function exports (`export name: func(...)`) are skipped;
+/// unqualified interface names are qualified with the file's `package`
+/// declaration.
+fn parse_world_items(source: &str, world: Option<&str>) -> Vec<(Direction, String)> {
+    let mut package: Option<String> = None;
+    let mut items = Vec::new();
+    let mut depth = 0usize;
+    let mut in_world = false;
+
+    for line in source.lines() {
+        let line = line.split("//").next().unwrap_or("").trim();
+
+        if depth == 0 {
+            if let Some(name) = line.strip_prefix("package ") {
+                package = Some(strip_version(name.trim_end_matches(';').trim()).to_string());
+                continue;
+            }
+            if let Some(name) = world_name(line) {
+                in_world = world.map_or(true, |world| world == name);
+            }
+        }
+
+        let opened = line.matches('{').count();
+        let closed = line.matches('}').count();
+        depth = (depth + opened).saturating_sub(closed);
+        if depth == 0 {
+            in_world = false;
+        }
+        // Items of inline interfaces sit one level deeper
+        if !in_world || depth != 1 {
+            continue;
+        }
+
+        let (direction, target) = if let Some(target) = line.strip_prefix("export ") {
+            (Direction::Export, target)
+        } else if let Some(target) = line.strip_prefix("import ") {
+            (Direction::Import, target)
+        } else {
+            continue;
+        };
+
+        let target = target.trim_end_matches(';').trim();
+        if target.contains(": ") || target.ends_with('{') {
+            continue;
+        }
+
+        let target = strip_version(target);
+        let interface = match (&package, target.contains(':')) {
+            (Some(package), false) => format!("{}/{}", package, target),
+            _ => target.to_string(),
+        };
+        items.push((direction, interface));
+    }
+
+    items
+}
+
+/// Interface name without its `@version` suffix
+pub(crate) fn strip_version(name: &str) -> &str {
+    name.split('@').next().unwrap_or(name)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::component::ComponentCategory;
+    use tempfile::TempDir;
+
+    const FUSION_WIT: &str = r#"package adas:sensor-fusion@0.1.0;
+
+interface fusion-engine {
+    fuse: func() -> u32;
+}
+
+world sensor-fusion {
+    import adas:data/perception-data@0.1.0;
+    export fusion-engine;
+    export adas:diagnostics/health-monitoring;
+    export process-frame: func() -> string;
+}
+"#;
+
+    /// Older components still carry the world their template generated
+    const STALE_COMPONENT_WIT: &str = r#"world system-component {
+    import adas:orchestration/execution-control;
+    export adas:diagnostics/health-monitoring;
+    export adas:diagnostics/performance-monitoring;
+}
+"#;
+
+    fn component(root: &Path, name: &str, wit: &str) -> Component {
+        let path = root.join(name);
+        std::fs::create_dir_all(path.join("wit")).unwrap();
+        std::fs::write(path.join("Cargo.toml"), format!("[package]\nname = \"{}\"\n", name)).unwrap();
+        std::fs::write(path.join("wit").join("world.wit"), wit).unwrap();
+        Component::load(&path, ComponentCategory::Fusion).unwrap()
+    }
+
+    #[test]
+    fn test_parse_world_items() {
+        let items = parse_world_items(FUSION_WIT, None);
+        assert_eq!(
+            items,
+            vec![
+                (Direction::Import, "adas:data/perception-data".to_string()),
+                (Direction::Export, "adas:sensor-fusion/fusion-engine".to_string()),
+                (Direction::Export, "adas:diagnostics/health-monitoring".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_inventory_across_components() {
+        let temp_dir = TempDir::new().unwrap();
+        let components = vec![
+            component(temp_dir.path(), "sensor-fusion", FUSION_WIT),
+            component(
+                temp_dir.path(),
+                "planner",
+                "package adas:planner;\nworld planner {\n    import adas:data/perception-data;\n}\n",
+            ),
+        ];
+
+        let inventory = InterfaceInventory::build(&components).unwrap();
+
+        let perception = &inventory.interfaces["adas:data/perception-data"];
+        assert!(perception.exporters.is_empty());
+        assert_eq!(perception.importers.len(), 2);
+
+        let health = inventory.find("health_monitoring");
+        assert_eq!(health.len(), 1);
+        assert!(health[0].1.exporters.contains("sensor-fusion"));
+
+        assert!(inventory.to_table().contains("| adas:data/perception-data | - | planner, sensor-fusion |"));
+        assert!(inventory.to_json().unwrap().contains("\"exporters\""));
+    }
+
+    #[test]
+    fn test_only_the_compiled_world_is_read() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("sensor-fusion");
+        std::fs::create_dir_all(path.join("wit")).unwrap();
+        std::fs::write(path.join("wit/component.wit"), STALE_COMPONENT_WIT).unwrap();
+
+        let exports = |path: &Path| -> Vec<String> {
+            world_items(path)
+                .unwrap()
+                .into_iter()
+                .filter(|(direction, _)| *direction == Direction::Export)
+                .map(|(_, interface)| interface)
+                .collect()
+        };
+
+        // Without generate! naming a world, wit-bindgen compiles world.wit
+        std::fs::write(path.join("wit/world.wit"), FUSION_WIT).unwrap();
+        assert_eq!(
+            exports(&path),
+            ["adas:sensor-fusion/fusion-engine", "adas:diagnostics/health-monitoring"]
+        );
+
+        // A world named in generate! is looked up across the WIT directory
+        let two_worlds = r#"package adas:sensor-fusion;
+
+world headless {
+    export fusion-engine;
+}
+
+world sensor-fusion {
+    export adas:diagnostics/health-monitoring;
+}
+"#;
+        std::fs::write(path.join("wit/world.wit"), two_worlds).unwrap();
+        std::fs::create_dir_all(path.join("src")).unwrap();
+        std::fs::write(
+            path.join("src/lib.rs"),
+            "wit_bindgen::generate!({\n    world: \"headless\",\n    path: \"wit/\",\n});\n",
+        )
+        .unwrap();
+        assert_eq!(exports(&path), ["adas:sensor-fusion/fusion-engine"]);
+
+        std::fs::write(path.join("src/lib.rs"), "wit_bindgen::generate!(\"missing\");\n").unwrap();
+        assert!(world_items(&path).is_err());
+    }
+}
@@ -7,18 +7,37 @@ use anyhow::{Context, Result};
 use serde::{Deserialize, Serialize};
 use std::collections::{HashMap, HashSet};
 use std::path::{Path, PathBuf};
-use tracing::{debug, info, warn};
+use tracing::{debug, info, info_span, instrument, warn};
 
+pub mod asil;
+pub mod budget;
 pub mod component;
 pub mod composition;
 pub mod config;
+pub mod error;
+pub mod graph;
+pub mod inventory;
 pub mod pipeline;
+pub mod schedule;
+pub mod smoke;
 pub mod validation;
+pub mod watch;
 
-pub use component::{Component, ComponentCategory, ComponentMetadata};
-pub use composition::{WacComposer, CompositionConfig};
-pub use config::{BuildConfig, BuildProfile};
-pub use pipeline::{BuildPipeline, BuildResult};
+pub use asil::AsilLevel;
+pub use budget::{BudgetPolicy, ResourceUsage};
+pub use component::{Component, ComponentCategory, ComponentFilter, ComponentMetadata};
+pub use composition::{
+    ArtifactDigest, ComposedWorld, CompositionConfig, CompositionManifest, CompositionStatus, ImportSource,
+    IncompatibleInterfaces, ManifestComponent, NamedComposition, UnsatisfiedImport, UnsatisfiedImports,
+    VersionMismatch, WacComposer, WiringLink,
+};
+pub use config::{BuildConfig, BuildProfile, ComponentFeatures, ConfigFormat, ProfileFeatures};
+pub use error::BuildError;
+pub use graph::{DependencyGraph, GraphFormat};
+pub use inventory::{InterfaceInventory, InterfaceUsage};
+pub use pipeline::{BuildEvent, BuildPipeline, BuildResult, ComponentStatus, PlannedBuild};
+pub use smoke::{SmokeTest, SmokeTestOutcome};
+pub use tokio_util::sync::CancellationToken;
 pub use validation::{ValidationResult, Validator};
 
 /// The main build orchestrator for ADAS components
@@ -39,19 +58,21 @@ pub struct AdasBuildSystem {
 
 impl AdasBuildSystem {
     /// Create a new build system instance
-    pub fn new(workspace_root: impl AsRef<Path>) -> Result<Self> {
+    #[instrument(name = "init", skip_all, fields(workspace = %workspace_root.as_ref().display()))]
+    pub fn new(workspace_root: impl AsRef<Path>) -> Result<Self, BuildError> {
         let workspace_root = workspace_root.as_ref();
         info!("Initializing ADAS build system at: {}", workspace_root.display());
         
         // Load build configuration
-        let config = BuildConfig::load(workspace_root)?;
+        let config = BuildConfig::load(workspace_root).map_err(BuildError::Config)?;
         
         // Discover components
-        let components = component::discover_components(workspace_root)?;
-        info!("Discovered {} components", components.len());
+        let components = info_span!("discover").in_scope(|| component::discover_components(&config))
+            .map_err(BuildError::Discovery)?;
+        info!(components = components.len(), "Discovered {} components", components.len());
         
         // Create build pipeline
-        let pipeline = BuildPipeline::new(&config, &components)?;
+        let pipeline = BuildPipeline::new(&config, &components).map_err(BuildError::Config)?;
         
         // Create validator
         let validator = Validator::new(&config);
@@ -64,30 +85,77 @@ impl AdasBuildSystem {
         })
     }
     
+    /// Components this build system works on, after the workspace's component filter
+    pub fn components(&self) -> &[Component] {
+        &self.components
+    }
+    
     /// Build all components
-    pub async fn build_all(&mut self, profile: BuildProfile) -> Result<BuildResult> {
+    ///
+    /// A component that fails to compile does not fail the call; it is listed
+    /// in the returned result so the artifacts of the others stay available.
+    /// [`BuildResult::compile_error`] turns it into a [`BuildError::Compile`].
+    /// A component whose cargo run cannot even be started is listed the same way.
+    pub async fn build_all(&mut self, profile: BuildProfile) -> Result<BuildResult, BuildError> {
+        self.build_all_with_cancellation(profile, CancellationToken::new()).await
+    }
+    
+    /// Build all components, aborting cleanly when `cancel` is triggered
+    ///
+    /// Cancelling stops new component builds from launching and kills the
+    /// in-flight cargo process; unfinished components are reported as
+    /// [`ComponentStatus::Cancelled`] in the returned result.
+    pub async fn build_all_with_cancellation(
+        &mut self,
+        profile: BuildProfile,
+        cancel: CancellationToken,
+    ) -> Result<BuildResult, BuildError> {
+        let (events, _) = tokio::sync::mpsc::unbounded_channel();
+        self.build_all_with_progress(profile, cancel, events).await
+    }
+    
+    /// Build all components, reporting live progress as [`BuildEvent`]s on `events`
+    ///
+    /// Runs in a `build` span; validation, every component's compile and
+    /// smoke test get child spans carrying the component name.
+    #[instrument(name = "build", skip_all, fields(profile = ?profile))]
+    pub async fn build_all_with_progress(
+        &mut self,
+        profile: BuildProfile,
+        cancel: CancellationToken,
+        events: tokio::sync::mpsc::UnboundedSender<BuildEvent>,
+    ) -> Result<BuildResult, BuildError> {
         info!("Building all components with profile: {:?}", profile);
         
         // Validate components first
-        self.validate_all()?;
-        
-        // Execute build pipeline
-        let result = self.pipeline.execute(profile).await?;
-        
-        info!("Build completed: {} succeeded, {} failed", 
+        self.validate_all().map_err(BuildError::Validation)?;
+        
+        // Execute build pipeline; per-component failures are part of the result,
+        // so an error here means the build graph itself is unusable
+        let mut result = self.pipeline.execute_with_progress(profile, cancel, events).await
+            .map_err(BuildError::from_pipeline)?;
+        self.validate_artifacts(&mut result).map_err(BuildError::Validation)?;
+        
+        info!(
+            succeeded = result.successful_components.len(),
+            failed = result.failed_components.len(),
+            cancelled = result.cancelled_components.len(),
+            "Build completed: {} succeeded, {} failed, {} cancelled",
             result.successful_components.len(),
-            result.failed_components.len()
+            result.failed_components.len(),
+            result.cancelled_components.len()
         );
         
         Ok(result)
     }
     
     /// Build specific components
+    #[instrument(name = "build", skip_all, fields(profile = ?profile, components = ?component_names))]
     pub async fn build_components(
         &mut self,
         component_names: &[String],
         profile: BuildProfile,
-    ) -> Result<BuildResult> {
+    ) -> Result<BuildResult, BuildError> {
         info!("Building components: {:?} with profile: {:?}", component_names, profile);
         
         // Filter components
@@ -98,39 +166,167 @@ impl AdasBuildSystem {
             .collect();
         
         if components.is_empty() {
-            anyhow::bail!("No matching components found");
+            return Err(BuildError::Discovery(anyhow::anyhow!("No matching components found")));
         }
         
         // Create filtered pipeline
-        let mut pipeline = BuildPipeline::new(&self.config, &components)?;
+        let mut pipeline = BuildPipeline::new(&self.config, &components).map_err(BuildError::Config)?;
         
         // Execute build
-        let result = pipeline.execute(profile).await?;
+        let mut result = pipeline.execute(profile).await.map_err(BuildError::from_pipeline)?;
+        self.validate_artifacts(&mut result).map_err(BuildError::Validation)?;
         
         Ok(result)
     }
     
+    /// Build all components, then rebuild whenever their sources change
+    ///
+    /// Runs until interrupted with Ctrl-C; see
+    /// [`watch_with_cancellation`](Self::watch_with_cancellation).
+    pub async fn watch(&mut self, profile: BuildProfile, on_result: impl Fn(BuildResult)) -> Result<(), BuildError> {
+        let cancel = CancellationToken::new();
+        let interrupt = cancel.clone();
+        tokio::spawn(async move {
+            if tokio::signal::ctrl_c().await.is_ok() {
+                interrupt.cancel();
+            }
+        });
+        self.watch_with_cancellation(profile, cancel, on_result).await
+    }
+    
+    /// Build all components, then rebuild whenever their sources change, until `cancel` is triggered
+    ///
+    /// Each component's `Cargo.toml`, `src/` and `wit/` trees and the shared
+    /// WIT directory are watched. Bursts of changes are debounced into one
+    /// rebuild cycle, which rediscovers the components and rebuilds the
+    /// changed ones and their dependents through the build cache.
+    /// `on_result` receives the result of the initial build and of every
+    /// cycle. A cycle that fails, e.g. on validation, is logged and watching
+    /// continues; only failing to set up the file watcher is an error.
+    #[instrument(name = "watch", skip_all, fields(profile = ?profile))]
+    pub async fn watch_with_cancellation(
+        &mut self,
+        profile: BuildProfile,
+        cancel: CancellationToken,
+        on_result: impl Fn(BuildResult),
+    ) -> Result<(), BuildError> {
+        let roots = [self.config.components_path(), self.config.wit_path()];
+        let mut watcher = watch::SourceWatcher::new(&roots, watch::DEBOUNCE).map_err(BuildError::Config)?;
+        
+        match self.build_all_with_cancellation(profile, cancel.clone()).await {
+            Ok(result) => on_result(result),
+            Err(e) => warn!("Initial build failed: {}", e),
+        }
+        info!("Watching {} components for changes", self.components.len());
+        
+        loop {
+            let changed = tokio::select! {
+                changed = watcher.next_batch() => changed,
+                _ = cancel.cancelled() => None,
+            };
+            let Some(changed) = changed else {
+                break;
+            };
+            
+            match self.rebuild_changed(profile, &changed, &cancel).await {
+                Ok(Some(result)) => on_result(result),
+                Ok(None) => debug!("Ignoring {} changed paths outside any component's inputs", changed.len()),
+                Err(e) => warn!("Rebuild failed: {}", e),
+            }
+        }
+        
+        info!("Watch mode stopped");
+        Ok(())
+    }
+    
+    /// One watch cycle: rebuild the components affected by `changed`
+    ///
+    /// Returns `None` if no component's inputs changed.
+    #[instrument(name = "rebuild", skip_all, fields(changed = changed.len()))]
+    async fn rebuild_changed(
+        &mut self,
+        profile: BuildProfile,
+        changed: &std::collections::BTreeSet<PathBuf>,
+        cancel: &CancellationToken,
+    ) -> Result<Option<BuildResult>, BuildError> {
+        // Manifests may have changed dependencies, and components may have been added
+        self.components = component::discover_components(&self.config)
+            .map_err(BuildError::Discovery)?;
+        self.pipeline = BuildPipeline::new(&self.config, &self.components).map_err(BuildError::Config)?;
+        
+        let affected = watch::affected_components(&self.components, &self.config.wit_path(), changed);
+        if affected.is_empty() {
+            return Ok(None);
+        }
+        let components = watch::rebuild_set(&self.components, &affected).map_err(BuildError::Config)?;
+        info!(
+            "Rebuilding after changes to {}",
+            affected.iter().map(|&i| self.components[i].name.as_str()).collect::<Vec<_>>().join(", ")
+        );
+        
+        self.validate_all().map_err(BuildError::Validation)?;
+        let mut pipeline = BuildPipeline::new(&self.config, &components).map_err(BuildError::Config)?;
+        let mut result = pipeline.execute_with_cancellation(profile, cancel.clone()).await
+            .map_err(BuildError::from_pipeline)?;
+        self.validate_artifacts(&mut result).map_err(BuildError::Validation)?;
+        
+        Ok(Some(result))
+    }
+    
     /// Compose components using WAC
+    ///
+    /// Composition is skipped when the output is up to date with its inputs
+    /// unless `force` is set.
     #[cfg(feature = "wac-composition")]
+    #[instrument(name = "compose", skip_all, fields(output = %output_path.as_ref().display()))]
     pub async fn compose_components(
         &self,
         output_path: impl AsRef<Path>,
         composition_config: Option<CompositionConfig>,
-    ) -> Result<()> {
+        force: bool,
+    ) -> Result<CompositionStatus, BuildError> {
         info!("Composing components to: {}", output_path.as_ref().display());
         
-        let config = composition_config.unwrap_or_else(|| {
-            CompositionConfig::from_workspace(&self.config)
-        });
+        let config = match composition_config {
+            Some(config) => config,
+            None => CompositionConfig::from_workspace(&self.config).map_err(BuildError::Config)?,
+        };
+        self.validate_composition_asil(&config).map_err(BuildError::Validation)?;
         
-        let composer = WacComposer::new(&self.config, config)?;
-        composer.compose(&self.components, output_path).await?;
+        let composer = WacComposer::new(&self.config, config).map_err(BuildError::Composition)?;
+        let status = composer.compose(&self.components, output_path.as_ref(), force).await
+            .map_err(BuildError::Composition)?;
         
-        info!("Composition completed successfully");
-        Ok(())
+        self.validate_composed_size(output_path.as_ref()).map_err(BuildError::Validation)?;
+        
+        info!("Composition completed: {:?}", status);
+        Ok(status)
+    }
+    
+    /// Compose the named composition `name` from the composition manifest
+    #[cfg(feature = "wac-composition")]
+    #[instrument(name = "compose", skip_all, fields(composition = name, output = %output_path.as_ref().display()))]
+    pub async fn compose_named(
+        &self,
+        name: &str,
+        output_path: impl AsRef<Path>,
+        force: bool,
+    ) -> Result<CompositionStatus, BuildError> {
+        let config = CompositionConfig::from_workspace(&self.config).map_err(BuildError::Config)?;
+        let named = config.named(name).map_err(BuildError::Config)?;
+        self.validate_composition_asil(&named).map_err(BuildError::Validation)?;
+        let composer = WacComposer::new(&self.config, config).map_err(BuildError::Composition)?;
+        let status = composer.compose_named(name, &self.components, output_path.as_ref(), force).await
+            .map_err(BuildError::Composition)?;
+        
+        self.validate_composed_size(output_path.as_ref()).map_err(BuildError::Validation)?;
+        
+        info!("Composition `{}` completed: {:?}", name, status);
+        Ok(status)
     }
     
     /// Validate all components
+    #[instrument(name = "validate", skip_all, fields(components = self.components.len()))]
     pub fn validate_all(&self) -> Result<Vec<ValidationResult>> {
         info!("Validating all components");
         
@@ -138,16 +334,29 @@ impl AdasBuildSystem {
         let mut has_errors = false;
         
         for component in &self.components {
+            let _span = info_span!("validate_component", component = %component.name).entered();
             let result = self.validator.validate_component(component)?;
             
             if result.has_errors() {
                 has_errors = true;
                 warn!("Component {} has validation errors", component.name);
+                for finding in result.errors() {
+                    warn!("  [{}] {}", finding.rule, finding.message);
+                }
             }
             
             results.push(result);
         }
         
+        let health_monitoring = self.validator.validate_health_monitoring(&self.components);
+        if health_monitoring.has_errors() {
+            has_errors = true;
+            for finding in health_monitoring.errors() {
+                warn!("[{}] {}", finding.rule, finding.message);
+            }
+        }
+        results.push(health_monitoring);
+        
         if has_errors {
             anyhow::bail!("Validation failed for one or more components");
         }
@@ -156,6 +365,16 @@ impl AdasBuildSystem {
         Ok(results)
     }
     
+    /// Map every WIT interface to the components that export and import it
+    pub fn interface_inventory(&self) -> Result<InterfaceInventory> {
+        InterfaceInventory::build(&self.components)
+    }
+    
+    /// Render how components connect through their WIT imports and exports
+    pub fn export_dependency_graph(&self, format: GraphFormat) -> Result<String> {
+        Ok(DependencyGraph::build(&self.components)?.render(format))
+    }
+    
     /// Get build status
     pub fn status(&self) -> BuildStatus {
         BuildStatus {
@@ -167,6 +386,7 @@ impl AdasBuildSystem {
     }
     
     /// Clean build artifacts
+    #[instrument(name = "clean", skip_all, fields(deep = deep))]
     pub async fn clean(&self, deep: bool) -> Result<()> {
         info!("Cleaning build artifacts (deep: {})", deep);
         
@@ -204,6 +424,49 @@ impl AdasBuildSystem {
     
     // Private helper methods
     
+    /// Check the ASIL levels of the components `config` composes
+    #[cfg(feature = "wac-composition")]
+    #[instrument(name = "validate_asil", skip_all)]
+    fn validate_composition_asil(&self, config: &CompositionConfig) -> Result<()> {
+        let included: Vec<Component> = self.components
+            .iter()
+            .filter(|c| config.components.is_empty() || config.components.contains(&c.name))
+            .cloned()
+            .collect();
+        
+        let result = self.validator.validate_asil(&included);
+        for finding in result.warnings() {
+            warn!("[{}] {}", finding.rule, finding.message);
+        }
+        if let Some(finding) = result.errors().next() {
+            anyhow::bail!("Composition fails its ASIL requirement: {}", finding.message);
+        }
+        Ok(())
+    }
+    
+    /// Check a composed artifact against its size budget
+    #[cfg(feature = "wac-composition")]
+    fn validate_composed_size(&self, output_path: &Path) -> Result<()> {
+        let size_check = self.validator.validate_composed_size(output_path)?;
+        if let Some(finding) = size_check.errors().next() {
+            anyhow::bail!("Composed artifact exceeds its size budget: {}", finding.message);
+        }
+        Ok(())
+    }
+    
+    #[instrument(name = "validate_artifacts", skip_all)]
+    fn validate_artifacts(&self, result: &mut BuildResult) -> Result<()> {
+        for (name, artifact) in &result.artifacts {
+            let _span = info_span!("validate_artifact", component = %name).entered();
+            let validation = self.validator.validate_artifact_size(name, artifact)?;
+            for finding in validation.errors() {
+                warn!("[{}] {}", finding.rule, finding.message);
+            }
+            result.validation.push(validation);
+        }
+        Ok(())
+    }
+    
     fn components_by_category(&self) -> HashMap<ComponentCategory, usize> {
         let mut map = HashMap::new();
         for component in &self.components {
@@ -212,6 +475,10 @@ impl AdasBuildSystem {
         map
     }
     
+    fn tested_components(&self) -> usize {
+        self.components.iter().filter(|c| c.has_tests).count()
+    }
+    
     async fn clean_wasm_files(&self, target_dir: &Path) -> Result<()> {
         use tokio::fs;
         use futures::stream::{self, StreamExt};
@@ -242,6 +509,7 @@ impl AdasBuildSystem {
             timestamp: chrono::Utc::now(),
             workspace_root: self.config.workspace_root.clone(),
             total_components: self.components.len(),
+            tested_components: self.tested_components(),
             components: self.components.clone(),
             configuration: self.config.clone(),
         };
@@ -255,7 +523,12 @@ impl AdasBuildSystem {
         report.push_str("# ADAS Build System Report\n\n");
         
         report.push_str(&format!("**Workspace**: `{}`\n", self.config.workspace_root.display()));
-        report.push_str(&format!("**Total Components**: {}\n\n", self.components.len()));
+        report.push_str(&format!("**Total Components**: {}\n", self.components.len()));
+        report.push_str(&format!(
+            "**Components With Tests**: {} / {}\n\n",
+            self.tested_components(),
+            self.components.len()
+        ));
         
         report.push_str("## Components by Category\n\n");
         for (category, count) in self.components_by_category() {
@@ -263,16 +536,17 @@ impl AdasBuildSystem {
         }
         
         report.push_str("\n## Component List\n\n");
-        report.push_str("| Name | Category | Safety Level | Dependencies |\n");
-        report.push_str("|------|----------|--------------|-------------|\n");
+        report.push_str("| Name | Category | Safety Level | Dependencies | Tested |\n");
+        report.push_str("|------|----------|--------------|--------------|--------|\n");
         
         for component in &self.components {
             report.push_str(&format!(
-                "| {} | {:?} | {} | {} |\n",
+                "| {} | {:?} | {} | {} | {} |\n",
                 component.name,
                 component.category,
                 component.metadata.safety_level.as_deref().unwrap_or("N/A"),
-                component.dependencies.len()
+                component.dependencies.len(),
+                if component.has_tests { "yes" } else { "no" }
             ));
         }
         
@@ -324,6 +598,7 @@ pub struct BuildReport {
     pub timestamp: chrono::DateTime<chrono::Utc>,
     pub workspace_root: PathBuf,
     pub total_components: usize,
+    pub tested_components: usize,
     pub components: Vec<Component>,
     pub configuration: BuildConfig,
 }
@@ -360,6 +635,6 @@ mod tests {
         let result = AdasBuildSystem::new(temp_dir.path());
         
         // Should handle missing workspace gracefully
-        assert!(result.is_err());
+        assert!(matches!(result, Err(BuildError::Config(_))));
     }
 }
\ No newline at end of file
@@ -0,0 +1,1638 @@
+//! Build pipeline for compiling ADAS components to WebAssembly
+//!
+//! The pipeline drives `cargo` for every discovered component and collects
+//! the per-component outcome into a [`BuildResult`].
+
+use anyhow::{Context, Result};
+use command_group::AsyncCommandGroup;
+use futures::stream::{FuturesUnordered, StreamExt};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::borrow::Cow;
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::path::{Path, PathBuf};
+use std::process::Stdio;
+use std::time::{Duration, Instant};
+use tokio::io::AsyncReadExt;
+use tokio::sync::mpsc::UnboundedSender;
+use tokio_util::sync::CancellationToken;
+use tracing::{debug, info, info_span, instrument, warn, Instrument};
+
+use crate::budget::{BudgetPolicy, ResourceUsage};
+use crate::component::Component;
+use crate::config::{BuildConfig, BuildProfile, ProfileFeatures};
+use crate::error::{BuildError, CompileFailure};
+use crate::graph::DependencyGraph;
+use crate::schedule::{crate_dependencies, describe_cycle, topological_order, BuildSchedule};
+pub use crate::smoke::{SmokeTest, SmokeTestOutcome};
+use crate::validation::ValidationResult;
+
+/// Default compilation target for ADAS components
+pub const DEFAULT_TARGET: &str = "wasm32-wasip1";
+
+/// Version of the JSON schema written by [`BuildResult::write_report`]
+pub const REPORT_SCHEMA_VERSION: &str = "1";
+
+/// Build cache file at the workspace root
+pub const CACHE_FILE_NAME: &str = ".adas-build-cache.json";
+
+/// Stderr fragments that indicate a failure unrelated to the source code
+const TRANSIENT_FAILURE_PATTERNS: &[&str] = &[
+    "Blocking waiting for file lock",
+    "spurious network error",
+    "failed to download",
+    "failed to get",
+    "Connection reset",
+    "connection refused",
+    "timed out",
+    "Resource temporarily unavailable",
+    "No space left on device",
+];
+
+/// Stderr fragments that indicate a genuine compile error
+const COMPILE_ERROR_PATTERNS: &[&str] = &["error[E", "could not compile", "error: expected", "error: cannot find"];
+
+/// Outcome of building a single component
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(tag = "status", content = "detail", rename_all = "snake_case")]
+pub enum ComponentStatus {
+    /// Component compiled successfully
+    Succeeded,
+    /// Compilation failed with the given error output
+    Failed(String),
+    /// Cargo was killed after running longer than the per-component timeout, in seconds
+    TimedOut(u64),
+    /// Build was cancelled before the component finished
+    Cancelled,
+    /// Inputs unchanged since the last successful build; not rebuilt
+    Skipped,
+    /// Would be built; the pipeline ran as a dry run
+    Planned,
+}
+
+impl ComponentStatus {
+    /// Status name as used in serialized output
+    pub fn label(&self) -> &'static str {
+        match self {
+            ComponentStatus::Succeeded => "succeeded",
+            ComponentStatus::Failed(_) => "failed",
+            ComponentStatus::TimedOut(_) => "timed_out",
+            ComponentStatus::Cancelled => "cancelled",
+            ComponentStatus::Skipped => "skipped",
+            ComponentStatus::Planned => "planned",
+        }
+    }
+}
+
+/// Result of a pipeline execution
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct BuildResult {
+    /// Components that compiled successfully
+    pub successful_components: Vec<String>,
+
+    /// Components that failed to compile, including those that timed out
+    pub failed_components: Vec<String>,
+
+    /// Components that were not built because the build was cancelled
+    pub cancelled_components: Vec<String>,
+
+    /// Components that were up to date and not rebuilt
+    pub skipped_components: Vec<String>,
+
+    /// Detailed status per component
+    pub component_status: BTreeMap<String, ComponentStatus>,
+
+    /// Number of retries needed per component (absent when built first time)
+    pub retry_counts: BTreeMap<String, u32>,
+
+    /// Artifact size and linear memory against budget per built component
+    pub resource_usage: BTreeMap<String, ResourceUsage>,
+
+    /// Wall-clock build time per component, including retries
+    pub durations_ms: BTreeMap<String, u64>,
+
+    /// Output artifact per component that built or was up to date
+    pub artifacts: BTreeMap<String, PathBuf>,
+
+    /// Target triple each entry was built for
+    pub targets: BTreeMap<String, String>,
+
+    /// Whether this is the plan of a dry run rather than a build
+    pub planned_only: bool,
+
+    /// Builds a dry run would perform, in launch order
+    pub plan: Vec<PlannedBuild>,
+
+    /// Post-build validation of the artifacts, e.g. against size budgets
+    pub validation: Vec<ValidationResult>,
+
+    /// Smoke test outcome per freshly built component, when smoke tests are enabled
+    pub smoke_tests: BTreeMap<String, SmokeTestOutcome>,
+}
+
+/// Progress notification sent while a pipeline runs
+///
+/// Events are sent from the task driving the pipeline, never from a build
+/// task. Every component entry gets exactly one `ComponentFinished`,
+/// preceded by `ComponentStarted` when cargo actually ran for it.
+/// Components are named by their [`BuildResult`] key.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum BuildEvent {
+    /// Cargo was launched for a component
+    ComponentStarted { name: String },
+
+    /// A component reached its final status; `duration` is zero if it was not built
+    ComponentFinished { name: String, success: bool, duration: Duration },
+
+    /// Every component of every target has finished
+    AllComplete { success: bool },
+}
+
+/// One component build a dry run would perform
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct PlannedBuild {
+    /// Result key of the component
+    pub component: String,
+
+    /// Target triple
+    pub target: String,
+
+    /// Parallel batch the build would run in; batch `n` waits for batch `n - 1`
+    pub batch: usize,
+
+    /// Exact cargo command line
+    pub command: String,
+}
+
+/// Top-level JSON build report
+#[derive(Serialize)]
+struct JsonReport<'a> {
+    schema_version: &'static str,
+    success: bool,
+    planned_only: bool,
+    components: Vec<ComponentReport<'a>>,
+}
+
+/// Per-component entry of the JSON build report
+#[derive(Serialize)]
+struct ComponentReport<'a> {
+    name: &'a str,
+    target: Option<&'a str>,
+    status: &'static str,
+    error: Option<Cow<'a, str>>,
+    duration_ms: Option<u64>,
+    artifact: Option<&'a Path>,
+    artifact_size: Option<u64>,
+    retries: u32,
+    smoke_test: Option<&'a SmokeTestOutcome>,
+}
+
+impl BuildResult {
+    /// Record the outcome of a single component
+    pub fn record(&mut self, component: &str, status: ComponentStatus) {
+        match status {
+            ComponentStatus::Succeeded => self.successful_components.push(component.to_string()),
+            ComponentStatus::Failed(_) | ComponentStatus::TimedOut(_) => {
+                self.failed_components.push(component.to_string())
+            }
+            ComponentStatus::Cancelled => self.cancelled_components.push(component.to_string()),
+            ComponentStatus::Skipped => self.skipped_components.push(component.to_string()),
+            ComponentStatus::Planned => {}
+        }
+        self.component_status.insert(component.to_string(), status);
+    }
+
+    /// Record that a component needed `retries` extra attempts
+    pub fn record_retries(&mut self, component: &str, retries: u32) {
+        if retries > 0 {
+            self.retry_counts.insert(component.to_string(), retries);
+        }
+    }
+
+    /// Whether every component built successfully and passed post-build validation and smoke tests
+    pub fn is_success(&self) -> bool {
+        self.failed_components.is_empty()
+            && self.cancelled_components.is_empty()
+            && !self.validation.iter().any(ValidationResult::has_errors)
+            && !self.smoke_tests.values().any(SmokeTestOutcome::is_failure)
+    }
+
+    /// First component that failed to compile, as a [`BuildError::Compile`]
+    pub fn compile_error(&self) -> Option<BuildError> {
+        let component = self.failed_components.first()?;
+        let message = match self.component_status.get(component) {
+            Some(ComponentStatus::Failed(message)) => message.clone(),
+            Some(ComponentStatus::TimedOut(secs)) => format!("cargo timed out after {}s", secs),
+            _ => String::new(),
+        };
+        Some(BuildError::Compile {
+            component: component.clone(),
+            message,
+        })
+    }
+
+    /// Whether the build was cancelled before all components finished
+    pub fn was_cancelled(&self) -> bool {
+        !self.cancelled_components.is_empty()
+    }
+
+    /// Write the result as a versioned JSON report for CI tooling
+    ///
+    /// Components are listed by name with their target, status, build duration,
+    /// artifact path and artifact size in bytes; fields that do not apply
+    /// (e.g. the artifact of a failed component) are `null`.
+    pub fn write_report(&self, path: &Path) -> Result<()> {
+        let components = self
+            .component_status
+            .iter()
+            .map(|(name, status)| ComponentReport {
+                name,
+                target: self.targets.get(name).map(String::as_str),
+                status: status.label(),
+                error: match status {
+                    ComponentStatus::Failed(error) => Some(Cow::Borrowed(error.as_str())),
+                    ComponentStatus::TimedOut(secs) => Some(Cow::Owned(format!("cargo timed out after {}s", secs))),
+                    _ => None,
+                },
+                duration_ms: self.durations_ms.get(name).copied(),
+                artifact: self.artifacts.get(name).map(PathBuf::as_path),
+                artifact_size: self.resource_usage.get(name).map(|usage| usage.artifact_size),
+                retries: self.retry_counts.get(name).copied().unwrap_or(0),
+                smoke_test: self.smoke_tests.get(name),
+            })
+            .collect();
+
+        let report = JsonReport {
+            schema_version: REPORT_SCHEMA_VERSION,
+            success: self.is_success(),
+            planned_only: self.planned_only,
+            components,
+        };
+
+        let content = serde_json::to_string_pretty(&report).context("Failed to serialize build report")?;
+        std::fs::write(path, content).with_context(|| format!("Failed to write {}", path.display()))
+    }
+}
+
+/// Input hashes of the last successful build of each component
+///
+/// A component whose `Cargo.toml`, `src/` tree, own `wit/` tree, referenced
+/// shared WIT packages and component dependencies all hash the same as at
+/// its last successful build, and whose artifact still exists, is skipped.
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct BuildCache {
+    /// Cache file location
+    #[serde(skip)]
+    path: PathBuf,
+
+    /// Input hash per component and profile
+    entries: BTreeMap<String, BTreeMap<BuildProfile, String>>,
+}
+
+impl BuildCache {
+    /// Load the cache of a workspace; a missing or unreadable cache is empty
+    pub fn load(workspace_root: &Path) -> Self {
+        let path = workspace_root.join(CACHE_FILE_NAME);
+        let mut cache = match std::fs::read_to_string(&path) {
+            Ok(content) => serde_json::from_str(&content).unwrap_or_else(|e| {
+                warn!("Ignoring corrupt build cache {}: {}", path.display(), e);
+                Self::default()
+            }),
+            Err(_) => Self::default(),
+        };
+        cache.path = path;
+        cache
+    }
+
+    /// Write the cache back to the workspace
+    pub fn save(&self) -> Result<()> {
+        let content = serde_json::to_string_pretty(self).context("Failed to serialize build cache")?;
+        std::fs::write(&self.path, content).with_context(|| format!("Failed to write {}", self.path.display()))
+    }
+
+    /// Whether `component` last built successfully from inputs hashing to `hash`
+    pub fn is_fresh(&self, component: &str, profile: BuildProfile, hash: &str) -> bool {
+        self.entries
+            .get(component)
+            .and_then(|profiles| profiles.get(&profile))
+            .map_or(false, |cached| cached == hash)
+    }
+
+    /// Record a successful build
+    pub fn record(&mut self, component: &str, profile: BuildProfile, hash: String) {
+        self.entries.entry(component.to_string()).or_default().insert(profile, hash);
+    }
+
+    /// Forget a component's last build for `profile`
+    pub fn invalidate(&mut self, component: &str, profile: BuildProfile) {
+        if let Some(profiles) = self.entries.get_mut(component) {
+            profiles.remove(&profile);
+        }
+    }
+}
+
+/// Hash every input that affects a component's build
+///
+/// `dependency_hashes` are the input hashes of the components it depends on,
+/// so a change in a dependency invalidates its dependents.
+pub fn input_hash(component: &Component, shared_wit: &Path, dependency_hashes: &[&str]) -> Result<String> {
+    let mut hasher = Sha256::new();
+
+    hash_file(&mut hasher, &component.path, &component.path.join("Cargo.toml"))?;
+    let build_script = component.path.join("build.rs");
+    if build_script.exists() {
+        hash_file(&mut hasher, &component.path, &build_script)?;
+    }
+    hash_tree(&mut hasher, &component.path, &component.path.join("src"), None)?;
+    hash_tree(&mut hasher, &component.path, &component.wit_dir(), None)?;
+
+    let packages = shared_wit_packages(shared_wit)?;
+    for dir in referenced_packages(&component.wit_dir(), &packages)? {
+        hash_tree(&mut hasher, shared_wit, &dir, Some("wit"))?;
+    }
+
+    for hash in dependency_hashes {
+        hasher.update(hash.as_bytes());
+    }
+
+    Ok(format!("{:x}", hasher.finalize()))
+}
+
+/// Shared WIT package directories keyed by package name (without version)
+///
+/// A package vendored into several `deps/` directories maps to all of them.
+fn shared_wit_packages(shared_wit: &Path) -> Result<BTreeMap<String, BTreeSet<PathBuf>>> {
+    let mut packages = BTreeMap::new();
+    if !shared_wit.is_dir() {
+        return Ok(packages);
+    }
+
+    for entry in walkdir::WalkDir::new(shared_wit).follow_links(true).sort_by_file_name() {
+        let entry = entry.with_context(|| format!("Failed to walk {}", shared_wit.display()))?;
+        if entry.path().extension().map_or(true, |ext| ext != "wit") {
+            continue;
+        }
+        let source = std::fs::read_to_string(entry.path())
+            .with_context(|| format!("Failed to read {}", entry.path().display()))?;
+        let declared = source
+            .lines()
+            .filter_map(|line| line.trim().strip_prefix("package "))
+            .map(|name| name.trim_end_matches(';').trim())
+            .next();
+        if let (Some(name), Some(dir)) = (declared, entry.path().parent()) {
+            let name = name.split('@').next().unwrap_or(name).to_string();
+            packages.entry(name).or_insert_with(BTreeSet::new).insert(dir.to_path_buf());
+        }
+    }
+
+    Ok(packages)
+}
+
+/// Shared package directories referenced from `wit_dir`, directly or through other shared packages
+fn referenced_packages(wit_dir: &Path, packages: &BTreeMap<String, BTreeSet<PathBuf>>) -> Result<BTreeSet<PathBuf>> {
+    let mut referenced = BTreeSet::new();
+    let mut pending = vec![wit_dir.to_path_buf()];
+
+    while let Some(dir) = pending.pop() {
+        let source = read_wit_sources(&dir)?;
+        for (name, package_dirs) in packages {
+            if !mentions_package(&source, name) {
+                continue;
+            }
+            for package_dir in package_dirs {
+                if referenced.insert(package_dir.clone()) {
+                    pending.push(package_dir.clone());
+                }
+            }
+        }
+    }
+
+    Ok(referenced)
+}
+
+/// Concatenated `.wit` sources under `dir`
+fn read_wit_sources(dir: &Path) -> Result<String> {
+    let mut source = String::new();
+    if !dir.is_dir() {
+        return Ok(source);
+    }
+    for entry in walkdir::WalkDir::new(dir).follow_links(true).sort_by_file_name() {
+        let entry = entry.with_context(|| format!("Failed to walk {}", dir.display()))?;
+        if entry.path().extension().map_or(false, |ext| ext == "wit") {
+            source.push_str(
+                &std::fs::read_to_string(entry.path())
+                    .with_context(|| format!("Failed to read {}", entry.path().display()))?,
+            );
+            source.push('\n');
+        }
+    }
+    Ok(source)
+}
+
+/// Whether `source` refers to package `name` (`adas:data` but not `adas:data-flow`)
+fn mentions_package(source: &str, name: &str) -> bool {
+    source.match_indices(name).any(|(at, _)| {
+        let before = source[..at].chars().next_back();
+        let after = source[at + name.len()..].chars().next();
+        !before.map_or(false, is_wit_ident_char) && !after.map_or(false, is_wit_ident_char)
+    })
+}
+
+fn is_wit_ident_char(c: char) -> bool {
+    c.is_ascii_alphanumeric() || c == '-' || c == '_' || c == ':'
+}
+
+/// Hash the files under `dir` (all files, or only those with `extension`)
+fn hash_tree(hasher: &mut Sha256, root: &Path, dir: &Path, extension: Option<&str>) -> Result<()> {
+    if !dir.is_dir() {
+        return Ok(());
+    }
+    for entry in walkdir::WalkDir::new(dir).follow_links(true).sort_by_file_name() {
+        let entry = entry.with_context(|| format!("Failed to walk {}", dir.display()))?;
+        let matches = extension.map_or(true, |ext| entry.path().extension().map_or(false, |e| e == ext));
+        if entry.file_type().is_file() && matches {
+            hash_file(hasher, root, entry.path())?;
+        }
+    }
+    Ok(())
+}
+
+fn hash_file(hasher: &mut Sha256, root: &Path, path: &Path) -> Result<()> {
+    let bytes = std::fs::read(path).with_context(|| format!("Failed to read {}", path.display()))?;
+    let relative = path.strip_prefix(root).unwrap_or(path);
+    hasher.update(relative.to_string_lossy().as_bytes());
+    hasher.update((bytes.len() as u64).to_le_bytes());
+    hasher.update(&bytes);
+    Ok(())
+}
+
+/// Pipeline that compiles a set of components
+#[derive(Debug)]
+pub struct BuildPipeline {
+    /// Workspace root used as the working directory for cargo
+    workspace_root: PathBuf,
+
+    /// Components built by this pipeline
+    components: Vec<Component>,
+
+    /// Maximum retries after a transient failure
+    max_retries: u32,
+
+    /// Backoff before the first retry
+    retry_base_delay: Duration,
+
+    /// Wall-clock limit of a single cargo invocation, if any
+    component_timeout: Option<Duration>,
+
+    /// Reaction to a component exceeding its resource budget
+    budget_policy: BudgetPolicy,
+
+    /// Maximum number of components built concurrently
+    max_parallel_jobs: usize,
+
+    /// Cancel in-flight and pending builds after the first failure
+    fail_fast: bool,
+
+    /// Shared WIT directory whose packages components may reference
+    shared_wit: PathBuf,
+
+    /// Rebuild every component regardless of the build cache
+    ignore_cache: bool,
+
+    /// Target triples every component is built for
+    targets: Vec<String>,
+
+    /// Component names in dependency order
+    build_order: Vec<String>,
+
+    /// Plan the build without running cargo
+    dry_run: bool,
+
+    /// Cargo features per component name and profile
+    features: HashMap<String, BTreeMap<BuildProfile, ProfileFeatures>>,
+
+    /// Post-build smoke test of freshly built components, if enabled
+    smoke_test: Option<SmokeTest>,
+}
+
+/// Final outcome of one component within a pipeline run
+#[derive(Debug)]
+struct ComponentOutcome {
+    status: ComponentStatus,
+    retries: u32,
+    resource_usage: Option<ResourceUsage>,
+    duration: Option<Duration>,
+    artifact: Option<PathBuf>,
+    planned_batch: Option<usize>,
+}
+
+impl ComponentOutcome {
+    /// Outcome of a component that was never built
+    fn unbuilt(status: ComponentStatus) -> Self {
+        Self {
+            status,
+            retries: 0,
+            resource_usage: None,
+            duration: None,
+            artifact: None,
+            planned_batch: None,
+        }
+    }
+}
+
+/// Outcome of a single cargo invocation
+#[derive(Debug)]
+struct BuildAttempt {
+    status: ComponentStatus,
+    exit_code: Option<i32>,
+}
+
+impl BuildPipeline {
+    /// Create a pipeline for the given components
+    ///
+    /// Fails if the components depend on each other in a cycle, through
+    /// crate dependencies or through imports of each other's exported WIT
+    /// interfaces; the error names every component on the cycle in order.
+    pub fn new(config: &BuildConfig, components: &[Component]) -> Result<Self> {
+        if config.targets.is_empty() {
+            anyhow::bail!("No build targets configured");
+        }
+
+        let build_order = match topological_order(&component_dependencies(components)?) {
+            Ok(order) => order.into_iter().map(|i| components[i].name.clone()).collect(),
+            Err(cycle) => anyhow::bail!("Circular component dependency: {}", describe_cycle(components, &cycle)),
+        };
+
+        Ok(Self {
+            workspace_root: config.workspace_root.clone(),
+            components: components.to_vec(),
+            max_retries: config.max_retries,
+            retry_base_delay: Duration::from_millis(config.retry_base_delay_ms),
+            component_timeout: config.component_timeout_secs.map(Duration::from_secs),
+            budget_policy: config.budget_policy,
+            max_parallel_jobs: config.max_parallel_jobs.max(1),
+            fail_fast: config.fail_fast,
+            shared_wit: config.wit_path(),
+            ignore_cache: config.ignore_cache,
+            targets: config.targets.clone(),
+            build_order,
+            dry_run: config.dry_run,
+            smoke_test: config
+                .run_smoke_tests
+                .then(|| SmokeTest::new(config.smoke_test_latency_budget_ms)),
+            features: components
+                .iter()
+                .map(|c| {
+                    let profiles = [BuildProfile::Debug, BuildProfile::Release]
+                        .into_iter()
+                        .map(|profile| (profile, config.features_for(&c.name, profile)))
+                        .collect();
+                    (c.name.clone(), profiles)
+                })
+                .collect(),
+        })
+    }
+
+    /// Component names ordered so each follows every component it depends on
+    pub fn build_order(&self) -> Vec<String> {
+        self.build_order.clone()
+    }
+
+    /// Build all components in the pipeline
+    pub async fn execute(&mut self, profile: BuildProfile) -> Result<BuildResult> {
+        self.execute_with_cancellation(profile, CancellationToken::new()).await
+    }
+
+    /// Build all components, stopping early when `cancel` is triggered
+    ///
+    /// Every component is built once per configured target. With a single
+    /// target, result entries are keyed by component name; with several they
+    /// are keyed `component@target`. Fails before building anything if a
+    /// target is not installed.
+    ///
+    /// Components whose inputs are unchanged since their last successful
+    /// build are reported as [`ComponentStatus::Skipped`] unless the cache
+    /// is ignored. Up to `max_parallel_jobs` components build at once; a
+    /// component starts only after every component it depends on has built
+    /// for the same target, and is reported as failed if one of them did
+    /// not. Results are listed in pipeline order regardless of completion
+    /// order.
+    ///
+    /// Once cancelled no further component builds are launched, the in-flight
+    /// cargo process groups are killed, and every unfinished component is
+    /// reported as [`ComponentStatus::Cancelled`]. With `fail_fast` the first
+    /// failure cancels the rest of the build the same way.
+    ///
+    /// As a dry run the same scheduling runs without cargo: every build is
+    /// assumed to succeed, components that would build are reported as
+    /// [`ComponentStatus::Planned`] and listed in [`BuildResult::plan`].
+    pub async fn execute_with_cancellation(
+        &mut self,
+        profile: BuildProfile,
+        cancel: CancellationToken,
+    ) -> Result<BuildResult> {
+        let (events, _) = tokio::sync::mpsc::unbounded_channel();
+        self.execute_with_progress(profile, cancel, events).await
+    }
+
+    /// Like [`execute_with_cancellation`](Self::execute_with_cancellation),
+    /// reporting live progress as [`BuildEvent`]s on `events`
+    ///
+    /// Sending stops silently once the receiver is dropped.
+    #[instrument(name = "pipeline", skip_all, fields(profile = ?profile, dry_run = self.dry_run))]
+    pub async fn execute_with_progress(
+        &mut self,
+        profile: BuildProfile,
+        cancel: CancellationToken,
+        events: UnboundedSender<BuildEvent>,
+    ) -> Result<BuildResult> {
+        // A plan is still useful without the toolchain it would need
+        if let Err(e) = self.ensure_targets_installed().await {
+            if !self.dry_run {
+                return Err(e);
+            }
+            warn!("{:#}", e);
+        }
+
+        let cancel = cancel.child_token();
+        let mut cache = BuildCache::load(&self.workspace_root);
+        let mut result = BuildResult {
+            planned_only: self.dry_run,
+            ..BuildResult::default()
+        };
+
+        let mut fresh_artifacts = Vec::new();
+        for target in &self.targets {
+            let outcomes = self.execute_target(target, profile, &cancel, &mut cache, &events).await?;
+            let mut plan = Vec::new();
+
+            for (index, outcome) in outcomes {
+                let component = &self.components[index];
+                let key = self.result_key(component, target);
+                if let Some(batch) = outcome.planned_batch {
+                    plan.push(PlannedBuild {
+                        component: key.clone(),
+                        target: target.clone(),
+                        batch,
+                        command: format!(
+                            "cargo {}",
+                            cargo_args(component, target, profile, self.features(component, profile)).join(" ")
+                        ),
+                    });
+                }
+                result.record_retries(&key, outcome.retries);
+                if let Some(usage) = outcome.resource_usage {
+                    result.resource_usage.insert(key.clone(), usage);
+                }
+                if let Some(duration) = outcome.duration {
+                    result.durations_ms.insert(key.clone(), duration.as_millis() as u64);
+                }
+                if let Some(artifact) = outcome.artifact {
+                    if outcome.status == ComponentStatus::Succeeded {
+                        fresh_artifacts.push((key.clone(), artifact.clone(), component.metadata.max_latency_ms));
+                    }
+                    result.artifacts.insert(key.clone(), artifact);
+                }
+                result.targets.insert(key.clone(), target.clone());
+                result.record(&key, outcome.status);
+            }
+
+            plan.sort_by_key(|build| build.batch);
+            result.plan.extend(plan);
+        }
+
+        if let Some(smoke_test) = &self.smoke_test {
+            run_smoke_tests(smoke_test, fresh_artifacts, &mut result).await;
+        }
+
+        if self.dry_run {
+            info!("Dry run: {} builds planned, {} up to date", result.plan.len(), result.skipped_components.len());
+        } else if let Err(e) = cache.save() {
+            warn!("Failed to save build cache: {:#}", e);
+        }
+
+        if result.was_cancelled() {
+            warn!("Build cancelled: {} components not built", result.cancelled_components.len());
+        }
+
+        let _ = events.send(BuildEvent::AllComplete {
+            success: result.is_success(),
+        });
+        Ok(result)
+    }
+
+    /// Build every component for one target in dependency order
+    #[instrument(name = "target", skip_all, fields(target_triple = target))]
+    async fn execute_target(
+        &self,
+        target: &str,
+        profile: BuildProfile,
+        cancel: &CancellationToken,
+        cache: &mut BuildCache,
+        events: &UnboundedSender<BuildEvent>,
+    ) -> Result<BTreeMap<usize, ComponentOutcome>> {
+        let mut schedule = BuildSchedule::new(&self.components)?;
+        let mut hashes: HashMap<usize, String> = HashMap::new();
+        let mut outcomes: BTreeMap<usize, ComponentOutcome> = BTreeMap::new();
+        let mut running = FuturesUnordered::new();
+        let mut planned: Vec<usize> = Vec::new();
+        let mut batch = 0;
+
+        loop {
+            while !cancel.is_cancelled() && running.len() + planned.len() < self.max_parallel_jobs {
+                let Some(index) = schedule.next_ready() else {
+                    break;
+                };
+                let component = &self.components[index];
+                let cache_key = format!("{}@{}", component.name, target);
+
+                // A changed feature selection invalidates the artifact like any other input
+                let feature_args = self.features(component, profile).cargo_args().join(" ");
+                let mut dependency_hashes: Vec<&str> = schedule
+                    .dependencies(index)
+                    .iter()
+                    .filter_map(|d| hashes.get(d).map(String::as_str))
+                    .collect();
+                dependency_hashes.push(&feature_args);
+                // Failures from here on belong to this component; builds already running carry on
+                let hash = match input_hash(component, &self.shared_wit, &dependency_hashes) {
+                    Ok(hash) => hash,
+                    Err(e) => {
+                        warn!("Cannot hash inputs of {}: {:#}", component.name, e);
+                        let outcome = ComponentOutcome::unbuilt(ComponentStatus::Failed(format!("{:#}", e)));
+                        self.settle(&mut schedule, &mut outcomes, events, target, index, outcome, false);
+                        continue;
+                    }
+                };
+                let artifact = artifact_path(&self.workspace_root, component, target, profile);
+                let fresh = !self.ignore_cache && cache.is_fresh(&cache_key, profile, &hash) && artifact.exists();
+                hashes.insert(index, hash);
+
+                if fresh {
+                    info!(component = %component.name, "Component {} is up to date for {}", component.name, target);
+                    let (outcome, usable) = match ResourceUsage::measure(&artifact, &component.metadata) {
+                        Ok(usage) => {
+                            let mut outcome = ComponentOutcome::unbuilt(ComponentStatus::Skipped);
+                            outcome.resource_usage = Some(usage);
+                            outcome.artifact = Some(artifact);
+                            (outcome, true)
+                        }
+                        Err(e) => {
+                            cache.invalidate(&cache_key, profile);
+                            (ComponentOutcome::unbuilt(ComponentStatus::Failed(format!("{:#}", e))), false)
+                        }
+                    };
+                    self.settle(&mut schedule, &mut outcomes, events, target, index, outcome, usable);
+                    continue;
+                }
+
+                if self.dry_run {
+                    planned.push(index);
+                    continue;
+                }
+
+                debug!("Starting build of {} for {}", component.name, target);
+                let _ = events.send(BuildEvent::ComponentStarted {
+                    name: self.result_key(component, target),
+                });
+                running.push(async move { (index, self.build_one(component, target, profile, cancel).await) });
+            }
+
+            // A dry run launches the ready batch in full and assumes it succeeds
+            if self.dry_run {
+                if planned.is_empty() {
+                    break;
+                }
+                for index in planned.drain(..) {
+                    let mut outcome = ComponentOutcome::unbuilt(ComponentStatus::Planned);
+                    outcome.planned_batch = Some(batch);
+                    self.settle(&mut schedule, &mut outcomes, events, target, index, outcome, true);
+                }
+                batch += 1;
+                continue;
+            }
+
+            let Some((index, outcome)) = running.next().await else {
+                break;
+            };
+            let component = &self.components[index];
+            // One component's broken cargo run is its own failure, not the whole build's
+            let outcome = outcome.unwrap_or_else(|e| {
+                let message = match e.downcast::<CompileFailure>() {
+                    Ok(failure) => failure.message,
+                    Err(e) => format!("{:#}", e),
+                };
+                ComponentOutcome::unbuilt(ComponentStatus::Failed(message))
+            });
+            let cache_key = format!("{}@{}", component.name, target);
+
+            match &outcome.status {
+                ComponentStatus::Succeeded => info!(
+                    component = %component.name,
+                    duration_ms = outcome.duration.unwrap_or_default().as_millis() as u64,
+                    "Built component {} for {}",
+                    component.name,
+                    target
+                ),
+                ComponentStatus::Failed(_) => warn!("Component {} failed to build for {}", component.name, target),
+                ComponentStatus::TimedOut(secs) => {
+                    warn!("Component {} timed out after {}s building for {}", component.name, secs, target)
+                }
+                ComponentStatus::Cancelled => warn!("Build of {} for {} cancelled", component.name, target),
+                ComponentStatus::Skipped | ComponentStatus::Planned => {}
+            }
+
+            let succeeded = outcome.status == ComponentStatus::Succeeded;
+            match hashes.get(&index) {
+                Some(hash) if succeeded => cache.record(&cache_key, profile, hash.clone()),
+                _ => cache.invalidate(&cache_key, profile),
+            }
+            let failed = matches!(outcome.status, ComponentStatus::Failed(_) | ComponentStatus::TimedOut(_));
+            if failed && self.fail_fast && !cancel.is_cancelled() {
+                warn!("Cancelling remaining builds after {} failed", component.name);
+                cancel.cancel();
+            }
+            self.settle(&mut schedule, &mut outcomes, events, target, index, outcome, succeeded);
+        }
+
+        for index in schedule.pending() {
+            self.finish(&mut outcomes, events, target, index, ComponentOutcome::unbuilt(ComponentStatus::Cancelled));
+        }
+
+        Ok(outcomes)
+    }
+
+    /// Record a component's outcome and release, or fail, the components waiting on it
+    #[allow(clippy::too_many_arguments)]
+    fn settle(
+        &self,
+        schedule: &mut BuildSchedule,
+        outcomes: &mut BTreeMap<usize, ComponentOutcome>,
+        events: &UnboundedSender<BuildEvent>,
+        target: &str,
+        index: usize,
+        outcome: ComponentOutcome,
+        succeeded: bool,
+    ) {
+        self.finish(outcomes, events, target, index, outcome);
+
+        for blocked in schedule.complete(index, succeeded) {
+            let failed: Vec<&str> = schedule
+                .failed_dependencies(blocked)
+                .map(|d| self.components[d].name.as_str())
+                .collect();
+            warn!("Skipping {}: dependency {} failed", self.components[blocked].name, failed.join(", "));
+            let outcome = ComponentOutcome::unbuilt(ComponentStatus::Failed(format!(
+                "Dependency {} failed to build",
+                failed.join(", ")
+            )));
+            self.finish(outcomes, events, target, blocked, outcome);
+        }
+    }
+
+    /// Record a component's final outcome and report it on `events`
+    fn finish(
+        &self,
+        outcomes: &mut BTreeMap<usize, ComponentOutcome>,
+        events: &UnboundedSender<BuildEvent>,
+        target: &str,
+        index: usize,
+        outcome: ComponentOutcome,
+    ) {
+        let _ = events.send(BuildEvent::ComponentFinished {
+            name: self.result_key(&self.components[index], target),
+            success: matches!(
+                outcome.status,
+                ComponentStatus::Succeeded | ComponentStatus::Skipped | ComponentStatus::Planned
+            ),
+            duration: outcome.duration.unwrap_or_default(),
+        });
+        outcomes.insert(index, outcome);
+    }
+
+    /// Cargo features `component` is built with under `profile`
+    fn features(&self, component: &Component, profile: BuildProfile) -> &ProfileFeatures {
+        static NONE: ProfileFeatures = ProfileFeatures {
+            features: Vec::new(),
+            no_default_features: false,
+        };
+        self.features
+            .get(&component.name)
+            .and_then(|profiles| profiles.get(&profile))
+            .unwrap_or(&NONE)
+    }
+
+    /// Key of a component's entries in the [`BuildResult`]
+    fn result_key(&self, component: &Component, target: &str) -> String {
+        if self.targets.len() > 1 {
+            format!("{}@{}", component.name, target)
+        } else {
+            component.name.clone()
+        }
+    }
+
+    /// Fail with the names of configured targets that rustup has not installed
+    ///
+    /// Skipped with a warning when rustup is not available, e.g. with a
+    /// distribution-packaged toolchain.
+    async fn ensure_targets_installed(&self) -> Result<()> {
+        let Ok(rustup) = which::which("rustup") else {
+            warn!("rustup not found; not checking that {} are installed", self.targets.join(", "));
+            return Ok(());
+        };
+
+        let output = tokio::process::Command::new(rustup)
+            .args(["target", "list", "--installed"])
+            .current_dir(&self.workspace_root)
+            .output()
+            .await
+            .context("Failed to run rustup target list")?;
+        if !output.status.success() {
+            anyhow::bail!("rustup target list failed: {}", String::from_utf8_lossy(&output.stderr));
+        }
+
+        let missing = missing_targets(&String::from_utf8_lossy(&output.stdout), &self.targets);
+        if !missing.is_empty() {
+            anyhow::bail!(
+                "Target {} not installed; run `rustup target add {}`",
+                missing.join(", "),
+                missing.join(" ")
+            );
+        }
+
+        Ok(())
+    }
+
+    /// Build one component and measure the artifact against its budget
+    #[instrument(
+        name = "component",
+        skip_all,
+        fields(component = %component.name, target_triple = target, profile = ?profile)
+    )]
+    async fn build_one(
+        &self,
+        component: &Component,
+        target: &str,
+        profile: BuildProfile,
+        cancel: &CancellationToken,
+    ) -> Result<ComponentOutcome> {
+        let started = Instant::now();
+        let (mut status, retries) = self.build_with_retries(component, target, profile, cancel).await?;
+        let duration = started.elapsed();
+        debug!(status = status.label(), retries, duration_ms = duration.as_millis() as u64, "compile finished");
+        let mut resource_usage = None;
+        let mut built_artifact = None;
+
+        let artifact = artifact_path(&self.workspace_root, component, target, profile);
+        if status == ComponentStatus::Succeeded && artifact.exists() {
+            let usage = info_span!("measure")
+                .in_scope(|| ResourceUsage::measure(&artifact, &component.metadata))
+                .map_err(|e| CompileFailure::new(&component.name, e))?;
+            if let Some(failure) = check_budget(&component.name, &usage, self.budget_policy) {
+                status = ComponentStatus::Failed(failure);
+            } else {
+                built_artifact = Some(artifact);
+            }
+            resource_usage = Some(usage);
+        }
+
+        Ok(ComponentOutcome {
+            status,
+            retries,
+            resource_usage,
+            duration: Some(duration),
+            artifact: built_artifact,
+            planned_batch: None,
+        })
+    }
+
+    /// Compile one component, retrying transient failures with exponential backoff
+    ///
+    /// Returns the final status and the number of retries performed. A build
+    /// that timed out is not retried; the timeout applies to each attempt.
+    async fn build_with_retries(
+        &self,
+        component: &Component,
+        target: &str,
+        profile: BuildProfile,
+        cancel: &CancellationToken,
+    ) -> Result<(ComponentStatus, u32)> {
+        let mut retries = 0;
+
+        loop {
+            let attempt = self.build_component(component, target, profile, cancel).await?;
+
+            let ComponentStatus::Failed(stderr) = &attempt.status else {
+                return Ok((attempt.status, retries));
+            };
+
+            if retries >= self.max_retries || !is_transient_failure(attempt.exit_code, stderr) {
+                return Ok((attempt.status, retries));
+            }
+
+            let delay = self.retry_base_delay * 2u32.saturating_pow(retries);
+            retries += 1;
+            warn!(
+                "Transient failure building {}, retry {}/{} in {:?}",
+                component.name, retries, self.max_retries, delay
+            );
+
+            tokio::select! {
+                _ = tokio::time::sleep(delay) => {}
+                _ = cancel.cancelled() => return Ok((ComponentStatus::Cancelled, retries)),
+            }
+        }
+    }
+
+    /// Compile one component with cargo
+    #[instrument(name = "compile", skip_all)]
+    async fn build_component(
+        &self,
+        component: &Component,
+        target: &str,
+        profile: BuildProfile,
+        cancel: &CancellationToken,
+    ) -> Result<BuildAttempt> {
+        let args = cargo_args(component, target, profile, self.features(component, profile));
+        debug!("Running cargo {}", args.join(" "));
+
+        let mut command = tokio::process::Command::new("cargo");
+        command.args(&args).current_dir(&self.workspace_root);
+        run_supervised(command, &component.name, cancel, self.component_timeout).await
+    }
+}
+
+/// Run a cargo command until it exits, `cancel` fires or `timeout` elapses
+///
+/// The command is spawned in its own process group. On cancellation or
+/// timeout the whole group is killed, so rustc and build-script children
+/// die with cargo, and cargo itself is reaped.
+async fn run_supervised(
+    mut command: tokio::process::Command,
+    component: &str,
+    cancel: &CancellationToken,
+    timeout: Option<Duration>,
+) -> Result<BuildAttempt> {
+    // Errors running cargo are tagged with the component so they surface as compile failures
+    let tag = |e: std::io::Error, action: &str| {
+        CompileFailure::new(component, anyhow::Error::new(e).context(action.to_string()))
+    };
+    let mut child = command
+        .stdout(Stdio::null())
+        .stderr(Stdio::piped())
+        .group_spawn()
+        .map_err(|e| tag(e, "Failed to spawn cargo"))?;
+
+    let stderr_reader = child.inner().stderr.take().map(|mut stderr| {
+        tokio::spawn(async move {
+            let mut output = String::new();
+            let _ = stderr.read_to_string(&mut output).await;
+            output
+        })
+    });
+
+    let deadline = async {
+        match timeout {
+            Some(timeout) => tokio::time::sleep(timeout).await,
+            None => std::future::pending().await,
+        }
+    };
+
+    let status = tokio::select! {
+        status = child.wait() => {
+            let status = status.map_err(|e| tag(e, "Failed to wait for cargo"))?;
+            let exit_code = status.code();
+            if status.success() {
+                return Ok(BuildAttempt { status: ComponentStatus::Succeeded, exit_code });
+            }
+            let stderr = match stderr_reader {
+                Some(handle) => handle.await.unwrap_or_default(),
+                None => String::new(),
+            };
+            return Ok(BuildAttempt { status: ComponentStatus::Failed(stderr), exit_code });
+        }
+        _ = cancel.cancelled() => ComponentStatus::Cancelled,
+        _ = deadline => {
+            let secs = timeout.unwrap_or_default().as_secs();
+            warn!("Cargo building {} exceeded its {}s timeout; killing it", component, secs);
+            ComponentStatus::TimedOut(secs)
+        }
+    };
+
+    child.kill().await.map_err(|e| tag(e, "Failed to kill cargo"))?;
+    let _ = child.wait().await;
+    Ok(BuildAttempt { status, exit_code: None })
+}
+
+/// Crate dependencies plus importer -> exporter interface links, by component index
+pub(crate) fn component_dependencies(components: &[Component]) -> Result<Vec<Vec<usize>>> {
+    let mut dependencies = crate_dependencies(components);
+    let index: HashMap<&str, usize> = components.iter().enumerate().map(|(i, c)| (c.name.as_str(), i)).collect();
+
+    for link in DependencyGraph::build(components)?.links {
+        if let (Some(&importer), Some(&exporter)) = (index.get(link.importer.as_str()), index.get(link.exporter.as_str())) {
+            dependencies[importer].push(exporter);
+        }
+    }
+    for deps in &mut dependencies {
+        deps.sort_unstable();
+        deps.dedup();
+    }
+
+    Ok(dependencies)
+}
+
+/// Artifact cargo produces for `component` for `target` under `profile`
+pub fn artifact_path(workspace_root: &Path, component: &Component, target: &str, profile: BuildProfile) -> PathBuf {
+    let profile_dir = match profile {
+        BuildProfile::Debug => "debug",
+        BuildProfile::Release => "release",
+    };
+    workspace_root
+        .join("target")
+        .join(target)
+        .join(profile_dir)
+        .join(format!("{}.wasm", component.metadata.package_name.replace('-', "_")))
+}
+
+/// Report budget violations, returning the failure message under [`BudgetPolicy::Fail`]
+pub(crate) fn check_budget(component: &str, usage: &ResourceUsage, policy: BudgetPolicy) -> Option<String> {
+    let violations = usage.violations();
+    if violations.is_empty() {
+        return None;
+    }
+
+    let message = format!("{} exceeds its resource budget: {}", component, violations.join("; "));
+    match policy {
+        BudgetPolicy::Warn => {
+            warn!("{}", message);
+            None
+        }
+        BudgetPolicy::Fail => Some(message),
+    }
+}
+
+/// Smoke test each freshly built artifact, recording the outcomes in `result`
+///
+/// Each test runs on a blocking thread since wasmtime calls are synchronous.
+async fn run_smoke_tests(
+    smoke_test: &SmokeTest,
+    artifacts: Vec<(String, PathBuf, Option<f32>)>,
+    result: &mut BuildResult,
+) {
+    for (key, artifact, latency_budget_ms) in artifacts {
+        let tester = smoke_test.clone();
+        let outcome = tokio::task::spawn_blocking(move || tester.run(&artifact, latency_budget_ms))
+            .instrument(info_span!("smoke_test", component = %key))
+            .await
+            .unwrap_or_else(|e| SmokeTestOutcome::Failed(format!("smoke test panicked: {}", e)));
+
+        match &outcome {
+            SmokeTestOutcome::Passed { .. } => debug!("Smoke test of {} passed", key),
+            SmokeTestOutcome::Failed(reason) => warn!("Smoke test of {} failed: {}", key, reason),
+            SmokeTestOutcome::Skipped(reason) => info!("Smoke test of {} skipped: {}", key, reason),
+        }
+        result.smoke_tests.insert(key, outcome);
+    }
+}
+
+/// Requested targets missing from `rustup target list --installed` output
+fn missing_targets<'a>(installed: &str, requested: &'a [String]) -> Vec<&'a str> {
+    let installed: BTreeSet<&str> = installed.lines().map(str::trim).collect();
+    requested
+        .iter()
+        .map(String::as_str)
+        .filter(|target| !installed.contains(target))
+        .collect()
+}
+
+/// Whether a failed build is worth retrying
+///
+/// Compile errors are never transient. A process killed by a signal (no exit
+/// code) or stderr matching a known environmental failure is.
+fn is_transient_failure(exit_code: Option<i32>, stderr: &str) -> bool {
+    if COMPILE_ERROR_PATTERNS.iter().any(|p| stderr.contains(p)) {
+        return false;
+    }
+
+    exit_code.is_none() || TRANSIENT_FAILURE_PATTERNS.iter().any(|p| stderr.contains(p))
+}
+
+/// Build the cargo argument vector for a component
+fn cargo_args(component: &Component, target: &str, profile: BuildProfile, features: &ProfileFeatures) -> Vec<String> {
+    let mut args = vec![
+        "build".to_string(),
+        "--manifest-path".to_string(),
+        component.path.join("Cargo.toml").display().to_string(),
+        "--target".to_string(),
+        target.to_string(),
+    ];
+
+    if matches!(profile, BuildProfile::Release) {
+        args.push("--release".to_string());
+    }
+
+    args.extend(features.cargo_args());
+    args
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_build_result_records_cancelled() {
+        let mut result = BuildResult::default();
+        result.record("camera-front", ComponentStatus::Succeeded);
+        result.record("object-detection", ComponentStatus::Cancelled);
+
+        assert_eq!(result.successful_components, vec!["camera-front"]);
+        assert_eq!(result.cancelled_components, vec!["object-detection"]);
+        assert!(result.was_cancelled());
+        assert!(!result.is_success());
+    }
+
+    fn write(path: &Path, content: &str) {
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(path, content).unwrap();
+    }
+
+    fn component_at(root: &Path, name: &str, world: &str) -> Component {
+        let path = root.join("components").join(name);
+        write(&path.join("Cargo.toml"), &format!("[package]\nname = \"{}\"\n", name));
+        write(&path.join("src/lib.rs"), "// component\n");
+        write(&path.join("wit/world.wit"), world);
+        Component::load(&path, crate::component::ComponentCategory::Sensors).unwrap()
+    }
+
+    #[test]
+    fn test_shared_wit_change_invalidates_dependents() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let root = temp_dir.path();
+        let shared = root.join("wit");
+        write(&shared.join("interfaces/adas-data/data.wit"), "package adas:data;\ninterface sensor-data {}\n");
+        write(&shared.join("interfaces/adas-data-flow/flow.wit"), "package adas:data-flow;\ninterface flow {}\n");
+
+        let camera = component_at(root, "camera-front", "world camera { export adas:data/sensor-data; }\n");
+        let radar = component_at(root, "radar-front", "world radar { export adas:data/sensor-data; }\n");
+        let flow = component_at(root, "flow-monitor", "world flow { import adas:data-flow/flow; }\n");
+
+        let hashes = |c: &Component| input_hash(c, &shared, &[]).unwrap();
+        let before = [hashes(&camera), hashes(&radar), hashes(&flow)];
+        assert_eq!(before[0], hashes(&camera));
+
+        write(&shared.join("interfaces/adas-data/data.wit"), "package adas:data;\ninterface sensor-data { }\n");
+        let after = [hashes(&camera), hashes(&radar), hashes(&flow)];
+        assert_ne!(before[0], after[0]);
+        assert_ne!(before[1], after[1]);
+        assert_eq!(before[2], after[2]);
+
+        // A changed dependency changes the dependent's hash
+        assert_ne!(input_hash(&flow, &shared, &["a"]).unwrap(), input_hash(&flow, &shared, &["b"]).unwrap());
+    }
+
+    #[test]
+    fn test_build_cache_round_trip() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let mut cache = BuildCache::load(temp_dir.path());
+        assert!(!cache.is_fresh("camera-front", BuildProfile::Release, "abc"));
+
+        cache.record("camera-front", BuildProfile::Release, "abc".to_string());
+        cache.save().unwrap();
+
+        let mut cache = BuildCache::load(temp_dir.path());
+        assert!(cache.is_fresh("camera-front", BuildProfile::Release, "abc"));
+        assert!(!cache.is_fresh("camera-front", BuildProfile::Debug, "abc"));
+        assert!(!cache.is_fresh("camera-front", BuildProfile::Release, "def"));
+
+        cache.invalidate("camera-front", BuildProfile::Release);
+        assert!(!cache.is_fresh("camera-front", BuildProfile::Release, "abc"));
+
+        std::fs::write(temp_dir.path().join(CACHE_FILE_NAME), "not json").unwrap();
+        assert!(!BuildCache::load(temp_dir.path()).is_fresh("camera-front", BuildProfile::Release, "abc"));
+    }
+
+    #[test]
+    fn test_write_report() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let mut result = BuildResult::default();
+        result.record("camera-front", ComponentStatus::Succeeded);
+        result.durations_ms.insert("camera-front".to_string(), 1250);
+        result.artifacts.insert("camera-front".to_string(), PathBuf::from("target/camera_front.wasm"));
+        result.resource_usage.insert(
+            "camera-front".to_string(),
+            ResourceUsage {
+                artifact_size: 4096,
+                max_artifact_size: None,
+                linear_memory: 0,
+                max_linear_memory: None,
+            },
+        );
+        result.record("object-detection", ComponentStatus::Failed("error[E0308]".to_string()));
+        result.record_retries("object-detection", 1);
+
+        let path = temp_dir.path().join("build-report.json");
+        result.write_report(&path).unwrap();
+        let report: serde_json::Value = serde_json::from_str(&std::fs::read_to_string(&path).unwrap()).unwrap();
+
+        assert_eq!(report["schema_version"], REPORT_SCHEMA_VERSION);
+        assert_eq!(report["success"], false);
+        let camera = &report["components"][0];
+        assert_eq!(camera["name"], "camera-front");
+        assert_eq!(camera["status"], "succeeded");
+        assert_eq!(camera["duration_ms"], 1250);
+        assert_eq!(camera["artifact"], "target/camera_front.wasm");
+        assert_eq!(camera["artifact_size"], 4096);
+        let detection = &report["components"][1];
+        assert_eq!(detection["status"], "failed");
+        assert_eq!(detection["error"], "error[E0308]");
+        assert!(detection["artifact"].is_null());
+        assert_eq!(detection["retries"], 1);
+    }
+
+    #[tokio::test]
+    async fn test_dry_run_plans_batches() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let root = temp_dir.path();
+        let camera = component_at(root, "camera-front", "world camera {}\n");
+        let radar = component_at(root, "radar-front", "world radar {}\n");
+        let fusion_path = root.join("components/sensor-fusion");
+        write(
+            &fusion_path.join("Cargo.toml"),
+            "[package]\nname = \"sensor-fusion\"\n\n[dependencies]\ncamera-front = { path = \"../camera-front\" }\n",
+        );
+        write(&fusion_path.join("src/lib.rs"), "// component\n");
+        let fusion = Component::load(&fusion_path, crate::component::ComponentCategory::Fusion).unwrap();
+
+        let config = BuildConfig {
+            workspace_root: root.to_path_buf(),
+            dry_run: true,
+            max_parallel_jobs: 4,
+            ..BuildConfig::default()
+        };
+        let mut pipeline = BuildPipeline::new(&config, &[camera, radar, fusion]).unwrap();
+        let result = pipeline.execute(BuildProfile::Release).await.unwrap();
+
+        assert!(result.planned_only);
+        assert!(result.successful_components.is_empty());
+        assert_eq!(result.component_status["sensor-fusion"], ComponentStatus::Planned);
+        let batches: Vec<(&str, usize)> = result.plan.iter().map(|p| (p.component.as_str(), p.batch)).collect();
+        assert_eq!(batches, vec![("camera-front", 0), ("radar-front", 0), ("sensor-fusion", 1)]);
+        assert!(result.plan[2].command.starts_with("cargo build --manifest-path"));
+        assert!(result.plan[2].command.ends_with("--target wasm32-wasip1 --release"));
+        assert!(!root.join(CACHE_FILE_NAME).exists());
+    }
+
+    #[tokio::test]
+    async fn test_unreadable_component_fails_alone() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let root = temp_dir.path();
+        let camera = component_at(root, "camera-front", "world camera {}\n");
+        let radar = component_at(root, "radar-front", "world radar {}\n");
+        std::fs::remove_file(root.join("components/radar-front/Cargo.toml")).unwrap();
+
+        let config = BuildConfig {
+            workspace_root: root.to_path_buf(),
+            dry_run: true,
+            ..BuildConfig::default()
+        };
+        let mut pipeline = BuildPipeline::new(&config, &[camera, radar]).unwrap();
+        let result = pipeline.execute(BuildProfile::Release).await.unwrap();
+
+        assert_eq!(result.component_status["camera-front"], ComponentStatus::Planned);
+        assert!(matches!(&result.component_status["radar-front"],
+            ComponentStatus::Failed(message) if message.starts_with("Failed to read")));
+        assert_eq!(result.failed_components, vec!["radar-front".to_string()]);
+    }
+
+    #[tokio::test]
+    async fn test_progress_events() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let root = temp_dir.path();
+        let camera = component_at(root, "camera-front", "world camera {}\n");
+        let radar = component_at(root, "radar-front", "world radar {}\n");
+
+        let config = BuildConfig {
+            workspace_root: root.to_path_buf(),
+            dry_run: true,
+            ..BuildConfig::default()
+        };
+        let mut pipeline = BuildPipeline::new(&config, &[camera, radar]).unwrap();
+        let (sender, mut receiver) = tokio::sync::mpsc::unbounded_channel();
+        pipeline
+            .execute_with_progress(BuildProfile::Debug, CancellationToken::new(), sender)
+            .await
+            .unwrap();
+
+        let mut events = Vec::new();
+        while let Some(event) = receiver.recv().await {
+            events.push(event);
+        }
+        let finished = |name: &str| BuildEvent::ComponentFinished {
+            name: name.to_string(),
+            success: true,
+            duration: Duration::ZERO,
+        };
+        assert_eq!(
+            events,
+            vec![finished("camera-front"), finished("radar-front"), BuildEvent::AllComplete { success: true }]
+        );
+    }
+
+    #[test]
+    fn test_missing_targets() {
+        let installed = "wasm32-unknown-unknown\nwasm32-wasip1\nx86_64-unknown-linux-gnu\n";
+        let requested = vec!["wasm32-wasip1".to_string(), "wasm32-wasip2".to_string()];
+        assert_eq!(missing_targets(installed, &requested), vec!["wasm32-wasip2"]);
+        assert!(missing_targets(installed, &requested[..1]).is_empty());
+    }
+
+    #[test]
+    fn test_artifact_and_args_per_target() {
+        let component = Component {
+            name: "camera-front".to_string(),
+            category: crate::component::ComponentCategory::Sensors,
+            path: PathBuf::from("components/sensors/camera-front"),
+            metadata: crate::component::ComponentMetadata {
+                package_name: "adas-camera-front".to_string(),
+                ..Default::default()
+            },
+            dependencies: Vec::new(),
+            has_tests: false,
+        };
+
+        let artifact = artifact_path(Path::new("/ws"), &component, "wasm32-unknown-unknown", BuildProfile::Release);
+        assert_eq!(artifact, PathBuf::from("/ws/target/wasm32-unknown-unknown/release/adas_camera_front.wasm"));
+
+        let args = cargo_args(&component, "wasm32-unknown-unknown", BuildProfile::Debug, &ProfileFeatures::default());
+        assert!(args.windows(2).any(|w| w == ["--target", "wasm32-unknown-unknown"]));
+        assert!(!args.contains(&"--release".to_string()));
+    }
+
+    #[test]
+    fn test_profile_features_in_cargo_args() {
+        let mut config = BuildConfig::default();
+        config.profile_features.insert(
+            BuildProfile::Debug,
+            ProfileFeatures {
+                features: vec!["verbose-logging".to_string()],
+                no_default_features: false,
+            },
+        );
+        config.profile_features.insert(
+            BuildProfile::Release,
+            ProfileFeatures {
+                features: vec!["simd".to_string(), "verbose-logging".to_string()],
+                no_default_features: true,
+            },
+        );
+        config.component_features.insert(
+            "radar-front".to_string(),
+            crate::config::ComponentFeatures {
+                disable: vec!["simd".to_string()],
+            },
+        );
+        let components = vec![
+            linked_component("camera-front", &[], &[]),
+            linked_component("radar-front", &[], &[]),
+        ];
+        let pipeline = BuildPipeline::new(&config, &components).unwrap();
+        let args = |index: usize, profile| {
+            let component = &components[index];
+            cargo_args(component, DEFAULT_TARGET, profile, pipeline.features(component, profile))
+        };
+
+        let debug = args(0, BuildProfile::Debug);
+        assert_eq!(debug[debug.len() - 2..], ["--features", "verbose-logging"]);
+        assert!(!debug.contains(&"--no-default-features".to_string()));
+
+        let release = args(0, BuildProfile::Release);
+        assert_eq!(
+            release[release.len() - 4..],
+            ["--release", "--no-default-features", "--features", "simd,verbose-logging"]
+        );
+
+        // radar-front opts out of simd only
+        let release = args(1, BuildProfile::Release);
+        assert_eq!(release[release.len() - 3..], ["--no-default-features", "--features", "verbose-logging"]);
+    }
+
+    fn linked_component(name: &str, imports: &[&str], exports: &[&str]) -> Component {
+        Component {
+            name: name.to_string(),
+            category: crate::component::ComponentCategory::Fusion,
+            path: PathBuf::from(name),
+            metadata: crate::component::ComponentMetadata {
+                imports: imports.iter().map(|i| i.to_string()).collect(),
+                exports: exports.iter().map(|e| e.to_string()).collect(),
+                ..Default::default()
+            },
+            dependencies: Vec::new(),
+            has_tests: false,
+        }
+    }
+
+    #[test]
+    fn test_interface_cycle_rejected() {
+        let components = vec![
+            linked_component("camera-front", &[], &["adas:data/sensor-data"]),
+            linked_component("sensor-fusion", &["adas:data/sensor-data", "adas:control/tracking"], &["adas:data/perception-data"]),
+            linked_component("planner", &["adas:data/perception-data"], &["adas:control/planning"]),
+            linked_component("tracker", &["adas:control/planning"], &["adas:control/tracking"]),
+        ];
+
+        let err = BuildPipeline::new(&BuildConfig::default(), &components).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Circular component dependency: sensor-fusion -> tracker -> planner -> sensor-fusion"
+        );
+
+        let acyclic = vec![components[0].clone(), linked_component("planner", &["adas:data/sensor-data"], &[])];
+        let pipeline = BuildPipeline::new(&BuildConfig::default(), &acyclic).unwrap();
+        assert_eq!(pipeline.build_order(), vec!["camera-front", "planner"]);
+    }
+
+    #[test]
+    fn test_transient_failure_classification() {
+        assert!(is_transient_failure(Some(101), "    Blocking waiting for file lock on package cache"));
+        assert!(is_transient_failure(Some(101), "warning: spurious network error (2 tries remaining)"));
+        assert!(is_transient_failure(None, ""));
+        assert!(!is_transient_failure(Some(101), "error[E0308]: mismatched types"));
+        assert!(!is_transient_failure(Some(101), "error: could not compile `adas-sensor-fusion-ecu`"));
+        assert!(!is_transient_failure(Some(1), "some unknown failure"));
+    }
+
+    #[test]
+    fn test_build_result_records_retries() {
+        let mut result = BuildResult::default();
+        result.record_retries("object-detection", 2);
+        result.record_retries("camera-front", 0);
+
+        assert_eq!(result.retry_counts.get("object-detection"), Some(&2));
+        assert!(!result.retry_counts.contains_key("camera-front"));
+    }
+
+    #[test]
+    fn test_budget_policy() {
+        let usage = ResourceUsage {
+            artifact_size: 600 << 20,
+            max_artifact_size: Some(512 << 20),
+            linear_memory: 0,
+            max_linear_memory: None,
+        };
+
+        assert!(check_budget("object-detection", &usage, BudgetPolicy::Warn).is_none());
+        let failure = check_budget("object-detection", &usage, BudgetPolicy::Fail).unwrap();
+        assert!(failure.starts_with("object-detection exceeds its resource budget"));
+
+        let within = ResourceUsage { max_artifact_size: None, ..usage };
+        assert!(check_budget("object-detection", &within, BudgetPolicy::Fail).is_none());
+    }
+
+    #[cfg(target_os = "linux")]
+    #[tokio::test]
+    async fn test_timeout_kills_process_group() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let pid_file = dir.path().join("grandchild.pid");
+        let cancel = CancellationToken::new();
+
+        // A hung cargo with a hung rustc child
+        let mut hung = tokio::process::Command::new("sh");
+        hung.arg("-c").arg(format!("sleep 30 & echo $! > {}; wait", pid_file.display()));
+        let started = Instant::now();
+        let attempt = run_supervised(hung, "object-detection", &cancel, Some(Duration::from_millis(300)))
+            .await
+            .unwrap();
+        assert!(matches!(attempt.status, ComponentStatus::TimedOut(_)));
+        assert!(started.elapsed() < Duration::from_secs(10));
+
+        let grandchild = std::fs::read_to_string(&pid_file).unwrap();
+        let stat = std::fs::read_to_string(format!("/proc/{}/stat", grandchild.trim())).unwrap_or_default();
+        let state = stat.rsplit(')').next().unwrap_or_default().trim_start().chars().next();
+        assert!(matches!(state, None | Some('Z') | Some('X')), "rustc child still running: {}", stat);
+
+        let mut quick = tokio::process::Command::new("sh");
+        quick.arg("-c").arg("exit 0");
+        let attempt = run_supervised(quick, "camera-front", &cancel, Some(Duration::from_secs(60))).await.unwrap();
+        assert_eq!(attempt.status, ComponentStatus::Succeeded);
+
+        let mut result = BuildResult::default();
+        result.record("object-detection", ComponentStatus::TimedOut(600));
+        assert_eq!(result.failed_components, vec!["object-detection".to_string()]);
+        assert!(result.compile_error().unwrap().to_string().contains("timed out after 600s"));
+    }
+
+    #[tokio::test]
+    async fn test_unrunnable_compiler_is_a_compile_error() {
+        let missing = tokio::process::Command::new("/nonexistent/cargo");
+        let error = run_supervised(missing, "object-detection", &CancellationToken::new(), None)
+            .await
+            .unwrap_err();
+        let error = BuildError::from_pipeline(error.context("Build of target wasm32-wasip1 stopped"));
+        assert!(matches!(&error, BuildError::Compile { component, message }
+            if component == "object-detection" && message.starts_with("Failed to spawn cargo: ")));
+
+        let error = BuildError::from_pipeline(anyhow::anyhow!("Target wasm32-wasip2 not installed"));
+        assert!(matches!(error, BuildError::Config(_)));
+    }
+}
@@ -0,0 +1,252 @@
+//! Dependency-ordered scheduling of component builds
+//!
+//! Components that depend on another component's crate wait until that
+//! component has built; everything else is ready immediately and can be
+//! built concurrently.
+
+use anyhow::Result;
+use std::collections::HashMap;
+
+use crate::component::Component;
+
+/// Progress of one component through the schedule
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum SlotState {
+    Pending,
+    Running,
+    Succeeded,
+    Failed,
+}
+
+/// Build order constraints between the components of a pipeline
+#[derive(Debug)]
+pub struct BuildSchedule {
+    /// Indices of the components each component depends on
+    dependencies: Vec<Vec<usize>>,
+
+    /// Current state per component, in pipeline order
+    states: Vec<SlotState>,
+}
+
+impl BuildSchedule {
+    /// Derive the schedule from the crate dependencies of `components`
+    ///
+    /// Dependencies on crates outside the pipeline are ignored. Fails if the
+    /// components depend on each other in a cycle.
+    pub fn new(components: &[Component]) -> Result<Self> {
+        let dependencies = crate_dependencies(components);
+        if let Err(cycle) = topological_order(&dependencies) {
+            anyhow::bail!("Circular component dependency: {}", describe_cycle(components, &cycle));
+        }
+
+        Ok(Self {
+            states: vec![SlotState::Pending; components.len()],
+            dependencies,
+        })
+    }
+
+    /// Take the next component whose dependencies have all built, in pipeline order
+    pub fn next_ready(&mut self) -> Option<usize> {
+        let ready = (0..self.states.len()).find(|&i| {
+            self.states[i] == SlotState::Pending
+                && self.dependencies[i].iter().all(|&d| self.states[d] == SlotState::Succeeded)
+        })?;
+        self.states[ready] = SlotState::Running;
+        Some(ready)
+    }
+
+    /// Record a finished build
+    ///
+    /// When the build failed, every pending component that transitively
+    /// depends on it can never build; those are marked failed and returned.
+    pub fn complete(&mut self, index: usize, succeeded: bool) -> Vec<usize> {
+        if succeeded {
+            self.states[index] = SlotState::Succeeded;
+            return Vec::new();
+        }
+
+        self.states[index] = SlotState::Failed;
+        let mut blocked = Vec::new();
+        loop {
+            let newly_blocked: Vec<usize> = (0..self.states.len())
+                .filter(|&i| {
+                    self.states[i] == SlotState::Pending
+                        && self.dependencies[i].iter().any(|&d| self.states[d] == SlotState::Failed)
+                })
+                .collect();
+            if newly_blocked.is_empty() {
+                break;
+            }
+            for &i in &newly_blocked {
+                self.states[i] = SlotState::Failed;
+            }
+            blocked.extend(newly_blocked);
+        }
+        blocked.sort_unstable();
+        blocked
+    }
+
+    /// Components `index` depends on
+    pub fn dependencies(&self, index: usize) -> &[usize] {
+        &self.dependencies[index]
+    }
+
+    /// Components of `index` that failed to build
+    pub fn failed_dependencies(&self, index: usize) -> impl Iterator<Item = usize> + '_ {
+        self.dependencies[index]
+            .iter()
+            .copied()
+            .filter(|&d| self.states[d] == SlotState::Failed)
+    }
+
+    /// Components not yet started
+    pub fn pending(&self) -> Vec<usize> {
+        (0..self.states.len())
+            .filter(|&i| self.states[i] == SlotState::Pending)
+            .collect()
+    }
+}
+
+/// Indices of the components each component depends on through its crate dependencies
+pub(crate) fn crate_dependencies(components: &[Component]) -> Vec<Vec<usize>> {
+    let by_package: HashMap<&str, usize> = components
+        .iter()
+        .enumerate()
+        .map(|(i, c)| (c.metadata.package_name.as_str(), i))
+        .collect();
+
+    components
+        .iter()
+        .enumerate()
+        .map(|(i, component)| {
+            let mut deps: Vec<usize> = component
+                .dependencies
+                .iter()
+                .filter_map(|dep| by_package.get(dep.as_str()).copied())
+                .filter(|dep| *dep != i)
+                .collect();
+            deps.sort_unstable();
+            deps.dedup();
+            deps
+        })
+        .collect()
+}
+
+/// Order nodes so each comes after all of its dependencies
+///
+/// Ties are broken by index, so the order is stable. On a cycle, returns the
+/// nodes of one cycle in dependency order: each depends on the next and the
+/// last depends on the first.
+pub(crate) fn topological_order(dependencies: &[Vec<usize>]) -> std::result::Result<Vec<usize>, Vec<usize>> {
+    let mut order = Vec::with_capacity(dependencies.len());
+    let mut done = vec![false; dependencies.len()];
+
+    while let Some(i) = (0..dependencies.len()).find(|&i| !done[i] && dependencies[i].iter().all(|&d| done[d])) {
+        done[i] = true;
+        order.push(i);
+    }
+
+    let Some(start) = done.iter().position(|d| !d) else {
+        return Ok(order);
+    };
+
+    // Every node left has an unfinished dependency; following them must revisit a node
+    let mut path = vec![start];
+    loop {
+        let current = *path.last().unwrap_or(&start);
+        let next = dependencies[current]
+            .iter()
+            .copied()
+            .find(|&d| !done[d])
+            .unwrap_or(start);
+        if let Some(at) = path.iter().position(|&n| n == next) {
+            return Err(path.split_off(at));
+        }
+        path.push(next);
+    }
+}
+
+/// `a -> b -> c -> a` for a cycle returned by [`topological_order`]
+pub(crate) fn describe_cycle(components: &[Component], cycle: &[usize]) -> String {
+    cycle
+        .iter()
+        .chain(cycle.first())
+        .map(|&i| components[i].name.as_str())
+        .collect::<Vec<_>>()
+        .join(" -> ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::component::{ComponentCategory, ComponentMetadata};
+    use std::path::PathBuf;
+
+    fn component(name: &str, dependencies: &[&str]) -> Component {
+        Component {
+            name: name.to_string(),
+            category: ComponentCategory::Sensors,
+            path: PathBuf::from(name),
+            metadata: ComponentMetadata {
+                package_name: format!("adas-{}", name),
+                ..Default::default()
+            },
+            dependencies: dependencies.iter().map(|d| d.to_string()).collect(),
+            has_tests: false,
+        }
+    }
+
+    #[test]
+    fn test_independent_components_ready_together() {
+        let components = vec![
+            component("camera-front", &["wit-bindgen"]),
+            component("radar-front", &[]),
+            component("sensor-fusion", &["adas-camera-front", "adas-radar-front"]),
+        ];
+        let mut schedule = BuildSchedule::new(&components).unwrap();
+
+        assert_eq!(schedule.next_ready(), Some(0));
+        assert_eq!(schedule.next_ready(), Some(1));
+        assert_eq!(schedule.next_ready(), None);
+
+        schedule.complete(1, true);
+        assert_eq!(schedule.next_ready(), None);
+        schedule.complete(0, true);
+        assert_eq!(schedule.next_ready(), Some(2));
+    }
+
+    #[test]
+    fn test_failure_blocks_transitive_dependents() {
+        let components = vec![
+            component("camera-front", &[]),
+            component("object-detection", &["adas-camera-front"]),
+            component("sensor-fusion", &["adas-object-detection"]),
+            component("radar-front", &[]),
+        ];
+        let mut schedule = BuildSchedule::new(&components).unwrap();
+
+        assert_eq!(schedule.next_ready(), Some(0));
+        assert_eq!(schedule.next_ready(), Some(3));
+        assert_eq!(schedule.complete(0, false), vec![1, 2]);
+        assert_eq!(schedule.failed_dependencies(1).collect::<Vec<_>>(), vec![0]);
+        assert!(schedule.pending().is_empty());
+    }
+
+    #[test]
+    fn test_topological_order() {
+        assert_eq!(topological_order(&[vec![2], vec![], vec![1]]), Ok(vec![1, 2, 0]));
+        // 0 waits on the 1 -> 2 -> 3 -> 1 cycle, which is reported without it
+        assert_eq!(topological_order(&[vec![1], vec![2], vec![3], vec![1]]), Err(vec![1, 2, 3]));
+    }
+
+    #[test]
+    fn test_cycle_is_rejected() {
+        let components = vec![
+            component("camera-front", &["adas-sensor-fusion"]),
+            component("sensor-fusion", &["adas-camera-front"]),
+            component("radar-front", &[]),
+        ];
+        let err = BuildSchedule::new(&components).unwrap_err();
+        assert!(err.to_string().contains("camera-front -> sensor-fusion -> camera-front"));
+    }
+}
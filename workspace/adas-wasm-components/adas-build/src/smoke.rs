@@ -0,0 +1,314 @@
+//! Post-build smoke tests of freshly built components
+//!
+//! Each component is instantiated in a minimal wasmtime host, its
+//! `run-diagnostic` is called and its reported average latency is checked
+//! against a budget. WASI is provided; every other import traps when called,
+//! so a component whose diagnostics need its peers fails rather than hangs.
+//! The host needs the `smoke-tests` feature; without it every smoke test is
+//! skipped.
+
+use serde::Serialize;
+use std::path::Path;
+
+/// Latency budget for components that do not declare `max-latency-ms`
+pub const DEFAULT_SMOKE_TEST_LATENCY_BUDGET_MS: f32 = 100.0;
+
+/// Interface providing `run-diagnostic`
+const HEALTH_INTERFACE: &str = "adas:diagnostics/health-monitoring";
+
+/// Interface providing `get-performance`
+const PERFORMANCE_INTERFACE: &str = "adas:diagnostics/performance-monitoring";
+
+/// Outcome of smoke testing one component
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(tag = "status", content = "detail", rename_all = "lowercase")]
+pub enum SmokeTestOutcome {
+    /// Diagnostics passed within the latency budget; latency is absent when
+    /// the component does not export performance monitoring
+    Passed { latency_avg_ms: Option<f32> },
+    /// Diagnostics failed, the latency budget was exceeded or the component
+    /// could not be instantiated
+    Failed(String),
+    /// The component could not be smoke tested, e.g. it has no diagnostics export
+    Skipped(String),
+}
+
+impl SmokeTestOutcome {
+    pub fn is_failure(&self) -> bool {
+        matches!(self, SmokeTestOutcome::Failed(_))
+    }
+}
+
+/// What a component reported when probed
+#[derive(Debug)]
+#[cfg_attr(not(feature = "smoke-tests"), allow(dead_code))]
+struct Probe {
+    /// Names of diagnostic tests that did not pass, or the error `run-diagnostic` returned
+    failed_tests: Result<Vec<String>, String>,
+
+    /// `latency-avg-ms` from `get-performance`
+    latency_avg_ms: Option<f32>,
+}
+
+/// Smoke test phase run by the pipeline after a build
+#[derive(Debug, Clone)]
+pub struct SmokeTest {
+    /// Latency budget for components without their own
+    default_latency_budget_ms: f32,
+}
+
+impl SmokeTest {
+    pub fn new(default_latency_budget_ms: f32) -> Self {
+        Self {
+            default_latency_budget_ms,
+        }
+    }
+
+    /// Smoke test the component artifact at `artifact`
+    ///
+    /// `latency_budget_ms` overrides the default budget for this component.
+    pub fn run(&self, artifact: &Path, latency_budget_ms: Option<f32>) -> SmokeTestOutcome {
+        if artifact.extension() != Some("wasm".as_ref()) {
+            return SmokeTestOutcome::Skipped("not a WebAssembly artifact".to_string());
+        }
+
+        match probe(artifact) {
+            Ok(Some(probe)) => self.judge(probe, latency_budget_ms),
+            Ok(None) if cfg!(feature = "smoke-tests") => {
+                SmokeTestOutcome::Skipped(format!("does not export {}", HEALTH_INTERFACE))
+            }
+            Ok(None) => SmokeTestOutcome::Skipped("adas-build was built without the smoke-tests feature".to_string()),
+            Err(e) => SmokeTestOutcome::Failed(format!("{:#}", e)),
+        }
+    }
+
+    fn judge(&self, probe: Probe, latency_budget_ms: Option<f32>) -> SmokeTestOutcome {
+        match probe.failed_tests {
+            Err(e) => return SmokeTestOutcome::Failed(format!("run-diagnostic failed: {}", e)),
+            Ok(failed) if !failed.is_empty() => {
+                return SmokeTestOutcome::Failed(format!("diagnostic tests failed: {}", failed.join(", ")))
+            }
+            Ok(_) => {}
+        }
+
+        let budget = latency_budget_ms.unwrap_or(self.default_latency_budget_ms);
+        match probe.latency_avg_ms {
+            Some(latency) if latency > budget => SmokeTestOutcome::Failed(format!(
+                "average latency {:.1}ms exceeds the {:.1}ms budget",
+                latency, budget
+            )),
+            latency_avg_ms => SmokeTestOutcome::Passed { latency_avg_ms },
+        }
+    }
+}
+
+impl Default for SmokeTest {
+    fn default() -> Self {
+        Self::new(DEFAULT_SMOKE_TEST_LATENCY_BUDGET_MS)
+    }
+}
+
+/// Full export name of `interface` among `exports`, ignoring its version
+#[cfg_attr(not(feature = "smoke-tests"), allow(dead_code))]
+fn find_export<'a>(exports: &'a [String], interface: &str) -> Option<&'a str> {
+    exports
+        .iter()
+        .map(String::as_str)
+        .find(|name| name.split('@').next() == Some(interface))
+}
+
+/// Instantiate the component and collect its diagnostics; `None` if it has none
+#[cfg(not(feature = "smoke-tests"))]
+fn probe(_artifact: &Path) -> anyhow::Result<Option<Probe>> {
+    Ok(None)
+}
+
+#[cfg(feature = "smoke-tests")]
+use host::probe;
+
+#[cfg(feature = "smoke-tests")]
+mod host {
+    use super::{find_export, Probe, HEALTH_INTERFACE, PERFORMANCE_INTERFACE};
+    use anyhow::{Context, Result};
+    use std::path::Path;
+    use wasmtime::component::{Component, Instance, Linker, ResourceTable, Val};
+    use wasmtime::{Config, Engine, Store};
+    use wasmtime_wasi::{WasiCtx, WasiCtxBuilder, WasiView};
+
+    /// Upper bound on the work a smoke test may do, so a spinning component fails
+    const SMOKE_TEST_FUEL: u64 = 10_000_000_000;
+
+    /// Diagnostic test results that count as not passed
+    const FAILING_RESULTS: [&str; 3] = ["failed", "error", "timeout"];
+
+    struct Host {
+        ctx: WasiCtx,
+        table: ResourceTable,
+    }
+
+    impl WasiView for Host {
+        fn table(&mut self) -> &mut ResourceTable {
+            &mut self.table
+        }
+
+        fn ctx(&mut self) -> &mut WasiCtx {
+            &mut self.ctx
+        }
+    }
+
+    pub(super) fn probe(artifact: &Path) -> Result<Option<Probe>> {
+        let mut config = Config::new();
+        config.wasm_component_model(true);
+        config.consume_fuel(true);
+        let engine = Engine::new(&config)?;
+        let component = Component::from_file(&engine, artifact)
+            .with_context(|| format!("Failed to load {}", artifact.display()))?;
+
+        let exports: Vec<String> = component
+            .component_type()
+            .exports(&engine)
+            .map(|(name, _)| name.to_string())
+            .collect();
+        let Some(health) = find_export(&exports, HEALTH_INTERFACE) else {
+            return Ok(None);
+        };
+        let performance = find_export(&exports, PERFORMANCE_INTERFACE);
+
+        let mut linker = Linker::new(&engine);
+        wasmtime_wasi::add_to_linker_sync(&mut linker)?;
+        linker.define_unknown_imports_as_traps(&component)?;
+
+        let host = Host {
+            ctx: WasiCtxBuilder::new().build(),
+            table: ResourceTable::new(),
+        };
+        let mut store = Store::new(&engine, host);
+        store.set_fuel(SMOKE_TEST_FUEL)?;
+        let instance = linker
+            .instantiate(&mut store, &component)
+            .context("Failed to instantiate component")?;
+
+        let failed_tests = match call(&instance, &mut store, health, "run-diagnostic")? {
+            Val::Result(Ok(Some(result))) => Ok(failed_tests(&result)),
+            Val::Result(Err(Some(error))) => match *error {
+                Val::String(message) => Err(message),
+                other => Err(format!("{:?}", other)),
+            },
+            other => anyhow::bail!("Unexpected run-diagnostic result: {:?}", other),
+        };
+
+        let latency_avg_ms = match performance {
+            Some(performance) => {
+                let metrics = call(&instance, &mut store, performance, "get-performance")?;
+                match field(&metrics, "base-metrics").and_then(|base| field(base, "latency-avg-ms")) {
+                    Some(Val::Float32(latency)) => Some(*latency),
+                    _ => None,
+                }
+            }
+            None => None,
+        };
+
+        Ok(Some(Probe {
+            failed_tests,
+            latency_avg_ms,
+        }))
+    }
+
+    /// Call a function without parameters returning a single value
+    fn call(instance: &Instance, store: &mut Store<Host>, interface: &str, function: &str) -> Result<Val> {
+        let missing = || format!("Component does not export {}#{}", interface, function);
+        let interface_index = instance.get_export(&mut *store, None, interface).with_context(missing)?;
+        let index = instance
+            .get_export(&mut *store, Some(&interface_index), function)
+            .with_context(missing)?;
+        let func = instance.get_func(&mut *store, index).with_context(missing)?;
+
+        let mut results = [Val::Bool(false)];
+        func.call(&mut *store, &[], &mut results)
+            .with_context(|| format!("{} trapped", function))?;
+        func.post_return(&mut *store)?;
+        let [result] = results;
+        Ok(result)
+    }
+
+    fn field<'a>(record: &'a Val, name: &str) -> Option<&'a Val> {
+        match record {
+            Val::Record(fields) => fields.iter().find(|(field, _)| field == name).map(|(_, value)| value),
+            _ => None,
+        }
+    }
+
+    /// Names of the tests in a `diagnostic-result` that did not pass
+    fn failed_tests(result: &Val) -> Vec<String> {
+        let Some(Val::List(tests)) = field(result, "test-results") else {
+            return Vec::new();
+        };
+        tests
+            .iter()
+            .filter(|test| match field(test, "test-result") {
+                Some(Val::Enum(result)) => FAILING_RESULTS.contains(&result.as_str()),
+                _ => false,
+            })
+            .map(|test| match field(test, "test-name") {
+                Some(Val::String(name)) => name.clone(),
+                _ => "unnamed test".to_string(),
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_judge_diagnostics_and_latency() {
+        let smoke_test = SmokeTest::new(50.0);
+        let probe = |failed_tests, latency_avg_ms| Probe {
+            failed_tests,
+            latency_avg_ms,
+        };
+
+        assert_eq!(
+            smoke_test.judge(probe(Ok(Vec::new()), Some(12.0)), None),
+            SmokeTestOutcome::Passed {
+                latency_avg_ms: Some(12.0)
+            }
+        );
+        assert_eq!(
+            smoke_test.judge(probe(Ok(Vec::new()), None), None),
+            SmokeTestOutcome::Passed { latency_avg_ms: None }
+        );
+
+        let slow = smoke_test.judge(probe(Ok(Vec::new()), Some(80.0)), None);
+        assert_eq!(
+            slow,
+            SmokeTestOutcome::Failed("average latency 80.0ms exceeds the 50.0ms budget".to_string())
+        );
+        // A component's own budget replaces the default
+        assert!(!smoke_test.judge(probe(Ok(Vec::new()), Some(80.0)), Some(100.0)).is_failure());
+
+        let failing = smoke_test.judge(probe(Ok(vec!["sensor-link".to_string()]), Some(1.0)), None);
+        assert_eq!(
+            failing,
+            SmokeTestOutcome::Failed("diagnostic tests failed: sensor-link".to_string())
+        );
+        assert!(smoke_test.judge(probe(Err("not initialized".to_string()), None), None).is_failure());
+    }
+
+    #[test]
+    fn test_finds_versioned_export_and_skips_native_artifacts() {
+        let exports = vec![
+            "adas:control/ai-control".to_string(),
+            "adas:diagnostics/health-monitoring@0.1.0".to_string(),
+        ];
+        assert_eq!(
+            find_export(&exports, HEALTH_INTERFACE),
+            Some("adas:diagnostics/health-monitoring@0.1.0")
+        );
+        assert_eq!(find_export(&exports, PERFORMANCE_INTERFACE), None);
+
+        let outcome = SmokeTest::default().run(Path::new("target/release/libadas_lidar.so"), None);
+        assert!(matches!(outcome, SmokeTestOutcome::Skipped(_)));
+    }
+}
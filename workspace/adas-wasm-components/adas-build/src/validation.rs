@@ -0,0 +1,764 @@
+//! Pre-build validation of ADAS components
+//!
+//! Validation runs before any component is compiled so that structural
+//! problems are reported with the component name attached instead of
+//! surfacing later as a compile or composition error.
+
+use anyhow::{Context, Result};
+use serde::Serialize;
+use std::collections::{BTreeMap, HashMap};
+use std::path::{Path, PathBuf};
+use tracing::debug;
+
+use crate::asil::AsilLevel;
+use crate::component::Component;
+use crate::config::BuildConfig;
+
+/// WIT package that defines the shared diagnostics interfaces
+pub const DIAGNOSTICS_PACKAGE: &str = "adas:diagnostics";
+
+/// Diagnostics interface every component must export
+pub const HEALTH_MONITORING_INTERFACE: &str = "adas:diagnostics/health-monitoring";
+
+/// `size_budgets` key of the budget for the composed artifact
+pub const COMPOSED_SIZE_BUDGET: &str = "composed";
+
+/// Severity of a validation finding
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Severity {
+    Warning,
+    Error,
+}
+
+/// A single problem found while validating a component
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ValidationFinding {
+    /// Rule that produced the finding
+    pub rule: String,
+
+    /// Finding severity
+    pub severity: Severity,
+
+    /// Human-readable description naming the offending item
+    pub message: String,
+}
+
+/// Validation outcome for one component
+#[derive(Debug, Clone, Serialize)]
+pub struct ValidationResult {
+    /// Component name
+    pub component: String,
+
+    /// All findings for the component
+    pub findings: Vec<ValidationFinding>,
+}
+
+impl ValidationResult {
+    fn new(component: &str) -> Self {
+        Self {
+            component: component.to_string(),
+            findings: Vec::new(),
+        }
+    }
+
+    fn push(&mut self, rule: &str, severity: Severity, message: String) {
+        self.findings.push(ValidationFinding {
+            rule: rule.to_string(),
+            severity,
+            message,
+        });
+    }
+
+    /// Whether any finding is an error
+    pub fn has_errors(&self) -> bool {
+        self.findings.iter().any(|f| f.severity == Severity::Error)
+    }
+
+    /// Error findings
+    pub fn errors(&self) -> impl Iterator<Item = &ValidationFinding> {
+        self.findings.iter().filter(|f| f.severity == Severity::Error)
+    }
+
+    /// Warning findings
+    pub fn warnings(&self) -> impl Iterator<Item = &ValidationFinding> {
+        self.findings.iter().filter(|f| f.severity == Severity::Warning)
+    }
+}
+
+/// Expected function signature in a WIT interface
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FunctionContract {
+    /// Function name in WIT (kebab-case)
+    pub name: String,
+
+    /// Number of parameters
+    pub param_count: usize,
+}
+
+/// Component validator
+#[derive(Debug)]
+pub struct Validator {
+    /// Diagnostics interfaces by name, e.g. `health-monitoring`
+    diagnostics_contract: BTreeMap<String, Vec<FunctionContract>>,
+
+    /// Warn about components without tests
+    warn_on_missing_tests: bool,
+
+    /// Fail components that do not export [`HEALTH_MONITORING_INTERFACE`]
+    require_health_monitoring: bool,
+
+    /// Artifact size limit in bytes per component
+    size_budgets: HashMap<String, u64>,
+
+    /// Artifact size limit for components without their own budget
+    default_size_budget: Option<u64>,
+
+    /// Lowest ASIL level a composed component may have
+    min_asil_level: Option<AsilLevel>,
+}
+
+impl Validator {
+    /// Create a validator, loading the diagnostics contract from the workspace WIT
+    pub fn new(config: &BuildConfig) -> Self {
+        let contract_path = config
+            .wit_path()
+            .join("interfaces")
+            .join("adas-diagnostics")
+            .join("diagnostics.wit");
+
+        let diagnostics_contract = match std::fs::read_to_string(&contract_path) {
+            Ok(source) => parse_wit_interfaces(&source),
+            Err(_) => {
+                debug!("No diagnostics WIT at {}, using built-in contract", contract_path.display());
+                default_diagnostics_contract()
+            }
+        };
+
+        Self {
+            diagnostics_contract,
+            warn_on_missing_tests: config.warn_on_missing_tests,
+            require_health_monitoring: config.require_health_monitoring,
+            size_budgets: config.size_budgets.clone(),
+            default_size_budget: config.default_size_budget,
+            min_asil_level: config.min_asil_level,
+        }
+    }
+
+    /// Validate a single component
+    pub fn validate_component(&self, component: &Component) -> Result<ValidationResult> {
+        let mut result = ValidationResult::new(&component.name);
+
+        if !component.path.join("Cargo.toml").exists() {
+            result.push(
+                "manifest",
+                Severity::Error,
+                format!("{}: Cargo.toml not found in {}", component.name, component.path.display()),
+            );
+        }
+
+        if self.warn_on_missing_tests && !component.has_tests {
+            result.push(
+                "tests",
+                Severity::Warning,
+                format!("{}: no tests found", component.name),
+            );
+        }
+
+        if !component.wit_dir().is_dir() {
+            result.push(
+                "wit",
+                Severity::Warning,
+                format!("{}: no wit/ directory", component.name),
+            );
+            return Ok(result);
+        }
+
+        self.validate_diagnostics(component, &mut result)?;
+
+        Ok(result)
+    }
+
+    /// Check that every component exports [`HEALTH_MONITORING_INTERFACE`]
+    ///
+    /// All offending components are reported in a single finding of a
+    /// `workspace` result.
+    pub fn validate_health_monitoring(&self, components: &[Component]) -> ValidationResult {
+        let mut result = ValidationResult::new("workspace");
+        if !self.require_health_monitoring {
+            return result;
+        }
+
+        let missing: Vec<&str> = components
+            .iter()
+            .filter(|c| !c.metadata.exports.iter().any(|e| e == HEALTH_MONITORING_INTERFACE))
+            .map(|c| c.name.as_str())
+            .collect();
+        if !missing.is_empty() {
+            result.push(
+                "health-monitoring",
+                Severity::Error,
+                format!("{} not exported by: {}", HEALTH_MONITORING_INTERFACE, missing.join(", ")),
+            );
+        }
+
+        result
+    }
+
+    /// Check that the components of a composition claim compatible ASIL levels
+    ///
+    /// With a configured minimum, every component below it or without a
+    /// declared level is an error. Without one, mixing QM or undeclared
+    /// components with ASIL-qualified ones is a warning. Findings name the
+    /// range of declared levels and the offending components.
+    pub fn validate_asil(&self, components: &[Component]) -> ValidationResult {
+        let mut result = ValidationResult::new("composition");
+
+        let declared: Vec<AsilLevel> = components.iter().filter_map(|c| c.metadata.asil_level).collect();
+        let range = match (declared.iter().min(), declared.iter().max()) {
+            (Some(min), Some(max)) => format!("declared levels {} to {}", min, max),
+            _ => "no declared levels".to_string(),
+        };
+        let describe = |c: &Component| match c.metadata.asil_level {
+            Some(level) => format!("{} ({})", c.name, level),
+            None => format!("{} (undeclared)", c.name),
+        };
+
+        if let Some(floor) = self.min_asil_level {
+            let offenders: Vec<String> = components
+                .iter()
+                .filter(|c| c.metadata.asil_level.map_or(true, |level| level < floor))
+                .map(describe)
+                .collect();
+            if !offenders.is_empty() {
+                result.push(
+                    "asil-level",
+                    Severity::Error,
+                    format!("Composition requires {} or higher ({}); below it: {}", floor, range, offenders.join(", ")),
+                );
+            }
+        } else if declared.iter().any(|level| *level > AsilLevel::Qm) {
+            let unqualified: Vec<String> = components
+                .iter()
+                .filter(|c| c.metadata.asil_level.map_or(true, |level| level == AsilLevel::Qm))
+                .map(describe)
+                .collect();
+            if !unqualified.is_empty() {
+                result.push(
+                    "asil-level",
+                    Severity::Warning,
+                    format!(
+                        "Composition mixes ASIL and unqualified components ({}): {}",
+                        range,
+                        unqualified.join(", ")
+                    ),
+                );
+            }
+        }
+
+        result
+    }
+
+    /// Check a built component artifact against its size budget
+    ///
+    /// `component` may be a build result key such as `camera-front@wasm32-wasip1`;
+    /// the budget is looked up by the component name.
+    pub fn validate_artifact_size(&self, component: &str, artifact: &Path) -> Result<ValidationResult> {
+        let name = component.split('@').next().unwrap_or(component);
+        let budget = self.size_budgets.get(name).copied().or(self.default_size_budget);
+        check_artifact_size(component, artifact, budget)
+    }
+
+    /// Check the composed artifact against the [`COMPOSED_SIZE_BUDGET`] budget
+    ///
+    /// The per-component default does not apply to the composition.
+    pub fn validate_composed_size(&self, artifact: &Path) -> Result<ValidationResult> {
+        check_artifact_size(COMPOSED_SIZE_BUDGET, artifact, self.size_budgets.get(COMPOSED_SIZE_BUDGET).copied())
+    }
+
+    /// Check that every declared diagnostics export is fully implemented
+    fn validate_diagnostics(&self, component: &Component, result: &mut ValidationResult) -> Result<()> {
+        let exports = declared_diagnostics_exports(component);
+        if exports.is_empty() {
+            return Ok(());
+        }
+
+        let sources = read_rust_sources(&component.path.join("src"))?;
+
+        for interface in exports {
+            let Some(contract) = self.diagnostics_contract.get(&interface) else {
+                result.push(
+                    "diagnostics-contract",
+                    Severity::Error,
+                    format!("{}: exports unknown diagnostics interface `{}`", component.name, interface),
+                );
+                continue;
+            };
+
+            let module = interface.replace('-', "_");
+            let Some(implemented) = guest_impl_methods(&sources, &module) else {
+                result.push(
+                    "diagnostics-contract",
+                    Severity::Error,
+                    format!(
+                        "{}: declares export of `{}` but has no `{}::Guest` implementation",
+                        component.name, interface, module
+                    ),
+                );
+                continue;
+            };
+
+            for function in contract {
+                let rust_name = function.name.replace('-', "_");
+                match implemented.get(&rust_name) {
+                    None => result.push(
+                        "diagnostics-contract",
+                        Severity::Error,
+                        format!(
+                            "{}: `{}` is missing method `{}`",
+                            component.name, interface, function.name
+                        ),
+                    ),
+                    Some(&params) if params != function.param_count => result.push(
+                        "diagnostics-contract",
+                        Severity::Error,
+                        format!(
+                            "{}: `{}` method `{}` takes {} parameters, contract expects {}",
+                            component.name, interface, function.name, params, function.param_count
+                        ),
+                    ),
+                    Some(_) => {}
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Compare an artifact's size on disk with `budget`
+fn check_artifact_size(name: &str, artifact: &Path, budget: Option<u64>) -> Result<ValidationResult> {
+    let mut result = ValidationResult::new(name);
+    let Some(budget) = budget else {
+        return Ok(result);
+    };
+
+    let size = std::fs::metadata(artifact)
+        .with_context(|| format!("Failed to read {}", artifact.display()))?
+        .len();
+    if size > budget {
+        result.push(
+            "size-budget",
+            Severity::Error,
+            format!(
+                "{}: {} is {} bytes, budget {} bytes ({} bytes over)",
+                name,
+                artifact.display(),
+                size,
+                budget,
+                size - budget
+            ),
+        );
+    }
+
+    Ok(result)
+}
+
+/// Built-in diagnostics contract used when the workspace WIT is unavailable
+fn default_diagnostics_contract() -> BTreeMap<String, Vec<FunctionContract>> {
+    let contract = |functions: &[(&str, usize)]| {
+        functions
+            .iter()
+            .map(|(name, param_count)| FunctionContract {
+                name: name.to_string(),
+                param_count: *param_count,
+            })
+            .collect::<Vec<_>>()
+    };
+
+    let mut map = BTreeMap::new();
+    map.insert(
+        "health-monitoring".to_string(),
+        contract(&[("get-health", 0), ("run-diagnostic", 0), ("get-last-diagnostic", 0)]),
+    );
+    map.insert(
+        "performance-monitoring".to_string(),
+        contract(&[("get-performance", 0), ("get-performance-history", 1), ("reset-counters", 0)]),
+    );
+    map
+}
+
+/// Extract `interface name { fn: func(...) }` signatures from WIT source
+pub fn parse_wit_interfaces(source: &str) -> BTreeMap<String, Vec<FunctionContract>> {
+    let mut interfaces = BTreeMap::new();
+    let mut current: Option<(String, Vec<FunctionContract>)> = None;
+    let mut depth = 0usize;
+
+    for line in source.lines() {
+        let line = line.split("//").next().unwrap_or("").trim();
+
+        if current.is_none() {
+            if let Some(rest) = line.strip_prefix("interface ") {
+                let name = rest.trim_end_matches('{').trim().to_string();
+                current = Some((name, Vec::new()));
+                depth = line.matches('{').count();
+            }
+            continue;
+        }
+
+        if depth == 1 {
+            if let Some((name, signature)) = line.split_once(": func(") {
+                let params = signature.split(')').next().unwrap_or("");
+                if let Some((_, functions)) = current.as_mut() {
+                    functions.push(FunctionContract {
+                        name: name.trim().to_string(),
+                        param_count: count_params(params),
+                    });
+                }
+            }
+        }
+
+        depth += line.matches('{').count();
+        depth = depth.saturating_sub(line.matches('}').count());
+
+        if depth == 0 {
+            if let Some((name, functions)) = current.take() {
+                interfaces.insert(name, functions);
+            }
+        }
+    }
+
+    interfaces
+}
+
+/// Diagnostics interfaces exported by the world a component compiles
+fn declared_diagnostics_exports(component: &Component) -> Vec<String> {
+    component
+        .metadata
+        .exports
+        .iter()
+        .filter_map(|export| export.strip_prefix(DIAGNOSTICS_PACKAGE)?.strip_prefix('/'))
+        .map(str::to_string)
+        .collect()
+}
+
+/// Concatenated Rust sources of a component
+fn read_rust_sources(src_dir: &Path) -> Result<String> {
+    let mut sources = String::new();
+    if !src_dir.is_dir() {
+        return Ok(sources);
+    }
+
+    for entry in walkdir::WalkDir::new(src_dir).into_iter().filter_map(Result::ok) {
+        let path: PathBuf = entry.into_path();
+        if path.extension().map_or(false, |ext| ext == "rs") {
+            sources.push_str(
+                &std::fs::read_to_string(&path)
+                    .with_context(|| format!("Failed to read {}", path.display()))?,
+            );
+            sources.push('\n');
+        }
+    }
+
+    Ok(sources)
+}
+
+/// Methods and their parameter counts in the `impl <module>::Guest for` block
+fn guest_impl_methods(sources: &str, module: &str) -> Option<BTreeMap<String, usize>> {
+    let marker = format!("{}::Guest for", module);
+    let start = sources.find(&marker)?;
+    let body_start = start + sources[start..].find('{')? + 1;
+
+    // Find the matching closing brace of the impl block
+    let mut depth = 1usize;
+    let mut body_end = body_start;
+    for (offset, ch) in sources[body_start..].char_indices() {
+        match ch {
+            '{' => depth += 1,
+            '}' => {
+                depth -= 1;
+                if depth == 0 {
+                    body_end = body_start + offset;
+                    break;
+                }
+            }
+            _ => {}
+        }
+    }
+
+    let body = &sources[body_start..body_end];
+    let mut methods = BTreeMap::new();
+    let mut rest = body;
+    while let Some(pos) = rest.find("fn ") {
+        rest = &rest[pos + 3..];
+        let Some(open) = rest.find('(') else { break };
+        let name = rest[..open].trim().to_string();
+        let Some(close) = rest[open..].find(')') else { break };
+        let params = &rest[open + 1..open + close];
+        let count = params
+            .split(',')
+            .map(str::trim)
+            .filter(|p| !p.is_empty() && !p.ends_with("self"))
+            .count();
+        methods.insert(name, count);
+        rest = &rest[open + close..];
+    }
+
+    Some(methods)
+}
+
+/// Count top-level comma-separated parameters
+fn count_params(params: &str) -> usize {
+    let mut depth = 0i32;
+    let mut count = 0;
+    let mut current_empty = true;
+
+    for ch in params.chars() {
+        match ch {
+            '<' | '(' => depth += 1,
+            '>' | ')' => depth -= 1,
+            ',' if depth == 0 => {
+                if !current_empty {
+                    count += 1;
+                }
+                current_empty = true;
+                continue;
+            }
+            _ => {}
+        }
+        if !ch.is_whitespace() {
+            current_empty = false;
+        }
+    }
+
+    if !current_empty {
+        count += 1;
+    }
+    count
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::component::{ComponentCategory, ComponentMetadata};
+    use tempfile::TempDir;
+
+    fn write_component(root: &Path, world: &str, source: &str) -> Component {
+        let path = root.join("components/system/test-monitor");
+        std::fs::create_dir_all(path.join("wit")).unwrap();
+        std::fs::create_dir_all(path.join("src")).unwrap();
+        std::fs::write(
+            path.join("Cargo.toml"),
+            "[package]\nname = \"adas-test-monitor\"\nversion = \"0.1.0\"\n",
+        )
+        .unwrap();
+        std::fs::write(path.join("wit/world.wit"), world).unwrap();
+        std::fs::write(path.join("src/lib.rs"), source).unwrap();
+        Component::load(&path, ComponentCategory::System).unwrap()
+    }
+
+    #[test]
+    fn test_parse_wit_interfaces() {
+        let source = r#"
+interface performance-monitoring {
+    record metric {
+        name: string,
+    }
+    get-performance: func() -> extended-performance;
+    get-performance-history: func(duration-seconds: u32) -> list<extended-performance>;
+    reset-counters: func();
+}
+"#;
+        let interfaces = parse_wit_interfaces(source);
+        let functions = &interfaces["performance-monitoring"];
+        assert_eq!(functions.len(), 3);
+        assert_eq!(functions[1].name, "get-performance-history");
+        assert_eq!(functions[1].param_count, 1);
+    }
+
+    #[test]
+    fn test_missing_diagnostics_method_is_reported() {
+        let temp_dir = TempDir::new().unwrap();
+        let world = "world test {\n    export adas:diagnostics/health-monitoring;\n    export adas:diagnostics/performance-monitoring;\n}\n";
+        let source = r#"
+impl exports::adas::diagnostics::health_monitoring::Guest for Component {
+    fn get_health() -> HealthReport { todo!() }
+    fn run_diagnostic() -> Result<DiagnosticResult, String> { todo!() }
+}
+impl exports::adas::diagnostics::performance_monitoring::Guest for Component {
+    fn get_performance() -> ExtendedPerformance { todo!() }
+    fn get_performance_history() -> Vec<ExtendedPerformance> { vec![] }
+    fn reset_counters() {}
+}
+"#;
+        let component = write_component(temp_dir.path(), world, source);
+        let config = BuildConfig::default();
+        let result = Validator::new(&config).validate_component(&component).unwrap();
+
+        assert!(result.has_errors());
+        let messages: Vec<_> = result.errors().map(|f| f.message.as_str()).collect();
+        assert!(messages.iter().any(|m| m.contains("test-monitor") && m.contains("get-last-diagnostic")));
+        assert!(messages.iter().any(|m| m.contains("get-performance-history") && m.contains("takes 0")));
+    }
+
+    #[test]
+    fn test_missing_guest_impl_is_reported() {
+        let temp_dir = TempDir::new().unwrap();
+        let world = "world test {\n    export adas:diagnostics/performance-monitoring;\n}\n";
+        let component = write_component(temp_dir.path(), world, "struct Component;\n");
+        let config = BuildConfig::default();
+        let result = Validator::new(&config).validate_component(&component).unwrap();
+
+        assert_eq!(result.errors().count(), 1);
+        assert!(result.findings[0].message.contains("performance_monitoring::Guest"));
+    }
+
+    #[test]
+    fn test_stale_sibling_world_is_ignored() {
+        let temp_dir = TempDir::new().unwrap();
+        let world = "package adas:test-monitor;\n\nworld test-monitor {\n    export diagnostics;\n}\n";
+        let component = write_component(temp_dir.path(), world, "struct Component;\n");
+        // Left over from before the component moved to its own world; wit-bindgen never compiles it
+        std::fs::write(
+            component.wit_dir().join("component.wit"),
+            "package adas:component;\n\nworld system-component {\n    export adas:diagnostics/health-monitoring;\n}\n",
+        )
+        .unwrap();
+        let component = Component::load(&component.path, ComponentCategory::System).unwrap();
+        let config = BuildConfig::default();
+        let result = Validator::new(&config).validate_component(&component).unwrap();
+
+        assert!(result.findings.iter().all(|f| f.rule != "diagnostics-contract"));
+    }
+
+    #[test]
+    fn test_health_monitoring_required() {
+        let temp_dir = TempDir::new().unwrap();
+        let exporting = write_component(
+            temp_dir.path(),
+            "package adas:test-monitor;\n\nworld test-monitor {\n    export adas:diagnostics/health-monitoring@0.1.0;\n}\n",
+            "",
+        );
+        let missing = |name: &str| Component {
+            name: name.to_string(),
+            metadata: ComponentMetadata {
+                exports: Vec::new(),
+                ..exporting.metadata.clone()
+            },
+            ..exporting.clone()
+        };
+        let components = vec![missing("radar-front"), exporting.clone(), missing("lidar")];
+
+        let config = BuildConfig {
+            require_health_monitoring: true,
+            ..BuildConfig::default()
+        };
+        let result = Validator::new(&config).validate_health_monitoring(&components);
+        assert_eq!(result.findings.len(), 1);
+        assert_eq!(result.findings[0].rule, "health-monitoring");
+        assert!(result.findings[0].message.ends_with("radar-front, lidar"));
+
+        // Opt-in
+        let result = Validator::new(&BuildConfig::default()).validate_health_monitoring(&components);
+        assert!(!result.has_errors());
+    }
+
+    #[test]
+    fn test_artifact_size_budgets() {
+        let temp_dir = TempDir::new().unwrap();
+        let artifact = temp_dir.path().join("adas_camera_front.wasm");
+        std::fs::write(&artifact, vec![0u8; 3000]).unwrap();
+
+        let mut config = BuildConfig::default();
+        config.size_budgets.insert("camera-front".to_string(), 2048);
+        config.size_budgets.insert(COMPOSED_SIZE_BUDGET.to_string(), 4096);
+        config.default_size_budget = Some(1024);
+        let validator = Validator::new(&config);
+
+        let result = validator.validate_artifact_size("camera-front@wasm32-wasip1", &artifact).unwrap();
+        let finding = result.errors().next().unwrap();
+        assert_eq!(finding.rule, "size-budget");
+        assert!(finding.message.contains("3000 bytes, budget 2048 bytes (952 bytes over)"));
+
+        // Falls back to the default budget
+        assert!(validator.validate_artifact_size("radar-front", &artifact).unwrap().has_errors());
+        assert!(!validator.validate_composed_size(&artifact).unwrap().has_errors());
+
+        let unbudgeted = Validator::new(&BuildConfig::default());
+        assert!(!unbudgeted.validate_artifact_size("camera-front", &artifact).unwrap().has_errors());
+        assert!(!unbudgeted.validate_composed_size(&artifact).unwrap().has_errors());
+    }
+
+    #[test]
+    fn test_asil_levels_checked_against_floor() {
+        let temp_dir = TempDir::new().unwrap();
+        let base = write_component(temp_dir.path(), "world test {}\n", "");
+        let component = |name: &str, level: Option<AsilLevel>| {
+            let mut component = Component {
+                name: name.to_string(),
+                ..base.clone()
+            };
+            component.metadata.asil_level = level;
+            component
+        };
+        let components = vec![
+            component("sensor-fusion", Some(AsilLevel::C)),
+            component("camera-front", Some(AsilLevel::B)),
+            component("adas-visualizer", Some(AsilLevel::Qm)),
+            component("lane-detection", None),
+        ];
+
+        // Without a floor, mixing in unqualified components only warns
+        let result = Validator::new(&BuildConfig::default()).validate_asil(&components);
+        assert!(!result.has_errors());
+        let warning = result.warnings().next().unwrap();
+        assert_eq!(warning.rule, "asil-level");
+        assert!(warning.message.contains("QM to ASIL-C"));
+        assert!(warning.message.ends_with("adas-visualizer (QM), lane-detection (undeclared)"));
+
+        let config = BuildConfig {
+            min_asil_level: Some(AsilLevel::B),
+            ..BuildConfig::default()
+        };
+        let validator = Validator::new(&config);
+        let result = validator.validate_asil(&components);
+        let error = result.errors().next().unwrap();
+        assert!(error.message.starts_with("Composition requires ASIL-B or higher"));
+        assert!(error.message.ends_with("below it: adas-visualizer (QM), lane-detection (undeclared)"));
+
+        assert!(validator.validate_asil(&components[..2]).findings.is_empty());
+    }
+
+    #[test]
+    fn test_asil_level_read_from_manifest() {
+        let temp_dir = TempDir::new().unwrap();
+        let component = write_component(temp_dir.path(), "world test {}\n", "");
+        let declare = |entry: &str| {
+            let manifest = format!("[package]\nname = \"adas-test-monitor\"\n\n[package.metadata.adas]\n{}\n", entry);
+            std::fs::write(component.path.join("Cargo.toml"), manifest).unwrap();
+        };
+
+        declare("safety-level = \"ASIL-D\"");
+        let loaded = Component::load(&component.path, ComponentCategory::System).unwrap();
+        assert_eq!(loaded.metadata.asil_level, Some(AsilLevel::D));
+        assert_eq!(loaded.metadata.safety_level.as_deref(), Some("ASIL-D"));
+
+        declare("asil-level = \"E\"");
+        assert!(Component::load(&component.path, ComponentCategory::System).is_err());
+    }
+
+    #[test]
+    fn test_missing_tests_warning() {
+        let temp_dir = TempDir::new().unwrap();
+        let component = write_component(temp_dir.path(), "world test {}\n", "struct Component;\n");
+        assert!(!component.has_tests);
+
+        let mut config = BuildConfig::default();
+        assert_eq!(Validator::new(&config).validate_component(&component).unwrap().warnings().count(), 0);
+
+        config.warn_on_missing_tests = true;
+        let result = Validator::new(&config).validate_component(&component).unwrap();
+        assert!(!result.has_errors());
+        assert!(result.warnings().any(|f| f.rule == "tests"));
+    }
+}
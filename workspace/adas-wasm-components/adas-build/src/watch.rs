@@ -0,0 +1,188 @@
+//! Watch mode support: filesystem notification, debouncing and change mapping
+//!
+//! [`SourceWatcher`] turns bursts of filesystem events into batches of changed
+//! paths; [`affected_components`] and [`rebuild_set`] map a batch onto the
+//! components that need rebuilding.
+
+use anyhow::{Context, Result};
+use notify::{EventKind, RecommendedWatcher, RecursiveMode, Watcher};
+use std::collections::{BTreeSet, VecDeque};
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+use tokio::sync::mpsc::{unbounded_channel, UnboundedReceiver};
+use tracing::{debug, warn};
+
+use crate::component::Component;
+use crate::pipeline::component_dependencies;
+
+/// Quiet period that ends a burst of filesystem events
+pub const DEBOUNCE: Duration = Duration::from_millis(300);
+
+/// Recursive filesystem watch delivering debounced batches of changed paths
+pub struct SourceWatcher {
+    /// Kept alive for as long as events should be delivered
+    _watcher: RecommendedWatcher,
+    events: UnboundedReceiver<Vec<PathBuf>>,
+    debounce: Duration,
+}
+
+impl std::fmt::Debug for SourceWatcher {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SourceWatcher").field("debounce", &self.debounce).finish_non_exhaustive()
+    }
+}
+
+impl SourceWatcher {
+    /// Watch every directory in `roots` recursively; missing ones are skipped
+    pub fn new(roots: &[PathBuf], debounce: Duration) -> Result<Self> {
+        let (sender, events) = unbounded_channel();
+        let mut watcher = notify::recommended_watcher(move |event: notify::Result<notify::Event>| match event {
+            Ok(event) if !matches!(event.kind, EventKind::Access(_)) => {
+                let _ = sender.send(event.paths);
+            }
+            Ok(_) => {}
+            Err(e) => warn!("File watcher error: {}", e),
+        })
+        .context("Failed to create file watcher")?;
+
+        for root in roots.iter().filter(|root| root.is_dir()) {
+            debug!("Watching {}", root.display());
+            watcher
+                .watch(root, RecursiveMode::Recursive)
+                .with_context(|| format!("Failed to watch {}", root.display()))?;
+        }
+
+        Ok(Self { _watcher: watcher, events, debounce })
+    }
+
+    /// Wait for the next burst of changes
+    ///
+    /// Returns once no further event has arrived for the debounce period, or
+    /// `None` if the watcher stopped.
+    pub async fn next_batch(&mut self) -> Option<BTreeSet<PathBuf>> {
+        let mut batch: BTreeSet<PathBuf> = self.events.recv().await?.into_iter().collect();
+        while let Ok(Some(paths)) = tokio::time::timeout(self.debounce, self.events.recv()).await {
+            batch.extend(paths);
+        }
+        Some(batch)
+    }
+}
+
+/// Indices of the components whose build inputs include one of `changed`
+///
+/// A component's inputs are its `Cargo.toml`, `src/` and `wit/` trees. A
+/// change to a `.wit` file under the shared WIT directory affects every
+/// component; the build cache skips those that do not reference it.
+pub fn affected_components(
+    components: &[Component],
+    shared_wit: &Path,
+    changed: &BTreeSet<PathBuf>,
+) -> BTreeSet<usize> {
+    let mut affected = BTreeSet::new();
+    for path in changed {
+        if path.starts_with(shared_wit) && path.extension().is_some_and(|ext| ext == "wit") {
+            return (0..components.len()).collect();
+        }
+
+        let owner = components.iter().position(|component| {
+            path.strip_prefix(&component.path).is_ok_and(|relative| {
+                relative == Path::new("Cargo.toml") || relative.starts_with("src") || relative.starts_with("wit")
+            })
+        });
+        if let Some(index) = owner {
+            affected.insert(index);
+        }
+    }
+    affected
+}
+
+/// Components a rebuild of `affected` involves, in their original order
+///
+/// That is every affected component and everything depending on it, plus
+/// the dependencies of those so their input hashes match a full build and
+/// unchanged dependencies are skipped by the build cache.
+pub fn rebuild_set(components: &[Component], affected: &BTreeSet<usize>) -> Result<Vec<Component>> {
+    let dependencies = component_dependencies(components)?;
+    let mut dependents = vec![Vec::new(); components.len()];
+    for (component, deps) in dependencies.iter().enumerate() {
+        for &dep in deps {
+            dependents[dep].push(component);
+        }
+    }
+
+    let rebuilt = closure(affected.iter().copied(), &dependents);
+    let selected = closure(rebuilt, &dependencies);
+    Ok(selected.into_iter().map(|index| components[index].clone()).collect())
+}
+
+/// Every node reachable from `start` along `edges`, including `start`
+fn closure(start: impl IntoIterator<Item = usize>, edges: &[Vec<usize>]) -> BTreeSet<usize> {
+    let mut reached = BTreeSet::new();
+    let mut queue: VecDeque<usize> = start.into_iter().collect();
+    while let Some(node) = queue.pop_front() {
+        if reached.insert(node) {
+            queue.extend(edges[node].iter().copied());
+        }
+    }
+    reached
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::component::{ComponentCategory, ComponentMetadata};
+
+    fn component(name: &str, dependencies: &[&str]) -> Component {
+        Component {
+            name: name.to_string(),
+            category: ComponentCategory::Fusion,
+            path: PathBuf::from("/ws/components/fusion").join(name),
+            metadata: ComponentMetadata { package_name: name.to_string(), ..ComponentMetadata::default() },
+            dependencies: dependencies.iter().map(|d| d.to_string()).collect(),
+            has_tests: false,
+        }
+    }
+
+    fn paths(paths: &[&str]) -> BTreeSet<PathBuf> {
+        paths.iter().map(PathBuf::from).collect()
+    }
+
+    #[test]
+    fn test_affected_components() {
+        let components = vec![component("camera-front", &[]), component("sensor-fusion", &[])];
+        let shared_wit = Path::new("/ws/wit");
+
+        let changed = paths(&[
+            "/ws/components/fusion/sensor-fusion/src/lib.rs",
+            "/ws/components/fusion/camera-front/target/debug/build.log",
+            "/ws/components/fusion/camera-front/README.md",
+        ]);
+        assert_eq!(affected_components(&components, shared_wit, &changed), BTreeSet::from([1]));
+
+        let changed = paths(&["/ws/components/fusion/camera-front/Cargo.toml"]);
+        assert_eq!(affected_components(&components, shared_wit, &changed), BTreeSet::from([0]));
+
+        let changed = paths(&["/ws/wit/interfaces/sensor-data.wit"]);
+        assert_eq!(affected_components(&components, shared_wit, &changed), BTreeSet::from([0, 1]));
+        assert!(affected_components(&components, shared_wit, &paths(&["/ws/wit/README.md"])).is_empty());
+    }
+
+    #[test]
+    fn test_rebuild_set_includes_dependents_and_their_dependencies() {
+        let components = vec![
+            component("adas-common-types", &[]),
+            component("camera-front", &["adas-common-types"]),
+            component("sensor-fusion", &["camera-front", "adas-common-types"]),
+            component("radar-front", &[]),
+            component("tracking", &[]),
+        ];
+
+        let names = |affected: &[usize]| -> Vec<String> {
+            rebuild_set(&components, &affected.iter().copied().collect()).unwrap().into_iter().map(|c| c.name).collect()
+        };
+        assert_eq!(names(&[1]), ["adas-common-types", "camera-front", "sensor-fusion"]);
+        assert_eq!(names(&[2]), ["adas-common-types", "camera-front", "sensor-fusion"]);
+        assert_eq!(names(&[3]), ["radar-front"]);
+        assert!(names(&[]).is_empty());
+    }
+}
@@ -0,0 +1,81 @@
+//! Monotonic timestamps and overflow-safe durations
+//!
+//! Component timestamps are wall-clock milliseconds since the Unix epoch so
+//! they can be compared across components, but the host clock may step
+//! backwards. [`monotonic_now_ms`] never returns less than it returned
+//! before, and every timestamp subtraction goes through [`elapsed_ms`] so a
+//! stale or future timestamp yields zero instead of underflowing.
+
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Highest timestamp handed out so far
+static LAST_TIMESTAMP_MS: AtomicU64 = AtomicU64::new(0);
+
+/// Current wall-clock time in milliseconds, never earlier than a previous call
+pub fn monotonic_now_ms() -> u64 {
+    let sample = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_millis() as u64)
+        .unwrap_or(0);
+    advance(&LAST_TIMESTAMP_MS, sample)
+}
+
+/// Record `sample` and return the larger of it and the previous high-water mark
+fn advance(last: &AtomicU64, sample: u64) -> u64 {
+    let previous = last.fetch_max(sample, Ordering::Relaxed);
+    previous.max(sample)
+}
+
+/// Milliseconds from `earlier` to `now`, zero if `earlier` is in the future
+pub fn elapsed_ms(now: u64, earlier: u64) -> u64 {
+    now.saturating_sub(earlier)
+}
+
+/// Seconds from `earlier` to `now`, zero if `earlier` is in the future
+pub fn elapsed_secs(now: u64, earlier: u64) -> f32 {
+    elapsed_ms(now, earlier) as f32 / 1000.0
+}
+
+/// [`elapsed_ms`] narrowed to `u32`, saturating at `u32::MAX`
+pub fn elapsed_ms_u32(now: u64, earlier: u64) -> u32 {
+    u32::try_from(elapsed_ms(now, earlier)).unwrap_or(u32::MAX)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_backwards_clock_holds_last_value() {
+        let last = AtomicU64::new(0);
+        assert_eq!(advance(&last, 1_000), 1_000);
+        assert_eq!(advance(&last, 1_500), 1_500);
+        // Host clock steps back by 400 ms
+        assert_eq!(advance(&last, 1_100), 1_500);
+        assert_eq!(advance(&last, 1_600), 1_600);
+    }
+
+    #[test]
+    fn test_stale_timestamp_does_not_underflow() {
+        assert_eq!(elapsed_ms(1_000, 1_250), 0);
+        assert_eq!(elapsed_secs(1_000, 1_250), 0.0);
+        assert_eq!(elapsed_ms_u32(1_000, u64::MAX), 0);
+        assert_eq!(elapsed_ms(1_250, 1_000), 250);
+    }
+
+    #[test]
+    fn test_large_gap_saturates_u32() {
+        assert_eq!(elapsed_ms_u32(u64::MAX, 0), u32::MAX);
+    }
+
+    #[test]
+    fn test_monotonic_now_never_decreases() {
+        let mut previous = monotonic_now_ms();
+        for _ in 0..1000 {
+            let now = monotonic_now_ms();
+            assert!(now >= previous);
+            previous = now;
+        }
+    }
+}
@@ -89,6 +89,40 @@ pub mod utils {
         result
     }
     
+    /// Intersection over union of two boxes, 0.0 when either is empty
+    pub fn iou(a: &Detection, b: &Detection) -> f32 {
+        let ix = ((a.x + a.width).min(b.x + b.width) - a.x.max(b.x)).max(0.0);
+        let iy = ((a.y + a.height).min(b.y + b.height) - a.y.max(b.y)).max(0.0);
+        let intersection = ix * iy;
+        let union = a.width * a.height + b.width * b.height - intersection;
+        
+        if union <= 0.0 {
+            0.0
+        } else {
+            intersection / union
+        }
+    }
+    
+    /// Non-maximum suppression per class
+    ///
+    /// Keeps detections in descending confidence order, dropping any box whose
+    /// IoU with an already kept box of the same class exceeds `iou_threshold`.
+    pub fn non_max_suppression(mut detections: Vec<Detection>, iou_threshold: f32) -> Vec<Detection> {
+        detections.sort_by(|a, b| b.confidence.total_cmp(&a.confidence));
+        
+        let mut kept: Vec<Detection> = Vec::with_capacity(detections.len());
+        for detection in detections {
+            let suppressed = kept
+                .iter()
+                .any(|k| k.class_id == detection.class_id && iou(k, &detection) > iou_threshold);
+            if !suppressed {
+                kept.push(detection);
+            }
+        }
+        
+        kept
+    }
+    
     /// Parse YOLO detection output tensor to bounding boxes
     pub fn parse_yolo_detections(
         output_data: &[f32],
@@ -211,6 +245,33 @@ mod tests {
         assert!(converted.iter().all(|&x| x == 1.0)); // Normalized 255 -> 1.0
     }
     
+    fn car(x: f32, y: f32, confidence: f32) -> Detection {
+        Detection { x, y, width: 100.0, height: 50.0, confidence, class_id: 2 }
+    }
+    
+    #[test]
+    fn test_iou() {
+        let a = car(0.0, 0.0, 0.9);
+        assert!((utils::iou(&a, &a) - 1.0).abs() < 1e-6);
+        assert!((utils::iou(&a, &car(50.0, 0.0, 0.9)) - 1.0 / 3.0).abs() < 1e-6);
+        assert_eq!(utils::iou(&a, &car(200.0, 0.0, 0.9)), 0.0);
+    }
+    
+    #[test]
+    fn test_nms_keeps_top_scoring_overlapping_box() {
+        let detections = vec![car(5.0, 2.0, 0.7), car(0.0, 0.0, 0.9), car(10.0, 5.0, 0.8)];
+        let kept = utils::non_max_suppression(detections, 0.45);
+        
+        assert_eq!(kept.len(), 1);
+        assert_eq!(kept[0].confidence, 0.9);
+        
+        // Other classes and distant boxes are not suppressed
+        let mut person = car(0.0, 0.0, 0.6);
+        person.class_id = 0;
+        let kept = utils::non_max_suppression(vec![car(0.0, 0.0, 0.9), person, car(300.0, 0.0, 0.5)], 0.45);
+        assert_eq!(kept.len(), 3);
+    }
+    
     #[test]
     fn test_coco_classes() {
         assert_eq!(COCO_CLASSES.len(), 80);
@@ -0,0 +1,133 @@
+// Interaction-aware post-processing of independently predicted trajectories
+// Holds back the object that has to yield when two predicted paths meet
+
+/// Distance (m) between two objects at the same time step that counts as a conflict
+pub const DEFAULT_CONFLICT_RADIUS_M: f32 = 2.0;
+
+/// Right-of-way rank of an object type; the lower rank yields
+///
+/// Pedestrians yield to nothing; everything else yields to pedestrians.
+pub fn yield_priority(object_type: &str) -> u8 {
+    match object_type {
+        "pedestrian" | "person" => 1,
+        _ => 0,
+    }
+}
+
+/// Predicted path of one object, sampled at the shared prediction steps
+#[derive(Debug, Clone, PartialEq)]
+pub struct PredictedPath {
+    pub priority: u8,
+    /// Current position (m)
+    pub start: [f32; 2],
+    /// Predicted position (m) per step
+    pub points: Vec<[f32; 2]>,
+    /// First step from which the object is held in place, once adjusted
+    pub held_from: Option<usize>,
+}
+
+impl PredictedPath {
+    pub fn new(priority: u8, start: [f32; 2], points: Vec<[f32; 2]>) -> Self {
+        Self { priority, start, points, held_from: None }
+    }
+
+    /// Stop the object at its last position before `step`
+    fn hold_from(&mut self, step: usize) {
+        let stop = if step == 0 { self.start } else { self.points[step - 1] };
+        for point in &mut self.points[step..] {
+            *point = stop;
+        }
+        self.held_from = Some(self.held_from.map_or(step, |held| held.min(step)));
+    }
+}
+
+/// First step at which two paths come within `radius_m` of each other
+fn first_conflict(a: &PredictedPath, b: &PredictedPath, radius_m: f32) -> Option<usize> {
+    a.points.iter().zip(&b.points).position(|(p, q)| {
+        ((p[0] - q[0]).powi(2) + (p[1] - q[1]).powi(2)).sqrt() < radius_m
+    })
+}
+
+/// Hold back the lower-priority object of every conflicting pair
+///
+/// Pairs of equal priority are left alone: the rules say nothing about who
+/// yields. Paths are processed in order, so a path already held back is
+/// checked against the others in its adjusted form. Returns the indices of
+/// the adjusted paths.
+pub fn resolve_conflicts(paths: &mut [PredictedPath], radius_m: f32) -> Vec<usize> {
+    let mut adjusted = Vec::new();
+
+    for i in 0..paths.len() {
+        for j in (i + 1)..paths.len() {
+            let yielding = match paths[i].priority.cmp(&paths[j].priority) {
+                std::cmp::Ordering::Less => i,
+                std::cmp::Ordering::Greater => j,
+                std::cmp::Ordering::Equal => continue,
+            };
+            let Some(step) = first_conflict(&paths[i], &paths[j], radius_m) else {
+                continue;
+            };
+
+            paths[yielding].hold_from(step);
+            if !adjusted.contains(&yielding) {
+                adjusted.push(yielding);
+            }
+        }
+    }
+
+    adjusted.sort_unstable();
+    adjusted
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Straight-line path sampled at 1 s steps
+    fn path(object_type: &str, start: [f32; 2], velocity: [f32; 2], steps: usize) -> PredictedPath {
+        let points = (1..=steps)
+            .map(|t| [start[0] + velocity[0] * t as f32, start[1] + velocity[1] * t as f32])
+            .collect();
+        PredictedPath::new(yield_priority(object_type), start, points)
+    }
+
+    #[test]
+    fn test_vehicle_yields_to_crossing_pedestrian() {
+        // Vehicle drives along x, pedestrian walks across its lane and both reach (10, 0) at t = 2
+        let mut paths = vec![
+            path("vehicle", [0.0, 0.0], [5.0, 0.0], 4),
+            path("pedestrian", [10.0, -3.0], [0.0, 1.5], 4),
+        ];
+        let pedestrian = paths[1].clone();
+
+        assert_eq!(resolve_conflicts(&mut paths, DEFAULT_CONFLICT_RADIUS_M), vec![0]);
+        assert_eq!(paths[0].held_from, Some(1));
+        assert_eq!(paths[0].points, vec![[5.0, 0.0]; 4]);
+        assert_eq!(paths[1], pedestrian);
+    }
+
+    #[test]
+    fn test_conflict_at_first_step_holds_at_start() {
+        let mut paths = vec![
+            path("pedestrian", [1.0, 0.0], [0.0, 0.0], 3),
+            path("car", [0.0, 0.0], [1.0, 0.0], 3),
+        ];
+
+        assert_eq!(resolve_conflicts(&mut paths, DEFAULT_CONFLICT_RADIUS_M), vec![1]);
+        assert_eq!(paths[1].points, vec![[0.0, 0.0]; 3]);
+    }
+
+    #[test]
+    fn test_separated_or_equal_priority_paths_untouched() {
+        let mut paths = vec![
+            path("vehicle", [0.0, 0.0], [5.0, 0.0], 4),
+            path("pedestrian", [0.0, 20.0], [1.0, 0.0], 4),
+            path("truck", [20.0, 0.0], [-5.0, 0.0], 4),
+        ];
+        let original = paths.clone();
+
+        // The vehicles meet head-on but neither has right of way over the other
+        assert!(resolve_conflicts(&mut paths, DEFAULT_CONFLICT_RADIUS_M).is_empty());
+        assert_eq!(paths, original);
+    }
+}
@@ -4,17 +4,24 @@ use behavior_prediction_ai_bindings::exports::adas::behavior_prediction::{
     diagnostics::{self, Health, TestResult},
 };
 
+use adas_confidence::{clamp_confidence, ConfidencePolicy, DEFAULT_CONFIDENCE_CEILING, DEFAULT_CONFIDENCE_FLOOR};
+use adas_lifecycle::{Lifecycle, LifecycleEvent, LifecycleState, Transition};
+use adas_timing::{elapsed_secs, monotonic_now_ms};
 use std::cell::RefCell;
-use std::time::{SystemTime, UNIX_EPOCH};
 use std::collections::HashMap;
 
+mod interaction;
+
+use interaction::{PredictedPath, DEFAULT_CONFLICT_RADIUS_M};
+
 // Component state
 struct BehaviorPredictionState {
     config: Config,
-    status: Status,
+    lifecycle: Lifecycle,
     frames_processed: u64,
     objects_tracked: u64,
     predictions_generated: u64,
+    trajectories_adjusted: u64,
     start_time: u64,
     last_frame_time: u64,
     health: Health,
@@ -30,6 +37,8 @@ impl Default for BehaviorPredictionState {
                 model_name: "lstm_trajectory_predictor".to_string(),
                 prediction_horizon_seconds: 3.0,
                 confidence_threshold: 0.6,
+                confidence_floor: DEFAULT_CONFIDENCE_FLOOR,
+                confidence_ceiling: DEFAULT_CONFIDENCE_CEILING,
                 max_tracked_objects: 50,
                 temporal_window_frames: 10,
                 motion_models: vec![
@@ -38,11 +47,13 @@ impl Default for BehaviorPredictionState {
                     "bicycle_model".to_string(),
                     "pedestrian_model".to_string(),
                 ],
+                enable_interaction_model: true,
             },
-            status: Status::Inactive,
+            lifecycle: Lifecycle::new("Behavior prediction"),
             frames_processed: 0,
             objects_tracked: 0,
             predictions_generated: 0,
+            trajectories_adjusted: 0,
             start_time: 0,
             last_frame_time: 0,
             health: Health::Healthy,
@@ -57,12 +68,26 @@ thread_local! {
     static STATE: RefCell<BehaviorPredictionState> = RefCell::new(BehaviorPredictionState::default());
 }
 
-// Helper to get current timestamp in milliseconds
+// Helper to get current timestamp in milliseconds (never goes backwards)
 fn get_timestamp_ms() -> u64 {
-    SystemTime::now()
-        .duration_since(UNIX_EPOCH)
-        .unwrap_or_default()
-        .as_millis() as u64
+    monotonic_now_ms()
+}
+
+// Map the shared lifecycle state onto the WIT status enum
+fn to_status(state: LifecycleState) -> Status {
+    match state {
+        LifecycleState::Uninitialized | LifecycleState::Inactive => Status::Inactive,
+        LifecycleState::Active => Status::Active,
+        LifecycleState::Error => Status::Error,
+    }
+}
+
+// Confidence band from the active configuration
+fn confidence_policy(config: &Config) -> ConfidencePolicy {
+    ConfidencePolicy {
+        floor: config.confidence_floor,
+        ceiling: config.confidence_ceiling,
+    }
 }
 
 // Component implementation
@@ -80,6 +105,7 @@ impl prediction_engine::Guest for Component {
             if cfg.confidence_threshold < 0.0 || cfg.confidence_threshold > 1.0 {
                 return Err("Invalid confidence threshold (must be 0.0-1.0)".to_string());
             }
+            ConfidencePolicy::new(cfg.confidence_floor, cfg.confidence_ceiling)?;
             if cfg.max_tracked_objects == 0 || cfg.max_tracked_objects > 200 {
                 return Err("Invalid max tracked objects (must be 1-200)".to_string());
             }
@@ -90,17 +116,19 @@ impl prediction_engine::Guest for Component {
             println!("Behavior Prediction: Initializing model '{}', {:.1}s horizon, {} motion models", 
                 cfg.model_name, cfg.prediction_horizon_seconds, cfg.motion_models.len());
             
+            s.lifecycle.check(LifecycleEvent::Initialize)?;
+            
             s.config = cfg;
-            s.status = Status::Initializing;
             s.frames_processed = 0;
             s.objects_tracked = 0;
             s.predictions_generated = 0;
+            s.trajectories_adjusted = 0;
             s.processing_times.clear();
             s.object_history.clear();
             
             // Simulate model loading
             s.model_loaded = true;
-            s.status = Status::Inactive;
+            s.lifecycle.apply(LifecycleEvent::Initialize)?;
             s.health = Health::Healthy;
             
             Ok(())
@@ -111,8 +139,8 @@ impl prediction_engine::Guest for Component {
         STATE.with(|state| {
             let mut s = state.borrow_mut();
             
-            if matches!(s.status, Status::Active) {
-                return Err("Behavior prediction already active".to_string());
+            if s.lifecycle.check(LifecycleEvent::Start)? == Transition::Unchanged {
+                return Ok(());
             }
             
             if !s.model_loaded {
@@ -120,7 +148,7 @@ impl prediction_engine::Guest for Component {
             }
             
             println!("Behavior Prediction: Starting trajectory prediction");
-            s.status = Status::Active;
+            s.lifecycle.apply(LifecycleEvent::Start)?;
             s.start_time = get_timestamp_ms();
             s.last_frame_time = s.start_time;
             
@@ -132,12 +160,11 @@ impl prediction_engine::Guest for Component {
         STATE.with(|state| {
             let mut s = state.borrow_mut();
             
-            if !matches!(s.status, Status::Active) {
-                return Err("Behavior prediction not active".to_string());
+            if s.lifecycle.apply(LifecycleEvent::Stop)? == Transition::Unchanged {
+                return Ok(());
             }
             
             println!("Behavior Prediction: Stopping trajectory prediction");
-            s.status = Status::Inactive;
             s.object_history.clear();
             
             Ok(())
@@ -148,11 +175,12 @@ impl prediction_engine::Guest for Component {
         STATE.with(|state| {
             let mut s = state.borrow_mut();
             
-            if !matches!(s.status, Status::Active) {
+            if !s.lifecycle.is_active() {
                 return Err("Behavior prediction not active".to_string());
             }
             
             let now = get_timestamp_ms();
+            s.lifecycle.record_activity(now);
             s.frames_processed += 1;
             s.last_frame_time = now;
             
@@ -171,6 +199,8 @@ impl prediction_engine::Guest for Component {
             
             // Generate trajectory predictions
             let mut trajectories = Vec::new();
+            let mut paths = Vec::new();
+            let policy = confidence_policy(&s.config);
             
             for obj in objects {
                 if obj.confidence < s.config.confidence_threshold {
@@ -244,7 +274,7 @@ impl prediction_engine::Guest for Component {
                     };
                     
                     // Confidence decreases over time
-                    let confidence = obj.confidence * (1.0 - time_factor * 0.3);
+                    let confidence = clamp_confidence(obj.confidence * (1.0 - time_factor * 0.3), &policy);
                     
                     trajectory_points.push(TrajectoryPoint {
                         position: predicted_position,
@@ -268,6 +298,12 @@ impl prediction_engine::Guest for Component {
                     (RiskLevel::Low, 0.05)
                 };
                 
+                paths.push(PredictedPath::new(
+                    interaction::yield_priority(&obj.object_type),
+                    [obj.position.x, obj.position.y],
+                    trajectory_points.iter().map(|p| [p.position.x, p.position.y]).collect(),
+                ));
+                
                 trajectories.push(PredictedTrajectory {
                     object_id: obj.object_id,
                     trajectory_points,
@@ -277,6 +313,23 @@ impl prediction_engine::Guest for Component {
                 });
             }
             
+            // Hold back objects that would run into one with right of way
+            if s.config.enable_interaction_model {
+                let adjusted = interaction::resolve_conflicts(&mut paths, DEFAULT_CONFLICT_RADIUS_M);
+                for &index in &adjusted {
+                    let path = &paths[index];
+                    let held_from = path.held_from.unwrap_or(path.points.len());
+                    for (step, (point, held)) in trajectories[index].trajectory_points.iter_mut().zip(&path.points).enumerate() {
+                        point.position.x = held[0];
+                        point.position.y = held[1];
+                        if step >= held_from {
+                            point.velocity = Velocity { x: 0.0, y: 0.0, z: 0.0 };
+                        }
+                    }
+                }
+                s.trajectories_adjusted += adjusted.len() as u64;
+            }
+            
             s.predictions_generated += trajectories.len() as u64;
             
             // Simulate processing time
@@ -305,14 +358,14 @@ impl prediction_engine::Guest for Component {
     }
 
     fn get_status() -> Status {
-        STATE.with(|state| state.borrow().status.clone())
+        STATE.with(|state| to_status(state.borrow().lifecycle.state()))
     }
 
     fn get_stats() -> Stats {
         STATE.with(|state| {
             let s = state.borrow();
             let elapsed_sec = if s.start_time > 0 {
-                ((get_timestamp_ms() - s.start_time) as f32) / 1000.0
+                elapsed_secs(get_timestamp_ms(), s.start_time)
             } else {
                 0.0
             };
@@ -328,6 +381,7 @@ impl prediction_engine::Guest for Component {
                 objects_tracked: s.objects_tracked,
                 predictions_generated: s.predictions_generated,
                 average_processing_time_ms: average_processing_time,
+                trajectories_adjusted: s.trajectories_adjusted,
                 cpu_percent: 40.0 + (elapsed_sec * 0.04).sin() * 10.0,
                 memory_mb: 1024,
             }
@@ -340,6 +394,7 @@ impl prediction_engine::Guest for Component {
             s.frames_processed = 0;
             s.objects_tracked = 0;
             s.predictions_generated = 0;
+            s.trajectories_adjusted = 0;
             s.processing_times.clear();
             s.object_history.clear();
             s.start_time = get_timestamp_ms();
@@ -419,6 +474,10 @@ impl diagnostics::Guest for Component {
         results
     }
 
+    fn get_last_activity() -> u64 {
+        STATE.with(|state| state.borrow().lifecycle.last_activity_ms().unwrap_or(0))
+    }
+
     fn get_report() -> String {
         STATE.with(|state| {
             let s = state.borrow();
@@ -439,11 +498,13 @@ Configuration:
   Max tracked objects: {}
   Temporal window: {} frames
   Motion models: {}
+  Interaction model: {}
 
 Performance:
   Frames processed: {}
   Objects tracked: {}
   Predictions generated: {}
+  Trajectories adjusted: {}
   Average processing time: {:.1} ms
   CPU usage: {:.1}%
   Memory usage: {} MB
@@ -457,7 +518,7 @@ AI Model Info:
   Risk assessment capability
   Temporal sequence analysis
 "#,
-                s.status,
+                s.lifecycle.state(),
                 s.health,
                 s.config.model_name,
                 s.config.prediction_horizon_seconds,
@@ -465,9 +526,11 @@ AI Model Info:
                 s.config.max_tracked_objects,
                 s.config.temporal_window_frames,
                 motion_models,
+                s.config.enable_interaction_model,
                 stats.frames_processed,
                 stats.objects_tracked,
                 stats.predictions_generated,
+                stats.trajectories_adjusted,
                 stats.average_processing_time_ms,
                 stats.cpu_percent,
                 stats.memory_mb,
@@ -0,0 +1,162 @@
+// Confidence calibration ahead of thresholding
+// Raw YOLO scores are overconfident; temperature scaling in logit space and a
+// per-class offset map them onto values downstream stages can treat as
+// probabilities
+
+/// Scores are kept this far inside (0, 1) so their logits stay finite
+const PROBABILITY_EPSILON: f32 = 1e-6;
+
+/// Largest correction a single class offset may apply
+pub const MAX_CLASS_OFFSET: f32 = 0.5;
+
+/// Monotonic recalibration of detector confidences
+#[derive(Debug, Clone, PartialEq)]
+pub struct Calibration {
+    /// Logits are divided by this; above 1 softens overconfident scores
+    pub temperature: f32,
+    /// Added to the scaled confidence of the named class
+    pub class_offsets: Vec<(String, f32)>,
+}
+
+impl Default for Calibration {
+    fn default() -> Self {
+        Self { temperature: 1.0, class_offsets: Vec::new() }
+    }
+}
+
+impl Calibration {
+    /// Create a calibration, rejecting non-positive temperatures and
+    /// offsets beyond [`MAX_CLASS_OFFSET`]
+    pub fn new(temperature: f32, class_offsets: Vec<(String, f32)>) -> Result<Self, String> {
+        if !temperature.is_finite() || temperature <= 0.0 {
+            return Err(format!("Invalid confidence temperature {} (must be positive)", temperature));
+        }
+        if let Some((class_name, offset)) = class_offsets
+            .iter()
+            .find(|(_, offset)| !offset.is_finite() || offset.abs() > MAX_CLASS_OFFSET)
+        {
+            return Err(format!(
+                "Invalid confidence offset {} for class '{}' (must be within ±{})",
+                offset, class_name, MAX_CLASS_OFFSET
+            ));
+        }
+        Ok(Self { temperature, class_offsets })
+    }
+
+    /// Whether every confidence passes through unchanged
+    pub fn is_identity(&self) -> bool {
+        self.temperature == 1.0 && self.class_offsets.iter().all(|(_, offset)| *offset == 0.0)
+    }
+
+    /// Offset configured for a class; unlisted classes get none
+    pub fn offset_for(&self, class_name: &str) -> f32 {
+        self.class_offsets
+            .iter()
+            .find(|(name, _)| name == class_name)
+            .map_or(0.0, |(_, offset)| *offset)
+    }
+
+    /// Calibrated confidence of a raw detector score
+    pub fn apply(&self, confidence: f32, class_name: &str) -> f32 {
+        (scale(confidence, self.temperature) + self.offset_for(class_name)).clamp(0.0, 1.0)
+    }
+
+    /// Raw score below which no class can calibrate above `threshold`
+    ///
+    /// Decoding candidates at this score instead of at `threshold` keeps
+    /// every detection the calibrated threshold would accept.
+    pub fn raw_threshold(&self, threshold: f32) -> f32 {
+        let max_offset = self.class_offsets.iter().map(|(_, offset)| *offset).fold(0.0, f32::max);
+        let target = threshold - max_offset;
+        if target <= 0.0 {
+            return 0.0;
+        }
+        if target >= 1.0 {
+            return 1.0;
+        }
+        // Inverse of the temperature scaling, widened against rounding
+        (scale(target, 1.0 / self.temperature) - PROBABILITY_EPSILON).max(0.0)
+    }
+}
+
+/// Temperature-scale a probability in logit space
+fn scale(probability: f32, temperature: f32) -> f32 {
+    if temperature == 1.0 {
+        return probability;
+    }
+    let p = probability.clamp(PROBABILITY_EPSILON, 1.0 - PROBABILITY_EPSILON);
+    let logit = (p / (1.0 - p)).ln();
+    1.0 / (1.0 + (-logit / temperature).exp())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_default_calibration_is_identity() {
+        let calibration = Calibration::default();
+        assert!(calibration.is_identity());
+        for confidence in [0.0, 0.25, 0.5, 0.9, 1.0] {
+            assert_eq!(calibration.apply(confidence, "person"), confidence);
+        }
+        assert_eq!(calibration.raw_threshold(0.5), 0.5 - PROBABILITY_EPSILON);
+    }
+
+    #[test]
+    fn test_temperature_softens_and_keeps_order() {
+        let calibration = Calibration::new(2.0, Vec::new()).unwrap();
+
+        // Scores move towards 0.5 without changing their order
+        assert!((calibration.apply(0.5, "car") - 0.5).abs() < 1e-6);
+        let calibrated: Vec<f32> = [0.6, 0.8, 0.95, 0.99].iter().map(|&c| calibration.apply(c, "car")).collect();
+        assert!(calibrated.windows(2).all(|pair| pair[0] < pair[1]));
+        assert!(calibrated.iter().zip([0.6, 0.8, 0.95, 0.99]).all(|(&after, before)| after < before));
+
+        // 0.9 has logit ln(9); halving it gives 3 / (3 + 1)
+        assert!((calibration.apply(0.9, "car") - 0.75).abs() < 1e-5);
+    }
+
+    #[test]
+    fn test_class_offsets() {
+        let offsets = vec![("person".to_string(), 0.1), ("traffic light".to_string(), -0.2)];
+        let calibration = Calibration::new(1.0, offsets).unwrap();
+        assert!(!calibration.is_identity());
+
+        assert!((calibration.apply(0.5, "person") - 0.6).abs() < 1e-6);
+        assert!((calibration.apply(0.5, "traffic light") - 0.3).abs() < 1e-6);
+        assert_eq!(calibration.apply(0.5, "car"), 0.5);
+        assert_eq!(calibration.apply(0.95, "person"), 1.0);
+        assert_eq!(calibration.apply(0.1, "traffic light"), 0.0);
+    }
+
+    #[test]
+    fn test_raw_threshold_keeps_every_accepted_score() {
+        let offsets = vec![("person".to_string(), 0.15)];
+        let calibration = Calibration::new(1.5, offsets).unwrap();
+        let threshold = 0.5;
+        let raw_threshold = calibration.raw_threshold(threshold);
+
+        for step in 0..=1000 {
+            let raw = step as f32 / 1000.0;
+            for class_name in ["person", "car"] {
+                if calibration.apply(raw, class_name) > threshold {
+                    assert!(raw > raw_threshold, "{} for {} is decoded below {}", raw, class_name, raw_threshold);
+                }
+            }
+        }
+
+        assert_eq!(calibration.raw_threshold(0.1), 0.0);
+        assert_eq!(Calibration::default().raw_threshold(1.0), 1.0);
+    }
+
+    #[test]
+    fn test_invalid_parameters_are_rejected() {
+        assert!(Calibration::new(0.0, Vec::new()).is_err());
+        assert!(Calibration::new(-1.0, Vec::new()).is_err());
+        assert!(Calibration::new(f32::NAN, Vec::new()).is_err());
+        assert!(Calibration::new(1.0, vec![("car".to_string(), 0.8)]).is_err());
+        assert!(Calibration::new(1.0, vec![("car".to_string(), f32::INFINITY)]).is_err());
+        assert!(Calibration::new(0.5, vec![("car".to_string(), -0.5)]).is_ok());
+    }
+}
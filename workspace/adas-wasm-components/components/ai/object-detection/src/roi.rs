@@ -0,0 +1,92 @@
+// Region-of-interest cropping ahead of detection
+// Resolves the configured ROI against each frame and maps boxes found in the
+// crop back into full-frame coordinates
+
+/// Area of a frame, in pixels, that is fed to the model
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CropWindow {
+    pub x: u32,
+    pub y: u32,
+    pub width: u32,
+    pub height: u32,
+}
+
+impl CropWindow {
+    /// The whole frame
+    pub fn full(frame_width: u32, frame_height: u32) -> Self {
+        Self { x: 0, y: 0, width: frame_width, height: frame_height }
+    }
+
+    /// Resolve a configured ROI against a frame
+    ///
+    /// A zero width or height selects the whole frame. The ROI is clipped to
+    /// the frame; one starting outside it is an error.
+    pub fn resolve(x: u32, y: u32, width: u32, height: u32, frame_width: u32, frame_height: u32) -> Result<Self, String> {
+        if width == 0 || height == 0 {
+            return Ok(Self::full(frame_width, frame_height));
+        }
+        if x >= frame_width || y >= frame_height {
+            return Err(format!(
+                "Region of interest at ({}, {}) lies outside the {}x{} frame",
+                x, y, frame_width, frame_height
+            ));
+        }
+        Ok(Self { x, y, width: width.min(frame_width - x), height: height.min(frame_height - y) })
+    }
+
+    pub fn is_full_frame(&self, frame_width: u32, frame_height: u32) -> bool {
+        *self == Self::full(frame_width, frame_height)
+    }
+
+    /// Point in crop pixels to full-frame pixels
+    pub fn to_frame(&self, x: f32, y: f32) -> (f32, f32) {
+        (x + self.x as f32, y + self.y as f32)
+    }
+
+    /// Point in full-frame pixels to crop pixels
+    pub fn to_crop(&self, x: f32, y: f32) -> (f32, f32) {
+        (x - self.x as f32, y - self.y as f32)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_empty_roi_selects_full_frame() {
+        let window = CropWindow::resolve(0, 0, 0, 0, 1920, 1080).unwrap();
+        assert_eq!(window, CropWindow::full(1920, 1080));
+        assert!(window.is_full_frame(1920, 1080));
+
+        // A zero extent on either axis also means the whole frame
+        assert!(CropWindow::resolve(100, 360, 1920, 0, 1920, 1080).unwrap().is_full_frame(1920, 1080));
+    }
+
+    #[test]
+    fn test_roi_is_clipped_to_frame() {
+        let window = CropWindow::resolve(0, 360, 1920, 1080, 1920, 1080).unwrap();
+        assert_eq!(window, CropWindow { x: 0, y: 360, width: 1920, height: 720 });
+        assert!(!window.is_full_frame(1920, 1080));
+
+        assert!(CropWindow::resolve(1920, 0, 100, 100, 1920, 1080).is_err());
+        assert!(CropWindow::resolve(0, 2000, 100, 100, 1920, 1080).is_err());
+    }
+
+    #[test]
+    fn test_box_round_trips_through_crop() {
+        // Drop the sky: the top third of a 1920x1080 front camera frame
+        let window = CropWindow::resolve(0, 360, 1920, 720, 1920, 1080).unwrap();
+        let (x, y, width, height) = (812.5, 540.0, 96.0, 210.0);
+
+        let (crop_x, crop_y) = window.to_crop(x, y);
+        assert_eq!((crop_x, crop_y), (812.5, 180.0));
+
+        let (left, top) = window.to_frame(crop_x, crop_y);
+        let (right, bottom) = window.to_frame(crop_x + width, crop_y + height);
+        assert_eq!((left, top, right - left, bottom - top), (x, y, width, height));
+
+        // The crop's bottom-right corner is the ROI's in the full frame
+        assert_eq!(window.to_frame(window.width as f32, window.height as f32), (1920.0, 1080.0));
+    }
+}
@@ -0,0 +1,128 @@
+// Health-based adaptive sensor weighting
+// Scales configured sensor weights by a rolling reliability estimate built from
+// each sensor type's recent latency and data quality
+
+use std::collections::{HashMap, HashSet};
+
+/// Smoothing factor of the rolling reliability estimate
+const RELIABILITY_SMOOTHING: f32 = 0.2;
+
+/// Reliability lost by a report that arrives exactly at the latency budget
+const LATENCY_PENALTY: f32 = 0.5;
+
+/// Rolling reliability per sensor type, starting at full trust
+#[derive(Debug, Clone, Default)]
+pub struct AdaptiveWeights {
+    reliability: HashMap<String, f32>,
+}
+
+impl AdaptiveWeights {
+    /// Feed one sensor status report into its type's reliability estimate
+    ///
+    /// A report over the latency budget counts as no reliability at all.
+    pub fn observe(&mut self, sensor_type: &str, latency_ms: u32, data_quality: f32, max_latency_ms: u32) {
+        let score = if latency_ms > max_latency_ms {
+            0.0
+        } else {
+            let latency_fraction = latency_ms as f32 / max_latency_ms.max(1) as f32;
+            data_quality.clamp(0.0, 1.0) * (1.0 - LATENCY_PENALTY * latency_fraction)
+        };
+
+        let estimate = self.reliability.entry(sensor_type.to_string()).or_insert(1.0);
+        *estimate += RELIABILITY_SMOOTHING * (score - *estimate);
+    }
+
+    /// Current reliability estimate of a sensor type
+    pub fn reliability(&self, sensor_type: &str) -> f32 {
+        self.reliability.get(sensor_type).copied().unwrap_or(1.0)
+    }
+
+    /// Effective weight of each configured sensor type for this frame
+    ///
+    /// Types in `active` get their configured weight scaled by their
+    /// reliability, renormalized to sum to 1; all other types get 0, so their
+    /// share goes to the sensors still delivering.
+    pub fn effective_weights(&self, configured: &[(String, f32)], active: &HashSet<String>) -> Vec<(String, f32)> {
+        let scaled: Vec<(String, f32)> = configured
+            .iter()
+            .map(|(sensor_type, weight)| {
+                let weight = if active.contains(sensor_type) {
+                    weight.max(0.0) * self.reliability(sensor_type)
+                } else {
+                    0.0
+                };
+                (sensor_type.clone(), weight)
+            })
+            .collect();
+
+        let total: f32 = scaled.iter().map(|(_, w)| w).sum();
+        if total <= 0.0 {
+            return scaled;
+        }
+        scaled
+            .into_iter()
+            .map(|(sensor_type, w)| (sensor_type, w / total))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn configured() -> Vec<(String, f32)> {
+        [("camera", 0.4), ("radar", 0.3), ("lidar", 0.3)]
+            .iter()
+            .map(|(t, w)| (t.to_string(), *w))
+            .collect()
+    }
+
+    fn set(types: &[&str]) -> HashSet<String> {
+        types.iter().map(|t| t.to_string()).collect()
+    }
+
+    fn weight_of(weights: &[(String, f32)], sensor_type: &str) -> f32 {
+        weights.iter().find(|(t, _)| t == sensor_type).unwrap().1
+    }
+
+    #[test]
+    fn test_degraded_sensor_loses_weight() {
+        let mut adaptive = AdaptiveWeights::default();
+        let all = set(&["camera", "radar", "lidar"]);
+
+        let fresh = adaptive.effective_weights(&configured(), &all);
+        assert!((weight_of(&fresh, "camera") - 0.4).abs() < 1e-6);
+
+        for _ in 0..20 {
+            adaptive.observe("camera", 80, 0.3, 100);
+            adaptive.observe("radar", 5, 0.95, 100);
+            adaptive.observe("lidar", 5, 0.95, 100);
+        }
+        let degraded = adaptive.effective_weights(&configured(), &all);
+        assert!(weight_of(&degraded, "camera") < 0.2);
+        assert!(weight_of(&degraded, "radar") > 0.3);
+        let total: f32 = degraded.iter().map(|(_, w)| w).sum();
+        assert!((total - 1.0).abs() < 1e-5);
+
+        // Recovery is gradual
+        adaptive.observe("camera", 5, 0.95, 100);
+        assert!(adaptive.reliability("camera") < adaptive.reliability("radar"));
+    }
+
+    #[test]
+    fn test_late_sensor_weight_is_redistributed() {
+        let mut adaptive = AdaptiveWeights::default();
+        adaptive.observe("radar", 150, 0.9, 100);
+        assert!(adaptive.reliability("radar") < 1.0);
+
+        let weights = adaptive.effective_weights(&configured(), &set(&["camera", "lidar"]));
+        assert_eq!(weight_of(&weights, "radar"), 0.0);
+        assert!((weight_of(&weights, "camera") - 0.4 / 0.7).abs() < 1e-5);
+        assert!((weight_of(&weights, "lidar") - 0.3 / 0.7).abs() < 1e-5);
+
+        assert!(adaptive
+            .effective_weights(&configured(), &set(&[]))
+            .iter()
+            .all(|(_, w)| *w == 0.0));
+    }
+}
@@ -0,0 +1,199 @@
+// Association of fused detections with existing object tracks
+// Keeps object IDs stable across frames by matching on predicted position
+
+/// Match each detection to at most one track, and each track to at most one detection
+///
+/// Minimises the total horizontal distance of the matched pairs with
+/// [`hungarian`]; pairs further apart than `gate_m` are never matched.
+/// Returns the matched track ID per detection, `None` for detections that
+/// start a new track.
+pub fn assign_to_tracks(tracks: &[(u32, [f32; 3])], detections: &[[f32; 3]], gate_m: f32) -> Vec<Option<u32>> {
+    let costs: Vec<Vec<f32>> = detections
+        .iter()
+        .map(|detection| {
+            tracks
+                .iter()
+                .map(|(_, track)| ((track[0] - detection[0]).powi(2) + (track[1] - detection[1]).powi(2)).sqrt())
+                .collect()
+        })
+        .collect();
+
+    hungarian(&costs, gate_m)
+        .into_iter()
+        .map(|column| column.map(|t| tracks[t].0))
+        .collect()
+}
+
+/// Optimal assignment of rows to columns of a cost matrix (Kuhn–Munkres)
+///
+/// Pairs costing more than `gate` are disallowed. Among the assignments with
+/// the most allowed pairs, returns the one with the lowest total cost, as the
+/// assigned column per row. The matrix may be rectangular; rows must all
+/// have the same length.
+pub fn hungarian(costs: &[Vec<f32>], gate: f32) -> Vec<Option<usize>> {
+    let rows = costs.len();
+    let cols = costs.first().map_or(0, Vec::len);
+    if rows == 0 || cols == 0 {
+        return vec![None; rows];
+    }
+
+    // Disallowed pairs cost more than any complete set of allowed ones, so
+    // the solver only uses them where no allowed pair is left
+    let allowed = |c: f32| c.is_finite() && c <= gate;
+    let allowed_total: f64 = costs.iter().flatten().filter(|c| allowed(**c)).map(|c| *c as f64).sum();
+    let disallowed = allowed_total + 1.0;
+
+    // Square matrix padded with zero-cost dummy rows or columns
+    let n = rows.max(cols);
+    let cost = |i: usize, j: usize| -> f64 {
+        match costs.get(i).and_then(|row| row.get(j)) {
+            Some(c) if allowed(*c) => *c as f64,
+            Some(_) => disallowed,
+            None => 0.0,
+        }
+    };
+
+    // Shortest augmenting path with row/column potentials, 1-based with a
+    // virtual column 0; row_of[j] is the row matched to column j
+    let mut u = vec![0.0f64; n + 1];
+    let mut v = vec![0.0f64; n + 1];
+    let mut row_of = vec![0usize; n + 1];
+    let mut way = vec![0usize; n + 1];
+
+    for i in 1..=n {
+        row_of[0] = i;
+        let mut j0 = 0;
+        let mut min_to = vec![f64::INFINITY; n + 1];
+        let mut used = vec![false; n + 1];
+
+        loop {
+            used[j0] = true;
+            let i0 = row_of[j0];
+            let mut delta = f64::INFINITY;
+            let mut j1 = 0;
+            for j in 1..=n {
+                if used[j] {
+                    continue;
+                }
+                let reduced = cost(i0 - 1, j - 1) - u[i0] - v[j];
+                if reduced < min_to[j] {
+                    min_to[j] = reduced;
+                    way[j] = j0;
+                }
+                if min_to[j] < delta {
+                    delta = min_to[j];
+                    j1 = j;
+                }
+            }
+            for j in 0..=n {
+                if used[j] {
+                    u[row_of[j]] += delta;
+                    v[j] -= delta;
+                } else {
+                    min_to[j] -= delta;
+                }
+            }
+            j0 = j1;
+            if row_of[j0] == 0 {
+                break;
+            }
+        }
+
+        // Flip the augmenting path
+        loop {
+            let j1 = way[j0];
+            row_of[j0] = row_of[j1];
+            j0 = j1;
+            if j0 == 0 {
+                break;
+            }
+        }
+    }
+
+    let mut assignment = vec![None; rows];
+    for j in 1..=cols {
+        let i = row_of[j];
+        if (1..=rows).contains(&i) && allowed(costs[i - 1][j - 1]) {
+            assignment[i - 1] = Some(j - 1);
+        }
+    }
+    assignment
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_closest_pair_wins() {
+        let tracks = [(7, [10.0, 0.0, 0.0]), (9, [12.0, 0.0, 0.0])];
+        // Detection 0 is nearest to track 9 but track 9 is closer still to detection 1
+        let detections = [[11.2, 0.0, 0.0], [12.1, 0.0, 0.0]];
+
+        assert_eq!(assign_to_tracks(&tracks, &detections, 3.0), vec![Some(7), Some(9)]);
+    }
+
+    #[test]
+    fn test_each_track_matched_once() {
+        let tracks = [(1, [0.0, 0.0, 0.0])];
+        let detections = [[0.5, 0.0, 0.0], [0.2, 0.0, 0.0]];
+
+        assert_eq!(assign_to_tracks(&tracks, &detections, 3.0), vec![None, Some(1)]);
+    }
+
+    #[test]
+    fn test_minimises_total_distance() {
+        // Greedy matching would pair detection 0 with the nearer track 2 and
+        // leave detection 1 to track 1, a far worse total
+        let tracks = [(1, [0.0, 0.0, 0.0]), (2, [1.5, 0.0, 0.0])];
+        let detections = [[1.0, 0.0, 0.0], [2.9, 0.0, 0.0]];
+
+        assert_eq!(assign_to_tracks(&tracks, &detections, 3.0), vec![Some(1), Some(2)]);
+    }
+
+    #[test]
+    fn test_hungarian_rectangular_and_gated() {
+        let costs = vec![vec![4.0, 1.0, 3.0], vec![2.0, 0.0, 5.0]];
+        assert_eq!(hungarian(&costs, 10.0), vec![Some(1), Some(0)]);
+
+        // Matching more pairs wins over a cheaper total
+        let costs = vec![vec![1.0, 2.0], vec![1.5, 9.0]];
+        assert_eq!(hungarian(&costs, 3.0), vec![Some(1), Some(0)]);
+
+        let costs = vec![vec![5.0], vec![0.5]];
+        assert_eq!(hungarian(&costs, 1.0), vec![None, Some(0)]);
+        assert_eq!(hungarian(&[vec![], vec![]], 1.0), vec![None, None]);
+    }
+
+    #[test]
+    fn test_crossing_trajectories_keep_ids() {
+        // Two objects pass each other 1 m apart laterally. Object 2 is
+        // detected 0.7 m towards object 1, object 1 0.5 m away from it; where
+        // they cross, greedy matching would give object 2's detection to track 1
+        let mut tracks = vec![(1, [-6.0, 0.0, 0.0]), (2, [6.0, 1.0, 0.0])];
+        let velocities = [1.0, -1.0];
+
+        for frame in 1..=12 {
+            let t = frame as f32;
+            let detections = [[6.0 - t, 0.3, 0.0], [-6.0 + t, -0.5, 0.0]];
+            let predicted: Vec<(u32, [f32; 3])> = tracks
+                .iter()
+                .zip(velocities)
+                .map(|((id, p), vx)| (*id, [p[0] + vx, p[1], p[2]]))
+                .collect();
+
+            let assignment = assign_to_tracks(&predicted, &detections, 3.0);
+            assert_eq!(assignment, vec![Some(2), Some(1)], "frame {}", frame);
+
+            tracks = predicted;
+        }
+    }
+
+    #[test]
+    fn test_outside_gate_starts_new_track() {
+        let tracks = [(1, [0.0, 0.0, 0.0])];
+
+        assert_eq!(assign_to_tracks(&tracks, &[[0.0, 4.0, 0.0]], 3.0), vec![None]);
+        assert_eq!(assign_to_tracks(&[], &[[0.0, 0.0, 0.0]], 3.0), vec![None]);
+    }
+}
@@ -0,0 +1,161 @@
+// Deterministic sensor-dropout schedule for degradation testing
+// Drops whole sensor types for fixed frame windows and grades fusion health
+// by how much configured sensor weight is still delivering data
+
+use std::collections::HashSet;
+
+/// Fraction of seen sensor weight below which fusion is critical
+const CRITICAL_WEIGHT_FRACTION: f32 = 0.5;
+
+/// One sensor type suppressed for a window of frames
+#[derive(Debug, Clone, PartialEq)]
+pub struct DropoutWindow {
+    pub sensor_type: String,
+    pub start_frame: u64,
+    pub duration_frames: u64,
+}
+
+impl DropoutWindow {
+    fn covers(&self, sensor_type: &str, frame: u64) -> bool {
+        self.sensor_type == sensor_type
+            && frame >= self.start_frame
+            && frame - self.start_frame < self.duration_frames
+    }
+}
+
+/// Frame-indexed dropout schedule
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct DropoutSchedule {
+    windows: Vec<DropoutWindow>,
+}
+
+impl DropoutSchedule {
+    pub fn new(windows: Vec<DropoutWindow>) -> Result<Self, String> {
+        if let Some(window) = windows.iter().find(|w| w.duration_frames == 0) {
+            return Err(format!("Dropout window for '{}' has zero duration", window.sensor_type));
+        }
+        Ok(Self { windows })
+    }
+
+    /// Whether data from `sensor_type` is suppressed on `frame`
+    pub fn is_dropped(&self, sensor_type: &str, frame: u64) -> bool {
+        self.windows.iter().any(|w| w.covers(sensor_type, frame))
+    }
+}
+
+/// Fusion health graded by remaining sensor coverage
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum CoverageHealth {
+    Healthy,
+    Degraded,
+    Critical,
+    Offline,
+}
+
+/// Grade health from the sensor types seen so far and those delivering now
+///
+/// Only sensor types that have reported at least once count, so a host that
+/// never feeds lidar is not permanently degraded.
+pub fn assess_coverage(weights: &[(String, f32)], seen: &HashSet<String>, available: &HashSet<String>) -> CoverageHealth {
+    let weight_of = |types: &HashSet<String>| -> f32 {
+        weights
+            .iter()
+            .filter(|(sensor_type, _)| seen.contains(sensor_type) && types.contains(sensor_type))
+            .map(|(_, weight)| weight.max(0.0))
+            .sum()
+    };
+
+    let expected = weight_of(seen);
+    if expected <= 0.0 {
+        return CoverageHealth::Healthy;
+    }
+
+    let remaining = weight_of(available) / expected;
+    if remaining >= 1.0 - f32::EPSILON {
+        CoverageHealth::Healthy
+    } else if remaining >= CRITICAL_WEIGHT_FRACTION {
+        CoverageHealth::Degraded
+    } else if remaining > 0.0 {
+        CoverageHealth::Critical
+    } else {
+        CoverageHealth::Offline
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn weights() -> Vec<(String, f32)> {
+        [("camera", 0.4), ("radar", 0.3), ("lidar", 0.25), ("ultrasonic", 0.05)]
+            .iter()
+            .map(|(t, w)| (t.to_string(), *w))
+            .collect()
+    }
+
+    fn set(types: &[&str]) -> HashSet<String> {
+        types.iter().map(|t| t.to_string()).collect()
+    }
+
+    #[test]
+    fn test_window_bounds() {
+        let schedule = DropoutSchedule::new(vec![DropoutWindow {
+            sensor_type: "radar".to_string(),
+            start_frame: 10,
+            duration_frames: 5,
+        }])
+        .unwrap();
+
+        assert!(!schedule.is_dropped("radar", 9));
+        assert!(schedule.is_dropped("radar", 10));
+        assert!(schedule.is_dropped("radar", 14));
+        assert!(!schedule.is_dropped("radar", 15));
+        assert!(!schedule.is_dropped("camera", 12));
+    }
+
+    #[test]
+    fn test_zero_duration_rejected() {
+        let windows = vec![DropoutWindow {
+            sensor_type: "radar".to_string(),
+            start_frame: 0,
+            duration_frames: 0,
+        }];
+        assert!(DropoutSchedule::new(windows).is_err());
+    }
+
+    #[test]
+    fn test_health_degrades_as_sensors_drop() {
+        let all = ["camera", "radar", "lidar", "ultrasonic"];
+        let schedule = DropoutSchedule::new(vec![
+            DropoutWindow { sensor_type: "radar".to_string(), start_frame: 10, duration_frames: 40 },
+            DropoutWindow { sensor_type: "camera".to_string(), start_frame: 20, duration_frames: 30 },
+            DropoutWindow { sensor_type: "lidar".to_string(), start_frame: 30, duration_frames: 20 },
+            DropoutWindow { sensor_type: "ultrasonic".to_string(), start_frame: 40, duration_frames: 10 },
+        ])
+        .unwrap();
+
+        let seen = set(&all);
+        let health_at = |frame: u64| {
+            let available = all
+                .iter()
+                .filter(|t| !schedule.is_dropped(t, frame))
+                .map(|t| t.to_string())
+                .collect();
+            assess_coverage(&weights(), &seen, &available)
+        };
+
+        assert_eq!(health_at(0), CoverageHealth::Healthy);
+        assert_eq!(health_at(10), CoverageHealth::Degraded);
+        assert_eq!(health_at(20), CoverageHealth::Critical);
+        assert_eq!(health_at(30), CoverageHealth::Critical);
+        assert_eq!(health_at(40), CoverageHealth::Offline);
+        assert_eq!(health_at(50), CoverageHealth::Healthy);
+    }
+
+    #[test]
+    fn test_unseen_sensors_do_not_degrade() {
+        let seen = set(&["camera"]);
+        assert_eq!(assess_coverage(&weights(), &seen, &set(&["camera"])), CoverageHealth::Healthy);
+        assert_eq!(assess_coverage(&weights(), &seen, &set(&[])), CoverageHealth::Offline);
+    }
+}
@@ -0,0 +1,163 @@
+// Coordinate frames and sensor-to-vehicle extrinsics
+// Brings every measurement into the common fusion frame before association
+
+/// Frame a measurement is expressed in
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CoordinateFrame {
+    /// The sensor's own frame: x along the boresight, y left, z up
+    Sensor,
+    /// ISO 8855 vehicle frame: origin at the rear axle, x forward, y left, z up
+    Vehicle,
+    /// SAE J670 vehicle frame: same origin, x forward, y right, z down
+    VehicleSae,
+}
+
+impl CoordinateFrame {
+    /// Parse a frame tag; unknown tags are rejected
+    pub fn parse(tag: &str) -> Result<Self, String> {
+        match tag.trim().to_ascii_lowercase().as_str() {
+            "sensor" | "sensor_frame" => Ok(CoordinateFrame::Sensor),
+            "vehicle" | "vehicle_frame" | "iso8855" => Ok(CoordinateFrame::Vehicle),
+            "vehicle_sae" | "sae" | "sae_j670" => Ok(CoordinateFrame::VehicleSae),
+            other => Err(format!("Unknown coordinate frame '{}'", other)),
+        }
+    }
+
+    /// Parse the configured common frame, which must be a vehicle frame
+    pub fn parse_common(tag: &str) -> Result<Self, String> {
+        match Self::parse(tag)? {
+            CoordinateFrame::Sensor => {
+                Err("Common coordinate system must be a vehicle frame, not 'sensor'".to_string())
+            }
+            frame => Ok(frame),
+        }
+    }
+}
+
+/// Rigid sensor-to-vehicle transform (ISO 8855 vehicle frame)
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Extrinsic {
+    /// Sensor origin in the vehicle frame (m)
+    pub translation: [f32; 3],
+    /// Rotation taking sensor axes to vehicle axes
+    rotation: [[f32; 3]; 3],
+}
+
+impl Extrinsic {
+    /// Build from the sensor mounting position and its roll, pitch and yaw in degrees
+    ///
+    /// Angles are applied as yaw, then pitch, then roll (R = Rz·Ry·Rx).
+    pub fn new(translation: [f32; 3], roll_deg: f32, pitch_deg: f32, yaw_deg: f32) -> Self {
+        let (sr, cr) = roll_deg.to_radians().sin_cos();
+        let (sp, cp) = pitch_deg.to_radians().sin_cos();
+        let (sy, cy) = yaw_deg.to_radians().sin_cos();
+        Self {
+            translation,
+            rotation: [
+                [cy * cp, cy * sp * sr - sy * cr, cy * sp * cr + sy * sr],
+                [sy * cp, sy * sp * sr + cy * cr, sy * sp * cr - cy * sr],
+                [-sp, cp * sr, cp * cr],
+            ],
+        }
+    }
+
+    fn rotate(&self, v: [f32; 3]) -> [f32; 3] {
+        let r = &self.rotation;
+        [
+            r[0][0] * v[0] + r[0][1] * v[1] + r[0][2] * v[2],
+            r[1][0] * v[0] + r[1][1] * v[1] + r[1][2] * v[2],
+            r[2][0] * v[0] + r[2][1] * v[1] + r[2][2] * v[2],
+        ]
+    }
+}
+
+/// Switch between the ISO and SAE vehicle frames; the mapping is its own inverse
+fn flip_y_z(v: [f32; 3]) -> [f32; 3] {
+    [v[0], -v[1], -v[2]]
+}
+
+/// Transform a measured position and velocity from `from` into `to`
+///
+/// The extrinsic is only used for measurements in the sensor frame. `to`
+/// must be a vehicle frame.
+pub fn to_common_frame(
+    position: [f32; 3],
+    velocity: [f32; 3],
+    from: CoordinateFrame,
+    extrinsic: &Extrinsic,
+    to: CoordinateFrame,
+) -> ([f32; 3], [f32; 3]) {
+    let (position, velocity) = match from {
+        CoordinateFrame::Sensor => {
+            let rotated = extrinsic.rotate(position);
+            let t = extrinsic.translation;
+            (
+                [rotated[0] + t[0], rotated[1] + t[1], rotated[2] + t[2]],
+                extrinsic.rotate(velocity),
+            )
+        }
+        CoordinateFrame::Vehicle => (position, velocity),
+        CoordinateFrame::VehicleSae => (flip_y_z(position), flip_y_z(velocity)),
+    };
+
+    match to {
+        CoordinateFrame::VehicleSae => (flip_y_z(position), flip_y_z(velocity)),
+        _ => (position, velocity),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_close(actual: [f32; 3], expected: [f32; 3]) {
+        for (a, e) in actual.iter().zip(expected) {
+            assert!((a - e).abs() < 1e-4, "{:?} != {:?}", actual, expected);
+        }
+    }
+
+    #[test]
+    fn test_rear_camera_point_into_vehicle_frame() {
+        // Rear camera 1 m behind the rear axle, 0.9 m up, looking backwards
+        let rear_camera = Extrinsic::new([-1.0, 0.0, 0.9], 0.0, 0.0, 180.0);
+
+        // 5 m straight behind the camera, 2 m to the camera's left, closing at 3 m/s
+        let (position, velocity) = to_common_frame(
+            [5.0, 2.0, 0.0],
+            [-3.0, 0.0, 0.0],
+            CoordinateFrame::Sensor,
+            &rear_camera,
+            CoordinateFrame::Vehicle,
+        );
+        assert_close(position, [-6.0, -2.0, 0.9]);
+        assert_close(velocity, [3.0, 0.0, 0.0]);
+
+        let (position, _) = to_common_frame(
+            [5.0, 2.0, 0.0],
+            [0.0; 3],
+            CoordinateFrame::Sensor,
+            &rear_camera,
+            CoordinateFrame::VehicleSae,
+        );
+        assert_close(position, [-6.0, 2.0, -0.9]);
+
+        // Measurements already in a vehicle frame ignore the extrinsic
+        let (position, _) = to_common_frame(
+            [4.0, 1.0, 0.5],
+            [0.0; 3],
+            CoordinateFrame::VehicleSae,
+            &rear_camera,
+            CoordinateFrame::Vehicle,
+        );
+        assert_close(position, [4.0, -1.0, -0.5]);
+    }
+
+    #[test]
+    fn test_rejects_unknown_frames() {
+        assert_eq!(CoordinateFrame::parse("vehicle_frame"), Ok(CoordinateFrame::Vehicle));
+        assert_eq!(CoordinateFrame::parse("Sensor"), Ok(CoordinateFrame::Sensor));
+        assert_eq!(CoordinateFrame::parse("SAE"), Ok(CoordinateFrame::VehicleSae));
+        assert!(CoordinateFrame::parse("utm").unwrap_err().contains("utm"));
+        assert!(CoordinateFrame::parse_common("sensor").is_err());
+    }
+}
@@ -0,0 +1,397 @@
+// Constant-velocity Kalman filter for fused object tracks
+// State is [x, y, z, vx, vy, vz] in the vehicle frame; sensors measure the full state
+
+/// State dimension: position and velocity on three axes
+const N: usize = 6;
+
+type Vector = [f64; N];
+type Matrix = [[f64; N]; N];
+
+/// White-noise acceleration driving the motion model (m/s²)
+pub const DEFAULT_ACCELERATION_NOISE: f32 = 2.0;
+
+/// Position noise (m) of a fully reliable sensor
+const BASE_POSITION_STD_M: f64 = 0.5;
+
+/// Velocity noise (m/s) of a fully reliable sensor
+const BASE_VELOCITY_STD_MPS: f64 = 1.0;
+
+/// Reliability below which a sensor is not trusted any further
+const MIN_RELIABILITY: f64 = 0.05;
+
+/// Entries in the upper triangle of the state covariance
+pub const COVARIANCE_LEN: usize = N * (N + 1) / 2;
+
+/// Measurement noise variances for the position and velocity components
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct MeasurementNoise {
+    pub position_variance: f32,
+    pub velocity_variance: f32,
+}
+
+impl MeasurementNoise {
+    /// Noise of a single sensor with the given reliability factor
+    ///
+    /// A fully reliable sensor has the base noise; variance grows inversely
+    /// with reliability.
+    pub fn for_reliability(reliability: f32) -> Self {
+        let scale = 1.0 / (reliability as f64).clamp(MIN_RELIABILITY, 1.0);
+        Self {
+            position_variance: (BASE_POSITION_STD_M.powi(2) * scale) as f32,
+            velocity_variance: (BASE_VELOCITY_STD_MPS.powi(2) * scale) as f32,
+        }
+    }
+
+    /// Noise of an estimate combining independent sensors
+    ///
+    /// Inverse-variance combination; `None` for no sensors.
+    pub fn combined(sensors: impl IntoIterator<Item = MeasurementNoise>) -> Option<Self> {
+        let (position_info, velocity_info, count) = sensors.into_iter().fold((0.0f64, 0.0f64, 0), |acc, n| {
+            (acc.0 + 1.0 / n.position_variance as f64, acc.1 + 1.0 / n.velocity_variance as f64, acc.2 + 1)
+        });
+        if count == 0 {
+            return None;
+        }
+        Some(Self {
+            position_variance: (1.0 / position_info) as f32,
+            velocity_variance: (1.0 / velocity_info) as f32,
+        })
+    }
+
+    /// Widen the position noise by the disagreement between sources (m)
+    pub fn with_position_spread(mut self, spread_m: f32) -> Self {
+        self.position_variance += spread_m * spread_m;
+        self
+    }
+}
+
+/// Track estimate and covariance of one object
+#[derive(Debug, Clone, PartialEq)]
+pub struct ConstantVelocityFilter {
+    state: Vector,
+    covariance: Matrix,
+    acceleration_noise: f64,
+    /// Innovation covariance of the most recent update
+    innovation_covariance: Option<Matrix>,
+}
+
+impl ConstantVelocityFilter {
+    /// Start a track at a measurement, with the measurement noise as initial covariance
+    pub fn new(position: [f32; 3], velocity: [f32; 3], noise: MeasurementNoise) -> Self {
+        let mut covariance = [[0.0; N]; N];
+        for axis in 0..3 {
+            covariance[axis][axis] = noise.position_variance as f64;
+            covariance[axis + 3][axis + 3] = noise.velocity_variance as f64;
+        }
+        Self {
+            state: to_vector(position, velocity),
+            covariance,
+            acceleration_noise: DEFAULT_ACCELERATION_NOISE as f64,
+            innovation_covariance: None,
+        }
+    }
+
+    /// Resume a track from its estimate and the upper triangle of its covariance
+    pub fn restore(position: [f32; 3], velocity: [f32; 3], covariance: &[f32; COVARIANCE_LEN]) -> Self {
+        let mut matrix = [[0.0; N]; N];
+        let upper = (0..N).flat_map(|i| (i..N).map(move |j| (i, j)));
+        for ((i, j), &value) in upper.zip(covariance) {
+            matrix[i][j] = value as f64;
+            matrix[j][i] = value as f64;
+        }
+        Self {
+            state: to_vector(position, velocity),
+            covariance: matrix,
+            acceleration_noise: DEFAULT_ACCELERATION_NOISE as f64,
+            innovation_covariance: None,
+        }
+    }
+
+    /// Upper triangle of the state covariance, row by row
+    pub fn covariance(&self) -> [f32; COVARIANCE_LEN] {
+        let mut out = [0.0; COVARIANCE_LEN];
+        let upper = (0..N).flat_map(|i| (i..N).map(move |j| (i, j)));
+        for (value, (i, j)) in out.iter_mut().zip(upper) {
+            *value = self.covariance[i][j] as f32;
+        }
+        out
+    }
+
+    /// Estimated position (m)
+    pub fn position(&self) -> [f32; 3] {
+        [self.state[0] as f32, self.state[1] as f32, self.state[2] as f32]
+    }
+
+    /// Estimated velocity (m/s)
+    pub fn velocity(&self) -> [f32; 3] {
+        [self.state[3] as f32, self.state[4] as f32, self.state[5] as f32]
+    }
+
+    /// Propagate the state `dt` seconds ahead: x = F x, P = F P Fᵀ + Q
+    pub fn predict(&mut self, dt: f32) {
+        if dt <= 0.0 {
+            return;
+        }
+        let dt = dt as f64;
+
+        let mut f = identity();
+        for axis in 0..3 {
+            f[axis][axis + 3] = dt;
+        }
+
+        // Discrete white-noise acceleration per axis
+        let q_var = self.acceleration_noise * self.acceleration_noise;
+        let mut q = [[0.0; N]; N];
+        for axis in 0..3 {
+            q[axis][axis] = dt.powi(4) / 4.0 * q_var;
+            q[axis][axis + 3] = dt.powi(3) / 2.0 * q_var;
+            q[axis + 3][axis] = dt.powi(3) / 2.0 * q_var;
+            q[axis + 3][axis + 3] = dt.powi(2) * q_var;
+        }
+
+        self.state = mul_vec(&f, &self.state);
+        self.covariance = add(&mul(&mul(&f, &self.covariance), &transpose(&f)), &q);
+    }
+
+    /// Correct the state with a measurement of position and velocity
+    ///
+    /// With H = I: y = z - x, S = P + R, K = P S⁻¹, x += K y, P = (I - K) P.
+    /// Returns `false` and leaves the state untouched when S is singular.
+    pub fn update(&mut self, position: [f32; 3], velocity: [f32; 3], noise: MeasurementNoise) -> bool {
+        let measurement = to_vector(position, velocity);
+        let mut innovation = measurement;
+        for (value, estimate) in innovation.iter_mut().zip(&self.state) {
+            *value -= estimate;
+        }
+
+        let mut s = self.covariance;
+        for axis in 0..3 {
+            s[axis][axis] += noise.position_variance as f64;
+            s[axis + 3][axis + 3] += noise.velocity_variance as f64;
+        }
+        let Some(s_inv) = invert(&s) else {
+            return false;
+        };
+
+        let gain = mul(&self.covariance, &s_inv);
+        let correction = mul_vec(&gain, &innovation);
+        for (value, delta) in self.state.iter_mut().zip(&correction) {
+            *value += delta;
+        }
+
+        let mut i_minus_k = identity();
+        for (row, gain_row) in i_minus_k.iter_mut().zip(&gain) {
+            for (value, k) in row.iter_mut().zip(gain_row) {
+                *value -= k;
+            }
+        }
+        self.covariance = symmetrize(&mul(&i_minus_k, &self.covariance));
+        self.innovation_covariance = Some(s);
+        true
+    }
+
+    /// Expected position error (m) of the last measurement against the prediction
+    ///
+    /// Root of the mean position variance of the innovation covariance;
+    /// `None` until the track has been updated.
+    pub fn innovation_position_std(&self) -> Option<f32> {
+        let s = self.innovation_covariance.as_ref()?;
+        Some(((s[0][0] + s[1][1] + s[2][2]) / 3.0).sqrt() as f32)
+    }
+}
+
+fn to_vector(position: [f32; 3], velocity: [f32; 3]) -> Vector {
+    let mut v = [0.0; N];
+    for axis in 0..3 {
+        v[axis] = position[axis] as f64;
+        v[axis + 3] = velocity[axis] as f64;
+    }
+    v
+}
+
+fn identity() -> Matrix {
+    let mut m = [[0.0; N]; N];
+    for (i, row) in m.iter_mut().enumerate() {
+        row[i] = 1.0;
+    }
+    m
+}
+
+fn mul(a: &Matrix, b: &Matrix) -> Matrix {
+    let mut out = [[0.0; N]; N];
+    for i in 0..N {
+        for j in 0..N {
+            out[i][j] = (0..N).map(|k| a[i][k] * b[k][j]).sum();
+        }
+    }
+    out
+}
+
+fn mul_vec(a: &Matrix, v: &Vector) -> Vector {
+    let mut out = [0.0; N];
+    for (value, row) in out.iter_mut().zip(a) {
+        *value = row.iter().zip(v).map(|(x, y)| x * y).sum();
+    }
+    out
+}
+
+fn add(a: &Matrix, b: &Matrix) -> Matrix {
+    let mut out = *a;
+    for (row, b_row) in out.iter_mut().zip(b) {
+        for (value, b_value) in row.iter_mut().zip(b_row) {
+            *value += b_value;
+        }
+    }
+    out
+}
+
+fn transpose(a: &Matrix) -> Matrix {
+    let mut out = [[0.0; N]; N];
+    for i in 0..N {
+        for j in 0..N {
+            out[j][i] = a[i][j];
+        }
+    }
+    out
+}
+
+// Keep the covariance symmetric against rounding drift
+fn symmetrize(a: &Matrix) -> Matrix {
+    let mut out = *a;
+    for i in 0..N {
+        for j in (i + 1)..N {
+            let mean = (a[i][j] + a[j][i]) / 2.0;
+            out[i][j] = mean;
+            out[j][i] = mean;
+        }
+    }
+    out
+}
+
+// Gauss-Jordan elimination with partial pivoting
+fn invert(a: &Matrix) -> Option<Matrix> {
+    let mut m = *a;
+    let mut inv = identity();
+
+    for col in 0..N {
+        let pivot = (col..N).max_by(|&x, &y| m[x][col].abs().total_cmp(&m[y][col].abs()))?;
+        if m[pivot][col].abs() < 1e-12 {
+            return None;
+        }
+        m.swap(col, pivot);
+        inv.swap(col, pivot);
+
+        let scale = m[col][col];
+        for j in 0..N {
+            m[col][j] /= scale;
+            inv[col][j] /= scale;
+        }
+
+        for row in 0..N {
+            if row == col {
+                continue;
+            }
+            let factor = m[row][col];
+            if factor == 0.0 {
+                continue;
+            }
+            for j in 0..N {
+                m[row][j] -= factor * m[col][j];
+                inv[row][j] -= factor * inv[col][j];
+            }
+        }
+    }
+
+    Some(inv)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_invert_round_trip() {
+        let mut a = identity();
+        a[0][3] = 0.5;
+        a[3][0] = 0.5;
+        a[2][2] = 4.0;
+        let product = mul(&a, &invert(&a).unwrap());
+        for (i, row) in product.iter().enumerate() {
+            for (j, value) in row.iter().enumerate() {
+                let expected = if i == j { 1.0 } else { 0.0 };
+                assert!((value - expected).abs() < 1e-9);
+            }
+        }
+        assert!(invert(&[[0.0; N]; N]).is_none());
+    }
+
+    #[test]
+    fn test_predict_moves_with_velocity_and_grows_covariance() {
+        let noise = MeasurementNoise::for_reliability(1.0);
+        let mut filter = ConstantVelocityFilter::new([10.0, 0.0, 0.0], [5.0, -1.0, 0.0], noise);
+        let before = filter.covariance[0][0];
+
+        filter.predict(0.5);
+
+        assert_eq!(filter.position(), [12.5, -0.5, 0.0]);
+        assert!(filter.covariance[0][0] > before);
+    }
+
+    #[test]
+    fn test_update_weights_by_measurement_noise() {
+        let prior = MeasurementNoise::for_reliability(1.0);
+        let mut trusted = ConstantVelocityFilter::new([0.0; 3], [0.0; 3], prior);
+        let mut doubted = trusted.clone();
+
+        assert!(trusted.update([2.0, 0.0, 0.0], [0.0; 3], MeasurementNoise::for_reliability(1.0)));
+        assert!(doubted.update([2.0, 0.0, 0.0], [0.0; 3], MeasurementNoise::for_reliability(0.1)));
+
+        // Equal prior and measurement noise split the difference
+        assert!((trusted.position()[0] - 1.0).abs() < 1e-5);
+        assert!(doubted.position()[0] < 0.5);
+        assert!(doubted.innovation_position_std().unwrap() > trusted.innovation_position_std().unwrap());
+    }
+
+    #[test]
+    fn test_tracks_constant_velocity_through_noise() {
+        let noise = MeasurementNoise::for_reliability(0.8);
+        let mut filter = ConstantVelocityFilter::new([0.0; 3], [0.0; 3], noise);
+        let dt = 0.1;
+
+        for step in 1..=100 {
+            let t = step as f32 * dt;
+            // Deterministic ±0.4 m jitter around x = 3 t
+            let jitter = if step % 2 == 0 { 0.4 } else { -0.4 };
+            filter.predict(dt);
+            filter.update([3.0 * t + jitter, 0.0, 0.0], [3.0, 0.0, 0.0], noise);
+        }
+
+        assert!((filter.position()[0] - 30.0).abs() < 0.3);
+        assert!((filter.velocity()[0] - 3.0).abs() < 0.1);
+    }
+
+    #[test]
+    fn test_restore_keeps_covariance() {
+        let noise = MeasurementNoise::for_reliability(0.8);
+        let mut filter = ConstantVelocityFilter::new([4.0, 1.0, 0.0], [2.0, 0.0, 0.0], noise);
+        filter.predict(0.3);
+        filter.update([4.7, 1.1, 0.0], [2.1, 0.0, 0.0], noise);
+
+        let restored = ConstantVelocityFilter::restore(filter.position(), filter.velocity(), &filter.covariance());
+        assert_eq!(restored.covariance(), filter.covariance());
+        assert_eq!(restored.position(), filter.position());
+        assert!(restored.covariance[3][0] != 0.0);
+        assert_eq!(restored.covariance[3][0], restored.covariance[0][3]);
+    }
+
+    #[test]
+    fn test_combined_noise_is_smaller_than_each_sensor() {
+        let radar = MeasurementNoise::for_reliability(0.9);
+        let camera = MeasurementNoise::for_reliability(0.8);
+        let combined = MeasurementNoise::combined([radar, camera]).unwrap();
+
+        assert!(combined.position_variance < radar.position_variance);
+        assert!(MeasurementNoise::combined([]).is_none());
+        assert!(radar.with_position_spread(1.0).position_variance > radar.position_variance);
+    }
+}
@@ -1,17 +1,34 @@
 // Sensor Fusion ECU Component - Multi-interface sensor data fusion engine
 use sensor_fusion_ecu_bindings::exports::adas::sensor_fusion::{
-    fusion_engine::{self, Config, SensorWeight, SensorData, FusedObject, Position, Velocity, Orientation, Dimensions, FusionResult, SensorStatus, Status, Stats},
+    fusion_engine::{self, Config, ConfidenceFusion, SensorWeight, SensorDropout, SensorData, FusedObject, Position, Velocity, Orientation, Dimensions, FusionResult, SensorStatus, Status, Stats, TrackAssociation},
     diagnostics::{self, Health, TestResult},
 };
 
+use adas_confidence::{clamp_confidence, ConfidencePolicy, DEFAULT_CONFIDENCE_CEILING, DEFAULT_CONFIDENCE_FLOOR};
+use adas_lifecycle::{Lifecycle, LifecycleEvent, LifecycleState, Transition};
+use adas_timing::{elapsed_ms_u32, elapsed_secs, monotonic_now_ms};
 use std::cell::RefCell;
-use std::time::{SystemTime, UNIX_EPOCH};
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
+
+mod adaptive_weights;
+mod dropout;
+mod association;
+mod frames;
+mod kalman;
+mod track_state;
+mod weighted_merge;
+
+use adaptive_weights::AdaptiveWeights;
+use dropout::{CoverageHealth, DropoutSchedule, DropoutWindow};
+use frames::{CoordinateFrame, Extrinsic};
+use kalman::{ConstantVelocityFilter, MeasurementNoise};
+use track_state::{TrackRecord, TrackSnapshot};
+use weighted_merge::{Observation, SourceWeight, DEFAULT_ASSOCIATION_GATE_M};
 
 // Component state
 struct SensorFusionState {
     config: Config,
-    status: Status,
+    lifecycle: Lifecycle,
     frames_processed: u64,
     objects_fused: u64,
     start_time: u64,
@@ -21,14 +38,20 @@ struct SensorFusionState {
     sensor_history: HashMap<String, Vec<SensorData>>,
     active_sensors: HashMap<String, u64>,
     kalman_states: HashMap<u32, KalmanState>,
+    next_object_id: u32,
     fusion_initialized: bool,
+    dropout_schedule: DropoutSchedule,
+    seen_sensor_types: HashSet<String>,
+    common_frame: CoordinateFrame,
+    adaptive_weights: AdaptiveWeights,
+    // Weight per configured sensor type in the last fused frame
+    effective_weights: Vec<(String, f32)>,
 }
 
-// Simplified Kalman filter state for object tracking
+// Kalman filter state for object tracking
 #[derive(Clone)]
 struct KalmanState {
-    position: Position,
-    velocity: Velocity,
+    filter: ConstantVelocityFilter,
     confidence: f32,
     last_update: u64,
 }
@@ -62,12 +85,16 @@ impl Default for SensorFusionState {
             config: Config {
                 fusion_rate_hz: 30.0,
                 confidence_threshold: 0.5,
+                confidence_floor: DEFAULT_CONFIDENCE_FLOOR,
+                confidence_ceiling: DEFAULT_CONFIDENCE_CEILING,
+                confidence_fusion: ConfidenceFusion::Complementary,
                 max_sensor_latency_ms: 100,
                 kalman_filter_enabled: true,
                 sensor_weights: default_weights,
                 coordinate_system: "vehicle_frame".to_string(),
+                dropout_schedule: Vec::new(),
             },
-            status: Status::Inactive,
+            lifecycle: Lifecycle::new("Sensor fusion"),
             frames_processed: 0,
             objects_fused: 0,
             start_time: 0,
@@ -77,7 +104,13 @@ impl Default for SensorFusionState {
             sensor_history: HashMap::new(),
             active_sensors: HashMap::new(),
             kalman_states: HashMap::new(),
+            next_object_id: 0,
             fusion_initialized: false,
+            dropout_schedule: DropoutSchedule::default(),
+            seen_sensor_types: HashSet::new(),
+            common_frame: CoordinateFrame::Vehicle,
+            adaptive_weights: AdaptiveWeights::default(),
+            effective_weights: Vec::new(),
         }
     }
 }
@@ -86,12 +119,239 @@ thread_local! {
     static STATE: RefCell<SensorFusionState> = RefCell::new(SensorFusionState::default());
 }
 
-// Helper to get current timestamp in milliseconds
+// Helper to get current timestamp in milliseconds (never goes backwards)
 fn get_timestamp_ms() -> u64 {
-    SystemTime::now()
-        .duration_since(UNIX_EPOCH)
-        .unwrap_or_default()
-        .as_millis() as u64
+    monotonic_now_ms()
+}
+
+// Map the shared lifecycle state onto the WIT status enum
+fn to_status(state: LifecycleState) -> Status {
+    match state {
+        LifecycleState::Uninitialized | LifecycleState::Inactive => Status::Inactive,
+        LifecycleState::Active => Status::Active,
+        LifecycleState::Error => Status::Error,
+    }
+}
+
+// Confidence band from the active configuration
+fn confidence_policy(config: &Config) -> ConfidencePolicy {
+    ConfidencePolicy {
+        floor: config.confidence_floor,
+        ceiling: config.confidence_ceiling,
+    }
+}
+
+// Map the configured confidence fusion onto the merge rule
+fn to_fusion_rule(fusion: ConfidenceFusion) -> weighted_merge::ConfidenceFusion {
+    match fusion {
+        ConfidenceFusion::WeightedMean => weighted_merge::ConfidenceFusion::WeightedMean,
+        ConfidenceFusion::Complementary => weighted_merge::ConfidenceFusion::Complementary,
+    }
+}
+
+// Build the dropout schedule from configuration
+fn to_dropout_schedule(dropouts: &[SensorDropout]) -> Result<DropoutSchedule, String> {
+    DropoutSchedule::new(
+        dropouts
+            .iter()
+            .map(|d| DropoutWindow {
+                sensor_type: d.sensor_type.clone(),
+                start_frame: d.start_frame,
+                duration_frames: d.duration_frames,
+            })
+            .collect(),
+    )
+}
+
+fn to_extrinsic(extrinsic: &fusion_engine::Extrinsic) -> Extrinsic {
+    let t = &extrinsic.translation;
+    let r = &extrinsic.rotation;
+    Extrinsic::new([t.x, t.y, t.z], r.roll, r.pitch, r.yaw)
+}
+
+// Map sensor coverage onto the diagnostics health enum
+fn to_health(coverage: CoverageHealth) -> Health {
+    match coverage {
+        CoverageHealth::Healthy => Health::Healthy,
+        CoverageHealth::Degraded => Health::Degraded,
+        CoverageHealth::Critical => Health::Critical,
+        CoverageHealth::Offline => Health::Offline,
+    }
+}
+
+// Dimensions assumed for a fused object of the given type
+fn dimensions_for(object_type: &str) -> Dimensions {
+    match object_type {
+        "pedestrian" => Dimensions { length: 0.6, width: 0.4, height: 1.7 },
+        "cyclist" => Dimensions { length: 1.8, width: 0.6, height: 1.2 },
+        _ => Dimensions { length: 4.5, width: 1.8, height: 1.5 },
+    }
+}
+
+// Reliability factor configured for a sensor type
+fn reliability_of(config: &Config, sensor_type: &str) -> f32 {
+    config.sensor_weights
+        .iter()
+        .find(|w| w.sensor_type == sensor_type)
+        .map_or(0.5, |w| w.reliability_factor)
+}
+
+// Predict the track to `now` and correct it with a measurement, starting a new
+// track for an unknown object; returns the track's position and velocity,
+// which is the raw measurement when Kalman filtering is disabled
+fn track_object(
+    s: &mut SensorFusionState,
+    object_id: u32,
+    position: [f32; 3],
+    velocity: [f32; 3],
+    noise: MeasurementNoise,
+    confidence: f32,
+    now: u64,
+) -> ([f32; 3], [f32; 3]) {
+    let filtering = s.config.kalman_filter_enabled;
+    match s.kalman_states.get_mut(&object_id) {
+        Some(kalman_state) if filtering => {
+            kalman_state.filter.predict(now.saturating_sub(kalman_state.last_update) as f32 / 1000.0);
+            kalman_state.filter.update(position, velocity, noise);
+            kalman_state.confidence = confidence;
+            kalman_state.last_update = now;
+            (kalman_state.filter.position(), kalman_state.filter.velocity())
+        }
+        _ => {
+            s.kalman_states.insert(object_id, KalmanState {
+                filter: ConstantVelocityFilter::new(position, velocity, noise),
+                confidence,
+                last_update: now,
+            });
+            (position, velocity)
+        }
+    }
+}
+
+// Confidence-weighted merge of observations that refer to the same object,
+// associated with the existing tracks by predicted position; also returns which
+// source detections each track absorbed
+fn merge_sensor_observations(
+    s: &mut SensorFusionState,
+    observations: &[Observation],
+    now: u64,
+) -> (Vec<FusedObject>, Vec<TrackAssociation>) {
+    let weights: HashMap<String, SourceWeight> = s.config.sensor_weights
+        .iter()
+        .map(|w| {
+            let weight = s.effective_weights
+                .iter()
+                .find(|(sensor_type, _)| *sensor_type == w.sensor_type)
+                .map_or(w.weight, |(_, weight)| *weight);
+            (w.sensor_type.clone(), SourceWeight { weight, reliability: w.reliability_factor })
+        })
+        .collect();
+    
+    let policy = confidence_policy(&s.config);
+    let fusion = to_fusion_rule(s.config.confidence_fusion);
+    
+    // Tracks no sensor has confirmed within the latency budget are gone
+    let max_age_ms = s.config.max_sensor_latency_ms as u64;
+    s.kalman_states.retain(|_, k| now.saturating_sub(k.last_update) <= max_age_ms);
+    
+    let mut estimates = Vec::new();
+    for group in weighted_merge::associate(observations, DEFAULT_ASSOCIATION_GATE_M) {
+        let Some(mut merged) = weighted_merge::merge_observations(&group, &weights, fusion) else {
+            continue;
+        };
+        merged.confidence = clamp_confidence(merged.confidence, &policy);
+        if merged.confidence < s.config.confidence_threshold {
+            continue;
+        }
+        
+        // Independent sensors tighten the measurement; disagreement between them widens it
+        let noise = MeasurementNoise::combined(
+            group.iter().map(|o| MeasurementNoise::for_reliability(reliability_of(&s.config, &o.sensor_type))),
+        )
+        .map_or_else(|| MeasurementNoise::for_reliability(0.5), |n| n.with_position_spread(merged.position_uncertainty));
+        estimates.push((merged, noise));
+    }
+    
+    let predicted: Vec<(u32, [f32; 3])> = s.kalman_states
+        .iter()
+        .map(|(id, k)| {
+            let dt = now.saturating_sub(k.last_update) as f32 / 1000.0;
+            let (p, v) = (k.filter.position(), k.filter.velocity());
+            (*id, [p[0] + v[0] * dt, p[1] + v[1] * dt, p[2] + v[2] * dt])
+        })
+        .collect();
+    let detections: Vec<[f32; 3]> = estimates.iter().map(|(merged, _)| merged.position).collect();
+    let assignment = association::assign_to_tracks(&predicted, &detections, DEFAULT_ASSOCIATION_GATE_M);
+    
+    let mut fused_objects = Vec::new();
+    let mut associations = Vec::new();
+    for ((merged, noise), track) in estimates.into_iter().zip(assignment) {
+        let object_id = track.unwrap_or_else(|| {
+            let id = s.next_object_id;
+            s.next_object_id = s.next_object_id.wrapping_add(1);
+            id
+        });
+        
+        let (p, v) = track_object(s, object_id, merged.position, merged.velocity, noise, merged.confidence, now);
+        
+        associations.extend(merged.source_detections.iter().map(|(sensor_id, detection_id)| TrackAssociation {
+            track_id: object_id,
+            source_id: sensor_id.clone(),
+            source_detection_id: *detection_id,
+        }));
+        fused_objects.push(FusedObject {
+            object_id,
+            position: Position { x: p[0], y: p[1], z: p[2] },
+            velocity: Velocity { x: v[0], y: v[1], z: v[2] },
+            acceleration: Velocity { x: 0.0, y: 0.0, z: 0.0 },
+            orientation: Orientation {
+                roll: 0.0,
+                pitch: 0.0,
+                yaw: v[1].atan2(v[0]).to_degrees(),
+            },
+            dimensions: dimensions_for(&merged.object_type),
+            object_type: merged.object_type,
+            confidence: merged.confidence,
+            source_sensors: merged.source_sensors,
+            timestamp: now,
+        });
+    }
+    
+    fused_objects.sort_by_key(|o| o.object_id);
+    associations.sort_by_key(|a| a.track_id);
+    (fused_objects, associations)
+}
+
+// Tracks in object ID order, with their covariance, for a state snapshot
+fn export_tracks(kalman_states: &HashMap<u32, KalmanState>) -> Vec<TrackRecord> {
+    let mut tracks: Vec<TrackRecord> = kalman_states
+        .iter()
+        .map(|(id, k)| TrackRecord {
+            object_id: *id,
+            position: k.filter.position(),
+            velocity: k.filter.velocity(),
+            covariance: k.filter.covariance(),
+            confidence: k.confidence,
+            last_update: k.last_update,
+        })
+        .collect();
+    tracks.sort_by_key(|t| t.object_id);
+    tracks
+}
+
+// Tracks from a snapshot, rebased so the newest was updated at `now`; the time
+// the component was down does not count against the latency budget, but the
+// tracks keep their ages relative to each other
+fn restore_tracks(tracks: Vec<TrackRecord>, now: u64) -> HashMap<u32, KalmanState> {
+    let newest = tracks.iter().map(|t| t.last_update).max().unwrap_or(0);
+    tracks
+        .into_iter()
+        .map(|t| (t.object_id, KalmanState {
+            filter: ConstantVelocityFilter::restore(t.position, t.velocity, &t.covariance),
+            confidence: t.confidence,
+            last_update: now.saturating_sub(newest - t.last_update),
+        }))
+        .collect()
 }
 
 // Component implementation
@@ -109,6 +369,7 @@ impl fusion_engine::Guest for Component {
             if cfg.confidence_threshold < 0.0 || cfg.confidence_threshold > 1.0 {
                 return Err("Invalid confidence threshold (must be 0.0-1.0)".to_string());
             }
+            ConfidencePolicy::new(cfg.confidence_floor, cfg.confidence_ceiling)?;
             if cfg.max_sensor_latency_ms == 0 || cfg.max_sensor_latency_ms > 1000 {
                 return Err("Invalid max sensor latency (must be 1-1000 ms)".to_string());
             }
@@ -119,21 +380,31 @@ impl fusion_engine::Guest for Component {
                 return Err("Sensor weights should sum to approximately 1.0".to_string());
             }
             
+            let dropout_schedule = to_dropout_schedule(&cfg.dropout_schedule)?;
+            let common_frame = CoordinateFrame::parse_common(&cfg.coordinate_system)?;
+            
             println!("Sensor Fusion: Initializing {:.1} Hz fusion, {} sensor types, Kalman: {}", 
                 cfg.fusion_rate_hz, cfg.sensor_weights.len(), cfg.kalman_filter_enabled);
             
+            s.lifecycle.check(LifecycleEvent::Initialize)?;
+            
             s.config = cfg;
-            s.status = Status::Initializing;
             s.frames_processed = 0;
             s.objects_fused = 0;
             s.processing_times.clear();
             s.sensor_history.clear();
             s.active_sensors.clear();
             s.kalman_states.clear();
+            s.next_object_id = 0;
+            s.dropout_schedule = dropout_schedule;
+            s.seen_sensor_types.clear();
+            s.common_frame = common_frame;
+            s.adaptive_weights = AdaptiveWeights::default();
+            s.effective_weights.clear();
             
             // Simulate fusion system initialization
             s.fusion_initialized = true;
-            s.status = Status::Inactive;
+            s.lifecycle.apply(LifecycleEvent::Initialize)?;
             s.health = Health::Healthy;
             
             Ok(())
@@ -144,8 +415,8 @@ impl fusion_engine::Guest for Component {
         STATE.with(|state| {
             let mut s = state.borrow_mut();
             
-            if matches!(s.status, Status::Active) {
-                return Err("Sensor fusion already active".to_string());
+            if s.lifecycle.check(LifecycleEvent::Start)? == Transition::Unchanged {
+                return Ok(());
             }
             
             if !s.fusion_initialized {
@@ -153,7 +424,7 @@ impl fusion_engine::Guest for Component {
             }
             
             println!("Sensor Fusion: Starting multi-sensor data fusion");
-            s.status = Status::Active;
+            s.lifecycle.apply(LifecycleEvent::Start)?;
             s.start_time = get_timestamp_ms();
             s.last_frame_time = s.start_time;
             
@@ -165,14 +436,14 @@ impl fusion_engine::Guest for Component {
         STATE.with(|state| {
             let mut s = state.borrow_mut();
             
-            if !matches!(s.status, Status::Active) {
-                return Err("Sensor fusion not active".to_string());
+            if s.lifecycle.apply(LifecycleEvent::Stop)? == Transition::Unchanged {
+                return Ok(());
             }
             
             println!("Sensor Fusion: Stopping data fusion");
-            s.status = Status::Inactive;
             s.sensor_history.clear();
             s.kalman_states.clear();
+            s.next_object_id = 0;
             
             Ok(())
         })
@@ -182,25 +453,46 @@ impl fusion_engine::Guest for Component {
         STATE.with(|state| {
             let mut s = state.borrow_mut();
             
-            if !matches!(s.status, Status::Active) {
+            if !s.lifecycle.is_active() {
                 return Err("Sensor fusion not active".to_string());
             }
             
+            // Reject data in a frame we cannot transform before touching any state
+            for input in &sensor_inputs {
+                CoordinateFrame::parse(&input.coordinate_frame)
+                    .map_err(|e| format!("Sensor {}: {}", input.sensor_id, e))?;
+            }
+            
             let now = get_timestamp_ms();
+            s.lifecycle.record_activity(now);
             s.frames_processed += 1;
             s.last_frame_time = now;
             
+            // Apply the configured dropout schedule before any fusion
+            let frame = s.frames_processed;
+            let (sensor_inputs, dropped_inputs): (Vec<SensorData>, Vec<SensorData>) = sensor_inputs
+                .into_iter()
+                .partition(|input| !s.dropout_schedule.is_dropped(&input.sensor_type, frame));
+            
             // Update sensor activity tracking
             let mut sensor_statuses = Vec::new();
+            let mut late_sensors = HashSet::new();
+            for input in &dropped_inputs {
+                s.seen_sensor_types.insert(input.sensor_type.clone());
+                sensor_statuses.push(SensorStatus {
+                    sensor_id: input.sensor_id.clone(),
+                    is_active: false,
+                    latency_ms: 0,
+                    data_quality: 0.0,
+                    last_update: s.active_sensors.get(&input.sensor_id).copied().unwrap_or(0),
+                });
+            }
             for input in &sensor_inputs {
+                s.seen_sensor_types.insert(input.sensor_type.clone());
                 s.active_sensors.insert(input.sensor_id.clone(), now);
                 
                 // Calculate latency
-                let latency_ms = if now >= input.timestamp {
-                    (now - input.timestamp) as u32
-                } else {
-                    0
-                };
+                let latency_ms = elapsed_ms_u32(now, input.timestamp);
                 
                 // Store sensor history
                 let history = s.sensor_history.entry(input.sensor_id.clone()).or_insert_with(Vec::new);
@@ -209,123 +501,55 @@ impl fusion_engine::Guest for Component {
                     history.remove(0);
                 }
                 
+                // Feed the rolling reliability; late data is left out of this frame
+                let max_latency_ms = s.config.max_sensor_latency_ms;
+                s.adaptive_weights.observe(&input.sensor_type, latency_ms, input.confidence, max_latency_ms);
+                if latency_ms > max_latency_ms {
+                    late_sensors.insert(input.sensor_id.clone());
+                }
+                
                 sensor_statuses.push(SensorStatus {
                     sensor_id: input.sensor_id.clone(),
-                    is_active: latency_ms <= s.config.max_sensor_latency_ms,
+                    is_active: latency_ms <= max_latency_ms,
                     latency_ms,
                     data_quality: input.confidence,
                     last_update: input.timestamp,
                 });
             }
             
-            // Simulate sensor data fusion process
-            let mut fused_objects = Vec::new();
-            let object_count = ((s.frames_processed % 6) + 1) as usize;
+            // Reweight the sensor types still delivering in time by their reliability
+            let configured_weights: Vec<(String, f32)> = s.config.sensor_weights
+                .iter()
+                .map(|w| (w.sensor_type.clone(), w.weight))
+                .collect();
+            let timely_inputs: Vec<&SensorData> = sensor_inputs
+                .iter()
+                .filter(|input| !late_sensors.contains(&input.sensor_id))
+                .collect();
+            let available: HashSet<String> = timely_inputs.iter().map(|i| i.sensor_type.clone()).collect();
+            s.effective_weights = s.adaptive_weights.effective_weights(&configured_weights, &available);
             
-            for i in 0..object_count {
-                let object_id = i as u32;
-                
-                // Get sensor weight for primary sensor type
-                let primary_sensor_type = match i % 4 {
-                    0 => "camera",
-                    1 => "radar", 
-                    2 => "lidar",
-                    _ => "ultrasonic",
-                };
-                
-                let sensor_weight = s.config.sensor_weights
-                    .iter()
-                    .find(|w| w.sensor_type == primary_sensor_type)
-                    .map(|w| w.weight)
-                    .unwrap_or(0.1);
-                
-                // Simulate position from multiple sensors
-                let base_x = 20.0 + (i as f32 * 15.0);
-                let base_y = -5.0 + (i as f32 * 8.0);
-                let base_z = 0.0;
-                
-                // Add noise and fusion uncertainty
-                let time_factor = s.frames_processed as f32 * 0.1;
-                let fusion_noise_x = (time_factor + i as f32).sin() * 2.0;
-                let fusion_noise_y = (time_factor * 0.7 + i as f32).cos() * 1.5;
-                
-                let mut position = Position {
-                    x: base_x + fusion_noise_x,
-                    y: base_y + fusion_noise_y,
-                    z: base_z,
-                };
-                
-                let mut velocity = Velocity {
-                    x: 5.0 + (time_factor * 0.05).sin() * 3.0,
-                    y: 1.0 + (time_factor * 0.03).cos() * 2.0,
-                    z: 0.0,
-                };
-                
-                // Apply Kalman filtering if enabled
-                if s.config.kalman_filter_enabled {
-                    if let Some(kalman_state) = s.kalman_states.get_mut(&object_id) {
-                        // Update Kalman filter (simplified)
-                        let dt = 0.033; // Assume ~30 Hz
-                        
-                        // Predict step
-                        kalman_state.position.x += kalman_state.velocity.x * dt;
-                        kalman_state.position.y += kalman_state.velocity.y * dt;
-                        kalman_state.position.z += kalman_state.velocity.z * dt;
-                        
-                        // Update step (blend with measurement)
-                        let alpha = 0.7; // Kalman gain approximation
-                        kalman_state.position.x = alpha * position.x + (1.0 - alpha) * kalman_state.position.x;
-                        kalman_state.position.y = alpha * position.y + (1.0 - alpha) * kalman_state.position.y;
-                        kalman_state.velocity.x = alpha * velocity.x + (1.0 - alpha) * kalman_state.velocity.x;
-                        kalman_state.velocity.y = alpha * velocity.y + (1.0 - alpha) * kalman_state.velocity.y;
-                        
-                        position = kalman_state.position.clone();
-                        velocity = kalman_state.velocity.clone();
-                    } else {
-                        // Initialize new Kalman state
-                        s.kalman_states.insert(object_id, KalmanState {
-                            position: position.clone(),
-                            velocity: velocity.clone(),
-                            confidence: sensor_weight,
-                            last_update: now,
-                        });
-                    }
-                }
-                
-                // Calculate fused confidence
-                let base_confidence = sensor_weight + 0.3;
-                let confidence = (base_confidence * 0.8 + (time_factor * 0.02).cos() * 0.1).min(1.0);
-                
-                // Determine contributing sensors
-                let source_sensors = vec![
-                    format!("{}-sensor-1", primary_sensor_type),
-                    format!("{}-sensor-2", primary_sensor_type),
-                ];
-                
-                // Object type and dimensions based on sensor data
-                let (object_type, dimensions) = match i % 3 {
-                    0 => ("vehicle", Dimensions { length: 4.5, width: 1.8, height: 1.5 }),
-                    1 => ("pedestrian", Dimensions { length: 0.6, width: 0.4, height: 1.7 }),
-                    _ => ("cyclist", Dimensions { length: 1.8, width: 0.6, height: 1.2 }),
-                };
-                
-                fused_objects.push(FusedObject {
-                    object_id,
-                    position,
-                    velocity,
-                    acceleration: Velocity { x: 0.5, y: 0.2, z: 0.0 },
-                    orientation: Orientation { 
-                        roll: 0.0, 
-                        pitch: 0.0, 
-                        yaw: (time_factor * 0.1 + i as f32).sin() * 10.0,
-                    },
-                    dimensions,
-                    object_type: object_type.to_string(),
-                    confidence,
-                    source_sensors,
-                    timestamp: now,
-                });
-            }
+            // Merge structured per-sensor observations when sources provide them,
+            // once they are all in the common frame
+            let common_frame = s.common_frame;
+            let observations: Vec<Observation> = timely_inputs
+                .iter()
+                .filter_map(|input| {
+                    let frame = CoordinateFrame::parse(&input.coordinate_frame).ok()?;
+                    let mut observation = Observation::parse(&input.sensor_id, &input.sensor_type, &input.raw_data, input.confidence)?;
+                    let (position, velocity) = frames::to_common_frame(
+                        observation.position,
+                        observation.velocity,
+                        frame,
+                        &to_extrinsic(&input.extrinsic),
+                        common_frame,
+                    );
+                    observation.position = position;
+                    observation.velocity = velocity;
+                    Some(observation)
+                })
+                .collect();
+            let (fused_objects, track_associations) = merge_sensor_observations(&mut s, &observations, now);
             
             s.objects_fused += fused_objects.len() as u64;
             
@@ -338,13 +562,12 @@ impl fusion_engine::Guest for Component {
                 s.processing_times.remove(0);
             }
             
-            // Simulate occasional fusion issues
-            if s.frames_processed % 80 == 0 {
-                s.health = Health::Degraded;
-            }
+            // Grade health by how much seen sensor weight is still delivering
+            s.health = to_health(dropout::assess_coverage(&configured_weights, &s.seen_sensor_types, &available));
             
             let result = FusionResult {
                 fused_objects,
+                track_associations,
                 sensor_status: sensor_statuses,
                 processing_time_ms: processing_time,
                 frame_number: s.frames_processed,
@@ -356,14 +579,14 @@ impl fusion_engine::Guest for Component {
     }
 
     fn get_status() -> Status {
-        STATE.with(|state| state.borrow().status.clone())
+        STATE.with(|state| to_status(state.borrow().lifecycle.state()))
     }
 
     fn get_stats() -> Stats {
         STATE.with(|state| {
             let s = state.borrow();
             let elapsed_sec = if s.start_time > 0 {
-                ((get_timestamp_ms() - s.start_time) as f32) / 1000.0
+                elapsed_secs(get_timestamp_ms(), s.start_time)
             } else {
                 0.0
             };
@@ -374,10 +597,15 @@ impl fusion_engine::Guest for Component {
                 0.0
             };
             
-            // Calculate fusion accuracy based on Kalman states
-            let fusion_accuracy = if s.config.kalman_filter_enabled && !s.kalman_states.is_empty() {
-                let avg_confidence: f32 = s.kalman_states.values().map(|k| k.confidence).sum::<f32>() / s.kalman_states.len() as f32;
-                (avg_confidence * 100.0).min(100.0)
+            // Calculate fusion accuracy from the expected position error of the
+            // last Kalman updates: 100% at 0 m, 50% at 1 m
+            let innovation_stds: Vec<f32> = s.kalman_states
+                .values()
+                .filter_map(|k| k.filter.innovation_position_std())
+                .collect();
+            let fusion_accuracy = if s.config.kalman_filter_enabled && !innovation_stds.is_empty() {
+                let mean_std = innovation_stds.iter().sum::<f32>() / innovation_stds.len() as f32;
+                100.0 / (1.0 + mean_std)
             } else {
                 85.0 + (elapsed_sec * 0.01).sin() * 10.0
             };
@@ -403,11 +631,48 @@ impl fusion_engine::Guest for Component {
             s.sensor_history.clear();
             s.active_sensors.clear();
             s.kalman_states.clear();
+            s.next_object_id = 0;
             s.start_time = get_timestamp_ms();
             s.health = Health::Healthy;
             println!("Sensor Fusion: Statistics reset");
         });
     }
+
+    fn export_state() -> Vec<u8> {
+        STATE.with(|state| {
+            let s = state.borrow();
+            TrackSnapshot {
+                was_active: s.lifecycle.is_active(),
+                frames_processed: s.frames_processed,
+                objects_fused: s.objects_fused,
+                tracks: export_tracks(&s.kalman_states),
+            }
+            .encode()
+        })
+    }
+
+    fn import_state(state_bytes: Vec<u8>) -> Result<(), String> {
+        let snapshot = TrackSnapshot::decode(&state_bytes)?;
+        
+        STATE.with(|state| {
+            let mut s = state.borrow_mut();
+            
+            if s.lifecycle.is_active() {
+                return Err("Cannot import track state while sensor fusion is active".to_string());
+            }
+            
+            s.kalman_states = restore_tracks(snapshot.tracks, get_timestamp_ms());
+            s.next_object_id = s.kalman_states.keys().max().map_or(0, |id| id + 1);
+            s.frames_processed = snapshot.frames_processed;
+            s.objects_fused = snapshot.objects_fused;
+            
+            println!("Sensor Fusion: Restored {} tracks (previously {})",
+                s.kalman_states.len(),
+                if snapshot.was_active { "active" } else { "inactive" });
+            
+            Ok(())
+        })
+    }
 }
 
 impl diagnostics::Guest for Component {
@@ -490,6 +755,10 @@ impl diagnostics::Guest for Component {
         results
     }
 
+    fn get_last_activity() -> u64 {
+        STATE.with(|state| state.borrow().lifecycle.last_activity_ms().unwrap_or(0))
+    }
+
     fn get_report() -> String {
         STATE.with(|state| {
             let s = state.borrow();
@@ -499,6 +768,15 @@ impl diagnostics::Guest for Component {
                 .iter()
                 .map(|w| format!("  {}: {:.2} (reliability: {:.2})", w.sensor_type, w.weight, w.reliability_factor))
                 .collect();
+            let effective_weights: Vec<String> = if s.effective_weights.is_empty() {
+                vec!["  (no frame fused yet)".to_string()]
+            } else {
+                s.effective_weights
+                    .iter()
+                    .map(|(sensor_type, weight)| format!("  {}: {:.2} (rolling reliability: {:.2})",
+                        sensor_type, weight, s.adaptive_weights.reliability(sensor_type)))
+                    .collect()
+            };
             
             format!(
                 r#"Sensor Fusion ECU Diagnostic Report
@@ -509,6 +787,7 @@ Health: {:?}
 Configuration:
   Fusion rate: {:.1} Hz
   Confidence threshold: {:.2}
+  Confidence fusion: {:?}
   Max sensor latency: {} ms
   Kalman filter: {}
   Coordinate system: {}
@@ -516,6 +795,9 @@ Configuration:
 Sensor Weights:
 {}
 
+Effective Weights:
+{}
+
 Performance:
   Frames processed: {}
   Objects fused: {}
@@ -535,14 +817,16 @@ Fusion Info:
   Real-time object estimation
   Confidence weighted fusion
 "#,
-                s.status,
+                s.lifecycle.state(),
                 s.health,
                 s.config.fusion_rate_hz,
                 s.config.confidence_threshold,
+                s.config.confidence_fusion,
                 s.config.max_sensor_latency_ms,
                 s.config.kalman_filter_enabled,
                 s.config.coordinate_system,
                 sensor_weights.join("\n"),
+                effective_weights.join("\n"),
                 stats.frames_processed,
                 stats.objects_fused,
                 stats.sensors_active,
@@ -559,3 +843,63 @@ Fusion Info:
 
 // Export the component with multi-interface support
 sensor_fusion_ecu_bindings::export!(Component with_types_in sensor_fusion_ecu_bindings);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn camera(x: f32) -> Observation {
+        Observation {
+            sensor_id: "camera-front".to_string(),
+            sensor_type: "camera".to_string(),
+            position: [x, 0.0, 0.0],
+            velocity: [10.0, 0.0, 0.0],
+            object_type: "vehicle".to_string(),
+            confidence: 0.9,
+            detection_id: None,
+        }
+    }
+
+    #[test]
+    fn test_imported_tracks_survive_restart_delay() {
+        let mut exporting = SensorFusionState::default();
+        merge_sensor_observations(&mut exporting, &[camera(20.0)], 1_000);
+        merge_sensor_observations(&mut exporting, &[camera(20.5)], 1_050);
+        let tracks = export_tracks(&exporting.kalman_states);
+        let covariance = tracks[0].covariance;
+
+        // The restarted instance imports 150 ms after the last update, past the 100 ms track age limit
+        let snapshot = TrackSnapshot::decode(&TrackSnapshot {
+            was_active: true,
+            frames_processed: 2,
+            objects_fused: 2,
+            tracks,
+        }
+        .encode())
+        .unwrap();
+        let mut restarted = SensorFusionState::default();
+        restarted.kalman_states = restore_tracks(snapshot.tracks, 1_200);
+        restarted.next_object_id = 1;
+        assert_eq!(restarted.kalman_states[&0].last_update, 1_200);
+        assert_eq!(restarted.kalman_states[&0].filter.covariance(), covariance);
+
+        let (fused, _) = merge_sensor_observations(&mut restarted, &[camera(20.7)], 1_210);
+        assert_eq!(fused.iter().map(|o| o.object_id).collect::<Vec<_>>(), [0]);
+        assert_eq!(restarted.kalman_states.len(), 1);
+    }
+
+    #[test]
+    fn test_restored_tracks_keep_relative_age() {
+        let record = |object_id, last_update| TrackRecord {
+            object_id,
+            position: [0.0; 3],
+            velocity: [0.0; 3],
+            covariance: [0.0; kalman::COVARIANCE_LEN],
+            confidence: 0.8,
+            last_update,
+        };
+        let restored = restore_tracks(vec![record(3, 5_000), record(4, 4_940)], 20_000);
+        assert_eq!(restored[&3].last_update, 20_000);
+        assert_eq!(restored[&4].last_update, 19_940);
+    }
+}
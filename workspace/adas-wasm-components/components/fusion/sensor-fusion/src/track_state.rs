@@ -0,0 +1,184 @@
+// Serializable snapshot of the fusion track set
+// Lets a restarted component resume with the previous tracks and object IDs
+
+use crate::kalman::COVARIANCE_LEN;
+
+/// Leading bytes of every snapshot
+const MAGIC: &[u8; 4] = b"ADTS";
+
+/// Snapshot format version; 2 added the track covariance
+const VERSION: u8 = 2;
+
+/// Bytes per encoded track
+const TRACK_SIZE: usize = 4 + 6 * 4 + COVARIANCE_LEN * 4 + 4 + 8;
+
+/// One Kalman track
+#[derive(Debug, Clone, PartialEq)]
+pub struct TrackRecord {
+    pub object_id: u32,
+    pub position: [f32; 3],
+    pub velocity: [f32; 3],
+    /// Upper triangle of the Kalman state covariance, row by row
+    pub covariance: [f32; COVARIANCE_LEN],
+    pub confidence: f32,
+    pub last_update: u64,
+}
+
+/// Track set plus the lifecycle information needed to resume
+#[derive(Debug, Clone, PartialEq)]
+pub struct TrackSnapshot {
+    /// Whether the component was active when the snapshot was taken
+    pub was_active: bool,
+    pub frames_processed: u64,
+    pub objects_fused: u64,
+    pub tracks: Vec<TrackRecord>,
+}
+
+impl TrackSnapshot {
+    /// Encode as a versioned little-endian byte buffer
+    pub fn encode(&self) -> Vec<u8> {
+        let mut out = Vec::with_capacity(4 + 1 + 1 + 8 + 8 + 4 + self.tracks.len() * TRACK_SIZE);
+        out.extend_from_slice(MAGIC);
+        out.push(VERSION);
+        out.push(self.was_active as u8);
+        out.extend_from_slice(&self.frames_processed.to_le_bytes());
+        out.extend_from_slice(&self.objects_fused.to_le_bytes());
+        out.extend_from_slice(&(self.tracks.len() as u32).to_le_bytes());
+
+        for track in &self.tracks {
+            out.extend_from_slice(&track.object_id.to_le_bytes());
+            for value in track.position.iter().chain(&track.velocity).chain(&track.covariance) {
+                out.extend_from_slice(&value.to_le_bytes());
+            }
+            out.extend_from_slice(&track.confidence.to_le_bytes());
+            out.extend_from_slice(&track.last_update.to_le_bytes());
+        }
+
+        out
+    }
+
+    /// Decode a buffer produced by [`TrackSnapshot::encode`]
+    pub fn decode(bytes: &[u8]) -> Result<Self, String> {
+        let mut reader = Reader { bytes, offset: 0 };
+
+        if reader.take(4)? != MAGIC {
+            return Err("Not a track state snapshot".to_string());
+        }
+        let version = reader.take(1)?[0];
+        if version != VERSION {
+            return Err(format!("Unsupported track state version {}", version));
+        }
+
+        let was_active = reader.take(1)?[0] != 0;
+        let frames_processed = reader.u64()?;
+        let objects_fused = reader.u64()?;
+        let count = reader.u32()? as usize;
+
+        if bytes.len() - reader.offset != count * TRACK_SIZE {
+            return Err(format!("Track state holds {} bytes for {} tracks", bytes.len() - reader.offset, count));
+        }
+
+        let mut tracks = Vec::with_capacity(count);
+        for _ in 0..count {
+            let object_id = reader.u32()?;
+            let mut values = [0.0f32; 6];
+            for value in &mut values {
+                *value = reader.f32()?;
+            }
+            let mut covariance = [0.0f32; COVARIANCE_LEN];
+            for value in &mut covariance {
+                *value = reader.f32()?;
+            }
+            tracks.push(TrackRecord {
+                object_id,
+                position: [values[0], values[1], values[2]],
+                velocity: [values[3], values[4], values[5]],
+                covariance,
+                confidence: reader.f32()?,
+                last_update: reader.u64()?,
+            });
+        }
+
+        Ok(Self {
+            was_active,
+            frames_processed,
+            objects_fused,
+            tracks,
+        })
+    }
+}
+
+struct Reader<'a> {
+    bytes: &'a [u8],
+    offset: usize,
+}
+
+impl<'a> Reader<'a> {
+    fn take(&mut self, len: usize) -> Result<&'a [u8], String> {
+        let end = self.offset + len;
+        let slice = self.bytes.get(self.offset..end).ok_or("Truncated track state")?;
+        self.offset = end;
+        Ok(slice)
+    }
+
+    fn u32(&mut self) -> Result<u32, String> {
+        Ok(u32::from_le_bytes(self.take(4)?.try_into().unwrap()))
+    }
+
+    fn u64(&mut self) -> Result<u64, String> {
+        Ok(u64::from_le_bytes(self.take(8)?.try_into().unwrap()))
+    }
+
+    fn f32(&mut self) -> Result<f32, String> {
+        Ok(f32::from_le_bytes(self.take(4)?.try_into().unwrap()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn snapshot() -> TrackSnapshot {
+        TrackSnapshot {
+            was_active: true,
+            frames_processed: 1200,
+            objects_fused: 4800,
+            tracks: vec![
+                TrackRecord {
+                    object_id: 7,
+                    position: [12.5, -1.0, 0.0],
+                    velocity: [3.0, 0.1, 0.0],
+                    covariance: [0.25; COVARIANCE_LEN],
+                    confidence: 0.9,
+                    last_update: 1_700_000_000_000,
+                },
+                TrackRecord {
+                    object_id: 42,
+                    position: [30.0, 4.0, 0.5],
+                    velocity: [-1.0, 0.0, 0.0],
+                    covariance: std::array::from_fn(|i| i as f32 * 0.1),
+                    confidence: 0.6,
+                    last_update: 1_700_000_000_100,
+                },
+            ],
+        }
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let original = snapshot();
+        assert_eq!(TrackSnapshot::decode(&original.encode()).unwrap(), original);
+    }
+
+    #[test]
+    fn test_truncated_and_foreign_data_rejected() {
+        let bytes = snapshot().encode();
+        assert!(TrackSnapshot::decode(&bytes[..bytes.len() - 1]).is_err());
+        assert!(TrackSnapshot::decode(b"not a snapshot").is_err());
+        assert!(TrackSnapshot::decode(&[]).is_err());
+
+        let mut wrong_version = bytes.clone();
+        wrong_version[4] = VERSION + 1;
+        assert!(TrackSnapshot::decode(&wrong_version).is_err());
+    }
+}
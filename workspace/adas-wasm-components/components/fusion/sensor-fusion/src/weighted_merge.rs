@@ -0,0 +1,361 @@
+// Confidence-weighted merging of per-sensor object observations
+// Combines reports of the same object from several sensors into one estimate
+
+use std::collections::HashMap;
+
+/// Minimum positional spread (m) attributed to a single-source estimate
+const MIN_POSITION_UNCERTAINTY: f32 = 0.1;
+
+/// Maximum distance (m) between observations considered the same object
+pub const DEFAULT_ASSOCIATION_GATE_M: f32 = 3.0;
+
+/// One sensor's report of an object in the common fusion frame
+#[derive(Debug, Clone, PartialEq)]
+pub struct Observation {
+    pub sensor_id: String,
+    pub sensor_type: String,
+    pub position: [f32; 3],
+    pub velocity: [f32; 3],
+    pub object_type: String,
+    pub confidence: f32,
+    /// ID the producer gave this detection, if it reports one
+    pub detection_id: Option<u32>,
+}
+
+impl Observation {
+    /// Parse an observation from sensor raw data
+    ///
+    /// Raw data is expected as `x,y,z,vx,vy,vz,object_type`, optionally
+    /// followed by `,detection_id`; returns `None` for payloads in any other
+    /// format.
+    pub fn parse(sensor_id: &str, sensor_type: &str, raw_data: &str, confidence: f32) -> Option<Self> {
+        let fields: Vec<&str> = raw_data.split(',').map(str::trim).collect();
+        let detection_id = match fields.len() {
+            7 => None,
+            8 => Some(fields[7].parse().ok()?),
+            _ => return None,
+        };
+
+        let mut values = [0.0f32; 6];
+        for (value, field) in values.iter_mut().zip(&fields[..6]) {
+            *value = field.parse().ok()?;
+        }
+
+        Some(Self {
+            sensor_id: sensor_id.to_string(),
+            sensor_type: sensor_type.to_string(),
+            position: [values[0], values[1], values[2]],
+            velocity: [values[3], values[4], values[5]],
+            object_type: fields[6].to_string(),
+            confidence,
+            detection_id,
+        })
+    }
+}
+
+/// Per-sensor-type weighting
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SourceWeight {
+    pub weight: f32,
+    pub reliability: f32,
+}
+
+/// How the confidences of contributing sensors combine into one
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConfidenceFusion {
+    /// Weighted mean of the source confidences; extra sensors do not add certainty
+    WeightedMean,
+    /// `1 - Π(1 - q)` over the sensor types, where `q` is reliability ×
+    /// confidence; every independent confirmation raises the result
+    Complementary,
+}
+
+/// Result of merging the observations of one object
+#[derive(Debug, Clone, PartialEq)]
+pub struct MergedEstimate {
+    pub position: [f32; 3],
+    pub velocity: [f32; 3],
+    pub object_type: String,
+    pub confidence: f32,
+    /// Weighted standard deviation of source positions (m)
+    pub position_uncertainty: f32,
+    pub source_sensors: Vec<String>,
+    /// `(sensor_id, detection_id)` of every contributing observation that has an ID
+    pub source_detections: Vec<(String, u32)>,
+}
+
+/// Quality of an observation: sensor reliability × reported confidence
+fn quality(observation: &Observation, weights: &HashMap<String, SourceWeight>) -> f32 {
+    let reliability = weights.get(&observation.sensor_type).map_or(0.5, |w| w.reliability);
+    (reliability * observation.confidence).clamp(0.0, 1.0)
+}
+
+/// Complementary combination of per-sensor-type qualities
+///
+/// Observations from the same sensor type are not independent confirmations,
+/// so only the best one per type contributes.
+fn complementary_confidence(observations: &[&Observation], weights: &HashMap<String, SourceWeight>) -> f32 {
+    let mut best_per_type: HashMap<&str, f32> = HashMap::new();
+    for observation in observations {
+        let best = best_per_type.entry(observation.sensor_type.as_str()).or_insert(0.0);
+        *best = best.max(quality(observation, weights));
+    }
+
+    1.0 - best_per_type.values().map(|q| 1.0 - q).product::<f32>()
+}
+
+/// Effective weight of an observation: sensor weight × reliability × confidence
+fn effective_weight(observation: &Observation, weights: &HashMap<String, SourceWeight>) -> f32 {
+    let source = weights
+        .get(&observation.sensor_type)
+        .copied()
+        .unwrap_or(SourceWeight { weight: 0.1, reliability: 0.5 });
+    (source.weight * source.reliability * observation.confidence.clamp(0.0, 1.0)).max(0.0)
+}
+
+/// Merge observations of a single object into one estimate
+///
+/// Position and velocity are weighted averages; the object type is the
+/// class with the highest summed weight. The position uncertainty is the
+/// weighted spread of the sources around the merged position, so it stays
+/// small when sensors agree and grows when they disagree. The source
+/// confidences are combined according to `fusion`.
+pub fn merge_observations(
+    observations: &[Observation],
+    weights: &HashMap<String, SourceWeight>,
+    fusion: ConfidenceFusion,
+) -> Option<MergedEstimate> {
+    let weighted: Vec<(f32, &Observation)> = observations
+        .iter()
+        .map(|o| (effective_weight(o, weights), o))
+        .filter(|(w, _)| *w > 0.0)
+        .collect();
+
+    let total_weight: f32 = weighted.iter().map(|(w, _)| w).sum();
+    if weighted.is_empty() || total_weight <= 0.0 {
+        return None;
+    }
+
+    let mut position = [0.0f32; 3];
+    let mut velocity = [0.0f32; 3];
+    let mut type_votes: HashMap<&str, f32> = HashMap::new();
+
+    for (w, o) in &weighted {
+        for axis in 0..3 {
+            position[axis] += w * o.position[axis] / total_weight;
+            velocity[axis] += w * o.velocity[axis] / total_weight;
+        }
+        *type_votes.entry(o.object_type.as_str()).or_insert(0.0) += w;
+    }
+
+    let variance: f32 = weighted
+        .iter()
+        .map(|(w, o)| {
+            let d2: f32 = (0..3).map(|axis| (o.position[axis] - position[axis]).powi(2)).sum();
+            w * d2
+        })
+        .sum::<f32>()
+        / total_weight;
+    let position_uncertainty = variance.sqrt().max(MIN_POSITION_UNCERTAINTY);
+
+    // Ties are broken by name so the result does not depend on input order
+    let object_type = type_votes
+        .iter()
+        .max_by(|a, b| a.1.total_cmp(b.1).then_with(|| b.0.cmp(a.0)))
+        .map(|(t, _)| t.to_string())
+        .unwrap_or_default();
+
+    // Agreement scales the combined confidence down as sources diverge
+    let combined_confidence = match fusion {
+        ConfidenceFusion::WeightedMean => {
+            weighted.iter().map(|(w, o)| w * o.confidence).sum::<f32>() / total_weight
+        }
+        ConfidenceFusion::Complementary => {
+            let sources: Vec<&Observation> = weighted.iter().map(|(_, o)| *o).collect();
+            complementary_confidence(&sources, weights)
+        }
+    };
+    let confidence = (combined_confidence / (1.0 + variance.sqrt())).clamp(0.0, 1.0);
+
+    let mut source_sensors: Vec<String> = weighted.iter().map(|(_, o)| o.sensor_id.clone()).collect();
+    source_sensors.dedup();
+    let source_detections = weighted
+        .iter()
+        .filter_map(|(_, o)| Some((o.sensor_id.clone(), o.detection_id?)))
+        .collect();
+
+    Some(MergedEstimate {
+        position,
+        velocity,
+        object_type,
+        confidence,
+        position_uncertainty,
+        source_sensors,
+        source_detections,
+    })
+}
+
+/// Group observations that refer to the same object
+///
+/// Greedy nearest-neighbour association: each observation joins the
+/// closest existing group within `gate_m`, otherwise it starts a new one.
+pub fn associate(observations: &[Observation], gate_m: f32) -> Vec<Vec<Observation>> {
+    let mut groups: Vec<Vec<Observation>> = Vec::new();
+
+    for observation in observations {
+        let closest = groups
+            .iter()
+            .enumerate()
+            .map(|(i, group)| (i, distance(&group[0].position, &observation.position)))
+            .filter(|(_, d)| *d <= gate_m)
+            .min_by(|a, b| a.1.total_cmp(&b.1));
+
+        match closest {
+            Some((i, _)) => groups[i].push(observation.clone()),
+            None => groups.push(vec![observation.clone()]),
+        }
+    }
+
+    groups
+}
+
+fn distance(a: &[f32; 3], b: &[f32; 3]) -> f32 {
+    (0..3).map(|axis| (a[axis] - b[axis]).powi(2)).sum::<f32>().sqrt()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn weights() -> HashMap<String, SourceWeight> {
+        let mut weights = HashMap::new();
+        weights.insert("camera".to_string(), SourceWeight { weight: 0.4, reliability: 0.8 });
+        weights.insert("radar".to_string(), SourceWeight { weight: 0.3, reliability: 0.9 });
+        weights.insert("lidar".to_string(), SourceWeight { weight: 0.25, reliability: 0.95 });
+        weights
+    }
+
+    fn observation(sensor_type: &str, x: f32, object_type: &str, confidence: f32) -> Observation {
+        Observation {
+            sensor_id: format!("{}-1", sensor_type),
+            sensor_type: sensor_type.to_string(),
+            position: [x, 0.0, 0.0],
+            velocity: [x / 10.0, 0.0, 0.0],
+            object_type: object_type.to_string(),
+            confidence,
+            detection_id: None,
+        }
+    }
+
+    #[test]
+    fn test_weighted_position_and_type_vote() {
+        let observations = vec![
+            observation("camera", 20.0, "pedestrian", 0.9),
+            observation("radar", 21.0, "vehicle", 0.9),
+            observation("lidar", 21.0, "vehicle", 0.9),
+        ];
+        let merged = merge_observations(&observations, &weights(), ConfidenceFusion::WeightedMean).unwrap();
+
+        // camera 0.288, radar 0.243, lidar 0.21375
+        let expected_x = (0.288 * 20.0 + 0.243 * 21.0 + 0.21375 * 21.0) / (0.288 + 0.243 + 0.21375);
+        assert!((merged.position[0] - expected_x).abs() < 1e-4);
+        assert!((merged.velocity[0] - expected_x / 10.0).abs() < 1e-4);
+        assert_eq!(merged.object_type, "vehicle");
+        assert_eq!(merged.source_sensors.len(), 3);
+    }
+
+    #[test]
+    fn test_uncertainty_reflects_agreement() {
+        let agreeing = vec![
+            observation("camera", 20.0, "vehicle", 0.9),
+            observation("radar", 20.1, "vehicle", 0.9),
+        ];
+        let disagreeing = vec![
+            observation("camera", 20.0, "vehicle", 0.9),
+            observation("radar", 24.0, "vehicle", 0.9),
+        ];
+
+        let tight = merge_observations(&agreeing, &weights(), ConfidenceFusion::WeightedMean).unwrap();
+        let wide = merge_observations(&disagreeing, &weights(), ConfidenceFusion::WeightedMean).unwrap();
+
+        assert!(tight.position_uncertainty < 0.2);
+        assert!(wide.position_uncertainty > 1.5);
+        assert!(tight.confidence > wide.confidence);
+    }
+
+    #[test]
+    fn test_complementary_confidence_is_monotonic_in_sensors() {
+        let fused = |sensors: &[&str]| {
+            let observations: Vec<Observation> = sensors.iter().map(|s| observation(s, 20.0, "vehicle", 0.8)).collect();
+            merge_observations(&observations, &weights(), ConfidenceFusion::Complementary)
+                .unwrap()
+                .confidence
+        };
+
+        let camera = fused(&["camera"]);
+        let radar = fused(&["radar"]);
+        let camera_radar = fused(&["camera", "radar"]);
+        let radar_lidar = fused(&["radar", "lidar"]);
+        let all = fused(&["camera", "radar", "lidar"]);
+
+        // Single sensor: its own quality (camera reliability 0.8 × 0.8)
+        assert!((camera - 0.64).abs() < 1e-5);
+        // Each independent confirmation raises confidence, losing one lowers it
+        assert!(camera < camera_radar && radar < camera_radar);
+        assert!(camera_radar < all && radar_lidar < all);
+        // A more reliable sensor contributes more than a less reliable one
+        assert!(camera < radar);
+        assert!(all < 1.0);
+    }
+
+    #[test]
+    fn test_same_sensor_type_is_not_independent() {
+        let single = vec![observation("camera", 20.0, "vehicle", 0.8)];
+        let mut repeated = single.clone();
+        repeated.push(Observation { sensor_id: "camera-2".to_string(), ..single[0].clone() });
+
+        let one = merge_observations(&single, &weights(), ConfidenceFusion::Complementary).unwrap();
+        let two = merge_observations(&repeated, &weights(), ConfidenceFusion::Complementary).unwrap();
+        assert_eq!(one.confidence, two.confidence);
+    }
+
+    #[test]
+    fn test_weighted_mean_ignores_confirmations() {
+        let camera = vec![observation("camera", 20.0, "vehicle", 0.8)];
+        let both = vec![observation("camera", 20.0, "vehicle", 0.8), observation("radar", 20.0, "vehicle", 0.8)];
+
+        let one = merge_observations(&camera, &weights(), ConfidenceFusion::WeightedMean).unwrap();
+        let two = merge_observations(&both, &weights(), ConfidenceFusion::WeightedMean).unwrap();
+        assert!((one.confidence - two.confidence).abs() < 1e-5);
+    }
+
+    #[test]
+    fn test_parse_and_associate() {
+        let a = Observation::parse("cam-1", "camera", "20.0,1.0,0.0,5.0,0.0,0.0,vehicle", 0.8).unwrap();
+        let b = Observation::parse("radar-1", "radar", "21.0,1.5,0.0,5.2,0.0,0.0,vehicle", 0.9).unwrap();
+        let c = Observation::parse("radar-1", "radar", "45.0,-3.0,0.0,0.0,0.0,0.0,pedestrian", 0.7).unwrap();
+        assert!(Observation::parse("cam-1", "camera", "jpeg-bytes", 0.8).is_none());
+        assert_eq!(a.detection_id, None);
+
+        let groups = associate(&[a, b, c], DEFAULT_ASSOCIATION_GATE_M);
+        assert_eq!(groups.len(), 2);
+        assert_eq!(groups[0].len(), 2);
+    }
+
+    #[test]
+    fn test_merge_keeps_source_detection_ids() {
+        let camera = Observation::parse("cam-1", "camera", "20.0,1.0,0.0,5.0,0.0,0.0,vehicle,1041", 0.8).unwrap();
+        let radar = Observation::parse("radar-1", "radar", "20.5,1.2,0.0,5.1,0.0,0.0,vehicle", 0.9).unwrap();
+        let lidar = Observation::parse("lidar-1", "lidar", "20.2,1.1,0.0,5.0,0.0,0.0,vehicle,7", 0.9).unwrap();
+        assert_eq!(camera.detection_id, Some(1041));
+        assert!(Observation::parse("cam-1", "camera", "20.0,1.0,0.0,5.0,0.0,0.0,vehicle,-1", 0.8).is_none());
+
+        let merged = merge_observations(&[camera, radar, lidar], &weights(), ConfidenceFusion::Complementary).unwrap();
+        assert_eq!(merged.source_detections, vec![("cam-1".to_string(), 1041), ("lidar-1".to_string(), 7)]);
+    }
+
+    #[test]
+    fn test_empty_input() {
+        assert!(merge_observations(&[], &weights(), ConfidenceFusion::Complementary).is_none());
+    }
+}
@@ -0,0 +1,119 @@
+// RGBA colors for overlays and the named palette
+// Colors can also be configured as hex strings like the HMI visualizer's "#FF0000"
+
+/// RGBA Color
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Color {
+    pub r: u8,
+    pub g: u8,
+    pub b: u8,
+    pub a: u8,
+}
+
+impl Color {
+    /// #FFFFFF
+    pub const WHITE: Color = Color { r: 255, g: 255, b: 255, a: 255 };
+    /// #000000
+    pub const BLACK: Color = Color { r: 0, g: 0, b: 0, a: 255 };
+    /// #FF0000
+    pub const RED: Color = Color { r: 255, g: 0, b: 0, a: 255 };
+    /// #00FF00
+    pub const GREEN: Color = Color { r: 0, g: 255, b: 0, a: 255 };
+    /// #0000FF
+    pub const BLUE: Color = Color { r: 0, g: 0, b: 255, a: 255 };
+    /// #FFFF00
+    pub const YELLOW: Color = Color { r: 255, g: 255, b: 0, a: 255 };
+    /// #00FFFF
+    pub const CYAN: Color = Color { r: 0, g: 255, b: 255, a: 255 };
+    /// #FF00FF
+    pub const MAGENTA: Color = Color { r: 255, g: 0, b: 255, a: 255 };
+    /// #FFA500
+    pub const ORANGE: Color = Color { r: 255, g: 165, b: 0, a: 255 };
+    /// #800080
+    pub const PURPLE: Color = Color { r: 128, g: 0, b: 128, a: 255 };
+    /// #808080
+    pub const GRAY: Color = Color { r: 128, g: 128, b: 128, a: 255 };
+
+    /// Parse `#RGB`, `#RRGGBB` or `#RRGGBBAA`; the `#` is optional
+    ///
+    /// Colors without an alpha component are opaque.
+    pub fn from_hex(hex: &str) -> Option<Color> {
+        let digits = hex.strip_prefix('#').unwrap_or(hex);
+        // from_str_radix alone would accept a leading sign
+        if !digits.chars().all(|c| c.is_ascii_hexdigit()) {
+            return None;
+        }
+        let channel = |i: usize| u8::from_str_radix(&digits[i * 2..i * 2 + 2], 16).ok();
+
+        match digits.len() {
+            3 => {
+                let mut rgb = [0u8; 3];
+                for (value, digit) in rgb.iter_mut().zip(digits.chars()) {
+                    // #F80 is shorthand for #FF8800
+                    *value = digit.to_digit(16)? as u8 * 0x11;
+                }
+                Some(Color { r: rgb[0], g: rgb[1], b: rgb[2], a: 255 })
+            }
+            6 => Some(Color { r: channel(0)?, g: channel(1)?, b: channel(2)?, a: 255 }),
+            8 => Some(Color { r: channel(0)?, g: channel(1)?, b: channel(2)?, a: channel(3)? }),
+            _ => None,
+        }
+    }
+
+    /// `#RRGGBB` for opaque colors, `#RRGGBBAA` otherwise
+    pub fn to_hex(self) -> String {
+        if self.a == 255 {
+            format!("#{:02X}{:02X}{:02X}", self.r, self.g, self.b)
+        } else {
+            format!("#{:02X}{:02X}{:02X}{:02X}", self.r, self.g, self.b, self.a)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_palette_round_trips_through_hex() {
+        let palette = [
+            (Color::WHITE, "#FFFFFF"),
+            (Color::BLACK, "#000000"),
+            (Color::RED, "#FF0000"),
+            (Color::GREEN, "#00FF00"),
+            (Color::BLUE, "#0000FF"),
+            (Color::YELLOW, "#FFFF00"),
+            (Color::CYAN, "#00FFFF"),
+            (Color::MAGENTA, "#FF00FF"),
+            (Color::ORANGE, "#FFA500"),
+            (Color::PURPLE, "#800080"),
+            (Color::GRAY, "#808080"),
+        ];
+
+        for (color, hex) in palette {
+            assert_eq!(color.to_hex(), hex);
+            assert_eq!(Color::from_hex(hex), Some(color));
+        }
+    }
+
+    #[test]
+    fn test_translucent_round_trip() {
+        let overlay = Color { r: 0, g: 0, b: 0, a: 180 };
+        assert_eq!(overlay.to_hex(), "#000000B4");
+        assert_eq!(Color::from_hex(&overlay.to_hex()), Some(overlay));
+    }
+
+    #[test]
+    fn test_from_hex_forms() {
+        assert_eq!(Color::from_hex("ff0000"), Some(Color::RED));
+        assert_eq!(Color::from_hex("#F0F"), Some(Color::MAGENTA));
+        assert_eq!(Color::from_hex("#FFA500FF"), Some(Color::ORANGE));
+    }
+
+    #[test]
+    fn test_from_hex_rejects_malformed() {
+        for bad in ["", "#", "#FF00", "#GG0000", "#FF00000", "#ÿÿÿ", "#+F0000"] {
+            assert_eq!(Color::from_hex(bad), None, "{}", bad);
+        }
+    }
+}
@@ -0,0 +1,297 @@
+// Embedded bitmap fonts for overlay text
+// Glyph rows are bit masks with the most significant used bit leftmost
+
+use std::collections::HashMap;
+
+/// Embedded bitmap font
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum FontId {
+    /// 5x7 glyphs for letters, digits and common punctuation
+    #[default]
+    Standard,
+    /// 8x8 block glyphs; only A-C, 0, 1 and a few symbols
+    Block,
+}
+
+impl FontId {
+    pub const ALL: [FontId; 2] = [FontId::Standard, FontId::Block];
+}
+
+/// 5x7 glyphs; lowercase letters are drawn with the uppercase ones
+const STANDARD_GLYPHS: &[(char, [u8; 7])] = &[
+    ('0', [0b01110, 0b10001, 0b10011, 0b10101, 0b11001, 0b10001, 0b01110]),
+    ('1', [0b00100, 0b01100, 0b00100, 0b00100, 0b00100, 0b00100, 0b01110]),
+    ('2', [0b01110, 0b10001, 0b00001, 0b00010, 0b00100, 0b01000, 0b11111]),
+    ('3', [0b11111, 0b00010, 0b00100, 0b00010, 0b00001, 0b10001, 0b01110]),
+    ('4', [0b00010, 0b00110, 0b01010, 0b10010, 0b11111, 0b00010, 0b00010]),
+    ('5', [0b11111, 0b10000, 0b11110, 0b00001, 0b00001, 0b10001, 0b01110]),
+    ('6', [0b00110, 0b01000, 0b10000, 0b11110, 0b10001, 0b10001, 0b01110]),
+    ('7', [0b11111, 0b00001, 0b00010, 0b00100, 0b01000, 0b01000, 0b01000]),
+    ('8', [0b01110, 0b10001, 0b10001, 0b01110, 0b10001, 0b10001, 0b01110]),
+    ('9', [0b01110, 0b10001, 0b10001, 0b01111, 0b00001, 0b00010, 0b01100]),
+    ('A', [0b01110, 0b10001, 0b10001, 0b11111, 0b10001, 0b10001, 0b10001]),
+    ('B', [0b11110, 0b10001, 0b10001, 0b11110, 0b10001, 0b10001, 0b11110]),
+    ('C', [0b01110, 0b10001, 0b10000, 0b10000, 0b10000, 0b10001, 0b01110]),
+    ('D', [0b11100, 0b10010, 0b10001, 0b10001, 0b10001, 0b10010, 0b11100]),
+    ('E', [0b11111, 0b10000, 0b10000, 0b11110, 0b10000, 0b10000, 0b11111]),
+    ('F', [0b11111, 0b10000, 0b10000, 0b11110, 0b10000, 0b10000, 0b10000]),
+    ('G', [0b01110, 0b10001, 0b10000, 0b10111, 0b10001, 0b10001, 0b01111]),
+    ('H', [0b10001, 0b10001, 0b10001, 0b11111, 0b10001, 0b10001, 0b10001]),
+    ('I', [0b01110, 0b00100, 0b00100, 0b00100, 0b00100, 0b00100, 0b01110]),
+    ('J', [0b00111, 0b00010, 0b00010, 0b00010, 0b00010, 0b10010, 0b01100]),
+    ('K', [0b10001, 0b10010, 0b10100, 0b11000, 0b10100, 0b10010, 0b10001]),
+    ('L', [0b10000, 0b10000, 0b10000, 0b10000, 0b10000, 0b10000, 0b11111]),
+    ('M', [0b10001, 0b11011, 0b10101, 0b10101, 0b10001, 0b10001, 0b10001]),
+    ('N', [0b10001, 0b10001, 0b11001, 0b10101, 0b10011, 0b10001, 0b10001]),
+    ('O', [0b01110, 0b10001, 0b10001, 0b10001, 0b10001, 0b10001, 0b01110]),
+    ('P', [0b11110, 0b10001, 0b10001, 0b11110, 0b10000, 0b10000, 0b10000]),
+    ('Q', [0b01110, 0b10001, 0b10001, 0b10001, 0b10101, 0b10010, 0b01101]),
+    ('R', [0b11110, 0b10001, 0b10001, 0b11110, 0b10100, 0b10010, 0b10001]),
+    ('S', [0b01111, 0b10000, 0b10000, 0b01110, 0b00001, 0b00001, 0b11110]),
+    ('T', [0b11111, 0b00100, 0b00100, 0b00100, 0b00100, 0b00100, 0b00100]),
+    ('U', [0b10001, 0b10001, 0b10001, 0b10001, 0b10001, 0b10001, 0b01110]),
+    ('V', [0b10001, 0b10001, 0b10001, 0b10001, 0b10001, 0b01010, 0b00100]),
+    ('W', [0b10001, 0b10001, 0b10001, 0b10101, 0b10101, 0b10101, 0b01010]),
+    ('X', [0b10001, 0b10001, 0b01010, 0b00100, 0b01010, 0b10001, 0b10001]),
+    ('Y', [0b10001, 0b10001, 0b10001, 0b01010, 0b00100, 0b00100, 0b00100]),
+    ('Z', [0b11111, 0b00001, 0b00010, 0b00100, 0b01000, 0b10000, 0b11111]),
+    (' ', [0b00000, 0b00000, 0b00000, 0b00000, 0b00000, 0b00000, 0b00000]),
+    ('.', [0b00000, 0b00000, 0b00000, 0b00000, 0b00000, 0b01100, 0b01100]),
+    (',', [0b00000, 0b00000, 0b00000, 0b00000, 0b01100, 0b00100, 0b01000]),
+    (':', [0b00000, 0b01100, 0b01100, 0b00000, 0b01100, 0b01100, 0b00000]),
+    ('%', [0b11000, 0b11001, 0b00010, 0b00100, 0b01000, 0b10011, 0b00011]),
+    ('#', [0b01010, 0b01010, 0b11111, 0b01010, 0b11111, 0b01010, 0b01010]),
+    ('(', [0b00010, 0b00100, 0b01000, 0b01000, 0b01000, 0b00100, 0b00010]),
+    (')', [0b01000, 0b00100, 0b00010, 0b00010, 0b00010, 0b00100, 0b01000]),
+    ('[', [0b01110, 0b01000, 0b01000, 0b01000, 0b01000, 0b01000, 0b01110]),
+    (']', [0b01110, 0b00010, 0b00010, 0b00010, 0b00010, 0b00010, 0b01110]),
+    ('|', [0b00100, 0b00100, 0b00100, 0b00100, 0b00100, 0b00100, 0b00100]),
+    ('-', [0b00000, 0b00000, 0b00000, 0b11111, 0b00000, 0b00000, 0b00000]),
+    ('_', [0b00000, 0b00000, 0b00000, 0b00000, 0b00000, 0b00000, 0b11111]),
+    ('+', [0b00000, 0b00100, 0b00100, 0b11111, 0b00100, 0b00100, 0b00000]),
+    ('=', [0b00000, 0b00000, 0b11111, 0b00000, 0b11111, 0b00000, 0b00000]),
+    ('/', [0b00000, 0b00001, 0b00010, 0b00100, 0b01000, 0b10000, 0b00000]),
+    ('*', [0b00000, 0b00100, 0b10101, 0b01110, 0b10101, 0b00100, 0b00000]),
+    ('!', [0b00100, 0b00100, 0b00100, 0b00100, 0b00100, 0b00000, 0b00100]),
+    ('?', [0b01110, 0b10001, 0b00001, 0b00010, 0b00100, 0b00000, 0b00100]),
+    ('\'', [0b00100, 0b00100, 0b01000, 0b00000, 0b00000, 0b00000, 0b00000]),
+    ('<', [0b00010, 0b00100, 0b01000, 0b10000, 0b01000, 0b00100, 0b00010]),
+    ('>', [0b01000, 0b00100, 0b00010, 0b00001, 0b00010, 0b00100, 0b01000]),
+];
+
+/// Bitmap font with a fixed character cell
+pub struct BitmapFont {
+    /// Bits used per glyph row
+    glyph_width: u32,
+    /// Horizontal distance between characters
+    char_width: u32,
+    /// Vertical distance between lines
+    char_height: u32,
+    font_data: HashMap<char, Vec<u8>>,
+}
+
+impl BitmapFont {
+    pub fn new(id: FontId) -> Self {
+        match id {
+            FontId::Standard => Self {
+                glyph_width: 5,
+                char_width: 6,
+                char_height: 8,
+                font_data: STANDARD_GLYPHS.iter().map(|(ch, rows)| (*ch, rows.to_vec())).collect(),
+            },
+            FontId::Block => Self::block(),
+        }
+    }
+    
+    fn block() -> Self {
+        let mut font_data = HashMap::new();
+        
+        // Simple 8x8 bitmap font for basic characters
+        // Each character is represented as 8 bytes (8x8 pixels)
+        
+        // Letter 'A'
+        font_data.insert('A', vec![
+            0b00111000,
+            0b01101100,
+            0b11000110,
+            0b11000110,
+            0b11111110,
+            0b11000110,
+            0b11000110,
+            0b00000000,
+        ]);
+        
+        // Letter 'B'
+        font_data.insert('B', vec![
+            0b11111100,
+            0b11000110,
+            0b11000110,
+            0b11111100,
+            0b11000110,
+            0b11000110,
+            0b11111100,
+            0b00000000,
+        ]);
+        
+        // Letter 'C'
+        font_data.insert('C', vec![
+            0b01111100,
+            0b11000110,
+            0b11000000,
+            0b11000000,
+            0b11000000,
+            0b11000110,
+            0b01111100,
+            0b00000000,
+        ]);
+        
+        // Digit '0'
+        font_data.insert('0', vec![
+            0b01111100,
+            0b11000110,
+            0b11001110,
+            0b11011110,
+            0b11110110,
+            0b11100110,
+            0b01111100,
+            0b00000000,
+        ]);
+        
+        // Digit '1'
+        font_data.insert('1', vec![
+            0b00011000,
+            0b00111000,
+            0b00011000,
+            0b00011000,
+            0b00011000,
+            0b00011000,
+            0b01111110,
+            0b00000000,
+        ]);
+        
+        // Space character
+        font_data.insert(' ', vec![
+            0b00000000,
+            0b00000000,
+            0b00000000,
+            0b00000000,
+            0b00000000,
+            0b00000000,
+            0b00000000,
+            0b00000000,
+        ]);
+        
+        // Colon ':'
+        font_data.insert(':', vec![
+            0b00000000,
+            0b00011000,
+            0b00011000,
+            0b00000000,
+            0b00011000,
+            0b00011000,
+            0b00000000,
+            0b00000000,
+        ]);
+        
+        // Percent '%'
+        font_data.insert('%', vec![
+            0b01100010,
+            0b01100100,
+            0b00001000,
+            0b00010000,
+            0b00100000,
+            0b01001100,
+            0b10001100,
+            0b00000000,
+        ]);
+        
+        Self {
+            glyph_width: 8,
+            char_width: 8,
+            char_height: 8,
+            font_data,
+        }
+    }
+    
+    /// Rows of the glyph for `ch`, falling back to its uppercase form
+    pub fn glyph(&self, ch: char) -> Option<&[u8]> {
+        self.font_data
+            .get(&ch)
+            .or_else(|| self.font_data.get(&ch.to_ascii_uppercase()))
+            .map(Vec::as_slice)
+    }
+    
+    /// Set pixels of the glyph for `ch` as (column, row); empty for unknown characters
+    pub fn glyph_pixels(&self, ch: char) -> Vec<(u32, u32)> {
+        let Some(rows) = self.glyph(ch) else {
+            return Vec::new();
+        };
+        
+        let mut pixels = Vec::new();
+        for (row, &bits) in rows.iter().enumerate() {
+            for col in 0..self.glyph_width {
+                if bits & (1 << (self.glyph_width - 1 - col)) != 0 {
+                    pixels.push((col, row as u32));
+                }
+            }
+        }
+        pixels
+    }
+    
+    pub fn char_width(&self) -> u32 {
+        self.char_width
+    }
+    
+    pub fn char_height(&self) -> u32 {
+        self.char_height
+    }
+    
+    /// Size in pixels of `text` at `scale`; lines are split on `\n`
+    pub fn measure(&self, text: &str, scale: f32) -> (u32, u32) {
+        let columns = text.split('\n').map(|line| line.chars().count()).max().unwrap_or(0) as u32;
+        let lines = text.split('\n').count() as u32;
+        (scaled(columns * self.char_width, scale), scaled(lines * self.char_height, scale))
+    }
+}
+
+/// Length in font pixels scaled to overlay pixels
+pub fn scaled(length: u32, scale: f32) -> u32 {
+    (length as f32 * scale).round() as u32
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    #[test]
+    fn test_standard_font_covers_labels() {
+        let font = BitmapFont::new(FontId::Standard);
+        for ch in "person #12: 87.5% | FPS: 30.0 | Render: 4.2ms".chars() {
+            assert!(font.glyph(ch).is_some(), "missing glyph {:?}", ch);
+        }
+        assert_eq!(font.glyph('a'), font.glyph('A'));
+    }
+    
+    #[test]
+    fn test_glyph_bits_leftmost_first() {
+        let standard = BitmapFont::new(FontId::Standard);
+        assert_eq!(standard.glyph_pixels('|'), (0..7).map(|row| (2, row)).collect::<Vec<_>>());
+        assert_eq!(standard.glyph_pixels('-'), (0..5).map(|col| (col, 3)).collect::<Vec<_>>());
+        assert!(standard.glyph_pixels('~').is_empty());
+        
+        // Bottom row of the 8x8 '1' is 0b01111110
+        let block = BitmapFont::new(FontId::Block);
+        let bottom: Vec<u32> = block.glyph_pixels('1').iter().filter(|(_, row)| *row == 6).map(|(col, _)| *col).collect();
+        assert_eq!(bottom, (1..7).collect::<Vec<_>>());
+    }
+    
+    #[test]
+    fn test_measure_multi_line_text() {
+        let font = BitmapFont::new(FontId::Standard);
+        assert_eq!(font.measure("FPS", 1.0), (18, 8));
+        // Widest line sets the width
+        assert_eq!(font.measure("A\nBCD\nEF", 2.0), (36, 48));
+        assert_eq!(font.measure("", 1.0), (0, 8));
+        assert_eq!(BitmapFont::new(FontId::Block).measure("AB", 1.5), (24, 12));
+    }
+}
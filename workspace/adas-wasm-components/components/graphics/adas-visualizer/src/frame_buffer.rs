@@ -2,6 +2,7 @@
 // Handles video frame storage and basic drawing operations
 
 use crate::{Color, overlay_renderer::OverlayRenderer};
+use image::codecs::jpeg::JpegEncoder;
 
 /// Pixel format for frame buffer
 #[derive(Debug, Clone, Copy)]
@@ -22,26 +23,34 @@ impl PixelFormat {
 }
 
 /// Frame buffer for storing and manipulating video frames
+///
+/// Double-buffered: drawing goes to the back buffer, readers only ever see
+/// the front buffer, which [`FrameBuffer::swap`] replaces in one step.
 pub struct FrameBuffer {
     width: u32,
     height: u32,
     format: PixelFormat,
-    data: Vec<u8>,
+    /// Frame being drawn
+    back: Vec<u8>,
+    /// Last presented frame
+    front: Vec<u8>,
+    frames_swapped: u64,
 }
 
 impl FrameBuffer {
     /// Create new frame buffer
     pub fn new(width: u32, height: u32, format: PixelFormat) -> Result<Self, String> {
         let size = (width * height) as usize * format.bytes_per_pixel();
-        let data = vec![0u8; size];
         
-        println!("📱 Creating frame buffer: {}x{} ({} bytes)", width, height, size);
+        println!("📱 Creating frame buffer: {}x{} ({} bytes, double-buffered)", width, height, size);
         
         Ok(Self {
             width,
             height,
             format,
-            data,
+            back: vec![0u8; size],
+            front: vec![0u8; size],
+            frames_swapped: 0,
         })
     }
     
@@ -49,7 +58,7 @@ impl FrameBuffer {
     pub fn clear(&mut self, color: Color) -> Result<(), String> {
         let bytes_per_pixel = self.format.bytes_per_pixel();
         
-        for chunk in self.data.chunks_mut(bytes_per_pixel) {
+        for chunk in self.back.chunks_mut(bytes_per_pixel) {
             match self.format {
                 PixelFormat::RGB8 => {
                     if chunk.len() >= 3 {
@@ -89,15 +98,15 @@ impl FrameBuffer {
     
     /// Draw image data to frame buffer
     pub fn draw_image(&mut self, image_data: &[u8]) -> Result<(), String> {
-        if image_data.len() != self.data.len() {
+        if image_data.len() != self.back.len() {
             return Err(format!(
                 "Image data size mismatch: expected {}, got {}",
-                self.data.len(),
+                self.back.len(),
                 image_data.len()
             ));
         }
         
-        self.data.copy_from_slice(image_data);
+        self.back.copy_from_slice(image_data);
         Ok(())
     }
     
@@ -109,39 +118,39 @@ impl FrameBuffer {
         
         let index = ((y * self.width + x) as usize) * self.format.bytes_per_pixel();
         
-        if index + self.format.bytes_per_pixel() > self.data.len() {
+        if index + self.format.bytes_per_pixel() > self.back.len() {
             return Err("Pixel index out of bounds".to_string());
         }
         
         match self.format {
             PixelFormat::RGB8 => {
-                self.data[index] = color.r;
-                self.data[index + 1] = color.g;
-                self.data[index + 2] = color.b;
+                self.back[index] = color.r;
+                self.back[index + 1] = color.g;
+                self.back[index + 2] = color.b;
             }
             PixelFormat::RGBA8 => {
-                self.data[index] = color.r;
-                self.data[index + 1] = color.g;
-                self.data[index + 2] = color.b;
-                self.data[index + 3] = color.a;
+                self.back[index] = color.r;
+                self.back[index + 1] = color.g;
+                self.back[index + 2] = color.b;
+                self.back[index + 3] = color.a;
             }
             PixelFormat::BGR8 => {
-                self.data[index] = color.b;
-                self.data[index + 1] = color.g;
-                self.data[index + 2] = color.r;
+                self.back[index] = color.b;
+                self.back[index + 1] = color.g;
+                self.back[index + 2] = color.r;
             }
             PixelFormat::BGRA8 => {
-                self.data[index] = color.b;
-                self.data[index + 1] = color.g;
-                self.data[index + 2] = color.r;
-                self.data[index + 3] = color.a;
+                self.back[index] = color.b;
+                self.back[index + 1] = color.g;
+                self.back[index + 2] = color.r;
+                self.back[index + 3] = color.a;
             }
         }
         
         Ok(())
     }
     
-    /// Get pixel at coordinates
+    /// Get pixel at coordinates from the frame being drawn
     pub fn get_pixel(&self, x: u32, y: u32) -> Result<Color, String> {
         if x >= self.width || y >= self.height {
             return Err(format!("Pixel coordinates out of bounds: ({}, {})", x, y));
@@ -149,38 +158,11 @@ impl FrameBuffer {
         
         let index = ((y * self.width + x) as usize) * self.format.bytes_per_pixel();
         
-        if index + self.format.bytes_per_pixel() > self.data.len() {
+        if index + self.format.bytes_per_pixel() > self.back.len() {
             return Err("Pixel index out of bounds".to_string());
         }
         
-        let color = match self.format {
-            PixelFormat::RGB8 => Color {
-                r: self.data[index],
-                g: self.data[index + 1],
-                b: self.data[index + 2],
-                a: 255,
-            },
-            PixelFormat::RGBA8 => Color {
-                r: self.data[index],
-                g: self.data[index + 1],
-                b: self.data[index + 2],
-                a: self.data[index + 3],
-            },
-            PixelFormat::BGR8 => Color {
-                r: self.data[index + 2],
-                g: self.data[index + 1],
-                b: self.data[index],
-                a: 255,
-            },
-            PixelFormat::BGRA8 => Color {
-                r: self.data[index + 2],
-                g: self.data[index + 1],
-                b: self.data[index],
-                a: self.data[index + 3],
-            },
-        };
-        
-        Ok(color)
+        Ok(decode_pixel(self.format, &self.back[index..]))
     }
     
     /// Draw line using Bresenham's algorithm
@@ -291,14 +273,60 @@ impl FrameBuffer {
         // For now, just return raw RGBA data prefixed with dimensions
         png_data.extend_from_slice(&self.width.to_be_bytes());
         png_data.extend_from_slice(&self.height.to_be_bytes());
-        png_data.extend_from_slice(&self.data);
+        png_data.extend_from_slice(&self.front);
         
         Ok(png_data)
     }
     
-    /// Get raw frame buffer data
+    /// Export frame buffer as JPEG
+    ///
+    /// JPEG has no alpha channel, so translucent pixels are flattened onto
+    /// `background` first. `quality` is clamped to 1..=100.
+    pub fn export_jpeg(&self, quality: u8, background: Color) -> Result<Vec<u8>, String> {
+        let quality = quality.clamp(1, 100);
+        let rgb = self.to_rgb_over(background);
+        
+        let mut jpeg_data = Vec::new();
+        JpegEncoder::new_with_quality(&mut jpeg_data, quality)
+            .encode(&rgb, self.width, self.height, image::ColorType::Rgb8)
+            .map_err(|e| format!("JPEG encoding failed: {}", e))?;
+        
+        Ok(jpeg_data)
+    }
+    
+    /// Frame as packed RGB8, alpha-blended over an opaque background
+    fn to_rgb_over(&self, background: Color) -> Vec<u8> {
+        let mut rgb = Vec::with_capacity((self.width * self.height) as usize * 3);
+        
+        for bytes in self.front.chunks_exact(self.format.bytes_per_pixel()) {
+            let pixel = decode_pixel(self.format, bytes);
+            let alpha = pixel.a as f32 / 255.0;
+            for (channel, back) in [(pixel.r, background.r), (pixel.g, background.g), (pixel.b, background.b)] {
+                rgb.push((channel as f32 * alpha + back as f32 * (1.0 - alpha)).round() as u8);
+            }
+        }
+        
+        rgb
+    }
+    
+    /// Publish the back buffer as the new front buffer
+    ///
+    /// The back buffer then starts from the presented frame, so drawing
+    /// continues exactly as on a single buffer.
+    pub fn swap(&mut self) {
+        std::mem::swap(&mut self.front, &mut self.back);
+        self.back.copy_from_slice(&self.front);
+        self.frames_swapped += 1;
+    }
+    
+    /// Number of frames published by [`FrameBuffer::swap`]
+    pub fn frames_swapped(&self) -> u64 {
+        self.frames_swapped
+    }
+    
+    /// Get raw data of the last presented frame
     pub fn get_raw_data(&self) -> &[u8] {
-        &self.data
+        &self.front
     }
     
     /// Get frame buffer dimensions
@@ -312,6 +340,16 @@ impl FrameBuffer {
     }
 }
 
+/// Decode the pixel at the start of `bytes`
+fn decode_pixel(format: PixelFormat, bytes: &[u8]) -> Color {
+    match format {
+        PixelFormat::RGB8 => Color { r: bytes[0], g: bytes[1], b: bytes[2], a: 255 },
+        PixelFormat::RGBA8 => Color { r: bytes[0], g: bytes[1], b: bytes[2], a: bytes[3] },
+        PixelFormat::BGR8 => Color { r: bytes[2], g: bytes[1], b: bytes[0], a: 255 },
+        PixelFormat::BGRA8 => Color { r: bytes[2], g: bytes[1], b: bytes[0], a: bytes[3] },
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -320,7 +358,7 @@ mod tests {
     fn test_frame_buffer_creation() {
         let fb = FrameBuffer::new(640, 480, PixelFormat::RGBA8).unwrap();
         assert_eq!(fb.dimensions(), (640, 480));
-        assert_eq!(fb.data.len(), 640 * 480 * 4);
+        assert_eq!(fb.back.len(), 640 * 480 * 4);
     }
     
     #[test]
@@ -359,4 +397,48 @@ mod tests {
         let pixel = fb.get_pixel(20, 20).unwrap();
         assert_eq!(pixel.r, 255);
     }
-}
\ No newline at end of file
+    
+    #[test]
+    fn test_export_jpeg_decodes_with_frame_dimensions() {
+        use image::ImageDecoder;
+        
+        let mut fb = FrameBuffer::new(64, 48, PixelFormat::RGBA8).unwrap();
+        fb.clear(Color { r: 0, g: 0, b: 0, a: 0 }).unwrap();
+        fb.fill_rectangle(0, 0, 32, 48, Color::WHITE).unwrap();
+        fb.swap();
+        
+        // Quality 0 is clamped rather than rejected
+        for quality in [0, 50, 100] {
+            let jpeg = fb.export_jpeg(quality, Color::BLUE).unwrap();
+            let decoder = image::codecs::jpeg::JpegDecoder::new(std::io::Cursor::new(&jpeg)).unwrap();
+            assert_eq!(decoder.dimensions(), (64, 48));
+        }
+        
+        // Transparent pixels come out as the background colour
+        let jpeg = fb.export_jpeg(90, Color::BLUE).unwrap();
+        let decoded = image::load_from_memory(&jpeg).unwrap().to_rgb8();
+        let opaque = decoded.get_pixel(8, 24).0;
+        let transparent = decoded.get_pixel(56, 24).0;
+        assert!(opaque.iter().all(|&c| c > 240), "{:?}", opaque);
+        assert!(transparent[0] < 16 && transparent[1] < 16 && transparent[2] > 240, "{:?}", transparent);
+    }
+    
+    #[test]
+    fn test_exports_read_front_buffer_until_swap() {
+        let mut fb = FrameBuffer::new(4, 4, PixelFormat::RGBA8).unwrap();
+        fb.clear(Color::RED).unwrap();
+        
+        // The in-progress frame is invisible to readers
+        assert!(fb.get_raw_data().iter().all(|&b| b == 0));
+        assert_eq!(fb.frames_swapped(), 0);
+        
+        fb.swap();
+        assert_eq!(&fb.get_raw_data()[..4], &[255, 0, 0, 255]);
+        assert_eq!(fb.frames_swapped(), 1);
+        
+        // Drawing the next frame starts from the presented one
+        assert_eq!(fb.get_pixel(2, 2).unwrap(), Color::RED);
+        fb.set_pixel(0, 0, Color::GREEN).unwrap();
+        assert_eq!(&fb.get_raw_data()[..4], &[255, 0, 0, 255]);
+    }
+}
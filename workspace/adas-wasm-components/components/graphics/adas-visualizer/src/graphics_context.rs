@@ -44,7 +44,18 @@ pub struct GraphicsContext {
 
 impl GraphicsContext {
     /// Create new graphics context
+    ///
+    /// Fails when the requested surface cannot be created, e.g. zero-sized
+    /// or larger than the surface supports.
     pub fn new(width: u32, height: u32) -> Result<Self, String> {
+        let (max_width, max_height) = SurfaceCapabilities::get_max_dimensions();
+        if width == 0 || height == 0 || width > max_width || height > max_height {
+            return Err(format!(
+                "Unsupported surface size {}x{} (max {}x{})",
+                width, height, max_width, max_height
+            ));
+        }
+        
         let surface_config = SurfaceConfig {
             width,
             height,
@@ -350,6 +361,12 @@ mod tests {
         assert_eq!(context.frames_presented, 0);
     }
     
+    #[test]
+    fn test_unsupported_surface_rejected() {
+        assert!(GraphicsContext::new(0, 480).is_err());
+        assert!(GraphicsContext::new(8192, 480).is_err());
+    }
+    
     #[test]
     fn test_surface_clearing() {
         let mut context = GraphicsContext::new(100, 100).unwrap();
@@ -7,30 +7,41 @@ wit_bindgen::generate!({
     generate_all,
 });
 
-use std::time::{SystemTime, UNIX_EPOCH, Instant};
+use std::cell::RefCell;
+use std::time::Instant;
 use std::collections::HashMap;
 
+mod color;
+mod font;
 mod frame_buffer;
 mod overlay_renderer;
 mod graphics_context;
+mod scaling;
 
+use color::Color;
+use font::FontId;
 use frame_buffer::{FrameBuffer, PixelFormat};
-use overlay_renderer::{OverlayRenderer, BoundingBox, TextLabel};
+use overlay_renderer::{OverlayRenderer, BoundingBox, TextLabel, passes_display_gate, threat_level};
 use graphics_context::{GraphicsContext, RenderTarget};
+use scaling::ScalingMode;
 
 struct Component;
 
-// Graphics state
-static mut RENDERER_INITIALIZED: bool = false;
-static mut RENDERING_ACTIVE: bool = false;
-static mut FRAMES_RENDERED: u64 = 0;
-static mut OVERLAY_OBJECTS: u32 = 0;
-static mut TOTAL_RENDER_TIME_MS: f64 = 0.0;
+/// Component-wide rendering state shared by the renderer resource and the
+/// diagnostics and control interfaces
+#[derive(Debug, Default)]
+struct VisualizerState {
+    renderer_initialized: bool,
+    rendering_active: bool,
+    rendering_headless: bool,
+    frames_rendered: u64,
+    overlay_objects: u32,
+    total_render_time_ms: f64,
+}
 
-// Current configuration
-static mut CURRENT_CONFIG: Option<GraphicsConfig> = None;
-static mut FRAME_BUFFER: Option<FrameBuffer> = None;
-static mut OVERLAY_RENDERER: Option<OverlayRenderer> = None;
+thread_local! {
+    static STATE: RefCell<VisualizerState> = RefCell::new(VisualizerState::default());
+}
 
 /// Graphics configuration
 #[derive(Debug, Clone)]
@@ -41,6 +52,15 @@ struct GraphicsConfig {
     show_fps: bool,
     show_metrics: bool,
     overlay_style: OverlayStyle,
+    /// HMI display gate, separate from the detector threshold
+    min_display_confidence: f32,
+    /// Resampling used for video frames unless overridden per frame
+    scaling: ScalingMode,
+    /// Backdrop for translucent pixels in formats without alpha
+    export_background: Color,
+    /// Overlay text magnification, e.g. 3.0 on a 1920x1080 surface
+    font_scale: f32,
+    font: FontId,
 }
 
 impl Default for GraphicsConfig {
@@ -52,6 +72,11 @@ impl Default for GraphicsConfig {
             show_fps: true,
             show_metrics: true,
             overlay_style: OverlayStyle::Detailed,
+            min_display_confidence: 0.6,
+            scaling: ScalingMode::NearestNeighbor,
+            export_background: Color::BLACK,
+            font_scale: 1.0,
+            font: FontId::Standard,
         }
     }
 }
@@ -62,34 +87,39 @@ enum OverlayStyle {
     Minimal,
     Detailed,
     Debug,
+    HeatMap,
 }
 
-/// RGBA Color
-#[derive(Debug, Clone, Copy)]
-struct Color {
-    r: u8,
-    g: u8,
-    b: u8,
-    a: u8,
-}
+/// Peak opacity of the threat tint around a maximally close pedestrian
+const HEAT_MAP_MAX_ALPHA: f32 = 160.0;
 
-impl Color {
-    const WHITE: Color = Color { r: 255, g: 255, b: 255, a: 255 };
-    const BLACK: Color = Color { r: 0, g: 0, b: 0, a: 255 };
-    const RED: Color = Color { r: 255, g: 0, b: 0, a: 255 };
-    const GREEN: Color = Color { r: 0, g: 255, b: 0, a: 255 };
-    const BLUE: Color = Color { r: 0, g: 0, b: 255, a: 255 };
-    const YELLOW: Color = Color { r: 255, g: 255, b: 0, a: 255 };
-    const CYAN: Color = Color { r: 0, g: 255, b: 255, a: 255 };
-    const MAGENTA: Color = Color { r: 255, g: 0, g: 255, a: 255 };
-}
+/// Gap (px) between the FPS and metrics readouts and the frame edge
+const OVERLAY_MARGIN: u32 = 10;
 
 /// Get timestamp in milliseconds
 fn get_timestamp() -> u64 {
-    SystemTime::now()
-        .duration_since(UNIX_EPOCH)
-        .unwrap()
-        .as_millis() as u64
+    adas_timing::monotonic_now_ms()
+}
+
+/// Map the WIT scaling mode onto the local one
+fn to_scaling_mode(mode: exports::adas::graphics::graphics_visualizer::ScalingMode) -> ScalingMode {
+    match mode {
+        exports::adas::graphics::graphics_visualizer::ScalingMode::NearestNeighbor => ScalingMode::NearestNeighbor,
+        exports::adas::graphics::graphics_visualizer::ScalingMode::Bilinear => ScalingMode::Bilinear,
+    }
+}
+
+/// Map the WIT font onto the local one
+fn to_font_id(font: exports::adas::graphics::graphics_visualizer::FontId) -> FontId {
+    match font {
+        exports::adas::graphics::graphics_visualizer::FontId::Standard => FontId::Standard,
+        exports::adas::graphics::graphics_visualizer::FontId::Block => FontId::Block,
+    }
+}
+
+/// Map a WIT color onto the local one
+fn to_color(color: exports::adas::graphics::graphics_visualizer::Color) -> Color {
+    Color { r: color.r, g: color.g, b: color.b, a: color.a }
 }
 
 /// Get color for object class
@@ -115,9 +145,20 @@ struct GraphicsRenderer {
     config: GraphicsConfig,
     frame_buffer: FrameBuffer,
     overlay_renderer: OverlayRenderer,
-    graphics_context: GraphicsContext,
+    /// Presentation surface; `None` when rendering headless
+    graphics_context: Option<GraphicsContext>,
     render_stats: RenderStats,
     last_frame_time: Option<Instant>,
+    capture: Option<FrameCapture>,
+    /// Canonical track ID per detection ID, as reported by the tracker
+    track_ids: HashMap<u32, u32>,
+}
+
+/// Active annotated-sequence capture
+#[derive(Debug)]
+struct FrameCapture {
+    output_dir: std::path::PathBuf,
+    next_frame: u64,
 }
 
 /// Render statistics
@@ -128,6 +169,8 @@ struct RenderStats {
     overlay_objects: u32,
     frame_rate: f32,
     memory_usage_mb: u32,
+    frames_captured: u64,
+    objects_filtered: u64,
 }
 
 impl exports::adas::graphics::graphics_visualizer::GuestGraphicsRenderer for GraphicsRenderer {
@@ -146,7 +189,13 @@ impl exports::adas::graphics::graphics_visualizer::GuestGraphicsRenderer for Gra
                 exports::adas::graphics::graphics_visualizer::OverlayStyle::Minimal => OverlayStyle::Minimal,
                 exports::adas::graphics::graphics_visualizer::OverlayStyle::Detailed => OverlayStyle::Detailed,
                 exports::adas::graphics::graphics_visualizer::OverlayStyle::Debug => OverlayStyle::Debug,
+                exports::adas::graphics::graphics_visualizer::OverlayStyle::HeatMap => OverlayStyle::HeatMap,
             },
+            min_display_confidence: config.min_display_confidence,
+            scaling: to_scaling_mode(config.scaling),
+            export_background: to_color(config.export_background),
+            font_scale: config.font_scale,
+            font: to_font_id(config.font),
         };
         
         // Initialize frame buffer
@@ -157,20 +206,28 @@ impl exports::adas::graphics::graphics_visualizer::GuestGraphicsRenderer for Gra
         ).expect("Failed to create frame buffer");
         
         // Initialize overlay renderer
-        let overlay_renderer = OverlayRenderer::new(
+        let mut overlay_renderer = OverlayRenderer::new(
             graphics_config.width,
             graphics_config.height,
         );
+        overlay_renderer.set_text_style(graphics_config.font, graphics_config.font_scale);
+        
+        // Initialize graphics context (would use wasi-gfx when available).
+        // Without a surface (headless CI, no wasi-gfx) keep compositing into
+        // the frame buffer so frames can still be exported.
+        let graphics_context = match GraphicsContext::new(graphics_config.width, graphics_config.height) {
+            Ok(context) => Some(context),
+            Err(e) => {
+                println!("⚠️  Graphics context unavailable ({}), rendering headless", e);
+                None
+            }
+        };
         
-        // Initialize graphics context (would use wasi-gfx when available)
-        let graphics_context = GraphicsContext::new(
-            graphics_config.width,
-            graphics_config.height,
-        ).expect("Failed to create graphics context");
-        
-        unsafe {
-            RENDERER_INITIALIZED = true;
-        }
+        STATE.with(|state| {
+            let mut s = state.borrow_mut();
+            s.renderer_initialized = true;
+            s.rendering_headless = graphics_context.is_none();
+        });
         
         println!("✅ Graphics Visualizer initialized successfully");
         
@@ -181,40 +238,21 @@ impl exports::adas::graphics::graphics_visualizer::GuestGraphicsRenderer for Gra
             graphics_context,
             render_stats: RenderStats::default(),
             last_frame_time: None,
+            capture: None,
+            track_ids: HashMap::new(),
         }
     }
     
     fn render_video_frame(&mut self, frame: exports::adas::data::data_flow::VideoFrame) -> Result<(), String> {
-        let start_time = Instant::now();
-        
-        // Clear frame buffer
-        self.frame_buffer.clear(Color::BLACK)?;
-        
-        // Scale and render video frame
-        let scaled_frame = self.scale_video_frame(&frame)?;
-        self.frame_buffer.draw_image(&scaled_frame)?;
-        
-        // Update render stats
-        self.render_stats.frames_rendered += 1;
-        
-        let render_time = start_time.elapsed().as_millis() as f32;
-        self.render_stats.render_time_ms = render_time;
-        
-        // Calculate FPS
-        if let Some(last_time) = self.last_frame_time {
-            let time_diff = start_time.duration_since(last_time).as_secs_f32();
-            if time_diff > 0.0 {
-                self.render_stats.frame_rate = 1.0 / time_diff;
-            }
-        }
-        self.last_frame_time = Some(start_time);
-        
-        unsafe {
-            FRAMES_RENDERED = self.render_stats.frames_rendered;
-            TOTAL_RENDER_TIME_MS += render_time as f64;
-        }
-        
-        Ok(())
+        self.render_scaled(&frame, self.config.scaling)
+    }
+    
+    fn render_video_frame_scaled(
+        &mut self,
+        frame: exports::adas::data::data_flow::VideoFrame,
+        scaling: exports::adas::graphics::graphics_visualizer::ScalingMode,
+    ) -> Result<(), String> {
+        self.render_scaled(&frame, to_scaling_mode(scaling))
     }
     
     fn render_detection_overlay(&mut self, detections: exports::adas::data::data_flow::DetectionResult) -> Result<(), String> {
@@ -223,9 +261,22 @@ impl exports::adas::graphics::graphics_visualizer::GuestGraphicsRenderer for Gra
         // Reset overlay count
         self.render_stats.overlay_objects = 0;
         
-        // Render each detected object
+        // Tint close pedestrians first so every box and label stays on top
+        if matches!(self.config.overlay_style, OverlayStyle::Debug | OverlayStyle::HeatMap) {
+            self.render_threat_heat_map(&detections)?;
+        }
+        
+        // Render each detected object the driver should see
         for object in &detections.objects {
+            if !passes_display_gate(object.confidence, self.config.min_display_confidence) {
+                self.render_stats.objects_filtered += 1;
+                continue;
+            }
+            
             let color = get_object_color(&object.class_name);
+            let track_id = self.track_ids.get(&object.object_id).copied();
+            // Keep labels clear of the box top at any text scale
+            let label_offset = 20.0 * self.config.font_scale;
             
             // Scale bounding box to display resolution
             let scaled_box = BoundingBox {
@@ -245,35 +296,44 @@ impl exports::adas::graphics::graphics_visualizer::GuestGraphicsRenderer for Gra
                     let label = TextLabel {
                         text: object.class_name.clone(),
                         x: scaled_box.x,
-                        y: scaled_box.y - 20.0,
+                        y: scaled_box.y - label_offset,
                         color,
+                        size: None,
                     };
                     self.overlay_renderer.draw_text_label(&label)?;
                 }
-                OverlayStyle::Detailed => {
-                    // Show class name and confidence
-                    let label_text = format!("{}: {:.1}%", 
+                OverlayStyle::Detailed | OverlayStyle::HeatMap => {
+                    // Show class name and confidence, prefixed by the track when known
+                    let track_prefix = track_id.map(|track| format!("T{} ", track)).unwrap_or_default();
+                    let label_text = format!("{}{}: {:.1}%", 
+                                           track_prefix,
                                            object.class_name, 
                                            object.confidence * 100.0);
                     let label = TextLabel {
                         text: label_text,
                         x: scaled_box.x,
-                        y: scaled_box.y - 20.0,
+                        y: scaled_box.y - label_offset,
                         color,
+                        size: None,
                     };
                     self.overlay_renderer.draw_text_label(&label)?;
                 }
                 OverlayStyle::Debug => {
-                    // Show all details including ID
-                    let label_text = format!("#{}: {} ({:.1}%)", 
-                                           object.object_id,
+                    // Show all details including detection and track IDs
+                    let id_text = match track_id {
+                        Some(track) => format!("#{} → T{}", object.object_id, track),
+                        None => format!("#{}", object.object_id),
+                    };
+                    let label_text = format!("{}: {} ({:.1}%)", 
+                                           id_text,
                                            object.class_name, 
                                            object.confidence * 100.0);
                     let label = TextLabel {
                         text: label_text,
                         x: scaled_box.x,
-                        y: scaled_box.y - 20.0,
+                        y: scaled_box.y - label_offset,
                         color,
+                        size: None,
                     };
                     self.overlay_renderer.draw_text_label(&label)?;
                     
@@ -300,13 +360,42 @@ impl exports::adas::graphics::graphics_visualizer::GuestGraphicsRenderer for Gra
         let overlay_time = start_time.elapsed().as_millis() as f32;
         self.render_stats.render_time_ms += overlay_time;
         
-        unsafe {
-            OVERLAY_OBJECTS = self.render_stats.overlay_objects;
+        STATE.with(|state| state.borrow_mut().overlay_objects = self.render_stats.overlay_objects);
+        
+        Ok(())
+    }
+    
+    fn render_trajectory_overlay(&mut self, trajectories: Vec<exports::adas::graphics::graphics_visualizer::PredictedTrajectory>) -> Result<(), String> {
+        for trajectory in &trajectories {
+            let color = get_object_color(&trajectory.class_name);
+            
+            // Scale path to display resolution
+            let points: Vec<(f32, f32)> = trajectory.points
+                .iter()
+                .map(|p| (p.x * self.config.scale_factor, p.y * self.config.scale_factor))
+                .collect();
+            
+            match points.as_slice() {
+                [] => continue,
+                [point] => self.overlay_renderer.draw_point(point.0, point.1, color)?,
+                [.., before_last, last] => {
+                    self.overlay_renderer.draw_polyline(&points, color, 2.0)?;
+                    // Head on the final segment shows the direction of travel
+                    self.overlay_renderer.draw_arrow(*before_last, *last, color)?;
+                }
+            }
         }
         
         Ok(())
     }
     
+    fn update_track_associations(&mut self, associations: Vec<exports::adas::data::data_flow::TrackAssociation>) -> Result<(), String> {
+        self.track_ids = associations.into_iter()
+            .map(|association| (association.source_detection_id, association.track_id))
+            .collect();
+        Ok(())
+    }
+    
     fn draw_rectangle(&mut self, rect: exports::adas::graphics::graphics_visualizer::Rectangle, color: exports::adas::graphics::graphics_visualizer::Color, filled: bool) -> Result<(), String> {
         let internal_color = Color {
             r: color.r,
@@ -338,6 +427,7 @@ impl exports::adas::graphics::graphics_visualizer::GuestGraphicsRenderer for Gra
             x: position.x,
             y: position.y,
             color: internal_color,
+            size: None,
         };
         
         self.overlay_renderer.draw_text_label(&label)
@@ -354,19 +444,49 @@ impl exports::adas::graphics::graphics_visualizer::GuestGraphicsRenderer for Gra
         self.overlay_renderer.draw_line(start.x, start.y, end.x, end.y, internal_color, thickness)
     }
     
+    fn draw_polyline(&mut self, points: Vec<exports::adas::graphics::graphics_visualizer::Point2d>, color: exports::adas::graphics::graphics_visualizer::Color, thickness: f32) -> Result<(), String> {
+        let internal_color = Color {
+            r: color.r,
+            g: color.g,
+            b: color.b,
+            a: color.a,
+        };
+        let points: Vec<(f32, f32)> = points.iter().map(|p| (p.x, p.y)).collect();
+        
+        self.overlay_renderer.draw_polyline(&points, internal_color, thickness)
+    }
+    
+    fn draw_arrow(&mut self, start: exports::adas::graphics::graphics_visualizer::Point2d, end: exports::adas::graphics::graphics_visualizer::Point2d, color: exports::adas::graphics::graphics_visualizer::Color) -> Result<(), String> {
+        let internal_color = Color {
+            r: color.r,
+            g: color.g,
+            b: color.b,
+            a: color.a,
+        };
+        
+        self.overlay_renderer.draw_arrow((start.x, start.y), (end.x, end.y), internal_color)
+    }
+    
     fn present_frame(&mut self) -> Result<(), String> {
         // Copy overlay to frame buffer
         self.frame_buffer.composite_overlay(&self.overlay_renderer)?;
         
-        // Present to graphics context (would use wasi-gfx surface)
-        self.graphics_context.present(&self.frame_buffer)?;
+        // Publish the finished frame; presentation and exports read the front buffer
+        self.frame_buffer.swap();
+        
+        // Present to graphics context (would use wasi-gfx surface); headless
+        // frames stay in the frame buffer for export
+        if let Some(graphics_context) = self.graphics_context.as_mut() {
+            graphics_context.present(&self.frame_buffer)?;
+        }
         
         // Clear overlay for next frame
         self.overlay_renderer.clear();
         
-        unsafe {
-            RENDERING_ACTIVE = true;
-        }
+        // Write the annotated frame if a sequence capture is running
+        self.capture_frame()?;
+        
+        STATE.with(|state| state.borrow_mut().rendering_active = true);
         
         Ok(())
     }
@@ -389,11 +509,46 @@ impl exports::adas::graphics::graphics_visualizer::GuestGraphicsRenderer for Gra
         self.frame_buffer.export_png()
     }
     
+    fn export_frame_jpeg(&mut self, quality: u8) -> Result<Vec<u8>, String> {
+        // Alpha is flattened onto the configured background
+        self.frame_buffer.export_jpeg(quality, self.config.export_background)
+    }
+    
     fn export_frame_raw(&mut self) -> Result<Vec<u8>, String> {
         // Export raw RGBA data
         Ok(self.frame_buffer.get_raw_data().to_vec())
     }
     
+    fn start_capture(&mut self, output_dir: String) -> Result<(), String> {
+        if self.capture.is_some() {
+            return Err("Frame capture already active".to_string());
+        }
+        
+        let output_dir = std::path::PathBuf::from(output_dir);
+        std::fs::create_dir_all(&output_dir)
+            .map_err(|e| format!("Failed to create capture directory {}: {}", output_dir.display(), e))?;
+        
+        println!("🎬 Capturing annotated frames to {}", output_dir.display());
+        
+        self.render_stats.frames_captured = 0;
+        self.capture = Some(FrameCapture {
+            output_dir,
+            next_frame: 0,
+        });
+        
+        Ok(())
+    }
+    
+    fn stop_capture(&mut self) -> Result<u64, String> {
+        let capture = self.capture.take()
+            .ok_or_else(|| "Frame capture not active".to_string())?;
+        
+        println!("🎬 Frame capture stopped: {} frames written to {}",
+                 capture.next_frame, capture.output_dir.display());
+        
+        Ok(capture.next_frame)
+    }
+    
     fn update_config(&mut self, config: exports::adas::graphics::graphics_visualizer::GraphicsConfig) -> Result<(), String> {
         println!("🎨 Updating graphics configuration");
         
@@ -403,7 +558,14 @@ impl exports::adas::graphics::graphics_visualizer::GuestGraphicsRenderer for Gra
             exports::adas::graphics::graphics_visualizer::OverlayStyle::Minimal => OverlayStyle::Minimal,
             exports::adas::graphics::graphics_visualizer::OverlayStyle::Detailed => OverlayStyle::Detailed,
             exports::adas::graphics::graphics_visualizer::OverlayStyle::Debug => OverlayStyle::Debug,
+            exports::adas::graphics::graphics_visualizer::OverlayStyle::HeatMap => OverlayStyle::HeatMap,
         };
+        self.config.min_display_confidence = config.min_display_confidence;
+        self.config.scaling = to_scaling_mode(config.scaling);
+        self.config.export_background = to_color(config.export_background);
+        self.config.font_scale = config.font_scale;
+        self.config.font = to_font_id(config.font);
+        self.overlay_renderer.set_text_style(self.config.font, self.config.font_scale);
         
         Ok(())
     }
@@ -415,55 +577,133 @@ impl exports::adas::graphics::graphics_visualizer::GuestGraphicsRenderer for Gra
             overlay_objects: self.render_stats.overlay_objects,
             frame_rate: self.render_stats.frame_rate,
             memory_usage_mb: self.render_stats.memory_usage_mb,
+            frames_captured: self.render_stats.frames_captured,
+            objects_filtered: self.render_stats.objects_filtered,
+            frames_swapped: self.frame_buffer.frames_swapped(),
+            render_mode: if self.graphics_context.is_some() {
+                exports::adas::graphics::graphics_visualizer::RenderMode::Surface
+            } else {
+                exports::adas::graphics::graphics_visualizer::RenderMode::Headless
+            },
         }
     }
     
     fn cleanup(&mut self) -> Result<(), String> {
         println!("🎨 Cleaning up graphics visualizer");
         
-        self.graphics_context.cleanup()?;
+        if let Some(graphics_context) = self.graphics_context.as_mut() {
+            graphics_context.cleanup()?;
+        }
         self.overlay_renderer.cleanup();
         
-        unsafe {
-            RENDERER_INITIALIZED = false;
-            RENDERING_ACTIVE = false;
-        }
+        STATE.with(|state| {
+            let mut s = state.borrow_mut();
+            s.renderer_initialized = false;
+            s.rendering_active = false;
+        });
         
         Ok(())
     }
 }
 
 impl GraphicsRenderer {
-    /// Scale video frame to display resolution
-    fn scale_video_frame(&self, frame: &exports::adas::data::data_flow::VideoFrame) -> Result<Vec<u8>, String> {
-        // Simple nearest-neighbor scaling
-        let src_width = frame.width as usize;
-        let src_height = frame.height as usize;
-        let dst_width = self.config.width as usize;
-        let dst_height = self.config.height as usize;
-        
-        let mut scaled_data = vec![0u8; dst_width * dst_height * 4]; // RGBA
-        
-        for y in 0..dst_height {
-            for x in 0..dst_width {
-                let src_x = (x * src_width) / dst_width;
-                let src_y = (y * src_height) / dst_height;
-                
-                if src_x < src_width && src_y < src_height {
-                    let src_idx = (src_y * src_width + src_x) * 3; // RGB source
-                    let dst_idx = (y * dst_width + x) * 4; // RGBA destination
-                    
-                    if src_idx + 2 < frame.data.len() && dst_idx + 3 < scaled_data.len() {
-                        scaled_data[dst_idx] = frame.data[src_idx];     // R
-                        scaled_data[dst_idx + 1] = frame.data[src_idx + 1]; // G
-                        scaled_data[dst_idx + 2] = frame.data[src_idx + 2]; // B
-                        scaled_data[dst_idx + 3] = 255; // A
-                    }
-                }
+    /// Scale a video frame into the cleared frame buffer and update the render stats
+    fn render_scaled(&mut self, frame: &exports::adas::data::data_flow::VideoFrame, scaling: ScalingMode) -> Result<(), String> {
+        let start_time = Instant::now();
+        
+        // Clear frame buffer
+        self.frame_buffer.clear(Color::BLACK)?;
+        
+        // Scale and render video frame
+        let scaled_frame = self.scale_video_frame(frame, scaling)?;
+        self.frame_buffer.draw_image(&scaled_frame)?;
+        
+        // Update render stats
+        self.render_stats.frames_rendered += 1;
+        
+        let render_time = start_time.elapsed().as_millis() as f32;
+        self.render_stats.render_time_ms = render_time;
+        
+        // Calculate FPS
+        if let Some(last_time) = self.last_frame_time {
+            let time_diff = start_time.duration_since(last_time).as_secs_f32();
+            if time_diff > 0.0 {
+                self.render_stats.frame_rate = 1.0 / time_diff;
+            }
+        }
+        self.last_frame_time = Some(start_time);
+        
+        STATE.with(|state| {
+            let mut s = state.borrow_mut();
+            s.frames_rendered = self.render_stats.frames_rendered;
+            s.total_render_time_ms += render_time as f64;
+        });
+        
+        Ok(())
+    }
+    
+    /// Radial red tint around pedestrians, stronger the closer they appear
+    fn render_threat_heat_map(&mut self, detections: &exports::adas::data::data_flow::DetectionResult) -> Result<(), String> {
+        for object in &detections.objects {
+            if !matches!(object.class_name.as_str(), "person" | "pedestrian")
+                || !passes_display_gate(object.confidence, self.config.min_display_confidence)
+            {
+                continue;
+            }
+            
+            let scaled_box = BoundingBox {
+                x: object.bounding_box.x * self.config.scale_factor,
+                y: object.bounding_box.y * self.config.scale_factor,
+                width: object.bounding_box.width * self.config.scale_factor,
+                height: object.bounding_box.height * self.config.scale_factor,
+            };
+            let threat = threat_level(&scaled_box, self.config.height);
+            if threat <= 0.0 {
+                continue;
             }
+            
+            let center = (scaled_box.x + scaled_box.width / 2.0, scaled_box.y + scaled_box.height / 2.0);
+            let radius = scaled_box.width.hypot(scaled_box.height) * 0.75;
+            let inner = Color { a: (HEAT_MAP_MAX_ALPHA * threat) as u8, ..Color::RED };
+            let outer = Color { a: 0, ..Color::RED };
+            self.overlay_renderer.draw_radial_gradient(center, radius, inner, outer)?;
         }
         
-        Ok(scaled_data)
+        Ok(())
+    }
+    
+    /// Scale video frame to display resolution
+    fn scale_video_frame(&self, frame: &exports::adas::data::data_flow::VideoFrame, mode: ScalingMode) -> Result<Vec<u8>, String> {
+        use exports::adas::data::data_flow::PixelFormat as FramePixelFormat;
+        
+        // Normalize whatever the producer emitted to RGB24
+        let format = match frame.format {
+            FramePixelFormat::Rgb24 => adas_pixel_format::PixelFormat::Rgb24,
+            FramePixelFormat::Bgr24 => adas_pixel_format::PixelFormat::Bgr24,
+            FramePixelFormat::Yuv420p => adas_pixel_format::PixelFormat::Yuv420p,
+            FramePixelFormat::Nv12 => adas_pixel_format::PixelFormat::Nv12,
+            FramePixelFormat::Gray8 => adas_pixel_format::PixelFormat::Gray8,
+        };
+        let rgb = adas_pixel_format::to_rgb24(&frame.data, frame.width, frame.height, format)?;
+        
+        scaling::scale_rgb_to_rgba(&rgb, frame.width, frame.height, self.config.width, self.config.height, mode)
+    }
+    
+    /// Write the presented frame to the capture directory as a numbered PNG
+    fn capture_frame(&mut self) -> Result<(), String> {
+        let Some(capture) = self.capture.as_mut() else {
+            return Ok(());
+        };
+        
+        let png = self.frame_buffer.export_png()?;
+        let path = capture.output_dir.join(format!("frame_{:06}.png", capture.next_frame));
+        std::fs::write(&path, png)
+            .map_err(|e| format!("Failed to write captured frame {}: {}", path.display(), e))?;
+        
+        capture.next_frame += 1;
+        self.render_stats.frames_captured = capture.next_frame;
+        
+        Ok(())
     }
     
     /// Render performance metrics overlay
@@ -475,12 +715,17 @@ impl GraphicsRenderer {
             self.render_stats.memory_usage_mb
         );
         
-        let label = TextLabel {
+        let mut label = TextLabel {
             text: metrics_text,
-            x: 10.0,
-            y: self.config.height as f32 - 40.0,
+            x: 0.0,
+            y: 0.0,
             color: Color::YELLOW,
+            size: None,
         };
+        // Bottom-right corner
+        let (width, height) = self.overlay_renderer.measure_label(&label);
+        label.x = self.config.width.saturating_sub(width + OVERLAY_MARGIN) as f32;
+        label.y = self.config.height.saturating_sub(height + OVERLAY_MARGIN) as f32;
         
         self.overlay_renderer.draw_text_label(&label)
     }
@@ -489,12 +734,16 @@ impl GraphicsRenderer {
     fn render_fps_overlay(&mut self) -> Result<(), String> {
         let fps_text = format!("FPS: {:.1}", self.render_stats.frame_rate);
         
-        let label = TextLabel {
+        let mut label = TextLabel {
             text: fps_text,
-            x: 10.0,
-            y: 30.0,
+            x: 0.0,
+            y: OVERLAY_MARGIN as f32,
             color: Color::GREEN,
+            size: None,
         };
+        // Top-right corner
+        let (width, _) = self.overlay_renderer.measure_label(&label);
+        label.x = self.config.width.saturating_sub(width + OVERLAY_MARGIN) as f32;
         
         self.overlay_renderer.draw_text_label(&label)
     }
@@ -503,14 +752,17 @@ impl GraphicsRenderer {
 // Implement health monitoring interface
 impl exports::adas::diagnostics::health_monitoring::Guest for Component {
     fn get_health() -> exports::adas::diagnostics::health_monitoring::HealthReport {
-        let overall_health = unsafe {
-            if RENDERER_INITIALIZED && RENDERING_ACTIVE {
-                adas::common_types::types::HealthStatus::Ok
-            } else if RENDERER_INITIALIZED {
-                adas::common_types::types::HealthStatus::Degraded
-            } else {
-                adas::common_types::types::HealthStatus::Offline
-            }
+        let (initialized, active, headless) = STATE.with(|state| {
+            let s = state.borrow();
+            (s.renderer_initialized, s.rendering_active, s.rendering_headless)
+        });
+        
+        let overall_health = if initialized && active {
+            adas::common_types::types::HealthStatus::Ok
+        } else if initialized {
+            adas::common_types::types::HealthStatus::Degraded
+        } else {
+            adas::common_types::types::HealthStatus::Offline
         };
         
         exports::adas::diagnostics::health_monitoring::HealthReport {
@@ -519,7 +771,7 @@ impl exports::adas::diagnostics::health_monitoring::Guest for Component {
             subsystem_health: vec![
                 exports::adas::diagnostics::health_monitoring::SubsystemHealth {
                     subsystem_name: "frame-buffer".to_string(),
-                    status: if unsafe { RENDERER_INITIALIZED } {
+                    status: if initialized {
                         adas::common_types::types::HealthStatus::Ok
                     } else {
                         adas::common_types::types::HealthStatus::Offline
@@ -528,13 +780,28 @@ impl exports::adas::diagnostics::health_monitoring::Guest for Component {
                 },
                 exports::adas::diagnostics::health_monitoring::SubsystemHealth {
                     subsystem_name: "overlay-renderer".to_string(),
-                    status: if unsafe { RENDERING_ACTIVE } {
+                    status: if active {
                         adas::common_types::types::HealthStatus::Ok
                     } else {
                         adas::common_types::types::HealthStatus::Offline
                     },
                     details: "Overlay rendering for object detection".to_string(),
                 },
+                exports::adas::diagnostics::health_monitoring::SubsystemHealth {
+                    subsystem_name: "presentation-surface".to_string(),
+                    status: if !initialized {
+                        adas::common_types::types::HealthStatus::Offline
+                    } else if headless {
+                        adas::common_types::types::HealthStatus::Degraded
+                    } else {
+                        adas::common_types::types::HealthStatus::Ok
+                    },
+                    details: if headless {
+                        "Headless: no graphics surface, frames kept in memory for export".to_string()
+                    } else {
+                        "Frames presented to graphics surface".to_string()
+                    },
+                },
             ],
             last_diagnostic: None,
             timestamp: get_timestamp(),
@@ -544,11 +811,15 @@ impl exports::adas::diagnostics::health_monitoring::Guest for Component {
     fn run_diagnostic() -> Result<exports::adas::diagnostics::health_monitoring::DiagnosticResult, String> {
         let mut test_results = Vec::new();
         let mut overall_score = 100.0;
+        let (initialized, frames_rendered, overlay_objects) = STATE.with(|state| {
+            let s = state.borrow();
+            (s.renderer_initialized, s.frames_rendered, s.overlay_objects)
+        });
         
         // Test renderer initialization
         test_results.push(exports::adas::diagnostics::health_monitoring::TestExecution {
             test_name: "graphics-renderer-init".to_string(),
-            test_result: if unsafe { RENDERER_INITIALIZED } {
+            test_result: if initialized {
                 adas::common_types::types::TestResult::Passed
             } else {
                 overall_score -= 40.0;
@@ -561,26 +832,26 @@ impl exports::adas::diagnostics::health_monitoring::Guest for Component {
         // Test frame rendering
         test_results.push(exports::adas::diagnostics::health_monitoring::TestExecution {
             test_name: "frame-rendering".to_string(),
-            test_result: if unsafe { FRAMES_RENDERED > 0 } {
+            test_result: if frames_rendered > 0 {
                 adas::common_types::types::TestResult::Passed
             } else {
                 overall_score -= 30.0;
                 adas::common_types::types::TestResult::Warning
             },
-            details: format!("{} frames rendered", unsafe { FRAMES_RENDERED }),
+            details: format!("{} frames rendered", frames_rendered),
             execution_time_ms: 5.0,
         });
         
         // Test overlay rendering
         test_results.push(exports::adas::diagnostics::health_monitoring::TestExecution {
             test_name: "overlay-rendering".to_string(),
-            test_result: if unsafe { OVERLAY_OBJECTS > 0 } {
+            test_result: if overlay_objects > 0 {
                 adas::common_types::types::TestResult::Passed
             } else {
                 overall_score -= 20.0;
                 adas::common_types::types::TestResult::Warning
             },
-            details: format!("{} overlay objects rendered", unsafe { OVERLAY_OBJECTS }),
+            details: format!("{} overlay objects rendered", overlay_objects),
             execution_time_ms: 2.0,
         });
         
@@ -608,9 +879,10 @@ impl exports::adas::diagnostics::health_monitoring::Guest for Component {
 // Implement performance monitoring interface
 impl exports::adas::diagnostics::performance_monitoring::Guest for Component {
     fn get_performance() -> exports::adas::diagnostics::performance_monitoring::ExtendedPerformance {
-        unsafe {
-            let avg_render_time = if FRAMES_RENDERED > 0 {
-                TOTAL_RENDER_TIME_MS / FRAMES_RENDERED as f64
+        STATE.with(|state| {
+            let s = state.borrow();
+            let avg_render_time = if s.frames_rendered > 0 {
+                s.total_render_time_ms / s.frames_rendered as f64
             } else {
                 0.0
             };
@@ -627,13 +899,13 @@ impl exports::adas::diagnostics::performance_monitoring::Guest for Component {
                 component_specific: vec![
                     exports::adas::diagnostics::performance_monitoring::Metric {
                         name: "frames_rendered".to_string(),
-                        value: FRAMES_RENDERED as f64,
+                        value: s.frames_rendered as f64,
                         unit: "count".to_string(),
                         description: "Total frames rendered".to_string(),
                     },
                     exports::adas::diagnostics::performance_monitoring::Metric {
                         name: "overlay_objects".to_string(),
-                        value: OVERLAY_OBJECTS as f64,
+                        value: s.overlay_objects as f64,
                         unit: "count".to_string(),
                         description: "Objects in current overlay".to_string(),
                     },
@@ -655,7 +927,7 @@ impl exports::adas::diagnostics::performance_monitoring::Guest for Component {
                 },
                 timestamp: get_timestamp(),
             }
-        }
+        })
     }
     
     fn get_performance_history(_duration_seconds: u32) -> Vec<exports::adas::diagnostics::performance_monitoring::ExtendedPerformance> {
@@ -663,11 +935,12 @@ impl exports::adas::diagnostics::performance_monitoring::Guest for Component {
     }
     
     fn reset_counters() {
-        unsafe {
-            FRAMES_RENDERED = 0;
-            OVERLAY_OBJECTS = 0;
-            TOTAL_RENDER_TIME_MS = 0.0;
-        }
+        STATE.with(|state| {
+            let mut s = state.borrow_mut();
+            s.frames_rendered = 0;
+            s.overlay_objects = 0;
+            s.total_render_time_ms = 0.0;
+        });
         println!("Graphics Visualizer: Reset performance counters");
     }
 }
@@ -678,12 +951,13 @@ impl exports::adas::control::system_control::Guest for Component {
         println!("🎨 Initializing Graphics Visualizer System");
         println!("   Component ID: {}", config.component_id);
         
-        unsafe {
-            RENDERER_INITIALIZED = true;
-            RENDERING_ACTIVE = false;
-            FRAMES_RENDERED = 0;
-            OVERLAY_OBJECTS = 0;
-        }
+        STATE.with(|state| {
+            let mut s = state.borrow_mut();
+            s.renderer_initialized = true;
+            s.rendering_active = false;
+            s.frames_rendered = 0;
+            s.overlay_objects = 0;
+        });
         
         Ok(())
     }
@@ -691,32 +965,31 @@ impl exports::adas::control::system_control::Guest for Component {
     fn start_system() -> Result<(), String> {
         println!("🎨 Starting Graphics Visualizer");
         
-        unsafe {
-            if !RENDERER_INITIALIZED {
+        STATE.with(|state| {
+            let mut s = state.borrow_mut();
+            if !s.renderer_initialized {
                 return Err("Graphics renderer not initialized".to_string());
             }
-            RENDERING_ACTIVE = true;
-        }
-        
-        Ok(())
+            s.rendering_active = true;
+            Ok(())
+        })
     }
     
     fn stop_system() -> Result<(), String> {
         println!("🎨 Stopping Graphics Visualizer");
         
-        unsafe {
-            RENDERING_ACTIVE = false;
-        }
+        STATE.with(|state| state.borrow_mut().rendering_active = false);
         
         Ok(())
     }
     
     fn get_system_status() -> exports::adas::control::system_control::SystemStatus {
-        unsafe {
+        STATE.with(|state| {
+            let s = state.borrow();
             exports::adas::control::system_control::SystemStatus {
                 component_id: "adas-gfx-visualizer".to_string(),
-                is_initialized: RENDERER_INITIALIZED,
-                is_running: RENDERING_ACTIVE,
+                is_initialized: s.renderer_initialized,
+                is_running: s.rendering_active,
                 uptime_seconds: 0, // Would need start time tracking
                 resource_usage: exports::adas::control::system_control::ResourceUsage {
                     cpu_percentage: 25.0,
@@ -727,18 +1000,19 @@ impl exports::adas::control::system_control::Guest for Component {
                 last_error: None,
                 timestamp: get_timestamp(),
             }
-        }
+        })
     }
     
     fn shutdown_system() -> Result<(), String> {
         println!("🎨 Shutting down Graphics Visualizer");
         
-        unsafe {
-            RENDERING_ACTIVE = false;
-            RENDERER_INITIALIZED = false;
-            FRAMES_RENDERED = 0;
-            OVERLAY_OBJECTS = 0;
-        }
+        STATE.with(|state| {
+            let mut s = state.borrow_mut();
+            s.rendering_active = false;
+            s.renderer_initialized = false;
+            s.frames_rendered = 0;
+            s.overlay_objects = 0;
+        });
         
         Ok(())
     }
@@ -2,6 +2,7 @@
 // Handles drawing detection overlays on top of video frames
 
 use crate::Color;
+use crate::font::{scaled, BitmapFont, FontId};
 use std::collections::HashMap;
 
 /// Bounding box for object detection
@@ -20,131 +21,50 @@ pub struct TextLabel {
     pub x: f32,
     pub y: f32,
     pub color: Color,
+    /// Text scale for this label instead of the renderer's font scale
+    pub size: Option<f32>,
 }
 
-/// Simple bitmap font for text rendering
-struct BitmapFont {
-    char_width: u32,
-    char_height: u32,
-    font_data: HashMap<char, Vec<u8>>,
+/// Whether a detection is confident enough to show to the driver
+///
+/// The gate is clamped to [0, 1]; a NaN confidence never passes.
+pub fn passes_display_gate(confidence: f32, min_display_confidence: f32) -> bool {
+    confidence >= min_display_confidence.clamp(0.0, 1.0)
 }
 
-impl BitmapFont {
-    fn new() -> Self {
-        let mut font_data = HashMap::new();
-        
-        // Simple 8x8 bitmap font for basic characters
-        // Each character is represented as 8 bytes (8x8 pixels)
-        
-        // Letter 'A'
-        font_data.insert('A', vec![
-            0b00111000,
-            0b01101100,
-            0b11000110,
-            0b11000110,
-            0b11111110,
-            0b11000110,
-            0b11000110,
-            0b00000000,
-        ]);
-        
-        // Letter 'B'
-        font_data.insert('B', vec![
-            0b11111100,
-            0b11000110,
-            0b11000110,
-            0b11111100,
-            0b11000110,
-            0b11000110,
-            0b11111100,
-            0b00000000,
-        ]);
-        
-        // Letter 'C'
-        font_data.insert('C', vec![
-            0b01111100,
-            0b11000110,
-            0b11000000,
-            0b11000000,
-            0b11000000,
-            0b11000110,
-            0b01111100,
-            0b00000000,
-        ]);
-        
-        // Add more characters as needed...
-        // For brevity, we'll add a few common ones
-        
-        // Digit '0'
-        font_data.insert('0', vec![
-            0b01111100,
-            0b11000110,
-            0b11001110,
-            0b11011110,
-            0b11110110,
-            0b11100110,
-            0b01111100,
-            0b00000000,
-        ]);
-        
-        // Digit '1'
-        font_data.insert('1', vec![
-            0b00011000,
-            0b00111000,
-            0b00011000,
-            0b00011000,
-            0b00011000,
-            0b00011000,
-            0b01111110,
-            0b00000000,
-        ]);
-        
-        // Space character
-        font_data.insert(' ', vec![
-            0b00000000,
-            0b00000000,
-            0b00000000,
-            0b00000000,
-            0b00000000,
-            0b00000000,
-            0b00000000,
-            0b00000000,
-        ]);
-        
-        // Colon ':'
-        font_data.insert(':', vec![
-            0b00000000,
-            0b00011000,
-            0b00011000,
-            0b00000000,
-            0b00011000,
-            0b00011000,
-            0b00000000,
-            0b00000000,
-        ]);
-        
-        // Percent '%'
-        font_data.insert('%', vec![
-            0b01100010,
-            0b01100100,
-            0b00001000,
-            0b00010000,
-            0b00100000,
-            0b01001100,
-            0b10001100,
-            0b00000000,
-        ]);
-        
-        Self {
-            char_width: 8,
-            char_height: 8,
-            font_data,
-        }
-    }
-    
-    fn get_char_bitmap(&self, ch: char) -> Option<&Vec<u8>> {
-        self.font_data.get(&ch)
+/// Text scales must be positive; anything else draws at native size
+fn sanitize_scale(scale: f32) -> f32 {
+    if scale.is_finite() && scale > 0.0 { scale } else { 1.0 }
+}
+
+/// Length (px) of the barbs of an arrow head
+const ARROW_HEAD_LENGTH: f32 = 10.0;
+
+/// Angle between an arrow's shaft and each barb
+const ARROW_HEAD_ANGLE_DEG: f32 = 25.0;
+
+/// Stroke width (px) of arrows
+const ARROW_THICKNESS: f32 = 2.0;
+
+/// Line segment between two overlay points
+type Segment = ((f32, f32), (f32, f32));
+
+/// Box height, as a fraction of the display, below which an object is no threat
+const THREAT_FAR_HEIGHT_FRACTION: f32 = 0.15;
+
+/// Box height, as a fraction of the display, at which an object is maximally close
+const THREAT_NEAR_HEIGHT_FRACTION: f32 = 0.5;
+
+/// Proximity threat of a detection in [0, 1], from its apparent size
+///
+/// Without range data, a box covering more of the display height is taken
+/// to be closer; 0 at or below 15% of the height, 1 from half of it.
+pub fn threat_level(bbox: &BoundingBox, display_height: u32) -> f32 {
+    if display_height == 0 {
+        return 0.0;
     }
+    let fraction = bbox.height / display_height as f32;
+    ((fraction - THREAT_FAR_HEIGHT_FRACTION) / (THREAT_NEAR_HEIGHT_FRACTION - THREAT_FAR_HEIGHT_FRACTION)).clamp(0.0, 1.0)
 }
 
 /// Overlay renderer for drawing on top of video frames
@@ -152,7 +72,9 @@ pub struct OverlayRenderer {
     width: u32,
     height: u32,
     buffer: Vec<u8>, // RGBA buffer for overlay
-    font: BitmapFont,
+    fonts: HashMap<FontId, BitmapFont>,
+    font_id: FontId,
+    font_scale: f32,
 }
 
 impl OverlayRenderer {
@@ -167,10 +89,18 @@ impl OverlayRenderer {
             width,
             height,
             buffer,
-            font: BitmapFont::new(),
+            fonts: FontId::ALL.into_iter().map(|id| (id, BitmapFont::new(id))).collect(),
+            font_id: FontId::default(),
+            font_scale: 1.0,
         }
     }
     
+    /// Font and scale for labels that don't set their own size
+    pub fn set_text_style(&mut self, font: FontId, scale: f32) {
+        self.font_id = font;
+        self.font_scale = sanitize_scale(scale);
+    }
+    
     /// Clear overlay buffer
     pub fn clear(&mut self) {
         self.buffer.fill(0); // Make everything transparent
@@ -193,24 +123,34 @@ impl OverlayRenderer {
         Ok(())
     }
     
+    /// Size of a label's background box, text plus padding
+    pub fn measure_label(&self, label: &TextLabel) -> (u32, u32) {
+        let scale = self.label_scale(label);
+        let (text_width, text_height) = self.fonts[&self.font_id].measure(&label.text, scale);
+        (text_width + scaled(4, scale), text_height + scaled(2, scale))
+    }
+    
     /// Draw text label
     pub fn draw_text_label(&mut self, label: &TextLabel) -> Result<(), String> {
         let x = label.x.max(0.0) as u32;
         let y = label.y.max(0.0) as u32;
+        let scale = self.label_scale(label);
         
         // Draw text background for better visibility
-        let text_width = label.text.len() as u32 * self.font.char_width;
-        let text_height = self.font.char_height;
-        
+        let (width, height) = self.measure_label(label);
         let bg_color = Color { r: 0, g: 0, b: 0, a: 180 }; // Semi-transparent black
-        self.fill_rectangle(x, y, text_width + 4, text_height + 2, bg_color)?;
+        self.fill_rectangle(x, y, width, height, bg_color)?;
         
         // Draw text
-        self.draw_text(&label.text, x + 2, y + 1, label.color)?;
+        self.draw_text(&label.text, x + scaled(2, scale), y + scaled(1, scale), label.color, scale)?;
         
         Ok(())
     }
     
+    fn label_scale(&self, label: &TextLabel) -> f32 {
+        label.size.map_or(self.font_scale, sanitize_scale)
+    }
+    
     /// Draw a point (small circle)
     pub fn draw_point(&mut self, x: f32, y: f32, color: Color) -> Result<(), String> {
         let center_x = x as i32;
@@ -254,6 +194,148 @@ impl OverlayRenderer {
         Ok(())
     }
     
+    /// Draw connected line segments through `points` with anti-aliased edges
+    pub fn draw_polyline(&mut self, points: &[(f32, f32)], color: Color, thickness: f32) -> Result<(), String> {
+        let segments: Vec<Segment> = points.windows(2).map(|w| (w[0], w[1])).collect();
+        self.stroke_segments(&segments, color, thickness)
+    }
+    
+    /// Draw an arrow from `start` with its head at `end`
+    pub fn draw_arrow(&mut self, start: (f32, f32), end: (f32, f32), color: Color) -> Result<(), String> {
+        let (dx, dy) = (end.0 - start.0, end.1 - start.1);
+        let length = (dx * dx + dy * dy).sqrt();
+        if length == 0.0 {
+            return self.draw_point(end.0, end.1, color);
+        }
+        
+        // Barbs point back along the shaft; short arrows get proportionally short heads
+        let head = ARROW_HEAD_LENGTH.min(length * 0.5);
+        let back = dy.atan2(dx) + std::f32::consts::PI;
+        let barb = |angle: f32| (end.0 + head * angle.cos(), end.1 + head * angle.sin());
+        let spread = ARROW_HEAD_ANGLE_DEG.to_radians();
+        
+        self.stroke_segments(
+            &[(start, end), (end, barb(back - spread)), (end, barb(back + spread))],
+            color,
+            ARROW_THICKNESS,
+        )
+    }
+    
+    /// Fill a disc fading from `inner_color` at the centre to `outer_color` at `radius`
+    ///
+    /// Colors are interpolated premultiplied, so fading to a transparent
+    /// color does not darken the edge, and blended over the existing overlay.
+    pub fn draw_radial_gradient(&mut self, center: (f32, f32), radius: f32, inner_color: Color, outer_color: Color) -> Result<(), String> {
+        if radius <= 0.0 {
+            return Ok(());
+        }
+        
+        let x0 = (center.0 - radius).floor().max(0.0) as u32;
+        let y0 = (center.1 - radius).floor().max(0.0) as u32;
+        let x1 = ((center.0 + radius).ceil().max(0.0) as u32).min(self.width);
+        let y1 = ((center.1 + radius).ceil().max(0.0) as u32).min(self.height);
+        
+        let premultiplied = |c: Color| {
+            let a = c.a as f32 / 255.0;
+            [c.r as f32 * a, c.g as f32 * a, c.b as f32 * a, a]
+        };
+        let (inner, outer) = (premultiplied(inner_color), premultiplied(outer_color));
+        
+        for y in y0..y1 {
+            for x in x0..x1 {
+                let distance = ((x as f32 + 0.5 - center.0).powi(2) + (y as f32 + 0.5 - center.1).powi(2)).sqrt();
+                if distance > radius {
+                    continue;
+                }
+                
+                let t = distance / radius;
+                let mut mixed = [0.0f32; 4];
+                for (value, (i, o)) in mixed.iter_mut().zip(inner.iter().zip(&outer)) {
+                    *value = i + (o - i) * t;
+                }
+                let alpha = mixed[3];
+                if alpha <= 0.0 {
+                    continue;
+                }
+                
+                let straight = |v: f32| (v / alpha).round().clamp(0.0, 255.0) as u8;
+                let color = Color {
+                    r: straight(mixed[0]),
+                    g: straight(mixed[1]),
+                    b: straight(mixed[2]),
+                    a: 255,
+                };
+                self.blend_pixel(x, y, color, alpha)?;
+            }
+        }
+        
+        Ok(())
+    }
+    
+    /// Rasterize thick segments with round caps and joins
+    ///
+    /// Coverage is the overlap of each pixel with a `thickness`-wide capsule
+    /// around the segment, approximated from the distance of the pixel centre.
+    /// Where segments overlap the larger coverage wins, so joins of
+    /// translucent strokes are not blended twice.
+    fn stroke_segments(&mut self, segments: &[Segment], color: Color, thickness: f32) -> Result<(), String> {
+        let half = thickness.max(1.0) / 2.0;
+        let points = segments.iter().flat_map(|(a, b)| [*a, *b]);
+        let (mut min_x, mut min_y, mut max_x, mut max_y) = (f32::MAX, f32::MAX, f32::MIN, f32::MIN);
+        for (x, y) in points {
+            min_x = min_x.min(x);
+            min_y = min_y.min(y);
+            max_x = max_x.max(x);
+            max_y = max_y.max(y);
+        }
+        
+        // Pixels that can be touched, clipped to the overlay
+        let x0 = (min_x - half - 1.0).floor().max(0.0) as u32;
+        let y0 = (min_y - half - 1.0).floor().max(0.0) as u32;
+        let x1 = ((max_x + half + 1.0).ceil().max(0.0) as u32).min(self.width);
+        let y1 = ((max_y + half + 1.0).ceil().max(0.0) as u32).min(self.height);
+        if segments.is_empty() || x0 >= x1 || y0 >= y1 {
+            return Ok(());
+        }
+        
+        for y in y0..y1 {
+            for x in x0..x1 {
+                let centre = (x as f32 + 0.5, y as f32 + 0.5);
+                let distance = segments
+                    .iter()
+                    .map(|(a, b)| distance_to_segment(centre, *a, *b))
+                    .fold(f32::MAX, f32::min);
+                let coverage = (half + 0.5 - distance).clamp(0.0, 1.0);
+                if coverage > 0.0 {
+                    self.blend_pixel(x, y, color, coverage)?;
+                }
+            }
+        }
+        
+        Ok(())
+    }
+    
+    /// Composite `color` over the overlay pixel with its alpha scaled by `coverage`
+    fn blend_pixel(&mut self, x: u32, y: u32, color: Color, coverage: f32) -> Result<(), String> {
+        let below = self.get_pixel(x, y)?;
+        let src_a = color.a as f32 / 255.0 * coverage;
+        let dst_a = below.a as f32 / 255.0;
+        let out_a = src_a + dst_a * (1.0 - src_a);
+        if out_a <= 0.0 {
+            return Ok(());
+        }
+        
+        let mix = |src: u8, dst: u8| {
+            ((src as f32 * src_a + dst as f32 * dst_a * (1.0 - src_a)) / out_a).round() as u8
+        };
+        self.set_pixel(x, y, Color {
+            r: mix(color.r, below.r),
+            g: mix(color.g, below.g),
+            b: mix(color.b, below.b),
+            a: (out_a * 255.0).round() as u8,
+        })
+    }
+    
     /// Set pixel in overlay buffer
     fn set_pixel(&mut self, x: u32, y: u32, color: Color) -> Result<(), String> {
         if x >= self.width || y >= self.height {
@@ -337,38 +419,38 @@ impl OverlayRenderer {
         Ok(())
     }
     
-    /// Draw text using bitmap font
-    fn draw_text(&mut self, text: &str, x: u32, y: u32, color: Color) -> Result<(), String> {
-        let mut current_x = x;
+    /// Draw text using bitmap font, one line per `\n`
+    fn draw_text(&mut self, text: &str, x: u32, y: u32, color: Color, scale: f32) -> Result<(), String> {
+        let (char_width, char_height) = {
+            let font = &self.fonts[&self.font_id];
+            (font.char_width(), font.char_height())
+        };
         
-        for ch in text.chars() {
-            if let Some(char_bitmap) = self.font.get_char_bitmap(ch) {
-                self.draw_char_bitmap(char_bitmap, current_x, y, color)?;
-            }
-            current_x += self.font.char_width;
+        for (line_index, line) in text.split('\n').enumerate() {
+            let line_y = y + scaled(line_index as u32 * char_height, scale);
             
-            // Stop if we exceed the overlay width
-            if current_x >= self.width {
-                break;
+            for (column, ch) in line.chars().enumerate() {
+                let char_x = x + scaled(column as u32 * char_width, scale);
+                // Stop if we exceed the overlay width
+                if char_x >= self.width {
+                    break;
+                }
+                
+                let pixels = self.fonts[&self.font_id].glyph_pixels(ch);
+                self.draw_glyph(&pixels, char_x, line_y, color, scale)?;
             }
         }
         
         Ok(())
     }
     
-    /// Draw character bitmap
-    fn draw_char_bitmap(&mut self, bitmap: &[u8], x: u32, y: u32, color: Color) -> Result<(), String> {
-        for (row, &byte) in bitmap.iter().enumerate() {
-            for col in 0..8 {
-                if byte & (1 << (7 - col)) != 0 {
-                    let px = x + col;
-                    let py = y + row as u32;
-                    
-                    if px < self.width && py < self.height {
-                        self.set_pixel(px, py, color)?;
-                    }
-                }
-            }
+    /// Draw glyph pixels, each as a block of `scale` overlay pixels
+    fn draw_glyph(&mut self, pixels: &[(u32, u32)], x: u32, y: u32, color: Color, scale: f32) -> Result<(), String> {
+        for &(col, row) in pixels {
+            let (x0, x1) = (scaled(col, scale), scaled(col + 1, scale));
+            let (y0, y1) = (scaled(row, scale), scaled(row + 1, scale));
+            // Keep thin strokes visible below scale 1
+            self.fill_rectangle(x + x0, y + y0, (x1 - x0).max(1), (y1 - y0).max(1), color)?;
         }
         
         Ok(())
@@ -424,6 +506,19 @@ impl OverlayRenderer {
     }
 }
 
+/// Distance from `p` to the segment `a`–`b`
+fn distance_to_segment(p: (f32, f32), a: (f32, f32), b: (f32, f32)) -> f32 {
+    let (dx, dy) = (b.0 - a.0, b.1 - a.1);
+    let length_sq = dx * dx + dy * dy;
+    let t = if length_sq > 0.0 {
+        (((p.0 - a.0) * dx + (p.1 - a.1) * dy) / length_sq).clamp(0.0, 1.0)
+    } else {
+        0.0
+    };
+    let (cx, cy) = (a.0 + t * dx, a.1 + t * dy);
+    ((p.0 - cx).powi(2) + (p.1 - cy).powi(2)).sqrt()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -435,6 +530,17 @@ mod tests {
         assert_eq!(overlay.buffer.len(), 640 * 480 * 4);
     }
     
+    #[test]
+    fn test_display_gate() {
+        assert!(passes_display_gate(0.7, 0.6));
+        assert!(passes_display_gate(0.6, 0.6));
+        assert!(!passes_display_gate(0.55, 0.6));
+        assert!(!passes_display_gate(f32::NAN, 0.0));
+        // Out-of-range gates are clamped instead of hiding everything
+        assert!(passes_display_gate(1.0, 1.5));
+        assert!(passes_display_gate(0.0, -1.0));
+    }
+    
     #[test]
     fn test_bounding_box_drawing() {
         let mut overlay = OverlayRenderer::new(100, 100);
@@ -461,6 +567,7 @@ mod tests {
             x: 10.0,
             y: 10.0,
             color: Color { r: 255, g: 255, b: 255, a: 255 },
+            size: None,
         };
         
         overlay.draw_text_label(&label).unwrap();
@@ -477,4 +584,142 @@ mod tests {
         }
         assert!(has_text);
     }
-}
\ No newline at end of file
+    
+    #[test]
+    fn test_label_scales_with_font_scale_and_size() {
+        let mut overlay = OverlayRenderer::new(200, 100);
+        let mut label = TextLabel {
+            text: "FPS".to_string(),
+            x: 0.0,
+            y: 0.0,
+            color: Color::WHITE,
+            size: None,
+        };
+        assert_eq!(overlay.measure_label(&label), (22, 10));
+        
+        overlay.set_text_style(FontId::Standard, 2.0);
+        assert_eq!(overlay.measure_label(&label), (44, 20));
+        
+        // A label's own size wins over the renderer's scale
+        label.size = Some(1.0);
+        assert_eq!(overlay.measure_label(&label), (22, 10));
+        
+        overlay.set_text_style(FontId::Block, f32::NAN);
+        label.size = None;
+        assert_eq!(overlay.measure_label(&label), (28, 10));
+    }
+    
+    #[test]
+    fn test_multi_line_label() {
+        let mut overlay = OverlayRenderer::new(100, 100);
+        let label = TextLabel {
+            text: "I\nI".to_string(),
+            x: 0.0,
+            y: 0.0,
+            color: Color::WHITE,
+            size: Some(2.0),
+        };
+        assert_eq!(overlay.measure_label(&label), (20, 36));
+        
+        overlay.draw_text_label(&label).unwrap();
+        
+        // Stem of each 'I' sits at glyph column 2, scaled to x 8..10 after
+        // the 4 pixel padding; lines start 16 pixels apart below the 2 pixel pad
+        assert_eq!(overlay.get_pixel(8, 6).unwrap(), Color::WHITE);
+        assert_eq!(overlay.get_pixel(9, 22).unwrap(), Color::WHITE);
+        assert_ne!(overlay.get_pixel(4, 22).unwrap(), Color::WHITE);
+    }
+    
+    #[test]
+    fn test_polyline_is_antialiased() {
+        let mut overlay = OverlayRenderer::new(40, 40);
+        let red = Color { r: 255, g: 0, b: 0, a: 255 };
+        
+        overlay.draw_polyline(&[(5.0, 10.0), (30.0, 10.0), (30.0, 30.0)], red, 3.0).unwrap();
+        
+        // Solid on both segments, soft at the stroke edge, empty elsewhere
+        assert_eq!(overlay.get_pixel(15, 9).unwrap(), red);
+        assert_eq!(overlay.get_pixel(29, 20).unwrap(), red);
+        let edge = overlay.get_pixel(15, 11).unwrap();
+        assert!(edge.a > 0 && edge.a < 255, "edge alpha {}", edge.a);
+        assert_eq!(overlay.get_pixel(15, 20).unwrap().a, 0);
+    }
+    
+    #[test]
+    fn test_translucent_join_blended_once() {
+        let mut overlay = OverlayRenderer::new(40, 40);
+        let translucent = Color { r: 0, g: 255, b: 0, a: 128 };
+        
+        overlay.draw_polyline(&[(5.0, 20.5), (20.5, 20.5), (20.5, 5.0)], translucent, 3.0).unwrap();
+        
+        assert_eq!(overlay.get_pixel(20, 20).unwrap().a, 128);
+        assert_eq!(overlay.get_pixel(10, 20).unwrap().a, 128);
+    }
+    
+    #[test]
+    fn test_arrow_head_at_end() {
+        let mut overlay = OverlayRenderer::new(60, 40);
+        let white = Color { r: 255, g: 255, b: 255, a: 255 };
+        
+        overlay.draw_arrow((5.0, 20.5), (50.0, 20.5), white).unwrap();
+        
+        // Barbs spread behind the tip, not behind the tail
+        assert!(overlay.get_pixel(43, 17).unwrap().a > 0);
+        assert!(overlay.get_pixel(43, 23).unwrap().a > 0);
+        assert_eq!(overlay.get_pixel(8, 17).unwrap().a, 0);
+    }
+    
+    #[test]
+    fn test_stroke_clipped_to_overlay() {
+        let mut overlay = OverlayRenderer::new(10, 10);
+        let red = Color { r: 255, g: 0, b: 0, a: 255 };
+        
+        overlay.draw_polyline(&[(-20.0, 5.0), (30.0, 5.0)], red, 2.0).unwrap();
+        overlay.draw_polyline(&[(-20.0, -20.0), (-10.0, -10.0)], red, 2.0).unwrap();
+        overlay.draw_polyline(&[(3.0, 3.0)], red, 2.0).unwrap();
+        
+        assert_eq!(overlay.get_pixel(0, 5).unwrap(), red);
+        assert_eq!(overlay.get_pixel(9, 5).unwrap(), red);
+    }
+    
+    #[test]
+    fn test_radial_gradient_fades_out() {
+        let mut overlay = OverlayRenderer::new(40, 40);
+        let red = Color { r: 255, g: 0, b: 0, a: 200 };
+        let clear = Color { r: 255, g: 0, b: 0, a: 0 };
+        
+        overlay.draw_radial_gradient((20.0, 20.0), 10.0, red, clear).unwrap();
+        
+        let centre = overlay.get_pixel(20, 20).unwrap();
+        let middle = overlay.get_pixel(25, 20).unwrap();
+        assert_eq!((centre.r, centre.g), (255, 0));
+        assert!(centre.a > 180);
+        assert!(middle.a > 50 && middle.a < centre.a);
+        // Fading to transparent keeps the hue instead of darkening
+        assert_eq!(middle.r, 255);
+        assert_eq!(overlay.get_pixel(20, 31).unwrap().a, 0);
+    }
+    
+    #[test]
+    fn test_radial_gradient_blends_over_existing() {
+        let mut overlay = OverlayRenderer::new(20, 20);
+        let blue = Color { r: 0, g: 0, b: 255, a: 255 };
+        let half_red = Color { r: 255, g: 0, b: 0, a: 128 };
+        overlay.set_pixel(10, 10, blue).unwrap();
+        
+        overlay.draw_radial_gradient((10.5, 10.5), 5.0, half_red, half_red).unwrap();
+        
+        let pixel = overlay.get_pixel(10, 10).unwrap();
+        assert_eq!(pixel.a, 255);
+        assert!(pixel.r > 120 && pixel.r < 135 && pixel.b > 120 && pixel.b < 135);
+    }
+    
+    #[test]
+    fn test_threat_level_from_box_height() {
+        let bbox = |height: f32| BoundingBox { x: 0.0, y: 0.0, width: 20.0, height };
+        assert_eq!(threat_level(&bbox(40.0), 400), 0.0);
+        assert!((threat_level(&bbox(130.0), 400) - 0.5).abs() < 1e-6);
+        assert_eq!(threat_level(&bbox(300.0), 400), 1.0);
+        assert_eq!(threat_level(&bbox(300.0), 0), 0.0);
+    }
+}
@@ -0,0 +1,120 @@
+// Video frame scaling to the display resolution
+// Resamples RGB24 camera frames into the RGBA8 frame buffer layout
+
+/// Resampling filter used when scaling video frames
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ScalingMode {
+    /// Nearest source pixel; cheapest, blocky when upscaling
+    #[default]
+    NearestNeighbor,
+    /// Weighted average of the four nearest source pixels
+    Bilinear,
+}
+
+/// Scale an RGB24 image to `dst_width`×`dst_height` RGBA8 with opaque alpha
+pub fn scale_rgb_to_rgba(
+    rgb: &[u8],
+    src_width: u32,
+    src_height: u32,
+    dst_width: u32,
+    dst_height: u32,
+    mode: ScalingMode,
+) -> Result<Vec<u8>, String> {
+    let (src_width, src_height) = (src_width as usize, src_height as usize);
+    let (dst_width, dst_height) = (dst_width as usize, dst_height as usize);
+
+    if src_width == 0 || src_height == 0 {
+        return Err(format!("Cannot scale an empty {}x{} frame", src_width, src_height));
+    }
+    if rgb.len() < src_width * src_height * 3 {
+        return Err(format!(
+            "Frame holds {} bytes, {}x{} RGB needs {}",
+            rgb.len(),
+            src_width,
+            src_height,
+            src_width * src_height * 3
+        ));
+    }
+
+    let mut scaled = vec![0u8; dst_width * dst_height * 4];
+    if scaled.is_empty() {
+        return Ok(scaled);
+    }
+    let pixel = |x: usize, y: usize| &rgb[(y * src_width + x) * 3..(y * src_width + x) * 3 + 3];
+
+    for (y, row) in scaled.chunks_exact_mut(dst_width * 4).enumerate() {
+        for (x, out) in row.chunks_exact_mut(4).enumerate() {
+            match mode {
+                ScalingMode::NearestNeighbor => {
+                    let src_x = (x * src_width) / dst_width;
+                    let src_y = (y * src_height) / dst_height;
+                    out[..3].copy_from_slice(pixel(src_x, src_y));
+                }
+                ScalingMode::Bilinear => {
+                    // Align pixel centres and clamp samples to the edge pixels
+                    let (x0, x1, fx) = sample_position(x, dst_width, src_width);
+                    let (y0, y1, fy) = sample_position(y, dst_height, src_height);
+                    let (top_left, top_right) = (pixel(x0, y0), pixel(x1, y0));
+                    let (bottom_left, bottom_right) = (pixel(x0, y1), pixel(x1, y1));
+
+                    for c in 0..3 {
+                        let top = top_left[c] as f32 * (1.0 - fx) + top_right[c] as f32 * fx;
+                        let bottom = bottom_left[c] as f32 * (1.0 - fx) + bottom_right[c] as f32 * fx;
+                        out[c] = (top * (1.0 - fy) + bottom * fy).round().clamp(0.0, 255.0) as u8;
+                    }
+                }
+            }
+            out[3] = 255;
+        }
+    }
+
+    Ok(scaled)
+}
+
+/// Neighbouring source indices and the weight of the second for one destination index
+fn sample_position(dst: usize, dst_len: usize, src_len: usize) -> (usize, usize, f32) {
+    let src = ((dst as f32 + 0.5) * src_len as f32 / dst_len as f32 - 0.5).clamp(0.0, (src_len - 1) as f32);
+    let lower = src.floor() as usize;
+    let upper = (lower + 1).min(src_len - 1);
+    (lower, upper, src - lower as f32)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // 2x1 image: black, white
+    const GRADIENT: [u8; 6] = [0, 0, 0, 255, 255, 255];
+
+    fn red_channel(rgba: &[u8]) -> Vec<u8> {
+        rgba.chunks_exact(4).map(|p| p[0]).collect()
+    }
+
+    #[test]
+    fn test_nearest_neighbor_duplicates_pixels() {
+        let scaled = scale_rgb_to_rgba(&GRADIENT, 2, 1, 4, 1, ScalingMode::NearestNeighbor).unwrap();
+        assert_eq!(red_channel(&scaled), vec![0, 0, 255, 255]);
+        assert!(scaled.chunks_exact(4).all(|p| p[3] == 255));
+    }
+
+    #[test]
+    fn test_bilinear_interpolates_and_clamps_edges() {
+        let scaled = scale_rgb_to_rgba(&GRADIENT, 2, 1, 4, 1, ScalingMode::Bilinear).unwrap();
+        // Outer pixels clamp to the edge samples, inner ones blend 3:1
+        assert_eq!(red_channel(&scaled), vec![0, 64, 191, 255]);
+        assert_eq!(&scaled[4..8], &[64, 64, 64, 255]);
+    }
+
+    #[test]
+    fn test_bilinear_keeps_uniform_colour() {
+        let rgb = [10, 20, 30].repeat(9);
+        let scaled = scale_rgb_to_rgba(&rgb, 3, 3, 7, 5, ScalingMode::Bilinear).unwrap();
+        assert!(scaled.chunks_exact(4).all(|p| p == [10, 20, 30, 255]));
+    }
+
+    #[test]
+    fn test_rejects_short_or_empty_frames() {
+        assert!(scale_rgb_to_rgba(&GRADIENT[..5], 2, 1, 4, 1, ScalingMode::Bilinear).is_err());
+        assert!(scale_rgb_to_rgba(&[], 0, 0, 4, 1, ScalingMode::NearestNeighbor).is_err());
+    }
+}
@@ -3,6 +3,12 @@
 use std::collections::HashMap;
 use std::time::Instant;
 
+use crate::heartbeat::{HeartbeatMonitor, StaleHeartbeat};
+use crate::reload::ComponentModule;
+use crate::supervision::{
+    FailureSeverity, RestartDecision, RestartEvent, RestartOutcome, RestartPolicy, Supervisor,
+};
+
 /// Component information for registration
 #[derive(Debug, Clone)]
 pub struct ComponentInfo {
@@ -10,6 +16,8 @@ pub struct ComponentInfo {
     pub component_type: String,
     pub interface_version: String,
     pub capabilities: Vec<String>,
+    /// Interfaces the component exports, without version
+    pub interfaces: Vec<String>,
 }
 
 /// Component state tracking
@@ -30,15 +38,20 @@ struct ComponentRuntime {
     info: ComponentInfo,
     state: ComponentState,
     start_time: Option<Instant>,
-    last_heartbeat: Option<Instant>,
     message_count: u64,
     error_count: u32,
+    /// Worst failure since the component was last started
+    failure: Option<FailureSeverity>,
+    /// Binary the component was last hot-reloaded from
+    module: Option<ComponentModule>,
 }
 
 /// Manages all components in the ADAS pipeline
 pub struct ComponentManager {
     components: HashMap<String, ComponentRuntime>,
     pipeline_order: Vec<String>,
+    heartbeats: HeartbeatMonitor,
+    supervisor: Supervisor,
 }
 
 impl ComponentManager {
@@ -46,9 +59,26 @@ impl ComponentManager {
         Self {
             components: HashMap::new(),
             pipeline_order: Vec::new(),
+            heartbeats: HeartbeatMonitor::default(),
+            supervisor: Supervisor::default(),
         }
     }
     
+    /// Restart policy for one component; others restart on critical failures
+    pub fn set_restart_policy(&mut self, component_id: &str, policy: RestartPolicy) {
+        self.supervisor.set_policy(component_id, policy);
+    }
+    
+    /// Allow at most `max_restarts` restarts of a component within `window_ms`
+    pub fn set_restart_limits(&mut self, max_restarts: u32, window_ms: u64) {
+        self.supervisor.set_limits(max_restarts, window_ms);
+    }
+    
+    /// Time without processing before a component is flagged as stalled
+    pub fn set_heartbeat_timeout(&mut self, timeout_ms: u64) {
+        self.heartbeats.set_threshold(timeout_ms);
+    }
+    
     /// Initialize the 5-component pipeline
     pub fn initialize_pipeline_components(&mut self) -> Result<(), String> {
         println!("🔧 Initializing 5-component ADAS pipeline");
@@ -63,17 +93,21 @@ impl ComponentManager {
         ];
         
         // Register each component with default info
-        for component_id in &self.pipeline_order {
+        for component_id in &self.pipeline_order.clone() {
             let info = ComponentInfo {
                 id: component_id.clone(),
                 component_type: self.get_component_type(component_id),
                 interface_version: "0.1.0".to_string(),
                 capabilities: self.get_component_capabilities(component_id),
+                interfaces: self.get_component_interfaces(component_id),
             };
             
             self.register_component(info)?;
         }
         
+        // The orchestrator cannot stop and re-initialize itself
+        self.set_restart_policy("orchestrator", RestartPolicy::Never);
+        
         println!("✅ Initialized {} components in pipeline", self.components.len());
         Ok(())
     }
@@ -86,11 +120,13 @@ impl ComponentManager {
             info: info.clone(),
             state: ComponentState::Registered,
             start_time: None,
-            last_heartbeat: Some(Instant::now()),
             message_count: 0,
             error_count: 0,
+            failure: None,
+            module: None,
         };
         
+        self.heartbeats.watch(&info.id, adas_timing::monotonic_now_ms());
         self.components.insert(info.id.clone(), runtime);
         Ok(())
     }
@@ -112,6 +148,7 @@ impl ComponentManager {
             println!("▶️  Starting component: {}", component_id);
             
             component.state = ComponentState::Initializing;
+            component.failure = None;
             
             // Simulate component startup based on type
             match component.info.component_type.as_str() {
@@ -177,6 +214,7 @@ impl ComponentManager {
             
             component.state = ComponentState::Offline;
             component.start_time = None;
+            self.heartbeats.unwatch(component_id);
             
             Ok(())
         } else {
@@ -184,11 +222,32 @@ impl ComponentManager {
         }
     }
     
-    /// Update component heartbeat
-    pub fn update_heartbeat(&mut self, component_id: &str) {
-        if let Some(component) = self.components.get_mut(component_id) {
-            component.last_heartbeat = Some(Instant::now());
+    /// Update component heartbeat from its polled last-activity timestamp
+    pub fn update_heartbeat(&mut self, component_id: &str, last_activity_ms: u64) {
+        self.heartbeats.record(component_id, last_activity_ms);
+    }
+    
+    /// Flag components that have not processed anything within the timeout
+    ///
+    /// A stalled component is treated as critical even if its own health
+    /// report still says Ok, since that report may be cached.
+    /// Returns the components newly flagged by this check.
+    pub fn check_heartbeats(&mut self, now_ms: u64) -> Vec<StaleHeartbeat> {
+        let mut flagged = Vec::new();
+        for heartbeat in self.heartbeats.stale(now_ms) {
+            let already_flagged = matches!(
+                self.components.get(&heartbeat.component_id).map(|c| &c.state),
+                Some(ComponentState::Error(_))
+            );
+            if !already_flagged {
+                self.report_critical(
+                    &heartbeat.component_id,
+                    format!("Heartbeat stale: no activity for {}ms", heartbeat.age_ms),
+                );
+                flagged.push(heartbeat);
+            }
         }
+        flagged
     }
     
     /// Increment message count for component
@@ -200,12 +259,117 @@ impl ComponentManager {
     
     /// Record error for component
     pub fn record_error(&mut self, component_id: &str, error: String) {
+        self.record_failure(component_id, FailureSeverity::Fault, error);
+    }
+    
+    /// Record a critical failure, e.g. `HealthStatus::Critical` or a stall
+    pub fn report_critical(&mut self, component_id: &str, error: String) {
+        self.record_failure(component_id, FailureSeverity::Critical, error);
+    }
+    
+    fn record_failure(&mut self, component_id: &str, severity: FailureSeverity, error: String) {
         if let Some(component) = self.components.get_mut(component_id) {
             component.error_count += 1;
             component.state = ComponentState::Error(error);
+            component.failure = component.failure.max(Some(severity));
         }
     }
     
+    /// Restart failed components according to their restart policy
+    ///
+    /// Each restart stops the component and re-initializes it, in pipeline
+    /// order. Returns the restart events recorded by this pass.
+    pub fn supervise(&mut self, now_ms: u64) -> Vec<RestartEvent> {
+        let mut events = Vec::new();
+        
+        for component_id in self.pipeline_order.clone() {
+            let (severity, reason) = match self.components.get(&component_id) {
+                Some(ComponentRuntime {
+                    state: ComponentState::Error(reason),
+                    failure: Some(severity),
+                    ..
+                }) => (*severity, reason.clone()),
+                _ => continue,
+            };
+            
+            let (attempt, outcome) = match self.supervisor.decide(&component_id, severity, now_ms) {
+                RestartDecision::Skip => continue,
+                RestartDecision::GiveUp => (0, RestartOutcome::Suppressed),
+                RestartDecision::Restart { attempt } => match self.restart_component(&component_id, now_ms) {
+                    Ok(()) => (attempt, RestartOutcome::Restarted),
+                    Err(e) => (attempt, RestartOutcome::Failed(e)),
+                },
+            };
+            
+            let event = RestartEvent {
+                component_id,
+                timestamp_ms: now_ms,
+                reason,
+                attempt,
+                outcome,
+            };
+            self.supervisor.record(event.clone());
+            events.push(event);
+        }
+        
+        events
+    }
+    
+    /// Stop and re-initialize a component, watching its heartbeat afresh
+    fn restart_component(&mut self, component_id: &str, now_ms: u64) -> Result<(), String> {
+        println!("🔄 Restarting component: {}", component_id);
+        self.stop_component(component_id)?;
+        self.start_component(component_id)?;
+        self.heartbeats.watch(component_id, now_ms);
+        Ok(())
+    }
+    
+    /// Swap a running component for the binary at `wasm_path`
+    ///
+    /// The new binary must export the interfaces the component registered
+    /// and, once reloaded, the same interface set as the binary it replaces.
+    /// The component is stopped,
+    /// `flush` discards the messages queued for the old instance, and the
+    /// component is started again. Other components keep their state.
+    /// Returns the number of messages flushed.
+    pub fn reload_component(
+        &mut self,
+        component_id: &str,
+        wasm_path: &str,
+        flush: impl FnOnce() -> usize,
+    ) -> Result<usize, String> {
+        let current = self.components.get(component_id)
+            .ok_or_else(|| format!("Component not found: {}", component_id))?;
+        
+        // Nothing is touched until the replacement is known to fit
+        let module = ComponentModule::load(wasm_path)?;
+        module.check_registered(&current.info.interfaces)?;
+        if let Some(previous) = &current.module {
+            previous.check_compatible(&module)?;
+        }
+        
+        println!("♻️  Reloading component {} from {}", component_id, wasm_path);
+        self.stop_component(component_id)?;
+        let messages_flushed = flush();
+        
+        let interfaces = module.exports.len();
+        if let Some(component) = self.components.get_mut(component_id) {
+            component.module = Some(module);
+            component.message_count = 0;
+            component.error_count = 0;
+        }
+        self.start_component(component_id)?;
+        self.heartbeats.watch(component_id, adas_timing::monotonic_now_ms());
+        
+        println!("✅ Reloaded {} ({} interfaces, {} messages flushed)", component_id, interfaces, messages_flushed);
+        Ok(messages_flushed)
+    }
+    
+    /// Restarts performed or withheld by supervision, oldest first
+    pub fn restart_history(&self) -> Vec<RestartEvent> {
+        self.supervisor.history()
+    }
+    
     /// Get component state
     pub fn get_component_state(&self, component_id: &str) -> Option<ComponentState> {
         self.components.get(component_id).map(|c| c.state.clone())
@@ -284,6 +448,26 @@ impl ComponentManager {
             _ => vec!["unknown".to_string()],
         }
     }
+    
+    /// Interfaces exported by the world each pipeline component is built from
+    fn get_component_interfaces(&self, component_id: &str) -> Vec<String> {
+        let interfaces: &[&str] = match component_id {
+            "object-detection" => &["adas:object-detection/detection-engine", "adas:object-detection/diagnostics"],
+            "visualizer" => &[
+                "adas:control/graphics-control",
+                "adas:diagnostics/health-monitoring",
+                "adas:diagnostics/performance-monitoring",
+            ],
+            "orchestrator" => &[
+                "adas:orchestration/orchestration-control",
+                "adas:diagnostics/health-monitoring",
+                "adas:diagnostics/performance-monitoring",
+            ],
+            // The video decoder and safety monitor export only functions
+            _ => &[],
+        };
+        interfaces.iter().map(|name| name.to_string()).collect()
+    }
 }
 
 /// Pipeline health summary
@@ -306,4 +490,40 @@ impl PipelineHealth {
         }
         (self.healthy_components as f32 / self.total_components as f32) * 100.0
     }
-}
\ No newline at end of file
+}
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::reload::tests::component_exporting;
+    
+    #[test]
+    fn test_first_reload_must_match_registered_interfaces() {
+        let mut manager = ComponentManager::new();
+        manager.initialize_pipeline_components().unwrap();
+        manager.start_all_components().unwrap();
+        
+        let path = std::env::temp_dir().join(format!("adas-orchestrator-first-reload-{}.wasm", std::process::id()));
+        std::fs::write(&path, component_exporting(&["adas:control/ai-control@0.1.0"])).unwrap();
+        let path = path.to_string_lossy().into_owned();
+        
+        let mut flushed = false;
+        let error = manager
+            .reload_component("object-detection", &path, || {
+                flushed = true;
+                0
+            })
+            .unwrap_err();
+        std::fs::remove_file(&path).unwrap();
+        
+        assert_eq!(
+            error,
+            format!(
+                "Incompatible reload: {} does not export registered adas:object-detection/detection-engine, \
+                 adas:object-detection/diagnostics and exports unregistered adas:control/ai-control",
+                path
+            )
+        );
+        assert!(!flushed);
+        assert_eq!(manager.get_component_state("object-detection"), Some(ComponentState::Running));
+    }
+}
@@ -2,9 +2,13 @@
 
 use std::collections::{HashMap, VecDeque};
 use std::sync::{Arc, Mutex};
-use crossbeam_channel::{bounded, unbounded, Receiver, Sender};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::Duration;
+use crossbeam_channel::{bounded, Receiver, RecvTimeoutError, SendTimeoutError, Sender, TrySendError};
 use serde::{Deserialize, Serialize};
 
+use crate::replay::{EventRecorder, EventReplayer};
+
 /// Data event types that flow through the system
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum DataEvent {
@@ -28,6 +32,17 @@ pub enum DataEvent {
     },
 }
 
+impl DataEvent {
+    /// Bus topic the event travels on
+    pub fn topic(&self) -> Topic {
+        match self {
+            DataEvent::VideoFrame { .. } => Topic::VideoFrame,
+            DataEvent::DetectionResult { .. } => Topic::DetectionResult,
+            DataEvent::SystemEvent { .. } => Topic::SystemEvent,
+        }
+    }
+}
+
 /// Detected object from AI processing
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct DetectedObject {
@@ -46,71 +61,200 @@ pub struct BoundingBox {
     pub height: f32,
 }
 
+/// Messages a topic queue holds before backpressure applies
+pub const DEFAULT_QUEUE_HIGH_WATER_MARK: usize = 4;
+
+/// Longest a blocking publish waits for consumers by default
+pub const DEFAULT_BLOCK_TIMEOUT: Duration = Duration::from_millis(33);
+
+/// Message bus topics
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Topic {
+    VideoFrame,
+    DetectionResult,
+    SystemEvent,
+}
+
+impl Topic {
+    /// A stale camera frame is superseded by the next one and detection
+    /// results must not be lost. System events have no consumer in the
+    /// pipeline, so blocking on them would stall every publisher; the
+    /// oldest are dropped instead
+    pub fn default_policy(self) -> BackpressurePolicy {
+        match self {
+            Topic::VideoFrame | Topic::SystemEvent => BackpressurePolicy::DropOldest,
+            Topic::DetectionResult => BackpressurePolicy::Block,
+        }
+    }
+}
+
+/// What a publisher does when a topic queue is at its high-water mark
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BackpressurePolicy {
+    /// Discard the oldest queued message to make room
+    DropOldest,
+    /// Wait for a consumer to make room, failing after the block timeout
+    Block,
+}
+
+/// Bounded queue for one topic
+struct TopicQueue {
+    topic: Topic,
+    tx: Sender<DataEvent>,
+    rx: Receiver<DataEvent>,
+    policy: BackpressurePolicy,
+    block_timeout: Duration,
+    dropped: AtomicU64,
+}
+
+impl TopicQueue {
+    fn new(topic: Topic, high_water_mark: usize, policy: BackpressurePolicy, block_timeout: Duration) -> Self {
+        let (tx, rx) = bounded(high_water_mark.max(1));
+        Self {
+            topic,
+            tx,
+            rx,
+            policy,
+            block_timeout,
+            dropped: AtomicU64::new(0),
+        }
+    }
+    
+    fn publish(&self, event: DataEvent) -> Result<(), String> {
+        match self.policy {
+            BackpressurePolicy::Block => self.tx.send_timeout(event, self.block_timeout).map_err(|e| match e {
+                SendTimeoutError::Timeout(_) => format!(
+                    "Consumers of {:?} lagging: queue still full after {}ms",
+                    self.topic,
+                    self.block_timeout.as_millis()
+                ),
+                SendTimeoutError::Disconnected(_) => format!("{:?} queue disconnected", self.topic),
+            }),
+            BackpressurePolicy::DropOldest => {
+                let mut event = event;
+                loop {
+                    match self.tx.try_send(event) {
+                        Ok(()) => return Ok(()),
+                        Err(TrySendError::Full(rejected)) => {
+                            // A consumer may have drained the queue in between
+                            if self.rx.try_recv().is_ok() {
+                                self.dropped.fetch_add(1, Ordering::Relaxed);
+                            }
+                            event = rejected;
+                        }
+                        Err(TrySendError::Disconnected(_)) => {
+                            return Err(format!("{:?} queue disconnected", self.topic));
+                        }
+                    }
+                }
+            }
+        }
+    }
+    
+    fn dropped(&self) -> u64 {
+        self.dropped.load(Ordering::Relaxed)
+    }
+}
+
 /// Message bus for inter-component communication
+///
+/// Every topic is a bounded queue; a publisher hitting the high-water mark
+/// applies the topic's [`BackpressurePolicy`].
 pub struct MessageBus {
-    video_frame_tx: Sender<DataEvent>,
-    video_frame_rx: Receiver<DataEvent>,
-    detection_tx: Sender<DataEvent>,
-    detection_rx: Receiver<DataEvent>,
-    system_event_tx: Sender<DataEvent>,
-    system_event_rx: Receiver<DataEvent>,
+    video_frames: TopicQueue,
+    detections: TopicQueue,
+    system_events: TopicQueue,
 }
 
 impl MessageBus {
     pub fn new() -> Self {
-        let (video_frame_tx, video_frame_rx) = bounded(100); // Buffer 100 video frames
-        let (detection_tx, detection_rx) = bounded(50);       // Buffer 50 detection results
-        let (system_event_tx, system_event_rx) = unbounded(); // Unlimited system events
+        Self::bounded(DEFAULT_QUEUE_HIGH_WATER_MARK, &HashMap::new(), DEFAULT_BLOCK_TIMEOUT)
+    }
+    
+    /// Bus with `high_water_mark` messages per topic
+    ///
+    /// Topics missing from `policies` use [`Topic::default_policy`].
+    pub fn bounded(high_water_mark: usize, policies: &HashMap<Topic, BackpressurePolicy>, block_timeout: Duration) -> Self {
+        let queue = |topic: Topic| {
+            let policy = policies.get(&topic).copied().unwrap_or(topic.default_policy());
+            TopicQueue::new(topic, high_water_mark, policy, block_timeout)
+        };
         
         Self {
-            video_frame_tx,
-            video_frame_rx,
-            detection_tx,
-            detection_rx,
-            system_event_tx,
-            system_event_rx,
+            video_frames: queue(Topic::VideoFrame),
+            detections: queue(Topic::DetectionResult),
+            system_events: queue(Topic::SystemEvent),
+        }
+    }
+    
+    fn queue(&self, topic: Topic) -> &TopicQueue {
+        match topic {
+            Topic::VideoFrame => &self.video_frames,
+            Topic::DetectionResult => &self.detections,
+            Topic::SystemEvent => &self.system_events,
         }
     }
     
     /// Publish video frame to the bus
     pub fn publish_video_frame(&self, frame: DataEvent) -> Result<(), String> {
-        self.video_frame_tx.send(frame)
+        self.video_frames.publish(frame)
             .map_err(|e| format!("Failed to publish video frame: {}", e))
     }
     
     /// Subscribe to video frames
     pub fn subscribe_video_frames(&self) -> Receiver<DataEvent> {
-        self.video_frame_rx.clone()
+        self.video_frames.rx.clone()
     }
     
     /// Publish detection result to the bus
     pub fn publish_detection_result(&self, result: DataEvent) -> Result<(), String> {
-        self.detection_tx.send(result)
+        self.detections.publish(result)
             .map_err(|e| format!("Failed to publish detection result: {}", e))
     }
     
     /// Subscribe to detection results
     pub fn subscribe_detection_results(&self) -> Receiver<DataEvent> {
-        self.detection_rx.clone()
+        self.detections.rx.clone()
     }
     
     /// Publish system event to the bus
     pub fn publish_system_event(&self, event: DataEvent) -> Result<(), String> {
-        self.system_event_tx.send(event)
+        self.system_events.publish(event)
             .map_err(|e| format!("Failed to publish system event: {}", e))
     }
     
     /// Subscribe to system events
     pub fn subscribe_system_events(&self) -> Receiver<DataEvent> {
-        self.system_event_rx.clone()
+        self.system_events.rx.clone()
+    }
+    
+    /// Take the oldest queued message of a topic, if any
+    pub fn take(&self, topic: Topic) -> Option<DataEvent> {
+        self.queue(topic).rx.try_recv().ok()
+    }
+    
+    /// Discard every queued message of a topic; returns how many there were
+    pub fn drain(&self, topic: Topic) -> usize {
+        self.queue(topic).rx.try_iter().count()
+    }
+    
+    /// Block until a message of `topic` arrives, failing after `timeout`
+    pub fn wait(&self, topic: Topic, timeout: Duration) -> Result<DataEvent, String> {
+        self.queue(topic).rx.recv_timeout(timeout).map_err(|e| match e {
+            RecvTimeoutError::Timeout => format!("No {:?} message within {}ms", topic, timeout.as_millis()),
+            RecvTimeoutError::Disconnected => format!("{:?} queue disconnected", topic),
+        })
     }
     
     /// Get bus statistics
     pub fn get_stats(&self) -> MessageBusStats {
         MessageBusStats {
-            video_frame_queue_len: self.video_frame_rx.len(),
-            detection_queue_len: self.detection_rx.len(),
-            system_event_queue_len: self.system_event_rx.len(),
+            video_frame_queue_len: self.video_frames.rx.len(),
+            detection_queue_len: self.detections.rx.len(),
+            system_event_queue_len: self.system_events.rx.len(),
+            video_frames_dropped: self.video_frames.dropped(),
+            detections_dropped: self.detections.dropped(),
+            system_events_dropped: self.system_events.dropped(),
         }
     }
 }
@@ -121,6 +265,10 @@ pub struct MessageBusStats {
     pub video_frame_queue_len: usize,
     pub detection_queue_len: usize,
     pub system_event_queue_len: usize,
+    /// Messages discarded by the drop-oldest policy since the bus was created
+    pub video_frames_dropped: u64,
+    pub detections_dropped: u64,
+    pub system_events_dropped: u64,
 }
 
 /// Data flow manager coordinates all pub/sub messaging
@@ -128,6 +276,10 @@ pub struct DataFlowManager {
     publishers: HashMap<String, PublisherInfo>,
     subscribers: HashMap<String, SubscriberInfo>,
     message_stats: MessageStats,
+    /// Message log receiving every published event, while recording
+    recorder: Option<EventRecorder>,
+    /// Message log feeding the pipeline instead of live stages, while replaying
+    replayer: Option<EventReplayer>,
 }
 
 /// Publisher information
@@ -166,6 +318,8 @@ impl DataFlowManager {
             publishers: HashMap::new(),
             subscribers: HashMap::new(),
             message_stats: MessageStats::default(),
+            recorder: None,
+            replayer: None,
         }
     }
     
@@ -267,4 +421,204 @@ impl DataFlowManager {
     pub fn list_subscribers(&self) -> Vec<String> {
         self.subscribers.keys().cloned().collect()
     }
-}
\ No newline at end of file
+    
+    /// Bus topics a component subscribes to, i.e. messages addressed to it
+    pub fn subscribed_topics(&self, component_id: &str) -> Vec<Topic> {
+        self.subscribers.values()
+            .filter(|subscriber| subscriber.component_id == component_id)
+            .filter_map(|subscriber| match subscriber.data_type.as_str() {
+                "video-frame" => Some(Topic::VideoFrame),
+                "detection-result" => Some(Topic::DetectionResult),
+                "system-event" => Some(Topic::SystemEvent),
+                _ => None,
+            })
+            .collect()
+    }
+    
+    /// Start writing every published event to the log at `path`
+    ///
+    /// A recording already in progress is finished first.
+    pub fn start_recording(&mut self, path: &str) -> Result<(), String> {
+        self.stop_recording()?;
+        self.recorder = Some(EventRecorder::create(path)?);
+        println!("⏺️  Recording message log to {}", path);
+        Ok(())
+    }
+    
+    /// Finish the current recording; returns the number of events written
+    pub fn stop_recording(&mut self) -> Result<u64, String> {
+        match self.recorder.take() {
+            Some(recorder) => recorder.finish(),
+            None => Ok(0),
+        }
+    }
+    
+    /// Log the events one pipeline step published, if recording
+    pub fn record_step(&mut self, step: u64, events: &[DataEvent]) -> Result<(), String> {
+        match self.recorder.as_mut() {
+            Some(recorder) => recorder.record_step(step, events),
+            None => Ok(()),
+        }
+    }
+    
+    /// Replace live stage output with the events logged at `path`
+    pub fn replay(&mut self, path: &str) -> Result<(), String> {
+        self.replayer = Some(EventReplayer::open(path)?);
+        println!("⏯️  Replaying message log from {}", path);
+        Ok(())
+    }
+    
+    pub fn stop_replay(&mut self) {
+        self.replayer = None;
+    }
+    
+    pub fn is_replaying(&self) -> bool {
+        self.replayer.is_some()
+    }
+    
+    /// Events of the next replayed step, waiting until it is due
+    ///
+    /// Leaves replay mode and returns `None` once the log is exhausted.
+    pub fn next_replay_step(&mut self) -> Result<Option<Vec<DataEvent>>, String> {
+        let Some(replayer) = self.replayer.as_mut() else {
+            return Ok(None);
+        };
+        let step = replayer.next_step();
+        match step {
+            Ok(Some(_)) => {}
+            Ok(None) => {
+                println!("⏹️  Message log replay finished");
+                self.replayer = None;
+            }
+            Err(_) => self.replayer = None,
+        }
+        step
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::component_manager::{ComponentManager, ComponentState};
+    use crate::reload::tests::component_exporting;
+    
+    fn frame(frame_number: u64) -> DataEvent {
+        DataEvent::VideoFrame {
+            frame_number,
+            width: 2,
+            height: 2,
+            data: vec![0; 12],
+            timestamp: 0,
+        }
+    }
+    
+    fn frame_number(event: DataEvent) -> u64 {
+        match event {
+            DataEvent::VideoFrame { frame_number, .. } => frame_number,
+            other => panic!("expected a video frame, got {:?}", other),
+        }
+    }
+    
+    #[test]
+    fn test_lagging_camera_consumer_drops_oldest_frames() {
+        let bus = MessageBus::bounded(2, &HashMap::new(), Duration::ZERO);
+        for n in 1..=5 {
+            bus.publish_video_frame(frame(n)).unwrap();
+        }
+        
+        let stats = bus.get_stats();
+        assert_eq!((stats.video_frame_queue_len, stats.video_frames_dropped), (2, 3));
+        assert_eq!(bus.take(Topic::VideoFrame).map(frame_number), Some(4));
+        assert_eq!(bus.take(Topic::VideoFrame).map(frame_number), Some(5));
+        assert!(bus.take(Topic::VideoFrame).is_none());
+    }
+    
+    #[test]
+    fn test_blocking_topic_fails_when_consumer_lags() {
+        let bus = MessageBus::bounded(1, &HashMap::new(), Duration::from_millis(1));
+        let result = |n| DataEvent::DetectionResult {
+            frame_number: n,
+            objects: Vec::new(),
+            processing_time_ms: 0.0,
+            timestamp: 0,
+        };
+        
+        bus.publish_detection_result(result(1)).unwrap();
+        assert!(bus.publish_detection_result(result(2)).is_err());
+        assert_eq!(bus.get_stats().detections_dropped, 0);
+        
+        // Draining makes room again
+        assert!(bus.take(Topic::DetectionResult).is_some());
+        bus.publish_detection_result(result(3)).unwrap();
+    }
+    
+    #[test]
+    fn test_policy_override_per_topic() {
+        let policies = HashMap::from([(Topic::VideoFrame, BackpressurePolicy::Block)]);
+        let bus = MessageBus::bounded(1, &policies, Duration::ZERO);
+        
+        bus.publish_video_frame(frame(1)).unwrap();
+        assert!(bus.publish_video_frame(frame(2)).is_err());
+        assert_eq!(bus.take(Topic::VideoFrame).map(frame_number), Some(1));
+    }
+    
+    #[test]
+    fn test_wait_returns_published_frame_or_times_out() {
+        let bus = Arc::new(MessageBus::new());
+        assert!(bus.wait(Topic::VideoFrame, Duration::from_millis(1)).is_err());
+        
+        let publisher = bus.clone();
+        let camera = std::thread::spawn(move || publisher.publish_video_frame(frame(7)));
+        let event = bus.wait(Topic::VideoFrame, Duration::from_secs(5)).unwrap();
+        camera.join().unwrap().unwrap();
+        assert_eq!(frame_number(event), 7);
+    }
+    
+    #[test]
+    fn test_unconsumed_system_events_do_not_block_publishers() {
+        let bus = MessageBus::bounded(1, &HashMap::new(), Duration::from_secs(5));
+        let event = |message: &str| DataEvent::SystemEvent {
+            event_type: "status".to_string(),
+            message: message.to_string(),
+            timestamp: 0,
+        };
+        
+        bus.publish_system_event(event("first")).unwrap();
+        bus.publish_system_event(event("second")).unwrap();
+        assert_eq!(bus.get_stats().system_events_dropped, 1);
+    }
+    
+    #[test]
+    fn test_reload_flushes_topics_addressed_to_component() {
+        let mut flow = DataFlowManager::new();
+        flow.initialize_message_bus().unwrap();
+        assert_eq!(flow.subscribed_topics("object-detection"), vec![Topic::VideoFrame]);
+        assert!(flow.subscribed_topics("video-decoder").is_empty());
+        
+        let bus = MessageBus::new();
+        bus.publish_video_frame(frame(1)).unwrap();
+        bus.publish_video_frame(frame(2)).unwrap();
+        
+        let path = std::env::temp_dir().join(format!("adas-orchestrator-reload-{}.wasm", std::process::id()));
+        let binary = component_exporting(&[
+            "adas:object-detection/detection-engine@0.2.0",
+            "adas:object-detection/diagnostics@0.2.0",
+        ]);
+        std::fs::write(&path, binary).unwrap();
+        
+        let mut manager = ComponentManager::new();
+        manager.initialize_pipeline_components().unwrap();
+        manager.start_all_components().unwrap();
+        let topics = flow.subscribed_topics("object-detection");
+        let flushed = manager
+            .reload_component("object-detection", &path.to_string_lossy(), || {
+                topics.iter().map(|topic| bus.drain(*topic)).sum()
+            })
+            .unwrap();
+        std::fs::remove_file(path).unwrap();
+        
+        assert_eq!(flushed, 2);
+        assert!(bus.take(Topic::VideoFrame).is_none());
+        assert_eq!(manager.get_component_state("object-detection"), Some(ComponentState::Running));
+    }
+}
@@ -0,0 +1,134 @@
+// Heartbeat watchdog for pipeline components
+// Each component reports when it last processed data; the orchestrator polls
+// that timestamp and flags components that stall while still reporting Ok
+
+use std::collections::HashMap;
+
+use adas_timing::elapsed_ms;
+
+/// Default time without processing before a component is flagged
+pub const DEFAULT_HEARTBEAT_TIMEOUT_MS: u64 = 500;
+
+/// A component whose heartbeat is older than the threshold
+#[derive(Debug, Clone, PartialEq)]
+pub struct StaleHeartbeat {
+    pub component_id: String,
+    pub age_ms: u64,
+}
+
+/// Tracks the last activity of every watched component
+#[derive(Debug)]
+pub struct HeartbeatMonitor {
+    stale_after_ms: u64,
+    last_activity: HashMap<String, u64>,
+}
+
+impl HeartbeatMonitor {
+    pub fn new(stale_after_ms: u64) -> Self {
+        Self {
+            stale_after_ms,
+            last_activity: HashMap::new(),
+        }
+    }
+
+    pub fn set_threshold(&mut self, stale_after_ms: u64) {
+        self.stale_after_ms = stale_after_ms;
+    }
+
+    /// Start watching a component; it has until `now_ms + threshold` to report
+    pub fn watch(&mut self, component_id: &str, now_ms: u64) {
+        self.last_activity.insert(component_id.to_string(), now_ms);
+    }
+
+    /// Stop watching a component, e.g. after a deliberate stop
+    pub fn unwatch(&mut self, component_id: &str) {
+        self.last_activity.remove(component_id);
+    }
+
+    /// Record a polled last-activity timestamp
+    ///
+    /// Zero means the component has never processed anything and leaves the
+    /// watch time in place; older timestamps never move the heartbeat back.
+    pub fn record(&mut self, component_id: &str, last_activity_ms: u64) {
+        if last_activity_ms == 0 {
+            return;
+        }
+        if let Some(last) = self.last_activity.get_mut(component_id) {
+            *last = (*last).max(last_activity_ms);
+        }
+    }
+
+    /// Watched components with no activity for longer than the threshold
+    pub fn stale(&self, now_ms: u64) -> Vec<StaleHeartbeat> {
+        let mut stale: Vec<StaleHeartbeat> = self
+            .last_activity
+            .iter()
+            .map(|(id, last)| StaleHeartbeat {
+                component_id: id.clone(),
+                age_ms: elapsed_ms(now_ms, *last),
+            })
+            .filter(|heartbeat| heartbeat.age_ms > self.stale_after_ms)
+            .collect();
+        stale.sort_by(|a, b| a.component_id.cmp(&b.component_id));
+        stale
+    }
+}
+
+impl Default for HeartbeatMonitor {
+    fn default() -> Self {
+        Self::new(DEFAULT_HEARTBEAT_TIMEOUT_MS)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_stalled_component_is_flagged() {
+        let mut monitor = HeartbeatMonitor::new(100);
+        monitor.watch("object-detection", 1_000);
+        monitor.watch("visualizer", 1_000);
+
+        monitor.record("object-detection", 1_050);
+        monitor.record("visualizer", 1_150);
+        assert!(monitor.stale(1_140).is_empty());
+
+        // Detection keeps answering health queries but stops processing
+        monitor.record("visualizer", 1_250);
+        let stale = monitor.stale(1_260);
+        assert_eq!(stale.len(), 1);
+        assert_eq!(stale[0].component_id, "object-detection");
+        assert_eq!(stale[0].age_ms, 210);
+    }
+
+    #[test]
+    fn test_never_reporting_component_times_out_from_watch() {
+        let mut monitor = HeartbeatMonitor::new(100);
+        monitor.watch("video-decoder", 1_000);
+        monitor.record("video-decoder", 0);
+
+        assert!(monitor.stale(1_100).is_empty());
+        assert_eq!(monitor.stale(1_101).len(), 1);
+    }
+
+    #[test]
+    fn test_unwatched_and_unknown_components_ignored() {
+        let mut monitor = HeartbeatMonitor::new(100);
+        monitor.watch("safety-monitor", 1_000);
+        monitor.unwatch("safety-monitor");
+        monitor.record("unknown", 1_000);
+
+        assert!(monitor.stale(10_000).is_empty());
+    }
+
+    #[test]
+    fn test_older_report_does_not_rewind() {
+        let mut monitor = HeartbeatMonitor::new(100);
+        monitor.watch("visualizer", 1_000);
+        monitor.record("visualizer", 1_500);
+        monitor.record("visualizer", 1_200);
+
+        assert!(monitor.stale(1_600).is_empty());
+    }
+}
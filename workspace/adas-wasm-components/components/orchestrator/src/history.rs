@@ -0,0 +1,166 @@
+// Performance history ring buffer
+// Keeps the most recent performance snapshots so callers can look at
+// trends over a time window instead of a single reading
+
+use std::collections::VecDeque;
+
+use adas_timing::elapsed_ms;
+
+/// Snapshots kept by default; about 20s of history at 30 steps per second
+pub const DEFAULT_PERFORMANCE_HISTORY_LEN: usize = 600;
+
+/// Bounded history of timestamped snapshots, oldest first
+#[derive(Debug)]
+pub struct PerformanceHistory<T> {
+    capacity: usize,
+    samples: VecDeque<(u64, T)>,
+}
+
+impl<T: Clone> PerformanceHistory<T> {
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            capacity: capacity.max(1),
+            samples: VecDeque::new(),
+        }
+    }
+
+    /// Change how many snapshots are kept, dropping the oldest if needed
+    pub fn set_capacity(&mut self, capacity: usize) {
+        self.capacity = capacity.max(1);
+        while self.samples.len() > self.capacity {
+            self.samples.pop_front();
+        }
+    }
+
+    /// Add a snapshot taken at `timestamp_ms`, evicting the oldest when full
+    pub fn record(&mut self, timestamp_ms: u64, sample: T) {
+        if self.samples.len() == self.capacity {
+            self.samples.pop_front();
+        }
+        self.samples.push_back((timestamp_ms, sample));
+    }
+
+    /// Snapshots taken within `window_ms` before `now_ms`, oldest first
+    pub fn window(&self, now_ms: u64, window_ms: u64) -> Vec<T> {
+        self.samples
+            .iter()
+            .filter(|(timestamp_ms, _)| elapsed_ms(now_ms, *timestamp_ms) <= window_ms)
+            .map(|(_, sample)| sample.clone())
+            .collect()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.samples.is_empty()
+    }
+
+    pub fn clear(&mut self) {
+        self.samples.clear();
+    }
+}
+
+impl<T: Clone> Default for PerformanceHistory<T> {
+    fn default() -> Self {
+        Self::new(DEFAULT_PERFORMANCE_HISTORY_LEN)
+    }
+}
+
+/// Measured duration and rate of pipeline steps since the last reset
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct StepStats {
+    steps: u64,
+    total_ms: f64,
+    max_ms: f32,
+    first_started_ms: u64,
+    last_finished_ms: u64,
+}
+
+impl StepStats {
+    /// Account for a step that finished at `timestamp_ms` after running `execution_ms`
+    pub fn record(&mut self, timestamp_ms: u64, execution_ms: f32) {
+        if self.steps == 0 {
+            self.first_started_ms = timestamp_ms.saturating_sub(execution_ms.ceil() as u64);
+        }
+        self.steps += 1;
+        self.total_ms += execution_ms as f64;
+        self.max_ms = self.max_ms.max(execution_ms);
+        self.last_finished_ms = timestamp_ms;
+    }
+
+    pub fn avg_ms(&self) -> f32 {
+        if self.steps == 0 {
+            return 0.0;
+        }
+        (self.total_ms / self.steps as f64) as f32
+    }
+
+    pub fn max_ms(&self) -> f32 {
+        self.max_ms
+    }
+
+    /// Fraction of the time since the first step started spent executing steps
+    pub fn busy_fraction(&self) -> f32 {
+        let span_ms = elapsed_ms(self.last_finished_ms, self.first_started_ms);
+        if span_ms == 0 {
+            return 0.0;
+        }
+        (self.total_ms / span_ms as f64).min(1.0) as f32
+    }
+
+    /// Steps completed per second since the first step started
+    pub fn steps_per_second(&self) -> f32 {
+        let span_ms = elapsed_ms(self.last_finished_ms, self.first_started_ms);
+        if span_ms == 0 {
+            return 0.0;
+        }
+        self.steps as f32 * 1000.0 / span_ms as f32
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_window_returns_recent_samples_in_order() {
+        let mut history = PerformanceHistory::new(10);
+        for (timestamp_ms, value) in [(1_000, 'a'), (2_000, 'b'), (3_000, 'c')] {
+            history.record(timestamp_ms, value);
+        }
+
+        assert_eq!(history.window(3_000, 1_000), ['b', 'c']);
+        assert_eq!(history.window(3_000, 5_000), ['a', 'b', 'c']);
+        assert!(history.window(10_000, 1_000).is_empty());
+    }
+
+    #[test]
+    fn test_oldest_samples_evicted_at_capacity() {
+        let mut history = PerformanceHistory::new(2);
+        for timestamp_ms in 1..=3 {
+            history.record(timestamp_ms, timestamp_ms);
+        }
+        assert_eq!(history.window(3, 10), [2, 3]);
+
+        history.set_capacity(1);
+        assert_eq!(history.window(3, 10), [3]);
+
+        history.clear();
+        assert!(history.is_empty());
+    }
+
+    #[test]
+    fn test_step_stats_follow_measured_steps() {
+        let mut stats = StepStats::default();
+        assert_eq!(stats.avg_ms(), 0.0);
+        assert_eq!(stats.steps_per_second(), 0.0);
+
+        // Five steps over half a second, the first starting at 0ms and one taking 25ms
+        for (timestamp_ms, execution_ms) in [(5, 5.0), (105, 5.0), (225, 25.0), (305, 5.0), (500, 5.0)] {
+            stats.record(timestamp_ms, execution_ms);
+        }
+
+        assert_eq!(stats.avg_ms(), 9.0);
+        assert_eq!(stats.max_ms(), 25.0);
+        assert!((stats.busy_fraction() - 0.09).abs() < 1e-6);
+        assert!((stats.steps_per_second() - 10.0).abs() < 1e-6);
+    }
+}
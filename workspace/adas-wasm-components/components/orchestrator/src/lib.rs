@@ -4,26 +4,47 @@
 // The bindings are generated as a separate crate based on the BUILD target name
 use adas_orchestrator_ecu_bindings::Guest;
 
+use std::cell::RefCell;
 use std::collections::{HashMap, VecDeque};
 use std::sync::{Arc, Mutex};
-use std::time::{SystemTime, UNIX_EPOCH, Instant};
+use std::time::Instant;
 use crossbeam_channel::{bounded, Receiver, Sender};
 
 mod data_flow;
 mod component_manager;
+mod heartbeat;
+mod history;
+mod negotiation;
 mod pipeline;
+mod reload;
+mod replay;
+mod scheduling;
+mod supervision;
 
-use data_flow::{DataFlowManager, DataEvent, MessageBus};
+use data_flow::{DataFlowManager, DataEvent};
 use component_manager::{ComponentManager, ComponentInfo, ComponentState};
+use history::{PerformanceHistory, StepStats};
 use pipeline::{Pipeline, PipelineConfig};
+use scheduling::SchedulingMode;
 
 struct Orchestrator;
 
 // Global orchestrator state
-static mut ORCHESTRATOR_RUNNING: bool = false;
-static mut PIPELINE_ACTIVE: bool = false;
-static mut COMPONENTS_REGISTERED: u32 = 0;
-static mut MESSAGES_PROCESSED: u64 = 0;
+#[derive(Debug, Default, Clone, Copy)]
+struct RunState {
+    orchestrator_running: bool,
+    pipeline_active: bool,
+    components_registered: u32,
+    messages_processed: u64,
+}
+
+thread_local! {
+    static RUN_STATE: RefCell<RunState> = RefCell::new(RunState::default());
+}
+
+fn run_state() -> RunState {
+    RUN_STATE.with(|state| *state.borrow())
+}
 
 // Shared state for the orchestrator
 lazy_static::lazy_static! {
@@ -33,15 +54,14 @@ lazy_static::lazy_static! {
         Arc::new(Mutex::new(ComponentManager::new()));
     static ref PIPELINE: Arc<Mutex<Option<Pipeline>>> = 
         Arc::new(Mutex::new(None));
-    static ref MESSAGE_BUS: Arc<MessageBus> = 
-        Arc::new(MessageBus::new());
+    static ref PERFORMANCE_HISTORY: Arc<Mutex<PerformanceHistory<exports::adas::diagnostics::performance_monitoring::ExtendedPerformance>>> = 
+        Arc::new(Mutex::new(PerformanceHistory::default()));
+    static ref STEP_STATS: Arc<Mutex<StepStats>> = 
+        Arc::new(Mutex::new(StepStats::default()));
 }
 
 fn get_timestamp() -> u64 {
-    SystemTime::now()
-        .duration_since(UNIX_EPOCH)
-        .unwrap()
-        .as_millis() as u64
+    adas_timing::monotonic_now_ms()
 }
 
 // Implement orchestration control interface
@@ -49,15 +69,35 @@ impl exports::adas::orchestration::orchestration_control::Guest for Orchestrator
     fn start_orchestration(config: exports::adas::orchestration::orchestration_control::OrchestrationConfig) -> Result<(), String> {
         println!("🚀 Starting ADAS Orchestrator");
         
-        unsafe {
-            if ORCHESTRATOR_RUNNING {
-                return Err("Orchestrator already running".to_string());
-            }
+        if run_state().orchestrator_running {
+            return Err("Orchestrator already running".to_string());
+        }
+        
+        // Create and start pipeline
+        let scheduling = match config.scheduling {
+            exports::adas::orchestration::orchestration_control::SchedulingMode::FixedRate(hz) => SchedulingMode::FixedRate { hz },
+            exports::adas::orchestration::orchestration_control::SchedulingMode::EventDriven => SchedulingMode::EventDriven,
+        };
+        let pipeline_config = PipelineConfig {
+            // Step budgets follow the fixed rate when one is set
+            target_fps: match scheduling {
+                SchedulingMode::FixedRate { hz } if hz > 0.0 => hz,
+                _ => config.target_fps,
+            },
+            scheduling,
+            max_latency_ms: config.max_latency_ms,
+            enable_diagnostics: config.enable_diagnostics,
+            ..PipelineConfig::default()
+        };
+        
+        if let Ok(mut history) = PERFORMANCE_HISTORY.lock() {
+            history.set_capacity(config.performance_history_len as usize);
         }
         
         // Initialize components
         let component_mgr = COMPONENT_MANAGER.clone();
         if let Ok(mut mgr) = component_mgr.lock() {
+            mgr.set_heartbeat_timeout(pipeline_config.heartbeat_timeout_ms);
             mgr.initialize_pipeline_components()?;
         }
         
@@ -67,23 +107,17 @@ impl exports::adas::orchestration::orchestration_control::Guest for Orchestrator
             flow.initialize_message_bus()?;
         }
         
-        // Create and start pipeline
-        let pipeline_config = PipelineConfig {
-            target_fps: config.target_fps,
-            max_latency_ms: config.max_latency_ms,
-            enable_diagnostics: config.enable_diagnostics,
-        };
-        
         if let Ok(mut pipeline_guard) = PIPELINE.lock() {
             let mut pipeline = Pipeline::new(pipeline_config);
             pipeline.start()?;
             *pipeline_guard = Some(pipeline);
         }
         
-        unsafe {
-            ORCHESTRATOR_RUNNING = true;
-            PIPELINE_ACTIVE = true;
-        }
+        RUN_STATE.with(|state| {
+            let mut s = state.borrow_mut();
+            s.orchestrator_running = true;
+            s.pipeline_active = true;
+        });
         
         println!("✅ ADAS Orchestrator started successfully");
         Ok(())
@@ -106,32 +140,46 @@ impl exports::adas::orchestration::orchestration_control::Guest for Orchestrator
             mgr.stop_all_components()?;
         }
         
-        unsafe {
-            ORCHESTRATOR_RUNNING = false;
-            PIPELINE_ACTIVE = false;
-        }
+        RUN_STATE.with(|state| {
+            let mut s = state.borrow_mut();
+            s.orchestrator_running = false;
+            s.pipeline_active = false;
+        });
         
         println!("✅ ADAS Orchestrator stopped");
         Ok(())
     }
     
     fn get_orchestration_status() -> exports::adas::orchestration::orchestration_control::OrchestrationStatus {
-        unsafe {
-            let status = if ORCHESTRATOR_RUNNING && PIPELINE_ACTIVE {
-                adas::common_types::types::HealthStatus::Ok
-            } else if ORCHESTRATOR_RUNNING {
-                adas::common_types::types::HealthStatus::Degraded
-            } else {
-                adas::common_types::types::HealthStatus::Offline
-            };
-            
-            exports::adas::orchestration::orchestration_control::OrchestrationStatus {
-                overall_status: status,
-                components_active: COMPONENTS_REGISTERED,
-                messages_processed: MESSAGES_PROCESSED,
-                pipeline_fps: if PIPELINE_ACTIVE { 30.0 } else { 0.0 },
-                timestamp: get_timestamp(),
-            }
+        // Sustained drops mean the frame consumers can't keep up
+        let (frames_dropped, measured_fps, jitter_ms) = PIPELINE
+            .lock()
+            .ok()
+            .and_then(|pipeline| {
+                pipeline.as_ref().map(|pipeline| {
+                    let timing = pipeline.timing_stats();
+                    (pipeline.frames_dropped(), timing.rate_hz(), timing.jitter_ms())
+                })
+            })
+            .unwrap_or((0, 0.0, 0.0));
+        
+        let state = run_state();
+        let status = if state.orchestrator_running && state.pipeline_active {
+            adas::common_types::types::HealthStatus::Ok
+        } else if state.orchestrator_running {
+            adas::common_types::types::HealthStatus::Degraded
+        } else {
+            adas::common_types::types::HealthStatus::Offline
+        };
+        
+        exports::adas::orchestration::orchestration_control::OrchestrationStatus {
+            overall_status: status,
+            components_active: state.components_registered,
+            messages_processed: state.messages_processed,
+            pipeline_fps: if state.pipeline_active { measured_fps } else { 0.0 },
+            frames_dropped,
+            jitter_ms,
+            timestamp: get_timestamp(),
         }
     }
     
@@ -145,34 +193,65 @@ impl exports::adas::orchestration::orchestration_control::Guest for Orchestrator
                 component_type: info.component_type,
                 interface_version: info.interface_version,
                 capabilities: info.capabilities,
+                interfaces: info.interfaces,
             })?;
         }
         
-        unsafe {
-            COMPONENTS_REGISTERED += 1;
-        }
+        RUN_STATE.with(|state| state.borrow_mut().components_registered += 1);
         
         Ok(())
     }
     
     fn execute_pipeline_step() -> Result<exports::adas::orchestration::orchestration_control::PipelineStepResult, String> {
-        unsafe {
-            if !PIPELINE_ACTIVE {
-                return Err("Pipeline not active".to_string());
-            }
+        if !run_state().pipeline_active {
+            return Err("Pipeline not active".to_string());
         }
         
         let start_time = Instant::now();
         
         // Execute one pipeline step
-        if let Ok(pipeline_guard) = PIPELINE.lock() {
-            if let Some(ref pipeline) = *pipeline_guard {
-                let step_result = pipeline.execute_step()?;
+        if let Ok(mut pipeline_guard) = PIPELINE.lock() {
+            if let Some(ref mut pipeline) = *pipeline_guard {
+                // A replayed step waits until it is due, keeping the recorded spacing
+                let replayed = DATA_FLOW_MANAGER.lock().map_err(|_| "Data flow manager unavailable".to_string())?.next_replay_step()?;
+                let step_result = match replayed {
+                    Some(recorded) => pipeline.replay_step(recorded)?,
+                    None => pipeline.execute_step()?,
+                };
+                
+                if let Ok(mut flow) = DATA_FLOW_MANAGER.lock() {
+                    flow.record_step(step_result.step_number, &step_result.consumed)?;
+                }
+                
+                if let Ok(mut mgr) = COMPONENT_MANAGER.lock() {
+                    // Mark components that missed their deadline as faulted
+                    for fault in &step_result.faulted_components {
+                        mgr.record_error(&fault.component_id, fault.reason.clone());
+                    }
+                    
+                    // Flag components that stopped processing, whatever their health says
+                    for (component_id, last_activity_ms) in &step_result.heartbeats {
+                        mgr.update_heartbeat(component_id, *last_activity_ms);
+                    }
+                    mgr.update_heartbeat("orchestrator", get_timestamp());
+                    for stale in mgr.check_heartbeats(get_timestamp()) {
+                        println!("⚠️  Component {} heartbeat stale ({}ms)", stale.component_id, stale.age_ms);
+                    }
+                    for event in mgr.supervise(get_timestamp()) {
+                        println!("🔄 Component {} restart: {:?} ({})", event.component_id, event.outcome, event.reason);
+                    }
+                }
                 
-                let execution_time = start_time.elapsed().as_millis() as f32;
+                let execution_time = start_time.elapsed().as_secs_f32() * 1000.0;
                 
-                unsafe {
-                    MESSAGES_PROCESSED += step_result.messages_processed as u64;
+                RUN_STATE.with(|state| state.borrow_mut().messages_processed += step_result.messages_processed as u64);
+                
+                let stats = STEP_STATS.lock().map(|mut stats| {
+                    stats.record(get_timestamp(), execution_time);
+                    stats.clone()
+                }).unwrap_or_default();
+                if let Ok(mut history) = PERFORMANCE_HISTORY.lock() {
+                    history.record(get_timestamp(), current_performance(&stats));
                 }
                 
                 return Ok(exports::adas::orchestration::orchestration_control::PipelineStepResult {
@@ -187,17 +266,122 @@ impl exports::adas::orchestration::orchestration_control::Guest for Orchestrator
         
         Err("Pipeline not available".to_string())
     }
+    
+    fn start_recording(path: String) -> Result<(), String> {
+        DATA_FLOW_MANAGER
+            .lock()
+            .map_err(|_| "Data flow manager unavailable".to_string())?
+            .start_recording(&path)
+    }
+    
+    fn stop_recording() -> Result<u64, String> {
+        DATA_FLOW_MANAGER
+            .lock()
+            .map_err(|_| "Data flow manager unavailable".to_string())?
+            .stop_recording()
+    }
+    
+    fn start_replay(path: String) -> Result<(), String> {
+        DATA_FLOW_MANAGER
+            .lock()
+            .map_err(|_| "Data flow manager unavailable".to_string())?
+            .replay(&path)
+    }
+    
+    fn stop_replay() {
+        if let Ok(mut flow) = DATA_FLOW_MANAGER.lock() {
+            flow.stop_replay();
+        }
+    }
+    
+    fn get_restart_history() -> Vec<exports::adas::orchestration::orchestration_control::RestartEvent> {
+        use exports::adas::orchestration::orchestration_control::{RestartEvent, RestartOutcome};
+        
+        let history = COMPONENT_MANAGER
+            .lock()
+            .map(|mgr| mgr.restart_history())
+            .unwrap_or_default();
+        
+        history
+            .into_iter()
+            .map(|event| RestartEvent {
+                component_id: event.component_id,
+                timestamp: event.timestamp_ms,
+                reason: event.reason,
+                attempt: event.attempt,
+                outcome: match event.outcome {
+                    supervision::RestartOutcome::Restarted => RestartOutcome::Restarted,
+                    supervision::RestartOutcome::Failed(e) => RestartOutcome::Failed(e),
+                    supervision::RestartOutcome::Suppressed => RestartOutcome::Suppressed,
+                },
+            })
+            .collect()
+    }
+    
+    fn reload_component(component_id: String, wasm_path: String) -> Result<u32, String> {
+        // Messages still queued for the old instance are dropped, not delivered to the new one
+        let topics = DATA_FLOW_MANAGER
+            .lock()
+            .map_err(|_| "Data flow manager unavailable".to_string())?
+            .subscribed_topics(&component_id);
+        let bus = PIPELINE
+            .lock()
+            .ok()
+            .and_then(|pipeline| pipeline.as_ref().map(|pipeline| pipeline.message_bus()));
+        let flush = || match &bus {
+            Some(bus) => topics.iter().map(|topic| bus.drain(*topic)).sum(),
+            None => 0,
+        };
+        
+        let flushed = COMPONENT_MANAGER
+            .lock()
+            .map_err(|_| "Component manager unavailable".to_string())?
+            .reload_component(&component_id, &wasm_path, flush)?;
+        Ok(flushed as u32)
+    }
+    
+    fn get_timing_stats() -> exports::adas::orchestration::orchestration_control::TimingStats {
+        let timing = PIPELINE
+            .lock()
+            .ok()
+            .and_then(|pipeline| pipeline.as_ref().map(|pipeline| pipeline.timing_stats().clone()))
+            .unwrap_or_default();
+        
+        exports::adas::orchestration::orchestration_control::TimingStats {
+            min_interval_ms: timing.min_ms(),
+            avg_interval_ms: timing.avg_ms(),
+            max_interval_ms: timing.max_ms(),
+            jitter_ms: timing.jitter_ms(),
+            samples: timing.samples(),
+        }
+    }
+    
+    fn publish_frame(frame_number: u64, width: u32, height: u32, data: Vec<u8>) -> Result<(), String> {
+        // Taken off the bus by the next step, or wakes a step waiting in event-driven mode
+        let bus = PIPELINE
+            .lock()
+            .map_err(|_| "Pipeline unavailable".to_string())?
+            .as_ref()
+            .map(|pipeline| pipeline.message_bus())
+            .ok_or_else(|| "Pipeline not available".to_string())?;
+        bus.publish_video_frame(DataEvent::VideoFrame {
+            frame_number,
+            width,
+            height,
+            data,
+            timestamp: get_timestamp(),
+        })
+    }
 }
 
 // Implement health monitoring interface
 impl exports::adas::diagnostics::health_monitoring::Guest for Orchestrator {
     fn get_health() -> exports::adas::diagnostics::health_monitoring::HealthReport {
-        let overall_health = unsafe {
-            if ORCHESTRATOR_RUNNING && PIPELINE_ACTIVE {
-                adas::common_types::types::HealthStatus::Ok
-            } else {
-                adas::common_types::types::HealthStatus::Offline
-            }
+        let state = run_state();
+        let overall_health = if state.orchestrator_running && state.pipeline_active {
+            adas::common_types::types::HealthStatus::Ok
+        } else {
+            adas::common_types::types::HealthStatus::Offline
         };
         
         let mut subsystem_health = Vec::new();
@@ -205,7 +389,7 @@ impl exports::adas::diagnostics::health_monitoring::Guest for Orchestrator {
         // Data flow subsystem
         subsystem_health.push(exports::adas::diagnostics::health_monitoring::SubsystemHealth {
             subsystem_name: "data-flow".to_string(),
-            status: if unsafe { ORCHESTRATOR_RUNNING } {
+            status: if state.orchestrator_running {
                 adas::common_types::types::HealthStatus::Ok
             } else {
                 adas::common_types::types::HealthStatus::Offline
@@ -216,18 +400,18 @@ impl exports::adas::diagnostics::health_monitoring::Guest for Orchestrator {
         // Component management subsystem
         subsystem_health.push(exports::adas::diagnostics::health_monitoring::SubsystemHealth {
             subsystem_name: "component-management".to_string(),
-            status: if unsafe { COMPONENTS_REGISTERED > 0 } {
+            status: if state.components_registered > 0 {
                 adas::common_types::types::HealthStatus::Ok
             } else {
                 adas::common_types::types::HealthStatus::Offline
             },
-            details: format!("{} components registered", unsafe { COMPONENTS_REGISTERED }),
+            details: format!("{} components registered", state.components_registered),
         });
         
         // Pipeline subsystem
         subsystem_health.push(exports::adas::diagnostics::health_monitoring::SubsystemHealth {
             subsystem_name: "pipeline".to_string(),
-            status: if unsafe { PIPELINE_ACTIVE } {
+            status: if state.pipeline_active {
                 adas::common_types::types::HealthStatus::Ok
             } else {
                 adas::common_types::types::HealthStatus::Offline
@@ -245,13 +429,14 @@ impl exports::adas::diagnostics::health_monitoring::Guest for Orchestrator {
     }
     
     fn run_diagnostic() -> Result<exports::adas::diagnostics::health_monitoring::DiagnosticResult, String> {
+        let state = run_state();
         let mut test_results = Vec::new();
         let mut overall_score = 100.0;
         
         // Test orchestrator initialization
         test_results.push(exports::adas::diagnostics::health_monitoring::TestExecution {
             test_name: "orchestrator-initialization".to_string(),
-            test_result: if unsafe { ORCHESTRATOR_RUNNING } {
+            test_result: if state.orchestrator_running {
                 adas::common_types::types::TestResult::Passed
             } else {
                 overall_score -= 30.0;
@@ -264,20 +449,20 @@ impl exports::adas::diagnostics::health_monitoring::Guest for Orchestrator {
         // Test component registration
         test_results.push(exports::adas::diagnostics::health_monitoring::TestExecution {
             test_name: "component-registration".to_string(),
-            test_result: if unsafe { COMPONENTS_REGISTERED > 0 } {
+            test_result: if state.components_registered > 0 {
                 adas::common_types::types::TestResult::Passed
             } else {
                 overall_score -= 20.0;
                 adas::common_types::types::TestResult::Failed
             },
-            details: format!("{} components registered", unsafe { COMPONENTS_REGISTERED }),
+            details: format!("{} components registered", state.components_registered),
             execution_time_ms: 3.0,
         });
         
         // Test pipeline execution
         test_results.push(exports::adas::diagnostics::health_monitoring::TestExecution {
             test_name: "pipeline-execution".to_string(),
-            test_result: if unsafe { PIPELINE_ACTIVE } {
+            test_result: if state.pipeline_active {
                 adas::common_types::types::TestResult::Passed
             } else {
                 overall_score -= 25.0;
@@ -290,13 +475,13 @@ impl exports::adas::diagnostics::health_monitoring::Guest for Orchestrator {
         // Test message processing
         test_results.push(exports::adas::diagnostics::health_monitoring::TestExecution {
             test_name: "message-processing".to_string(),
-            test_result: if unsafe { MESSAGES_PROCESSED > 0 } {
+            test_result: if state.messages_processed > 0 {
                 adas::common_types::types::TestResult::Passed
             } else {
                 overall_score -= 15.0;
                 adas::common_types::types::TestResult::Warning
             },
-            details: format!("{} messages processed", unsafe { MESSAGES_PROCESSED }),
+            details: format!("{} messages processed", state.messages_processed),
             execution_time_ms: 2.0,
         });
         
@@ -324,60 +509,71 @@ impl exports::adas::diagnostics::health_monitoring::Guest for Orchestrator {
 // Implement performance monitoring interface
 impl exports::adas::diagnostics::performance_monitoring::Guest for Orchestrator {
     fn get_performance() -> exports::adas::diagnostics::performance_monitoring::ExtendedPerformance {
-        unsafe {
-            exports::adas::diagnostics::performance_monitoring::ExtendedPerformance {
-                base_metrics: adas::common_types::types::PerformanceMetrics {
-                    latency_avg_ms: 10.0,  // Orchestration overhead
-                    latency_max_ms: 25.0,
-                    cpu_utilization: 0.15, // Light orchestration load
-                    memory_usage_mb: 64,   // Message buffers + state
-                    throughput_hz: if PIPELINE_ACTIVE { 30.0 } else { 0.0 },
-                    error_rate: 0.001,
-                },
-                component_specific: vec![
-                    exports::adas::diagnostics::performance_monitoring::Metric {
-                        name: "components_registered".to_string(),
-                        value: COMPONENTS_REGISTERED as f64,
-                        unit: "count".to_string(),
-                        description: "Number of registered components".to_string(),
-                    },
-                    exports::adas::diagnostics::performance_monitoring::Metric {
-                        name: "messages_processed".to_string(),
-                        value: MESSAGES_PROCESSED as f64,
-                        unit: "count".to_string(),
-                        description: "Total messages processed".to_string(),
-                    },
-                    exports::adas::diagnostics::performance_monitoring::Metric {
-                        name: "pipeline_fps".to_string(),
-                        value: if PIPELINE_ACTIVE { 30.0 } else { 0.0 },
-                        unit: "fps".to_string(),
-                        description: "Pipeline execution frequency".to_string(),
-                    },
-                ],
-                resource_usage: exports::adas::diagnostics::performance_monitoring::ResourceUsage {
-                    cpu_cores_used: 0.15,
-                    memory_allocated_mb: 64,
-                    memory_peak_mb: 96,
-                    disk_io_mb: 0.1,
-                    network_io_mb: 0.0,
-                    gpu_utilization: 0.0,
-                    gpu_memory_mb: 0,
-                },
-                timestamp: get_timestamp(),
-            }
-        }
+        current_performance(&STEP_STATS.lock().map(|stats| stats.clone()).unwrap_or_default())
     }
     
-    fn get_performance_history(_duration_seconds: u32) -> Vec<exports::adas::diagnostics::performance_monitoring::ExtendedPerformance> {
-        vec![] // Not implemented
+    fn get_performance_history(duration_seconds: u32) -> Vec<exports::adas::diagnostics::performance_monitoring::ExtendedPerformance> {
+        PERFORMANCE_HISTORY
+            .lock()
+            .map(|history| history.window(get_timestamp(), duration_seconds as u64 * 1000))
+            .unwrap_or_default()
     }
     
     fn reset_counters() {
-        unsafe {
-            MESSAGES_PROCESSED = 0;
+        RUN_STATE.with(|state| state.borrow_mut().messages_processed = 0);
+        if let Ok(mut history) = PERFORMANCE_HISTORY.lock() {
+            history.clear();
+        }
+        if let Ok(mut stats) = STEP_STATS.lock() {
+            *stats = StepStats::default();
         }
         println!("Orchestrator: Reset performance counters");
     }
 }
 
+/// Performance snapshot from the measured pipeline steps, recorded after every step
+fn current_performance(stats: &StepStats) -> exports::adas::diagnostics::performance_monitoring::ExtendedPerformance {
+    let state = run_state();
+    exports::adas::diagnostics::performance_monitoring::ExtendedPerformance {
+        base_metrics: adas::common_types::types::PerformanceMetrics {
+            latency_avg_ms: stats.avg_ms(),
+            latency_max_ms: stats.max_ms(),
+            cpu_utilization: stats.busy_fraction(),
+            memory_usage_mb: 64,   // Message buffers + state
+            throughput_hz: if state.pipeline_active { stats.steps_per_second() } else { 0.0 },
+            error_rate: 0.001,
+        },
+        component_specific: vec![
+            exports::adas::diagnostics::performance_monitoring::Metric {
+                name: "components_registered".to_string(),
+                value: state.components_registered as f64,
+                unit: "count".to_string(),
+                description: "Number of registered components".to_string(),
+            },
+            exports::adas::diagnostics::performance_monitoring::Metric {
+                name: "messages_processed".to_string(),
+                value: state.messages_processed as f64,
+                unit: "count".to_string(),
+                description: "Total messages processed".to_string(),
+            },
+            exports::adas::diagnostics::performance_monitoring::Metric {
+                name: "pipeline_fps".to_string(),
+                value: if state.pipeline_active { stats.steps_per_second() as f64 } else { 0.0 },
+                unit: "fps".to_string(),
+                description: "Pipeline execution frequency".to_string(),
+            },
+        ],
+        resource_usage: exports::adas::diagnostics::performance_monitoring::ResourceUsage {
+            cpu_cores_used: stats.busy_fraction(),
+            memory_allocated_mb: 64,
+            memory_peak_mb: 96,
+            disk_io_mb: 0.1,
+            network_io_mb: 0.0,
+            gpu_utilization: 0.0,
+            gpu_memory_mb: 0,
+        },
+        timestamp: get_timestamp(),
+    }
+}
+
 export!(Orchestrator);
\ No newline at end of file
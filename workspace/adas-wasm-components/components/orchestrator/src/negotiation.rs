@@ -0,0 +1,101 @@
+// Resolution negotiation between the video decoder and its consumers
+// Picks one decode size so the frame is resampled once, not once per consumer
+
+/// Frame size in pixels
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Resolution {
+    pub width: u32,
+    pub height: u32,
+}
+
+impl Resolution {
+    pub const fn new(width: u32, height: u32) -> Self {
+        Self { width, height }
+    }
+
+    fn area(&self) -> u64 {
+        self.width as u64 * self.height as u64
+    }
+}
+
+impl std::fmt::Display for Resolution {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}x{}", self.width, self.height)
+    }
+}
+
+/// Outcome of a negotiation round
+#[derive(Debug, Clone, PartialEq)]
+pub struct NegotiatedResolution {
+    /// Size the decoder emits
+    pub resolution: Resolution,
+    /// Consumer whose preference set the size
+    pub driven_by: Option<String>,
+}
+
+/// Largest size with the source aspect ratio that fits inside `target`
+///
+/// This is the content area a letterboxing consumer actually uses, e.g. a
+/// 16:10 frame inside a 640x640 detector input fills 640x400.
+pub fn fit_aspect(source: Resolution, target: Resolution) -> Resolution {
+    if source.width == 0 || source.height == 0 {
+        return target;
+    }
+
+    let scale = (target.width as f64 / source.width as f64).min(target.height as f64 / source.height as f64);
+    let even = |v: f64| ((v.round() as u32) & !1).max(2);
+    Resolution::new(even(source.width as f64 * scale), even(source.height as f64 * scale))
+}
+
+/// Negotiate the decoder output size from consumer preferences
+///
+/// Each consumer advertises its preferred input size; the decoder emits the
+/// largest content area any consumer uses, keeping the source aspect ratio,
+/// so no consumer has to upscale and letterboxing consumers only pad.
+pub fn negotiate(source: Resolution, preferences: &[(String, Resolution)]) -> NegotiatedResolution {
+    preferences
+        .iter()
+        .map(|(consumer, preferred)| (consumer, fit_aspect(source, *preferred)))
+        .max_by_key(|(_, fitted)| fitted.area())
+        .map(|(consumer, resolution)| NegotiatedResolution {
+            resolution,
+            driven_by: Some(consumer.clone()),
+        })
+        .unwrap_or(NegotiatedResolution {
+            resolution: source,
+            driven_by: None,
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_letterboxed_detector_and_visualizer_agree() {
+        let preferences = vec![
+            ("object-detection".to_string(), Resolution::new(640, 640)),
+            ("visualizer".to_string(), Resolution::new(640, 400)),
+        ];
+        let negotiated = negotiate(Resolution::new(320, 200), &preferences);
+        assert_eq!(negotiated.resolution, Resolution::new(640, 400));
+    }
+
+    #[test]
+    fn test_largest_consumer_wins() {
+        let preferences = vec![
+            ("preview".to_string(), Resolution::new(160, 100)),
+            ("recorder".to_string(), Resolution::new(1280, 800)),
+        ];
+        let negotiated = negotiate(Resolution::new(320, 200), &preferences);
+        assert_eq!(negotiated.resolution, Resolution::new(1280, 800));
+        assert_eq!(negotiated.driven_by.as_deref(), Some("recorder"));
+    }
+
+    #[test]
+    fn test_no_consumers_keeps_source() {
+        let negotiated = negotiate(Resolution::new(320, 200), &[]);
+        assert_eq!(negotiated.resolution, Resolution::new(320, 200));
+        assert!(negotiated.driven_by.is_none());
+    }
+}
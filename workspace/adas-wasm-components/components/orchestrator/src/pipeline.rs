@@ -1,8 +1,17 @@
 // Pipeline - Main execution engine for the 5-component ADAS system
 
+use std::collections::hash_map::Entry;
+use std::collections::HashMap;
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
 use std::time::{Duration, Instant};
 use std::thread;
-use crate::data_flow::{DataEvent, MessageBus};
+use crossbeam_channel::{RecvTimeoutError, Sender};
+use crate::data_flow::{BackpressurePolicy, DataEvent, MessageBus, Topic, DEFAULT_QUEUE_HIGH_WATER_MARK};
+use crate::heartbeat::DEFAULT_HEARTBEAT_TIMEOUT_MS;
+use crate::negotiation::{self, NegotiatedResolution, Resolution};
+use crate::scheduling::{IntervalStats, SchedulingMode};
 
 /// Pipeline configuration
 #[derive(Debug, Clone)]
@@ -10,18 +19,127 @@ pub struct PipelineConfig {
     pub target_fps: f32,
     pub max_latency_ms: u32,
     pub enable_diagnostics: bool,
+    /// Latency budget per stage (component id -> ms)
+    pub stage_budgets_ms: HashMap<String, u32>,
+    /// Native resolution of the decoded video source
+    pub source_resolution: Resolution,
+    /// Preferred input resolution advertised by each frame consumer
+    pub consumer_resolutions: Vec<(String, Resolution)>,
+    /// Time without processing before a component is flagged as stalled
+    pub heartbeat_timeout_ms: u64,
+    /// Messages a bus topic holds before its backpressure policy applies
+    pub queue_high_water_mark: usize,
+    /// Backpressure per bus topic; unlisted topics use `Topic::default_policy`
+    pub backpressure: HashMap<Topic, BackpressurePolicy>,
+    /// Whether steps follow the caller's clock or camera frame arrivals
+    pub scheduling: SchedulingMode,
 }
 
 impl Default for PipelineConfig {
     fn default() -> Self {
+        let stage_budgets_ms = [
+            ("video-decoder", 5),
+            ("object-detection", 20),
+            ("visualizer", 5),
+            ("safety-monitor", 3),
+        ]
+        .iter()
+        .map(|(id, ms)| (id.to_string(), *ms))
+        .collect();
+        
+        let consumer_resolutions = vec![
+            ("object-detection".to_string(), Resolution::new(640, 640)),
+            ("visualizer".to_string(), Resolution::new(640, 400)),
+        ];
+        
         Self {
             target_fps: 30.0,
             max_latency_ms: 33, // 33ms for 30 FPS
             enable_diagnostics: true,
+            stage_budgets_ms,
+            source_resolution: Resolution::new(320, 200),
+            consumer_resolutions,
+            heartbeat_timeout_ms: DEFAULT_HEARTBEAT_TIMEOUT_MS,
+            queue_high_water_mark: DEFAULT_QUEUE_HIGH_WATER_MARK,
+            backpressure: HashMap::new(),
+            scheduling: SchedulingMode::default(),
         }
     }
 }
 
+impl PipelineConfig {
+    /// Latency budget for a stage, defaulting to the whole-step budget
+    pub fn stage_budget_ms(&self, stage: &str) -> u32 {
+        self.stage_budgets_ms.get(stage).copied().unwrap_or(self.max_latency_ms)
+    }
+}
+
+/// Outcome of a single pipeline stage call
+#[derive(Debug, Clone, PartialEq)]
+pub enum StageOutcome {
+    /// Stage produced output within its budget
+    Completed,
+    /// Stage exceeded its latency budget; its output was discarded
+    TimedOut { elapsed_ms: u32, budget_ms: u32 },
+    /// Stage produced no output
+    Failed,
+    /// Stage was not called: it is still running a call that overran, dispatched `elapsed_ms` ago
+    Busy { elapsed_ms: u32 },
+}
+
+type StageJob = Box<dyn FnOnce() + Send>;
+
+/// Thread that runs one stage's calls, one at a time, for the life of the pipeline
+struct StageWorker {
+    jobs: Sender<StageJob>,
+    /// Cleared on dispatch, set again once the call returns
+    idle: Arc<AtomicBool>,
+    dispatched_at: Instant,
+}
+
+impl StageWorker {
+    fn spawn(stage: &str) -> std::io::Result<Self> {
+        let (jobs, queue) = crossbeam_channel::unbounded::<StageJob>();
+        let idle = Arc::new(AtomicBool::new(true));
+        let worker_idle = idle.clone();
+        thread::Builder::new()
+            .name(format!("stage-{}", stage))
+            .spawn(move || {
+                // Runs until the pipeline drops its sender; a panicking call only loses its output
+                for job in queue {
+                    let _ = panic::catch_unwind(AssertUnwindSafe(job));
+                    worker_idle.store(true, Ordering::Release);
+                }
+            })?;
+        Ok(Self {
+            jobs,
+            idle,
+            dispatched_at: Instant::now(),
+        })
+    }
+    
+    /// Hand a call to the worker, or report how long the previous one has been running
+    fn dispatch(&mut self, job: StageJob) -> Result<(), StageOutcome> {
+        if !self.idle.load(Ordering::Acquire) {
+            let elapsed_ms = self.dispatched_at.elapsed().as_millis() as u32;
+            return Err(StageOutcome::Busy { elapsed_ms });
+        }
+        self.idle.store(false, Ordering::Release);
+        self.dispatched_at = Instant::now();
+        self.jobs.send(job).map_err(|_| {
+            self.idle.store(true, Ordering::Release);
+            StageOutcome::Failed
+        })
+    }
+}
+
+/// A component that faulted during a pipeline step
+#[derive(Debug, Clone)]
+pub struct StageFault {
+    pub component_id: String,
+    pub reason: String,
+}
+
 /// Pipeline execution result
 #[derive(Debug)]
 pub struct PipelineStepResult {
@@ -29,6 +147,12 @@ pub struct PipelineStepResult {
     pub messages_processed: u32,
     pub components_updated: u32,
     pub execution_time_ms: f32,
+    /// Components that missed their deadline or failed in this step
+    pub faulted_components: Vec<StageFault>,
+    /// Last-activity timestamp polled from each stage after this step
+    pub heartbeats: Vec<(String, u64)>,
+    /// Camera frame and detection result consumed downstream in this step
+    pub consumed: Vec<DataEvent>,
 }
 
 /// Main pipeline execution engine
@@ -39,10 +163,31 @@ pub struct Pipeline {
     last_step_time: Option<Instant>,
     total_frames_processed: u64,
     total_detections: u64,
+    /// Last output of each stage that completed within budget
+    last_good_outputs: HashMap<String, DataEvent>,
+    /// Decoder output size agreed with the frame consumers at start
+    frame_resolution: NegotiatedResolution,
+    /// Last time each stage completed a call
+    last_activity_ms: HashMap<String, u64>,
+    /// Bounded queues carrying stage outputs to their consumers
+    bus: Arc<MessageBus>,
+    /// Start of the previous step, for measuring the step interval
+    previous_step_start: Option<Instant>,
+    /// Time between consecutive step starts since the pipeline started
+    intervals: IntervalStats,
+    /// Worker thread of each stage, started on its first call
+    workers: HashMap<String, StageWorker>,
 }
 
 impl Pipeline {
     pub fn new(config: PipelineConfig) -> Self {
+        let frame_resolution = negotiation::negotiate(config.source_resolution, &config.consumer_resolutions);
+        // A blocked producer may hold up the step for at most its latency budget
+        let bus = Arc::new(MessageBus::bounded(
+            config.queue_high_water_mark,
+            &config.backpressure,
+            Duration::from_millis(config.max_latency_ms as u64),
+        ));
         Self {
             config,
             step_number: 0,
@@ -50,25 +195,68 @@ impl Pipeline {
             last_step_time: None,
             total_frames_processed: 0,
             total_detections: 0,
+            last_good_outputs: HashMap::new(),
+            frame_resolution,
+            last_activity_ms: HashMap::new(),
+            bus,
+            previous_step_start: None,
+            intervals: IntervalStats::default(),
+            workers: HashMap::new(),
         }
     }
     
+    /// Bus the stages publish to, for additional subscribers
+    pub fn message_bus(&self) -> Arc<MessageBus> {
+        self.bus.clone()
+    }
+    
+    /// Camera frames discarded because their consumer fell behind
+    pub fn frames_dropped(&self) -> u64 {
+        self.bus.get_stats().video_frames_dropped
+    }
+    
+    /// Min/avg/max step interval and jitter against the fixed-rate target
+    pub fn timing_stats(&self) -> &IntervalStats {
+        &self.intervals
+    }
+    
     /// Start the pipeline
+    ///
+    /// Starting a running pipeline is a no-op and keeps its step counter.
     pub fn start(&mut self) -> Result<(), String> {
+        if self.is_running {
+            return Ok(());
+        }
+        
         println!("🚀 Starting ADAS pipeline");
         println!("  Target FPS: {:.1}", self.config.target_fps);
         println!("  Max latency: {}ms", self.config.max_latency_ms);
+        println!("  Scheduling: {:?}", self.config.scheduling);
+        
+        self.frame_resolution = negotiation::negotiate(self.config.source_resolution, &self.config.consumer_resolutions);
+        println!("  Frame resolution: {} (source {}, set by {})",
+                 self.frame_resolution.resolution,
+                 self.config.source_resolution,
+                 self.frame_resolution.driven_by.as_deref().unwrap_or("source"));
         
         self.is_running = true;
         self.step_number = 0;
         self.last_step_time = Some(Instant::now());
+        self.previous_step_start = None;
+        self.intervals = IntervalStats::default();
         
         println!("✅ Pipeline started successfully");
         Ok(())
     }
     
     /// Stop the pipeline
+    ///
+    /// Stopping a pipeline that is not running is a no-op.
     pub fn stop(&mut self) -> Result<(), String> {
+        if !self.is_running {
+            return Ok(());
+        }
+        
         println!("🛑 Stopping ADAS pipeline");
         
         self.is_running = false;
@@ -83,33 +271,117 @@ impl Pipeline {
     }
     
     /// Execute one pipeline step
-    pub fn execute_step(&self) -> Result<PipelineStepResult, String> {
+    ///
+    /// Every stage runs under its latency budget. A stage that overruns or
+    /// fails is reported in `faulted_components` and the step continues with
+    /// that stage's last good output (or a safe default).
+    ///
+    /// Stage outputs travel over the bounded message bus. When consumers lag,
+    /// publishing drops the oldest camera frame or, for detection results,
+    /// fails the step once the block timeout passes.
+    ///
+    /// In [`SchedulingMode::EventDriven`] the step runs no decoder stage of
+    /// its own: it blocks until a camera frame is published on the bus and
+    /// fails if none arrives within the heartbeat timeout.
+    pub fn execute_step(&mut self) -> Result<PipelineStepResult, String> {
+        self.run_step(None)
+    }
+    
+    /// Execute one step on events from a message log instead of live stages
+    ///
+    /// The logged camera frame and detection result stand in for the decoder
+    /// and detection stages, so everything downstream sees exactly what it
+    /// saw when the log was recorded.
+    pub fn replay_step(&mut self, recorded: Vec<DataEvent>) -> Result<PipelineStepResult, String> {
+        self.run_step(Some(recorded))
+    }
+    
+    fn run_step(&mut self, mut recorded: Option<Vec<DataEvent>>) -> Result<PipelineStepResult, String> {
         if !self.is_running {
             return Err("Pipeline not running".to_string());
         }
         
-        let step_start = Instant::now();
+        self.step_number += 1;
         let mut messages_processed = 0;
         let mut components_updated = 0;
+        let mut faults = Vec::new();
+        let mut consumed = Vec::new();
         
         // Simulate pipeline execution for the 5-component system
         
         // Step 1: Video Decoder - Generate/decode video frame
-        if let Some(video_frame) = self.simulate_video_decoder_step() {
+        let (step_start, camera_frame) = match (recorded.as_mut(), self.config.scheduling) {
+            (Some(recorded), _) => {
+                let video_frame = Self::take_recorded(recorded, Topic::VideoFrame);
+                if video_frame.is_some() {
+                    self.poll_heartbeat("video-decoder", &StageOutcome::Completed);
+                }
+                (Instant::now(), video_frame)
+            }
+            (None, SchedulingMode::FixedRate { .. }) => {
+                let step_start = Instant::now();
+                let (resolution, frame_number) = (self.frame_resolution.resolution, self.step_number);
+                let decode = move || Self::simulate_video_decoder_step(resolution, frame_number);
+                if let Some(video_frame) = self.run_data_stage("video-decoder", &mut faults, decode) {
+                    self.bus.publish_video_frame(video_frame)?;
+                }
+                (step_start, None)
+            }
+            (None, SchedulingMode::EventDriven) => {
+                let timeout = Duration::from_millis(self.config.heartbeat_timeout_ms);
+                let video_frame = self.bus.wait(Topic::VideoFrame, timeout)?;
+                self.poll_heartbeat("video-decoder", &StageOutcome::Completed);
+                (Instant::now(), Some(video_frame))
+            }
+        };
+        self.record_interval(step_start);
+        
+        // Detection takes the oldest frame still queued
+        if let Some(video_frame) = camera_frame.or_else(|| self.bus.take(Topic::VideoFrame)) {
             messages_processed += 1;
             components_updated += 1;
             
             // Step 2: Object Detection - Process video frame
-            if let Some(detection_result) = self.simulate_object_detection_step(&video_frame) {
+            let replayed_detection = recorded
+                .as_mut()
+                .and_then(|recorded| Self::take_recorded(recorded, Topic::DetectionResult));
+            let detection_result = match replayed_detection {
+                Some(detection_result) => {
+                    self.poll_heartbeat("object-detection", &StageOutcome::Completed);
+                    detection_result
+                }
+                None => {
+                    let frame = video_frame.clone();
+                    let detect = move || Self::simulate_object_detection_step(&frame);
+                    self.run_data_stage("object-detection", &mut faults, detect)
+                        .unwrap_or_else(|| Self::empty_detection_result(&video_frame))
+                }
+            };
+            consumed.push(video_frame);
+            self.bus.publish_detection_result(detection_result)?;
+            
+            if let Some(detection_result) = self.bus.take(Topic::DetectionResult) {
+                consumed.push(detection_result.clone());
                 messages_processed += 1;
                 components_updated += 1;
                 
                 // Step 3: Visualizer - Display results
-                self.simulate_visualizer_step(&detection_result);
+                let (step_number, diagnostics) = (self.step_number, self.config.enable_diagnostics);
+                let (_, outcome) = self.run_stage("visualizer", move || {
+                    Self::simulate_visualizer_step(&detection_result, step_number, diagnostics);
+                    Some(())
+                });
+                self.poll_heartbeat("visualizer", &outcome);
+                Self::record_fault("visualizer", outcome, &mut faults);
                 components_updated += 1;
                 
                 // Step 4: Safety Monitor - Check system health
-                self.simulate_safety_monitor_step();
+                let (_, outcome) = self.run_stage("safety-monitor", move || {
+                    Self::simulate_safety_monitor_step(step_number, diagnostics);
+                    Some(())
+                });
+                self.poll_heartbeat("safety-monitor", &outcome);
+                Self::record_fault("safety-monitor", outcome, &mut faults);
                 components_updated += 1;
             }
         }
@@ -124,29 +396,176 @@ impl Pipeline {
         }
         
         Ok(PipelineStepResult {
-            step_number: self.step_number + 1,
+            step_number: self.step_number,
             messages_processed,
             components_updated,
             execution_time_ms: execution_time,
+            faulted_components: faults,
+            heartbeats: self.last_activity_ms.iter().map(|(id, ms)| (id.clone(), *ms)).collect(),
+            consumed,
         })
     }
     
+    /// Remove the first logged event of a topic
+    fn take_recorded(recorded: &mut Vec<DataEvent>, topic: Topic) -> Option<DataEvent> {
+        let index = recorded.iter().position(|event| event.topic() == topic)?;
+        Some(recorded.remove(index))
+    }
+    
+    /// Record the time since the previous step started
+    fn record_interval(&mut self, step_start: Instant) {
+        if let Some(previous) = self.previous_step_start.replace(step_start) {
+            let interval_ms = step_start.duration_since(previous).as_secs_f32() * 1000.0;
+            self.intervals.record(interval_ms, self.config.scheduling.target_interval_ms());
+        }
+    }
+    
+    /// Call a stage and hold it to its latency budget
+    ///
+    /// The call runs on the stage's worker thread and the step waits for it
+    /// at most the stage budget. A stage that overruns is abandoned: the step
+    /// goes on without its output, and the worker's late result is dropped.
+    /// Until that call returns the stage is not called again; each step
+    /// reports it [`StageOutcome::Busy`], so a hung stage ties up one thread
+    /// however many steps it spans.
+    ///
+    /// wasm32 guests have no threads, so there the call runs inline and the
+    /// budget is only checked once it returns: a hung stage blocks the step.
+    fn run_stage<T: Send + 'static>(
+        &mut self,
+        stage: &str,
+        call: impl FnOnce() -> Option<T> + Send + 'static,
+    ) -> (Option<T>, StageOutcome) {
+        let budget_ms = self.config.stage_budget_ms(stage);
+        let start = Instant::now();
+        
+        let output = if cfg!(target_family = "wasm") {
+            let output = call();
+            let elapsed_ms = start.elapsed().as_millis() as u32;
+            if output.is_some() && elapsed_ms > budget_ms {
+                return (None, StageOutcome::TimedOut { elapsed_ms, budget_ms });
+            }
+            output
+        } else {
+            let worker = match self.workers.entry(stage.to_string()) {
+                Entry::Occupied(entry) => entry.into_mut(),
+                Entry::Vacant(entry) => match StageWorker::spawn(stage) {
+                    Ok(worker) => entry.insert(worker),
+                    Err(e) => {
+                        println!("⚠️  Could not start stage {}: {}", stage, e);
+                        return (None, StageOutcome::Failed);
+                    }
+                },
+            };
+            let (done, result) = crossbeam_channel::bounded(1);
+            if let Err(outcome) = worker.dispatch(Box::new(move || {
+                let _ = done.send(call());
+            })) {
+                return (None, outcome);
+            }
+            match result.recv_timeout(Duration::from_millis(budget_ms as u64)) {
+                Ok(output) => output,
+                Err(RecvTimeoutError::Timeout) => {
+                    let elapsed_ms = start.elapsed().as_millis() as u32;
+                    return (None, StageOutcome::TimedOut { elapsed_ms, budget_ms });
+                }
+                // The stage panicked
+                Err(RecvTimeoutError::Disconnected) => None,
+            }
+        };
+        
+        match output {
+            Some(output) => (Some(output), StageOutcome::Completed),
+            None => (None, StageOutcome::Failed),
+        }
+    }
+    
+    /// Run a stage that produces data, falling back to its last good output
+    fn run_data_stage(
+        &mut self,
+        stage: &str,
+        faults: &mut Vec<StageFault>,
+        call: impl FnOnce() -> Option<DataEvent> + Send + 'static,
+    ) -> Option<DataEvent> {
+        let (output, outcome) = self.run_stage(stage, call);
+        self.poll_heartbeat(stage, &outcome);
+        
+        match output {
+            Some(event) => {
+                self.last_good_outputs.insert(stage.to_string(), event.clone());
+                Some(event)
+            }
+            None => {
+                Self::record_fault(stage, outcome, faults);
+                self.last_good_outputs.get(stage).cloned()
+            }
+        }
+    }
+    
+    /// Poll a stage's last-activity timestamp after calling it
+    ///
+    /// Only a call that completed within its budget counts as activity; a
+    /// stage that keeps overrunning is abandoned every step and goes stale.
+    fn poll_heartbeat(&mut self, stage: &str, outcome: &StageOutcome) {
+        if *outcome == StageOutcome::Completed {
+            self.last_activity_ms.insert(stage.to_string(), adas_timing::monotonic_now_ms());
+        }
+    }
+    
+    /// Record a stage fault for anything other than a completed call
+    fn record_fault(stage: &str, outcome: StageOutcome, faults: &mut Vec<StageFault>) {
+        let reason = match outcome {
+            StageOutcome::Completed => return,
+            StageOutcome::TimedOut { elapsed_ms, budget_ms } => {
+                format!("Exceeded latency budget: {}ms > {}ms", elapsed_ms, budget_ms)
+            }
+            StageOutcome::Failed => "Stage produced no output".to_string(),
+            StageOutcome::Busy { elapsed_ms } => {
+                format!("Still running a call dispatched {}ms ago", elapsed_ms)
+            }
+        };
+        
+        println!("⚠️  Stage {} faulted: {}", stage, reason);
+        faults.push(StageFault {
+            component_id: stage.to_string(),
+            reason,
+        });
+    }
+    
+    /// Safe default when detection has no usable output: no objects
+    fn empty_detection_result(video_frame: &DataEvent) -> DataEvent {
+        let frame_number = match video_frame {
+            DataEvent::VideoFrame { frame_number, .. } => *frame_number,
+            _ => 0,
+        };
+        
+        DataEvent::DetectionResult {
+            frame_number,
+            objects: Vec::new(),
+            processing_time_ms: 0.0,
+            timestamp: crate::get_timestamp(),
+        }
+    }
+    
     /// Simulate video decoder step
-    fn simulate_video_decoder_step(&self) -> Option<DataEvent> {
-        // Simulate generating a video frame
-        let frame_data = vec![128u8; 320 * 200 * 3]; // 320x200 RGB frame
+    ///
+    /// The decoder emits the negotiated resolution so consumers receive the
+    /// frame at their working size instead of each resampling it.
+    fn simulate_video_decoder_step(resolution: Resolution, frame_number: u64) -> Option<DataEvent> {
+        let Resolution { width, height } = resolution;
+        let frame_data = vec![128u8; (width * height * 3) as usize]; // RGB frame
         
         Some(DataEvent::VideoFrame {
-            frame_number: self.step_number + 1,
-            width: 320,
-            height: 200,
+            frame_number,
+            width,
+            height,
             data: frame_data,
             timestamp: crate::get_timestamp(),
         })
     }
     
     /// Simulate object detection step
-    fn simulate_object_detection_step(&self, video_frame: &DataEvent) -> Option<DataEvent> {
+    fn simulate_object_detection_step(video_frame: &DataEvent) -> Option<DataEvent> {
         if let DataEvent::VideoFrame { frame_number, .. } = video_frame {
             // Simulate AI processing delay
             thread::sleep(Duration::from_millis(5));
@@ -189,10 +608,10 @@ impl Pipeline {
     }
     
     /// Simulate visualizer step
-    fn simulate_visualizer_step(&self, detection_result: &DataEvent) {
+    fn simulate_visualizer_step(detection_result: &DataEvent, step_number: u64, enable_diagnostics: bool) {
         if let DataEvent::DetectionResult { objects, frame_number, .. } = detection_result {
             // Simulate rendering detection results
-            if self.config.enable_diagnostics && self.step_number % 30 == 0 {
+            if enable_diagnostics && step_number % 30 == 0 {
                 println!("🎨 Frame {}: Rendered {} objects", frame_number, objects.len());
                 for (i, obj) in objects.iter().enumerate() {
                     println!("   Object {}: {} ({:.1}% confidence)", 
@@ -212,9 +631,9 @@ impl Pipeline {
     }
     
     /// Simulate safety monitor step
-    fn simulate_safety_monitor_step(&self) {
+    fn simulate_safety_monitor_step(step_number: u64, enable_diagnostics: bool) {
         // Simulate safety checks
-        if self.step_number % 100 == 0 && self.config.enable_diagnostics {
+        if step_number % 100 == 0 && enable_diagnostics {
             println!("🛡️  Safety check: All systems operational");
         }
     }
@@ -287,4 +706,119 @@ impl PipelineStatistics {
         
         (self.effective_fps / self.target_fps * 100.0).min(100.0)
     }
-}
\ No newline at end of file
+}
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    fn event_driven_pipeline() -> Pipeline {
+        let mut pipeline = Pipeline::new(PipelineConfig {
+            enable_diagnostics: false,
+            scheduling: SchedulingMode::EventDriven,
+            ..PipelineConfig::default()
+        });
+        pipeline.start().unwrap();
+        pipeline
+    }
+    
+    fn camera_frame(frame_number: u64) -> DataEvent {
+        DataEvent::VideoFrame {
+            frame_number,
+            width: 320,
+            height: 200,
+            data: Vec::new(),
+            timestamp: 0,
+        }
+    }
+    
+    fn frame_number(event: Option<DataEvent>) -> Option<u64> {
+        match event? {
+            DataEvent::VideoFrame { frame_number, .. } => Some(frame_number),
+            other => panic!("expected a video frame, got {:?}", other),
+        }
+    }
+    
+    #[test]
+    fn test_event_driven_step_runs_on_published_frame() {
+        let mut pipeline = event_driven_pipeline();
+        let bus = pipeline.message_bus();
+        let camera = thread::spawn(move || bus.publish_video_frame(camera_frame(42)));
+        let step = pipeline.execute_step().unwrap();
+        camera.join().unwrap().unwrap();
+        
+        assert_eq!(step.step_number, 1);
+        assert_eq!(step.messages_processed, 2);
+        match &step.consumed[0] {
+            DataEvent::VideoFrame { frame_number, .. } => assert_eq!(*frame_number, 42),
+            other => panic!("expected a video frame, got {:?}", other),
+        }
+    }
+    
+    #[test]
+    fn test_overrunning_stage_is_abandoned_at_its_deadline() {
+        let mut pipeline = Pipeline::new(PipelineConfig {
+            stage_budgets_ms: HashMap::from([("hung".to_string(), 10), ("quick".to_string(), 5_000)]),
+            ..PipelineConfig::default()
+        });
+        
+        let start = Instant::now();
+        let (output, outcome) = pipeline.run_stage("hung", || {
+            thread::sleep(Duration::from_secs(5));
+            Some(())
+        });
+        assert!(start.elapsed() < Duration::from_secs(2));
+        assert!(output.is_none());
+        assert!(matches!(outcome, StageOutcome::TimedOut { budget_ms: 10, .. }));
+        
+        assert_eq!(pipeline.run_stage("quick", || Some(7)), (Some(7), StageOutcome::Completed));
+        assert_eq!(pipeline.run_stage("quick", || None::<u32>), (None, StageOutcome::Failed));
+    }    
+    #[test]
+    fn test_hung_stage_is_skipped_until_it_returns() {
+        let mut pipeline = Pipeline::new(PipelineConfig {
+            stage_budgets_ms: HashMap::from([("video-decoder".to_string(), 20)]),
+            ..PipelineConfig::default()
+        });
+        let mut faults = Vec::new();
+        let good = pipeline.run_data_stage("video-decoder", &mut faults, || Some(camera_frame(1)));
+        assert_eq!(frame_number(good), Some(1));
+        
+        // The second call hangs until released
+        let (release, hang) = crossbeam_channel::bounded::<()>(1);
+        let output = pipeline.run_data_stage("video-decoder", &mut faults, move || {
+            let _ = hang.recv();
+            Some(camera_frame(2))
+        });
+        assert_eq!(frame_number(output), Some(1));
+        
+        // Later steps neither call the stage nor wait for it
+        let calls = Arc::new(AtomicBool::new(false));
+        for _ in 0..3 {
+            let called = calls.clone();
+            let start = Instant::now();
+            let output = pipeline.run_data_stage("video-decoder", &mut faults, move || {
+                called.store(true, Ordering::SeqCst);
+                Some(camera_frame(3))
+            });
+            assert!(start.elapsed() < Duration::from_millis(20));
+            assert_eq!(frame_number(output), Some(1));
+        }
+        assert!(!calls.load(Ordering::SeqCst));
+        assert_eq!(faults.len(), 4);
+        assert!(faults[0].reason.starts_with("Exceeded latency budget"));
+        assert!(faults[1..].iter().all(|f| f.reason.starts_with("Still running a call")));
+        
+        // Once the hung call returns, the same worker takes calls again
+        release.send(()).unwrap();
+        let deadline = Instant::now() + Duration::from_secs(2);
+        let outcome = loop {
+            let (output, outcome) = pipeline.run_stage("video-decoder", || Some(4));
+            if !matches!(outcome, StageOutcome::Busy { .. }) || Instant::now() > deadline {
+                break (output, outcome);
+            }
+            thread::sleep(Duration::from_millis(1));
+        };
+        assert_eq!(outcome, (Some(4), StageOutcome::Completed));
+        assert_eq!(pipeline.workers.len(), 1);
+    }
+}
@@ -0,0 +1,285 @@
+// Hot reload support - reads the interfaces a recompiled component exports
+// A reload is only accepted when the new binary exports exactly the interface
+// set of the instance it replaces, so its consumers stay wired correctly
+
+use std::collections::BTreeSet;
+
+/// Component model binary preamble: magic, version 0x0d, layer 1
+const COMPONENT_MAGIC: [u8; 4] = *b"\0asm";
+const COMPONENT_LAYER: [u8; 2] = [0x01, 0x00];
+
+const EXPORT_SECTION_ID: u8 = 11;
+const INSTANCE_SORT: u8 = 0x05;
+
+/// Interfaces a reloadable component binary exports
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ComponentModule {
+    pub path: String,
+    pub exports: BTreeSet<String>,
+}
+
+impl ComponentModule {
+    /// Read the component binary at `path`
+    pub fn load(path: &str) -> Result<Self, String> {
+        let bytes = std::fs::read(path).map_err(|e| format!("Failed to read {}: {}", path, e))?;
+        let exports = exported_interfaces(&bytes).map_err(|e| format!("{}: {}", path, e))?;
+        Ok(Self {
+            path: path.to_string(),
+            exports,
+        })
+    }
+
+    /// Reject a replacement whose exported interface set differs
+    pub fn check_compatible(&self, replacement: &ComponentModule) -> Result<(), String> {
+        let missing: Vec<_> = self.exports.difference(&replacement.exports).cloned().collect();
+        let added: Vec<_> = replacement.exports.difference(&self.exports).cloned().collect();
+        incompatibility(&replacement.path, &missing, "no longer exports", &added, "newly exports")
+    }
+
+    /// Reject a binary that does not export exactly the registered interfaces
+    ///
+    /// Registered names carry no version, so versions are ignored here.
+    pub fn check_registered(&self, registered: &[String]) -> Result<(), String> {
+        let registered: BTreeSet<&str> = registered.iter().map(|name| unversioned(name)).collect();
+        let exported: BTreeSet<&str> = self.exports.iter().map(|name| unversioned(name)).collect();
+        let missing: Vec<_> = registered.difference(&exported).map(|name| name.to_string()).collect();
+        let added: Vec<_> = exported.difference(&registered).map(|name| name.to_string()).collect();
+        incompatibility(&self.path, &missing, "does not export registered", &added, "exports unregistered")
+    }
+}
+
+/// Interface name without its `@version` suffix
+fn unversioned(name: &str) -> &str {
+    name.split('@').next().unwrap_or(name)
+}
+
+/// Describe the interfaces a binary is missing and the ones it adds, if any
+fn incompatibility(
+    path: &str,
+    missing: &[String],
+    missing_label: &str,
+    added: &[String],
+    added_label: &str,
+) -> Result<(), String> {
+    let mut problems = Vec::new();
+    if !missing.is_empty() {
+        problems.push(format!("{} {}", missing_label, missing.join(", ")));
+    }
+    if !added.is_empty() {
+        problems.push(format!("{} {}", added_label, added.join(", ")));
+    }
+    if problems.is_empty() {
+        return Ok(());
+    }
+    Err(format!("Incompatible reload: {} {}", path, problems.join(" and ")))
+}
+
+/// Names of the interfaces (instance exports) of a component binary
+///
+/// Only the top-level export section is read; nested components and core
+/// modules are skipped.
+pub fn exported_interfaces(bytes: &[u8]) -> Result<BTreeSet<String>, String> {
+    if bytes.len() < 8 || bytes[..4] != COMPONENT_MAGIC {
+        return Err("not a WebAssembly binary".to_string());
+    }
+    if bytes[6..8] != COMPONENT_LAYER {
+        return Err("a core module, not a component".to_string());
+    }
+
+    let mut reader = Reader { bytes, pos: 8 };
+    let mut interfaces = BTreeSet::new();
+    while !reader.at_end() {
+        let id = reader.byte()?;
+        let size = reader.u32()? as usize;
+        let section = reader.take(size)?;
+        if id == EXPORT_SECTION_ID {
+            read_exports(section, &mut interfaces)?;
+        }
+    }
+    Ok(interfaces)
+}
+
+fn read_exports(section: &[u8], interfaces: &mut BTreeSet<String>) -> Result<(), String> {
+    let mut reader = Reader { bytes: section, pos: 0 };
+    for _ in 0..reader.u32()? {
+        // exportname: 0x00 or 0x01 followed by the name
+        match reader.byte()? {
+            0x00 | 0x01 => {}
+            other => return Err(format!("invalid export name prefix 0x{:02x}", other)),
+        }
+        let name = reader.string()?;
+
+        // sortidx: sort (core sorts carry a second byte), then the index
+        let sort = reader.byte()?;
+        if sort == 0x00 {
+            reader.byte()?;
+        }
+        reader.u32()?;
+
+        // Optional ascribed type
+        if reader.byte()? == 0x01 {
+            reader.skip_extern_desc()?;
+        }
+
+        if sort == INSTANCE_SORT {
+            interfaces.insert(name);
+        }
+    }
+    Ok(())
+}
+
+struct Reader<'a> {
+    bytes: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> Reader<'a> {
+    fn at_end(&self) -> bool {
+        self.pos >= self.bytes.len()
+    }
+
+    fn byte(&mut self) -> Result<u8, String> {
+        let byte = *self.bytes.get(self.pos).ok_or("unexpected end of component binary")?;
+        self.pos += 1;
+        Ok(byte)
+    }
+
+    fn take(&mut self, len: usize) -> Result<&'a [u8], String> {
+        let end = self.pos.checked_add(len).filter(|end| *end <= self.bytes.len());
+        let end = end.ok_or("section extends past the end of the component binary")?;
+        let slice = &self.bytes[self.pos..end];
+        self.pos = end;
+        Ok(slice)
+    }
+
+    /// Unsigned LEB128; also skips the signed encodings used for value types
+    fn u32(&mut self) -> Result<u32, String> {
+        let mut value: u32 = 0;
+        for shift in (0..35).step_by(7) {
+            let byte = self.byte()?;
+            value |= ((byte & 0x7f) as u32).checked_shl(shift).unwrap_or(0);
+            if byte & 0x80 == 0 {
+                return Ok(value);
+            }
+        }
+        Err("malformed LEB128 integer".to_string())
+    }
+
+    fn string(&mut self) -> Result<String, String> {
+        let len = self.u32()? as usize;
+        let bytes = self.take(len)?;
+        String::from_utf8(bytes.to_vec()).map_err(|_| "export name is not UTF-8".to_string())
+    }
+
+    fn skip_extern_desc(&mut self) -> Result<(), String> {
+        match self.byte()? {
+            // Core module: 0x11 marker then type index
+            0x00 => {
+                self.byte()?;
+                self.u32()?;
+            }
+            // Function, component or instance type index
+            0x01 | 0x04 | 0x05 => {
+                self.u32()?;
+            }
+            // Value and type bounds: a tag then an index or value type
+            0x02 | 0x03 => {
+                self.byte()?;
+                self.u32()?;
+            }
+            other => return Err(format!("invalid extern descriptor 0x{:02x}", other)),
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+
+    /// Component binary with one export section holding `exports`
+    fn component(exports: &[(&str, u8)]) -> Vec<u8> {
+        let mut section = vec![exports.len() as u8];
+        for (name, sort) in exports {
+            section.push(0x00);
+            section.push(name.len() as u8);
+            section.extend_from_slice(name.as_bytes());
+            section.extend_from_slice(&[*sort, 0x00]);
+            // Ascribe an instance type to interfaces, nothing to functions
+            if *sort == INSTANCE_SORT {
+                section.extend_from_slice(&[0x01, 0x05, 0x02]);
+            } else {
+                section.push(0x00);
+            }
+        }
+
+        let mut bytes = b"\0asm\x0d\x00\x01\x00".to_vec();
+        // A custom section before the exports is skipped
+        bytes.extend_from_slice(&[0x00, 0x03, 0x01, b'x', 0xff]);
+        bytes.push(EXPORT_SECTION_ID);
+        bytes.push(section.len() as u8);
+        bytes.extend(section);
+        bytes
+    }
+
+    /// Component binary exporting each of `interfaces`
+    pub(crate) fn component_exporting(interfaces: &[&str]) -> Vec<u8> {
+        let exports: Vec<_> = interfaces.iter().map(|name| (*name, INSTANCE_SORT)).collect();
+        component(&exports)
+    }
+
+    fn module(path: &str, exports: &[&str]) -> ComponentModule {
+        ComponentModule {
+            path: path.to_string(),
+            exports: exports.iter().map(|name| name.to_string()).collect(),
+        }
+    }
+
+    #[test]
+    fn test_reads_exported_interfaces() {
+        let bytes = component(&[
+            ("adas:diagnostics/health-monitoring@0.1.0", INSTANCE_SORT),
+            ("run", 0x01),
+            ("adas:control/ai-control@0.1.0", INSTANCE_SORT),
+        ]);
+        let interfaces: Vec<_> = exported_interfaces(&bytes).unwrap().into_iter().collect();
+        assert_eq!(
+            interfaces,
+            ["adas:control/ai-control@0.1.0", "adas:diagnostics/health-monitoring@0.1.0"]
+        );
+
+        assert!(exported_interfaces(b"\0asm\x01\x00\x00\x00").unwrap_err().contains("core module"));
+        assert!(exported_interfaces(b"ELF").is_err());
+        let truncated = &bytes[..bytes.len() - 4];
+        assert!(exported_interfaces(truncated).is_err());
+    }
+
+    #[test]
+    fn test_rejects_changed_interface_set() {
+        let current = module("old.wasm", &["adas:control/ai-control", "adas:data/data-flow"]);
+        let reordered = module("new.wasm", &["adas:data/data-flow", "adas:control/ai-control"]);
+        assert!(current.check_compatible(&reordered).is_ok());
+
+        let error = current
+            .check_compatible(&module("new.wasm", &["adas:control/ai-control", "adas:control/sensor-control"]))
+            .unwrap_err();
+        assert_eq!(
+            error,
+            "Incompatible reload: new.wasm no longer exports adas:data/data-flow \
+             and newly exports adas:control/sensor-control"
+        );
+    }
+
+    #[test]
+    fn test_checks_registered_interfaces_without_version() {
+        let binary = module("new.wasm", &["adas:control/ai-control@0.1.0", "adas:data/data-flow@0.1.0"]);
+        let registered = ["adas:data/data-flow".to_string(), "adas:control/ai-control".to_string()];
+        assert!(binary.check_registered(&registered).is_ok());
+
+        assert_eq!(
+            binary.check_registered(&registered[..1]).unwrap_err(),
+            "Incompatible reload: new.wasm exports unregistered adas:control/ai-control"
+        );
+        assert!(module("new.wasm", &[]).check_registered(&[]).is_ok());
+    }
+}
@@ -0,0 +1,271 @@
+// Message log recording and deterministic replay
+// Every event a pipeline step publishes is written as one JSON line tagged
+// with its step number and time since recording started; replay feeds the
+// logged events back one step at a time with the original spacing
+
+use std::fs::File;
+use std::io::{BufRead, BufReader, BufWriter, Lines, Write};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use serde::{Deserialize, Serialize};
+
+use crate::data_flow::DataEvent;
+
+/// One logged event
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RecordedEvent {
+    /// Pipeline step that published the event
+    pub step: u64,
+    /// Time since recording started
+    pub offset_ms: u64,
+    pub event: DataEvent,
+}
+
+/// Writes published events to a message log
+pub struct EventRecorder {
+    writer: BufWriter<File>,
+    started: Instant,
+    events_recorded: u64,
+}
+
+impl EventRecorder {
+    /// Create (or truncate) the log at `path`
+    pub fn create(path: &str) -> Result<Self, String> {
+        let file = File::create(path)
+            .map_err(|e| format!("Failed to create message log {}: {}", path, e))?;
+        Ok(Self {
+            writer: BufWriter::new(file),
+            started: Instant::now(),
+            events_recorded: 0,
+        })
+    }
+
+    /// Append the events published by one step
+    pub fn record_step(&mut self, step: u64, events: &[DataEvent]) -> Result<(), String> {
+        let offset_ms = self.started.elapsed().as_millis() as u64;
+        for event in events {
+            let record = RecordedEvent {
+                step,
+                offset_ms,
+                event: event.clone(),
+            };
+            serde_json::to_writer(&mut self.writer, &record)
+                .map_err(|e| format!("Failed to record event: {}", e))?;
+            self.writer
+                .write_all(b"\n")
+                .map_err(|e| format!("Failed to record event: {}", e))?;
+            self.events_recorded += 1;
+        }
+        Ok(())
+    }
+
+    /// Flush the log; returns the number of events recorded
+    pub fn finish(mut self) -> Result<u64, String> {
+        self.writer
+            .flush()
+            .map_err(|e| format!("Failed to flush message log: {}", e))?;
+        Ok(self.events_recorded)
+    }
+}
+
+/// Reads a message log back one step at a time
+pub struct EventReplayer {
+    lines: Lines<BufReader<File>>,
+    /// First event of the next step, read while collecting the previous one
+    pending: Option<RecordedEvent>,
+    /// Replay start and the log offset it corresponds to
+    origin: Option<(Instant, u64)>,
+}
+
+impl EventReplayer {
+    pub fn open(path: &str) -> Result<Self, String> {
+        let file =
+            File::open(path).map_err(|e| format!("Failed to open message log {}: {}", path, e))?;
+        Ok(Self {
+            lines: BufReader::new(file).lines(),
+            pending: None,
+            origin: None,
+        })
+    }
+
+    /// Events of the next logged step, or `None` at the end of the log
+    ///
+    /// Blocks until the step is due, so steps keep the spacing they had
+    /// when recorded relative to the first replayed step.
+    pub fn next_step(&mut self) -> Result<Option<Vec<DataEvent>>, String> {
+        let first = match self.pending.take() {
+            Some(record) => record,
+            None => match self.read_record()? {
+                Some(record) => record,
+                None => return Ok(None),
+            },
+        };
+
+        let (step, offset_ms) = (first.step, first.offset_ms);
+        let mut events = vec![first.event];
+        loop {
+            match self.read_record()? {
+                Some(record) if record.step == step => events.push(record.event),
+                next => {
+                    self.pending = next;
+                    break;
+                }
+            }
+        }
+
+        let (started, base_offset_ms) = *self
+            .origin
+            .get_or_insert_with(|| (Instant::now(), offset_ms));
+        let due = Duration::from_millis(offset_ms.saturating_sub(base_offset_ms));
+        if let Some(wait) = due.checked_sub(started.elapsed()) {
+            thread::sleep(wait);
+        }
+
+        Ok(Some(events))
+    }
+
+    fn read_record(&mut self) -> Result<Option<RecordedEvent>, String> {
+        for line in self.lines.by_ref() {
+            let line = line.map_err(|e| format!("Failed to read message log: {}", e))?;
+            if line.trim().is_empty() {
+                continue;
+            }
+            return serde_json::from_str(&line)
+                .map(Some)
+                .map_err(|e| format!("Corrupt message log entry: {}", e));
+        }
+        Ok(None)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pipeline::{Pipeline, PipelineConfig};
+
+    fn system_event(message: &str) -> DataEvent {
+        DataEvent::SystemEvent {
+            event_type: "test".to_string(),
+            message: message.to_string(),
+            timestamp: 42,
+        }
+    }
+
+    fn messages(events: Vec<DataEvent>) -> Vec<String> {
+        events
+            .into_iter()
+            .map(|event| match event {
+                DataEvent::SystemEvent { message, .. } => message,
+                other => panic!("expected a system event, got {:?}", other),
+            })
+            .collect()
+    }
+
+    fn log_path(name: &str) -> String {
+        std::env::temp_dir()
+            .join(format!(
+                "adas-orchestrator-{}-{}.jsonl",
+                name,
+                std::process::id()
+            ))
+            .to_string_lossy()
+            .into_owned()
+    }
+
+    #[test]
+    fn test_replay_returns_recorded_steps_in_order() {
+        let path = log_path("order");
+        let mut recorder = EventRecorder::create(&path).unwrap();
+        recorder
+            .record_step(1, &[system_event("a"), system_event("b")])
+            .unwrap();
+        recorder.record_step(2, &[]).unwrap();
+        recorder.record_step(3, &[system_event("c")]).unwrap();
+        assert_eq!(recorder.finish().unwrap(), 3);
+
+        let mut replayer = EventReplayer::open(&path).unwrap();
+        assert_eq!(messages(replayer.next_step().unwrap().unwrap()), ["a", "b"]);
+        assert_eq!(messages(replayer.next_step().unwrap().unwrap()), ["c"]);
+        assert!(replayer.next_step().unwrap().is_none());
+        std::fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn test_replay_preserves_relative_timing() {
+        let path = log_path("timing");
+        let records = [(1, 1_000), (2, 1_030)].map(|(step, offset_ms)| RecordedEvent {
+            step,
+            offset_ms,
+            event: system_event("tick"),
+        });
+        let log: String = records
+            .iter()
+            .map(|record| serde_json::to_string(record).unwrap() + "\n")
+            .collect();
+        std::fs::write(&path, log).unwrap();
+
+        let mut replayer = EventReplayer::open(&path).unwrap();
+        let start = Instant::now();
+        replayer.next_step().unwrap().unwrap();
+        assert!(start.elapsed() < Duration::from_millis(30));
+        replayer.next_step().unwrap().unwrap();
+        assert!(start.elapsed() >= Duration::from_millis(30));
+        std::fs::remove_file(path).unwrap();
+    }
+
+    fn frame_numbers(events: &[DataEvent]) -> Vec<u64> {
+        events
+            .iter()
+            .map(|event| match event {
+                DataEvent::VideoFrame { frame_number, .. }
+                | DataEvent::DetectionResult { frame_number, .. } => *frame_number,
+                other => panic!("expected a frame or detection, got {:?}", other),
+            })
+            .collect()
+    }
+
+    fn started_pipeline() -> Pipeline {
+        let mut pipeline = Pipeline::new(PipelineConfig {
+            enable_diagnostics: false,
+            ..PipelineConfig::default()
+        });
+        pipeline.start().unwrap();
+        pipeline
+    }
+
+    #[test]
+    fn test_pipeline_steps_round_trip_through_log() {
+        let path = log_path("round-trip");
+        let mut pipeline = started_pipeline();
+        let mut recorder = EventRecorder::create(&path).unwrap();
+        for expected in 1..=3 {
+            let step = pipeline.execute_step().unwrap();
+            assert_eq!(step.step_number, expected);
+            recorder.record_step(step.step_number, &step.consumed).unwrap();
+        }
+        assert_eq!(recorder.finish().unwrap(), 6);
+
+        let mut replayer = EventReplayer::open(&path).unwrap();
+        let mut pipeline = started_pipeline();
+        for expected in 1..=3 {
+            let recorded = replayer.next_step().unwrap().unwrap();
+            assert_eq!(frame_numbers(&recorded), [expected, expected]);
+            let step = pipeline.replay_step(recorded).unwrap();
+            assert_eq!(step.step_number, expected);
+            assert_eq!(frame_numbers(&step.consumed), [expected, expected]);
+        }
+        assert!(replayer.next_step().unwrap().is_none());
+        std::fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn test_corrupt_log_entry_is_an_error() {
+        let path = log_path("corrupt");
+        std::fs::write(&path, "not json\n").unwrap();
+
+        let mut replayer = EventReplayer::open(&path).unwrap();
+        assert!(replayer.next_step().is_err());
+        std::fs::remove_file(path).unwrap();
+    }
+}
@@ -0,0 +1,140 @@
+// Pipeline scheduling modes and step interval statistics
+// A fixed-rate pipeline is measured against its target interval; an
+// event-driven one steps whenever a camera frame arrives
+
+/// When the pipeline takes a step
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SchedulingMode {
+    /// Step at a fixed rate, driven by the caller's clock
+    FixedRate { hz: f32 },
+    /// Step when a new camera frame is published on the message bus
+    EventDriven,
+}
+
+impl Default for SchedulingMode {
+    fn default() -> Self {
+        SchedulingMode::FixedRate { hz: 30.0 }
+    }
+}
+
+impl SchedulingMode {
+    /// Intended time between steps, if the mode has one
+    pub fn target_interval_ms(&self) -> Option<f32> {
+        match self {
+            SchedulingMode::FixedRate { hz } if *hz > 0.0 => Some(1000.0 / hz),
+            _ => None,
+        }
+    }
+}
+
+/// Running statistics of the time between consecutive step starts
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct IntervalStats {
+    samples: u64,
+    total_ms: f64,
+    min_ms: f32,
+    max_ms: f32,
+    /// Sum of deviations from the target interval
+    total_jitter_ms: f64,
+    jitter_samples: u64,
+}
+
+impl IntervalStats {
+    /// Record one interval, measured against `target_ms` when there is one
+    pub fn record(&mut self, interval_ms: f32, target_ms: Option<f32>) {
+        if self.samples == 0 {
+            self.min_ms = interval_ms;
+            self.max_ms = interval_ms;
+        } else {
+            self.min_ms = self.min_ms.min(interval_ms);
+            self.max_ms = self.max_ms.max(interval_ms);
+        }
+        self.samples += 1;
+        self.total_ms += interval_ms as f64;
+
+        if let Some(target_ms) = target_ms {
+            self.total_jitter_ms += (interval_ms - target_ms).abs() as f64;
+            self.jitter_samples += 1;
+        }
+    }
+
+    pub fn samples(&self) -> u64 {
+        self.samples
+    }
+
+    pub fn min_ms(&self) -> f32 {
+        self.min_ms
+    }
+
+    pub fn max_ms(&self) -> f32 {
+        self.max_ms
+    }
+
+    pub fn avg_ms(&self) -> f32 {
+        if self.samples == 0 {
+            return 0.0;
+        }
+        (self.total_ms / self.samples as f64) as f32
+    }
+
+    /// Mean absolute deviation from the target interval; zero without a target
+    pub fn jitter_ms(&self) -> f32 {
+        if self.jitter_samples == 0 {
+            return 0.0;
+        }
+        (self.total_jitter_ms / self.jitter_samples as f64) as f32
+    }
+
+    /// Steps per second implied by the mean interval
+    pub fn rate_hz(&self) -> f32 {
+        let avg_ms = self.avg_ms();
+        if avg_ms > 0.0 {
+            1000.0 / avg_ms
+        } else {
+            0.0
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fixed_rate_target_interval() {
+        assert_eq!(
+            SchedulingMode::FixedRate { hz: 20.0 }.target_interval_ms(),
+            Some(50.0)
+        );
+        assert_eq!(
+            SchedulingMode::FixedRate { hz: 0.0 }.target_interval_ms(),
+            None
+        );
+        assert_eq!(SchedulingMode::EventDriven.target_interval_ms(), None);
+    }
+
+    #[test]
+    fn test_interval_stats_and_jitter() {
+        let mut stats = IntervalStats::default();
+        for interval in [30.0, 36.0, 33.0, 33.0] {
+            stats.record(interval, Some(33.0));
+        }
+
+        assert_eq!(stats.samples(), 4);
+        assert_eq!((stats.min_ms(), stats.max_ms()), (30.0, 36.0));
+        assert_eq!(stats.avg_ms(), 33.0);
+        assert_eq!(stats.jitter_ms(), 1.5);
+        assert!((stats.rate_hz() - 30.3).abs() < 0.1);
+    }
+
+    #[test]
+    fn test_event_driven_has_no_jitter() {
+        let mut stats = IntervalStats::default();
+        assert_eq!((stats.avg_ms(), stats.rate_hz()), (0.0, 0.0));
+
+        stats.record(80.0, None);
+        stats.record(20.0, None);
+        assert_eq!(stats.avg_ms(), 50.0);
+        assert_eq!(stats.jitter_ms(), 0.0);
+    }
+}
@@ -0,0 +1,257 @@
+// Restart supervision for pipeline components
+// A failed component is stopped and re-initialized according to its restart
+// policy; restarts per component are capped within a window so a component
+// that keeps crashing is left down instead of looping
+
+use std::collections::{HashMap, HashSet, VecDeque};
+
+use adas_timing::elapsed_ms;
+
+/// Default restarts allowed per component within the restart window
+pub const DEFAULT_MAX_RESTARTS: u32 = 3;
+
+/// Default window over which restarts are counted
+pub const DEFAULT_RESTART_WINDOW_MS: u64 = 10_000;
+
+/// Restart events kept for `restart_history`
+const RESTART_HISTORY_LEN: usize = 64;
+
+/// When a failed component is restarted
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RestartPolicy {
+    Never,
+    /// Only after a critical failure, e.g. a stalled heartbeat
+    OnCritical,
+    /// After any failure, including a missed stage deadline
+    Always,
+}
+
+/// How badly a component failed
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum FailureSeverity {
+    /// Recoverable fault such as a missed deadline
+    Fault,
+    /// The component reported or showed a critical health status
+    Critical,
+}
+
+impl RestartPolicy {
+    pub fn restarts_on(self, severity: FailureSeverity) -> bool {
+        match self {
+            RestartPolicy::Never => false,
+            RestartPolicy::OnCritical => severity == FailureSeverity::Critical,
+            RestartPolicy::Always => true,
+        }
+    }
+}
+
+/// What the supervisor wants done with a failed component
+#[derive(Debug, Clone, PartialEq)]
+pub enum RestartDecision {
+    /// Leave the component as it is
+    Skip,
+    /// Restart it; `attempt` counts restarts within the current window
+    Restart { attempt: u32 },
+    /// The restart cap was just reached; the component stays down until
+    /// its oldest restart leaves the window
+    GiveUp,
+}
+
+/// Result of a supervised restart
+#[derive(Debug, Clone, PartialEq)]
+pub enum RestartOutcome {
+    Restarted,
+    Failed(String),
+    /// Not restarted because the restart cap was reached
+    Suppressed,
+}
+
+/// A restart performed or withheld by the supervisor
+#[derive(Debug, Clone, PartialEq)]
+pub struct RestartEvent {
+    pub component_id: String,
+    pub timestamp_ms: u64,
+    /// Failure that triggered the restart
+    pub reason: String,
+    /// Restarts within the window including this one; zero when suppressed
+    pub attempt: u32,
+    pub outcome: RestartOutcome,
+}
+
+/// Restart policies, crash-loop limits and restart history
+#[derive(Debug)]
+pub struct Supervisor {
+    default_policy: RestartPolicy,
+    policies: HashMap<String, RestartPolicy>,
+    max_restarts: u32,
+    window_ms: u64,
+    /// Restart times per component within the window, oldest first
+    recent_restarts: HashMap<String, VecDeque<u64>>,
+    /// Components left down after reaching the restart cap
+    given_up: HashSet<String>,
+    history: VecDeque<RestartEvent>,
+}
+
+impl Supervisor {
+    pub fn new(default_policy: RestartPolicy) -> Self {
+        Self {
+            default_policy,
+            policies: HashMap::new(),
+            max_restarts: DEFAULT_MAX_RESTARTS,
+            window_ms: DEFAULT_RESTART_WINDOW_MS,
+            recent_restarts: HashMap::new(),
+            given_up: HashSet::new(),
+            history: VecDeque::new(),
+        }
+    }
+
+    pub fn set_policy(&mut self, component_id: &str, policy: RestartPolicy) {
+        self.policies.insert(component_id.to_string(), policy);
+    }
+
+    pub fn policy(&self, component_id: &str) -> RestartPolicy {
+        self.policies
+            .get(component_id)
+            .copied()
+            .unwrap_or(self.default_policy)
+    }
+
+    /// Allow at most `max_restarts` restarts of a component within `window_ms`
+    pub fn set_limits(&mut self, max_restarts: u32, window_ms: u64) {
+        self.max_restarts = max_restarts;
+        self.window_ms = window_ms;
+    }
+
+    /// Decide whether a component that failed with `severity` is restarted
+    ///
+    /// A `Restart` decision counts against the cap whether or not the
+    /// restart then succeeds.
+    pub fn decide(
+        &mut self,
+        component_id: &str,
+        severity: FailureSeverity,
+        now_ms: u64,
+    ) -> RestartDecision {
+        if !self.policy(component_id).restarts_on(severity) {
+            return RestartDecision::Skip;
+        }
+
+        let window_ms = self.window_ms;
+        let recent = self
+            .recent_restarts
+            .entry(component_id.to_string())
+            .or_default();
+        while recent
+            .front()
+            .is_some_and(|&at| elapsed_ms(now_ms, at) >= window_ms)
+        {
+            recent.pop_front();
+        }
+
+        if recent.len() >= self.max_restarts as usize {
+            // Report giving up once, not on every check while the window lasts
+            return if self.given_up.insert(component_id.to_string()) {
+                RestartDecision::GiveUp
+            } else {
+                RestartDecision::Skip
+            };
+        }
+
+        self.given_up.remove(component_id);
+        recent.push_back(now_ms);
+        RestartDecision::Restart {
+            attempt: recent.len() as u32,
+        }
+    }
+
+    pub fn record(&mut self, event: RestartEvent) {
+        if self.history.len() == RESTART_HISTORY_LEN {
+            self.history.pop_front();
+        }
+        self.history.push_back(event);
+    }
+
+    /// Restart events, oldest first
+    pub fn history(&self) -> Vec<RestartEvent> {
+        self.history.iter().cloned().collect()
+    }
+}
+
+impl Default for Supervisor {
+    fn default() -> Self {
+        Self::new(RestartPolicy::OnCritical)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_policy_selects_failures_to_restart() {
+        let mut supervisor = Supervisor::default();
+        supervisor.set_policy("orchestrator", RestartPolicy::Never);
+        supervisor.set_policy("visualizer", RestartPolicy::Always);
+
+        assert_eq!(
+            supervisor.decide("object-detection", FailureSeverity::Fault, 0),
+            RestartDecision::Skip
+        );
+        assert_eq!(
+            supervisor.decide("object-detection", FailureSeverity::Critical, 0),
+            RestartDecision::Restart { attempt: 1 }
+        );
+        assert_eq!(
+            supervisor.decide("orchestrator", FailureSeverity::Critical, 0),
+            RestartDecision::Skip
+        );
+        assert_eq!(
+            supervisor.decide("visualizer", FailureSeverity::Fault, 0),
+            RestartDecision::Restart { attempt: 1 }
+        );
+    }
+
+    #[test]
+    fn test_crash_loop_is_capped_until_window_passes() {
+        let mut supervisor = Supervisor::default();
+        supervisor.set_limits(2, 1_000);
+        let decide = |supervisor: &mut Supervisor, now_ms| {
+            supervisor.decide("video-decoder", FailureSeverity::Critical, now_ms)
+        };
+
+        assert_eq!(
+            decide(&mut supervisor, 0),
+            RestartDecision::Restart { attempt: 1 }
+        );
+        assert_eq!(
+            decide(&mut supervisor, 100),
+            RestartDecision::Restart { attempt: 2 }
+        );
+        assert_eq!(decide(&mut supervisor, 200), RestartDecision::GiveUp);
+        assert_eq!(decide(&mut supervisor, 300), RestartDecision::Skip);
+
+        // The first restart has left the window
+        assert_eq!(
+            decide(&mut supervisor, 1_000),
+            RestartDecision::Restart { attempt: 2 }
+        );
+    }
+
+    #[test]
+    fn test_history_keeps_latest_events() {
+        let mut supervisor = Supervisor::default();
+        for n in 0..RESTART_HISTORY_LEN as u64 + 2 {
+            supervisor.record(RestartEvent {
+                component_id: "visualizer".to_string(),
+                timestamp_ms: n,
+                reason: "Heartbeat stale".to_string(),
+                attempt: 1,
+                outcome: RestartOutcome::Restarted,
+            });
+        }
+
+        let history = supervisor.history();
+        assert_eq!(history.len(), RESTART_HISTORY_LEN);
+        assert_eq!(history[0].timestamp_ms, 2);
+    }
+}
@@ -0,0 +1,72 @@
+// Performance history ring buffer
+// Keeps the most recent per-frame performance snapshots so callers can look
+// at trends over a time window instead of a single reading
+
+use std::collections::VecDeque;
+
+use adas_timing::elapsed_ms;
+
+/// Snapshots kept; about 20s of history at 30 frames per second
+pub const PERFORMANCE_HISTORY_LEN: usize = 600;
+
+/// Bounded history of timestamped snapshots, oldest first
+#[derive(Debug)]
+pub struct PerformanceHistory<T> {
+    capacity: usize,
+    samples: VecDeque<(u64, T)>,
+}
+
+impl<T: Clone> PerformanceHistory<T> {
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            capacity: capacity.max(1),
+            samples: VecDeque::new(),
+        }
+    }
+
+    /// Add a snapshot taken at `timestamp_ms`, evicting the oldest when full
+    pub fn record(&mut self, timestamp_ms: u64, sample: T) {
+        if self.samples.len() == self.capacity {
+            self.samples.pop_front();
+        }
+        self.samples.push_back((timestamp_ms, sample));
+    }
+
+    /// Snapshots taken within `window_ms` before `now_ms`, oldest first
+    pub fn window(&self, now_ms: u64, window_ms: u64) -> Vec<T> {
+        self.samples
+            .iter()
+            .filter(|(timestamp_ms, _)| elapsed_ms(now_ms, *timestamp_ms) <= window_ms)
+            .map(|(_, sample)| sample.clone())
+            .collect()
+    }
+
+    pub fn clear(&mut self) {
+        self.samples.clear();
+    }
+}
+
+impl<T: Clone> Default for PerformanceHistory<T> {
+    fn default() -> Self {
+        Self::new(PERFORMANCE_HISTORY_LEN)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_window_is_bounded_and_recent() {
+        let mut history = PerformanceHistory::new(3);
+        for timestamp_ms in [1_000, 2_000, 3_000, 4_000] {
+            history.record(timestamp_ms, timestamp_ms);
+        }
+
+        assert_eq!(history.window(4_000, 10_000), [2_000, 3_000, 4_000]);
+        assert_eq!(history.window(4_000, 1_000), [3_000, 4_000]);
+
+        history.clear();
+        assert!(history.window(4_000, 10_000).is_empty());
+    }
+}
@@ -0,0 +1,339 @@
+// Lane marking extraction and polynomial fitting
+// Bright marking runs are sampled row by row in the lower part of a luma
+// image; on each side the run nearest the image center is taken as the ego
+// lane line and the samples are fitted with a quadratic x(y)
+
+/// Tuning for marking extraction
+#[derive(Debug, Clone, PartialEq)]
+pub struct LaneParams {
+    /// Rows above this fraction of the frame height are ignored
+    pub roi_top: f32,
+    /// Minimum luma of a marking pixel
+    pub marking_threshold: u8,
+    /// Bright runs outside this width range are not lane markings
+    pub min_marking_width_px: u32,
+    pub max_marking_width_px: u32,
+    /// Samples needed before a line is fitted
+    pub min_line_points: u32,
+}
+
+/// Lane line x = c0 + c1·y + c2·y², in frame pixels
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct LanePolynomial {
+    pub c0: f32,
+    pub c1: f32,
+    pub c2: f32,
+}
+
+impl LanePolynomial {
+    pub fn x_at(&self, y: f32) -> f32 {
+        self.c0 + self.c1 * y + self.c2 * y * y
+    }
+}
+
+/// A fitted ego lane line
+#[derive(Debug, Clone, PartialEq)]
+pub struct FittedLine {
+    pub polynomial: LanePolynomial,
+    pub points_used: u32,
+    /// Fraction of the searched rows that had a marking on this side
+    pub confidence: f32,
+}
+
+/// Ego lane lines found in one frame
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct LaneFit {
+    pub left: Option<FittedLine>,
+    pub right: Option<FittedLine>,
+}
+
+/// Find and fit the ego lane lines in a luma image
+pub fn detect_lanes(luma: &[u8], width: u32, height: u32, params: &LaneParams) -> LaneFit {
+    let (w, h) = (width as usize, height as usize);
+    if w == 0 || h == 0 || luma.len() < w * h {
+        return LaneFit::default();
+    }
+
+    let first_row = ((params.roi_top.clamp(0.0, 1.0) * h as f32) as usize).min(h - 1);
+    let center_x = width as f32 / 2.0;
+    let mut left_points = Vec::new();
+    let mut right_points = Vec::new();
+
+    for y in first_row..h {
+        let centers = marking_centers(&luma[y * w..(y + 1) * w], params);
+        if let Some(x) = centers
+            .iter()
+            .copied()
+            .filter(|&x| x < center_x)
+            .reduce(f32::max)
+        {
+            left_points.push((y as f32, x));
+        }
+        if let Some(x) = centers
+            .iter()
+            .copied()
+            .filter(|&x| x >= center_x)
+            .reduce(f32::min)
+        {
+            right_points.push((y as f32, x));
+        }
+    }
+
+    let rows_searched = (h - first_row) as f32;
+    let fit_side = |points: &[(f32, f32)]| {
+        if points.len() < params.min_line_points.max(3) as usize {
+            return None;
+        }
+        fit_quadratic(points, height as f32).map(|polynomial| FittedLine {
+            polynomial,
+            points_used: points.len() as u32,
+            confidence: points.len() as f32 / rows_searched,
+        })
+    };
+
+    LaneFit {
+        left: fit_side(&left_points),
+        right: fit_side(&right_points),
+    }
+}
+
+/// Centers of the bright runs in a row whose width fits a lane marking
+fn marking_centers(row: &[u8], params: &LaneParams) -> Vec<f32> {
+    let mut centers = Vec::new();
+    let mut run_start = None;
+
+    // A trailing dark sentinel closes a run that touches the right edge
+    for (x, &luma) in row.iter().chain(std::iter::once(&0)).enumerate() {
+        match (luma >= params.marking_threshold && x < row.len(), run_start) {
+            (true, None) => run_start = Some(x),
+            (false, Some(start)) => {
+                let run_width = (x - start) as u32;
+                if (params.min_marking_width_px..=params.max_marking_width_px).contains(&run_width)
+                {
+                    centers.push((start + x - 1) as f32 / 2.0);
+                }
+                run_start = None;
+            }
+            _ => {}
+        }
+    }
+
+    centers
+}
+
+/// Least-squares quadratic through `(y, x)` samples
+///
+/// `y` is normalized by `height` while solving to keep the normal equations
+/// well conditioned. Returns `None` when the samples span fewer than three
+/// distinct rows.
+pub fn fit_quadratic(points: &[(f32, f32)], height: f32) -> Option<LanePolynomial> {
+    let scale = height.max(1.0) as f64;
+
+    // Power sums of t and t·x for the 3x3 normal equations
+    let mut t_sums = [0.0f64; 5];
+    let mut tx_sums = [0.0f64; 3];
+    for &(y, x) in points {
+        let t = y as f64 / scale;
+        let mut power = 1.0;
+        for (k, sum) in t_sums.iter_mut().enumerate() {
+            *sum += power;
+            if k < 3 {
+                tx_sums[k] += power * x as f64;
+            }
+            power *= t;
+        }
+    }
+
+    let mut system = [[0.0f64; 4]; 3];
+    for (row, equation) in system.iter_mut().enumerate() {
+        equation[..3].copy_from_slice(&t_sums[row..row + 3]);
+        equation[3] = tx_sums[row];
+    }
+    let [a0, a1, a2] = solve3(system)?;
+
+    Some(LanePolynomial {
+        c0: a0 as f32,
+        c1: (a1 / scale) as f32,
+        c2: (a2 / (scale * scale)) as f32,
+    })
+}
+
+/// Gaussian elimination with partial pivoting on an augmented 3x4 matrix
+fn solve3(mut m: [[f64; 4]; 3]) -> Option<[f64; 3]> {
+    for col in 0..3 {
+        let pivot = (col..3).max_by(|&a, &b| m[a][col].abs().total_cmp(&m[b][col].abs()))?;
+        if m[pivot][col].abs() < 1e-9 {
+            return None;
+        }
+        m.swap(col, pivot);
+        let pivot_row = m[col];
+        for row in m.iter_mut().skip(col + 1) {
+            let factor = row[col] / pivot_row[col];
+            for (value, pivot_value) in row[col..].iter_mut().zip(&pivot_row[col..]) {
+                *value -= factor * pivot_value;
+            }
+        }
+    }
+
+    let mut solution = [0.0; 3];
+    for row in (0..3).rev() {
+        let known: f64 = (row + 1..3).map(|k| m[row][k] * solution[k]).sum();
+        solution[row] = (m[row][3] - known) / m[row][row];
+    }
+    Some(solution)
+}
+
+/// Lateral position of the vehicle between its lane lines
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct LanePosition {
+    /// Distance of the vehicle center from the lane center; positive when
+    /// the vehicle is right of center
+    pub offset_m: f32,
+    /// Gap between each side of the vehicle and the line on that side
+    pub left_clearance_m: f32,
+    pub right_clearance_m: f32,
+}
+
+/// Position of the vehicle at the bottom row of the frame
+///
+/// Assumes a forward camera on the vehicle centerline and a lane of
+/// `lane_width_m`, which sets the pixel scale at that row.
+pub fn lane_position(
+    left: &LanePolynomial,
+    right: &LanePolynomial,
+    width: u32,
+    height: u32,
+    lane_width_m: f32,
+    vehicle_width_m: f32,
+) -> Option<LanePosition> {
+    let y = height.saturating_sub(1) as f32;
+    let (left_x, right_x) = (left.x_at(y), right.x_at(y));
+    if right_x <= left_x {
+        return None;
+    }
+
+    let meters_per_px = lane_width_m / (right_x - left_x);
+    let vehicle_x = width as f32 / 2.0;
+    let half_vehicle_m = vehicle_width_m / 2.0;
+
+    Some(LanePosition {
+        offset_m: (vehicle_x - (left_x + right_x) / 2.0) * meters_per_px,
+        left_clearance_m: (vehicle_x - left_x) * meters_per_px - half_vehicle_m,
+        right_clearance_m: (right_x - vehicle_x) * meters_per_px - half_vehicle_m,
+    })
+}
+
+/// Side of the lane the vehicle is drifting out of
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DepartureSide {
+    Left,
+    Right,
+}
+
+/// The side whose clearance is below `threshold_m`, and that clearance
+pub fn departure(position: &LanePosition, threshold_m: f32) -> Option<(DepartureSide, f32)> {
+    let (side, clearance_m) = if position.left_clearance_m <= position.right_clearance_m {
+        (DepartureSide::Left, position.left_clearance_m)
+    } else {
+        (DepartureSide::Right, position.right_clearance_m)
+    };
+    (clearance_m < threshold_m).then_some((side, clearance_m))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn params() -> LaneParams {
+        LaneParams {
+            roi_top: 0.5,
+            marking_threshold: 180,
+            min_marking_width_px: 2,
+            max_marking_width_px: 20,
+            min_line_points: 8,
+        }
+    }
+
+    /// Left edge of a marking per row, and its width in pixels
+    type Marking = (fn(f32) -> f32, u32);
+
+    /// Dark road with markings painted on every row
+    fn road(width: u32, height: u32, markings: &[Marking]) -> Vec<u8> {
+        let mut luma = vec![40u8; (width * height) as usize];
+        for y in 0..height {
+            for &(x_of, marking_width) in markings {
+                let start = x_of(y as f32) as u32;
+                for x in start..(start + marking_width).min(width) {
+                    luma[(y * width + x) as usize] = 230;
+                }
+            }
+        }
+        luma
+    }
+
+    #[test]
+    fn test_straight_lines_are_fitted() {
+        let luma = road(200, 100, &[(|_| 60.0, 5), (|_| 140.0, 5)]);
+        let fit = detect_lanes(&luma, 200, 100, &params());
+
+        let left = fit.left.unwrap();
+        let right = fit.right.unwrap();
+        assert!((left.polynomial.x_at(99.0) - 62.0).abs() < 0.01);
+        assert!((right.polynomial.x_at(50.0) - 142.0).abs() < 0.01);
+        assert_eq!((left.points_used, left.confidence), (50, 1.0));
+    }
+
+    #[test]
+    fn test_quadratic_is_recovered() {
+        let points: Vec<(f32, f32)> = (0..40)
+            .map(|y| {
+                let y = y as f32 * 10.0;
+                (y, 10.0 + 0.5 * y + 0.001 * y * y)
+            })
+            .collect();
+        let polynomial = fit_quadratic(&points, 400.0).unwrap();
+
+        assert!((polynomial.c0 - 10.0).abs() < 1e-3);
+        assert!((polynomial.c1 - 0.5).abs() < 1e-4);
+        assert!((polynomial.c2 - 0.001).abs() < 1e-6);
+        assert!(fit_quadratic(&[(5.0, 1.0), (5.0, 2.0), (5.0, 3.0)], 10.0).is_none());
+    }
+
+    #[test]
+    fn test_wide_bright_regions_and_sparse_sides_ignored() {
+        // A bright region wider than any marking on the left, nothing on the right
+        let luma = road(200, 100, &[(|_| 10.0, 60)]);
+        assert_eq!(detect_lanes(&luma, 200, 100, &params()), LaneFit::default());
+
+        // A marking only in the lowest rows has too few samples
+        let mut luma = vec![40u8; 200 * 100];
+        for y in 95..100 {
+            luma[y * 200 + 60..y * 200 + 65].fill(230);
+        }
+        assert!(detect_lanes(&luma, 200, 100, &params()).left.is_none());
+    }
+
+    #[test]
+    fn test_lane_position_and_departure() {
+        let line = |x| LanePolynomial {
+            c0: x,
+            c1: 0.0,
+            c2: 0.0,
+        };
+
+        // 80 px lane of 3.5 m with the vehicle 2 px left of its center
+        let position = lane_position(&line(62.0), &line(142.0), 200, 100, 3.5, 1.8).unwrap();
+        assert!((position.offset_m + 0.0875).abs() < 1e-4);
+        assert!((position.left_clearance_m - 0.7625).abs() < 1e-4);
+        assert!((position.right_clearance_m - 0.9375).abs() < 1e-4);
+        assert_eq!(departure(&position, 0.3), None);
+
+        // Drifted right until the right side is under 0.2 m from its line
+        let position = lane_position(&line(45.0), &line(125.0), 200, 100, 3.5, 1.8).unwrap();
+        let (side, clearance_m) = departure(&position, 0.3).unwrap();
+        assert_eq!(side, DepartureSide::Right);
+        assert!((clearance_m - 0.19375).abs() < 1e-4);
+
+        assert!(lane_position(&line(100.0), &line(90.0), 200, 100, 3.5, 1.8).is_none());
+    }
+}
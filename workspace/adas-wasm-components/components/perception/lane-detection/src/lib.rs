@@ -0,0 +1,513 @@
+// Lane Detection Component
+// Fits the ego lane lines in front camera frames and warns when the vehicle
+// drifts too close to one of them
+use lane_detection_bindings::exports::adas::lane_detection::lane_detection::{
+    self, Config, DepartureSide, LaneDepartureWarning, LaneLine, LanePolynomial, LaneResult,
+    Stats, Status,
+};
+use lane_detection_bindings::exports::adas::diagnostics::{
+    health_monitoring::{self, DiagnosticResult, HealthReport, SubsystemHealth, TestExecution},
+    performance_monitoring::{self, ExtendedPerformance, Metric, ResourceUsage},
+};
+use lane_detection_bindings::adas::common_types::types::{HealthStatus, PerformanceMetrics, TestResult};
+use lane_detection_bindings::adas::data::sensor_data::{CameraFrame, PixelFormat};
+
+use adas_lifecycle::{Lifecycle, LifecycleEvent, LifecycleState, Transition};
+use adas_timing::{elapsed_ms, monotonic_now_ms};
+use std::cell::RefCell;
+
+mod history;
+mod lanes;
+
+use history::PerformanceHistory;
+use lanes::{FittedLine, LaneParams};
+
+// Component state
+struct LaneDetectionState {
+    config: Config,
+    lifecycle: Lifecycle,
+    frames_processed: u64,
+    frames_with_lane: u64,
+    departure_warnings: u64,
+    start_time: u64,
+    health: HealthStatus,
+    processing_times: Vec<f32>,
+    /// Total processing time since `start_time` (ms)
+    busy_ms: f64,
+    /// Bytes held for the most recent frame and its luma plane, and the most ever held
+    frame_bytes: usize,
+    peak_frame_bytes: usize,
+    performance_history: PerformanceHistory<ExtendedPerformance>,
+    last_diagnostic: Option<DiagnosticResult>,
+}
+
+impl Default for LaneDetectionState {
+    fn default() -> Self {
+        Self {
+            config: Config {
+                roi_top: 0.55,
+                marking_threshold: 180,
+                min_marking_width_px: 2,
+                max_marking_width_px: 40,
+                min_line_points: 8,
+                lane_width_m: 3.5,
+                vehicle_width_m: 1.8,
+                departure_threshold_m: 0.3,
+            },
+            lifecycle: Lifecycle::new("Lane detection"),
+            frames_processed: 0,
+            frames_with_lane: 0,
+            departure_warnings: 0,
+            start_time: 0,
+            health: HealthStatus::Ok,
+            processing_times: Vec::new(),
+            busy_ms: 0.0,
+            frame_bytes: 0,
+            peak_frame_bytes: 0,
+            performance_history: PerformanceHistory::default(),
+            last_diagnostic: None,
+        }
+    }
+}
+
+thread_local! {
+    static STATE: RefCell<LaneDetectionState> = RefCell::new(LaneDetectionState::default());
+}
+
+// Helper to get current timestamp in milliseconds (never goes backwards)
+fn get_timestamp_ms() -> u64 {
+    monotonic_now_ms()
+}
+
+// Map the shared lifecycle state onto the WIT status enum
+fn to_status(state: LifecycleState) -> Status {
+    match state {
+        LifecycleState::Uninitialized | LifecycleState::Inactive => Status::Inactive,
+        LifecycleState::Active => Status::Active,
+        LifecycleState::Error => Status::Error,
+    }
+}
+
+// Map the camera frame format onto the shared conversion crate; raw frames
+// are taken to be single-channel luma
+fn to_shared_format(format: PixelFormat) -> adas_pixel_format::PixelFormat {
+    match format {
+        PixelFormat::Rgb8 => adas_pixel_format::PixelFormat::Rgb24,
+        PixelFormat::Rgba8 => adas_pixel_format::PixelFormat::Rgba8,
+        PixelFormat::Yuv420 => adas_pixel_format::PixelFormat::Yuv420p,
+        PixelFormat::Yuv422 => adas_pixel_format::PixelFormat::Yuyv422,
+        PixelFormat::Raw => adas_pixel_format::PixelFormat::Gray8,
+    }
+}
+
+fn lane_params(config: &Config) -> LaneParams {
+    LaneParams {
+        roi_top: config.roi_top,
+        marking_threshold: config.marking_threshold,
+        min_marking_width_px: config.min_marking_width_px,
+        max_marking_width_px: config.max_marking_width_px,
+        min_line_points: config.min_line_points,
+    }
+}
+
+fn to_lane_line(line: &FittedLine) -> LaneLine {
+    LaneLine {
+        polynomial: LanePolynomial {
+            c0: line.polynomial.c0,
+            c1: line.polynomial.c1,
+            c2: line.polynomial.c2,
+        },
+        points_used: line.points_used,
+        confidence: line.confidence,
+    }
+}
+
+fn average_processing_time(s: &LaneDetectionState) -> f32 {
+    if s.processing_times.is_empty() {
+        0.0
+    } else {
+        s.processing_times.iter().sum::<f32>() / s.processing_times.len() as f32
+    }
+}
+
+// Performance snapshot at `now`, from the frames processed since the last reset
+fn performance(s: &LaneDetectionState, now: u64) -> ExtendedPerformance {
+    let running_ms = if s.start_time > 0 { elapsed_ms(now, s.start_time) } else { 0 };
+    let latency_avg_ms = average_processing_time(s);
+    let lane_rate = if s.frames_processed > 0 {
+        s.frames_with_lane as f64 / s.frames_processed as f64
+    } else {
+        0.0
+    };
+    // Share of wall-clock time spent processing frames on this single thread
+    let busy = if running_ms > 0 {
+        (s.busy_ms / running_ms as f64).min(1.0) as f32
+    } else {
+        0.0
+    };
+    let to_mb = |bytes: usize| bytes.div_ceil(1024 * 1024) as u32;
+
+    ExtendedPerformance {
+        base_metrics: PerformanceMetrics {
+            latency_avg_ms,
+            latency_max_ms: s.processing_times.iter().copied().fold(0.0, f32::max),
+            cpu_utilization: busy,
+            memory_usage_mb: to_mb(s.frame_bytes),
+            throughput_hz: if running_ms > 0 {
+                s.frames_processed as f32 * 1000.0 / running_ms as f32
+            } else {
+                0.0
+            },
+            error_rate: 0.0,
+        },
+        component_specific: vec![
+            Metric {
+                name: "lane_detection_rate".to_string(),
+                value: lane_rate,
+                unit: "ratio".to_string(),
+                description: "Fraction of frames with both lane lines".to_string(),
+            },
+            Metric {
+                name: "departure_warnings".to_string(),
+                value: s.departure_warnings as f64,
+                unit: "count".to_string(),
+                description: "Lane departure warnings raised".to_string(),
+            },
+        ],
+        resource_usage: ResourceUsage {
+            cpu_cores_used: busy,
+            memory_allocated_mb: to_mb(s.frame_bytes),
+            memory_peak_mb: to_mb(s.peak_frame_bytes),
+            disk_io_mb: 0.0,
+            network_io_mb: 0.0,
+            gpu_utilization: 0.0,
+            gpu_memory_mb: 0,
+        },
+        timestamp: now,
+    }
+}
+
+fn run_frame(s: &mut LaneDetectionState, frame: &CameraFrame) -> Result<LaneResult, String> {
+    if !s.lifecycle.is_active() {
+        return Err("Lane detection not active".to_string());
+    }
+
+    let now = get_timestamp_ms();
+    s.lifecycle.record_activity(now);
+
+    let luma = adas_pixel_format::convert(
+        &frame.data,
+        frame.width,
+        frame.height,
+        to_shared_format(frame.format),
+        adas_pixel_format::PixelFormat::Gray8,
+    )
+    .map_err(|e| format!("Invalid camera frame: {}", e))?;
+
+    let fit = lanes::detect_lanes(&luma, frame.width, frame.height, &lane_params(&s.config));
+    let position = match (&fit.left, &fit.right) {
+        (Some(left), Some(right)) => lanes::lane_position(
+            &left.polynomial,
+            &right.polynomial,
+            frame.width,
+            frame.height,
+            s.config.lane_width_m,
+            s.config.vehicle_width_m,
+        ),
+        _ => None,
+    };
+    let departure_warning = position
+        .as_ref()
+        .and_then(|position| lanes::departure(position, s.config.departure_threshold_m))
+        .map(|(side, clearance_m)| LaneDepartureWarning {
+            side: match side {
+                lanes::DepartureSide::Left => DepartureSide::Left,
+                lanes::DepartureSide::Right => DepartureSide::Right,
+            },
+            clearance_m,
+        });
+
+    let processing_time = elapsed_ms(get_timestamp_ms(), now) as f32;
+    s.frames_processed += 1;
+    if position.is_some() {
+        s.frames_with_lane += 1;
+    }
+    if departure_warning.is_some() {
+        s.departure_warnings += 1;
+    }
+    s.processing_times.push(processing_time);
+    s.busy_ms += processing_time as f64;
+    s.frame_bytes = frame.data.len() + luma.len();
+    s.peak_frame_bytes = s.peak_frame_bytes.max(s.frame_bytes);
+
+    // Keep only last 100 processing times for average calculation
+    let excess = s.processing_times.len().saturating_sub(100);
+    s.processing_times.drain(..excess);
+
+    s.health = if processing_time > 50.0 {
+        HealthStatus::Degraded
+    } else {
+        HealthStatus::Ok
+    };
+
+    let snapshot = performance(s, now);
+    s.performance_history.record(now, snapshot);
+
+    if let Some(warning) = &departure_warning {
+        println!(
+            "Lane Detection: Departure warning on {:?} side, {:.2}m clearance",
+            warning.side, warning.clearance_m
+        );
+    }
+
+    Ok(LaneResult {
+        left_line: fit.left.as_ref().map(to_lane_line),
+        right_line: fit.right.as_ref().map(to_lane_line),
+        ego_lane_offset_m: position.map(|position| position.offset_m),
+        departure_warning,
+        processing_time_ms: processing_time,
+        frame_number: s.frames_processed,
+        timestamp: now,
+    })
+}
+
+// Component implementation
+struct Component;
+
+impl lane_detection::Guest for Component {
+    fn initialize(cfg: Config) -> Result<(), String> {
+        STATE.with(|state| {
+            let mut s = state.borrow_mut();
+
+            // Validate configuration
+            if !(0.0..1.0).contains(&cfg.roi_top) {
+                return Err("Invalid ROI top (must be 0.0-1.0)".to_string());
+            }
+            if cfg.min_marking_width_px == 0 || cfg.min_marking_width_px > cfg.max_marking_width_px {
+                return Err("Invalid marking width range (min must be 1 or more and at most max)".to_string());
+            }
+            if cfg.min_line_points < 3 {
+                return Err("Invalid min line points (must be at least 3)".to_string());
+            }
+            if cfg.vehicle_width_m <= 0.0 || cfg.lane_width_m <= cfg.vehicle_width_m {
+                return Err("Invalid widths (vehicle must be positive and narrower than the lane)".to_string());
+            }
+            if cfg.departure_threshold_m < 0.0 {
+                return Err("Invalid departure threshold (must not be negative)".to_string());
+            }
+
+            println!(
+                "Lane Detection: Initializing, ROI from {:.0}% of frame height, {:.1}m lane",
+                cfg.roi_top * 100.0,
+                cfg.lane_width_m
+            );
+
+            s.lifecycle.apply(LifecycleEvent::Initialize)?;
+            s.config = cfg;
+            s.frames_processed = 0;
+            s.frames_with_lane = 0;
+            s.departure_warnings = 0;
+            s.processing_times.clear();
+            s.busy_ms = 0.0;
+            s.peak_frame_bytes = 0;
+            s.performance_history.clear();
+            s.health = HealthStatus::Ok;
+
+            Ok(())
+        })
+    }
+
+    fn start() -> Result<(), String> {
+        STATE.with(|state| {
+            let mut s = state.borrow_mut();
+
+            if s.lifecycle.apply(LifecycleEvent::Start)? == Transition::Unchanged {
+                return Ok(());
+            }
+
+            println!("Lane Detection: Starting");
+            s.start_time = get_timestamp_ms();
+
+            Ok(())
+        })
+    }
+
+    fn stop() -> Result<(), String> {
+        STATE.with(|state| {
+            let mut s = state.borrow_mut();
+
+            if s.lifecycle.apply(LifecycleEvent::Stop)? == Transition::Unchanged {
+                return Ok(());
+            }
+
+            println!("Lane Detection: Stopping");
+
+            Ok(())
+        })
+    }
+
+    fn process_frame(frame: CameraFrame) -> Result<LaneResult, String> {
+        STATE.with(|state| run_frame(&mut state.borrow_mut(), &frame))
+    }
+
+    fn get_status() -> Status {
+        STATE.with(|state| to_status(state.borrow().lifecycle.state()))
+    }
+
+    fn get_stats() -> Stats {
+        STATE.with(|state| {
+            let s = state.borrow();
+            Stats {
+                frames_processed: s.frames_processed,
+                frames_with_lane: s.frames_with_lane,
+                departure_warnings: s.departure_warnings,
+                average_processing_time_ms: average_processing_time(&s),
+            }
+        })
+    }
+
+    fn reset_stats() {
+        STATE.with(|state| {
+            let mut s = state.borrow_mut();
+            s.frames_processed = 0;
+            s.frames_with_lane = 0;
+            s.departure_warnings = 0;
+            s.processing_times.clear();
+            s.busy_ms = 0.0;
+            s.peak_frame_bytes = s.frame_bytes;
+            s.performance_history.clear();
+            s.start_time = get_timestamp_ms();
+            s.health = HealthStatus::Ok;
+            println!("Lane Detection: Statistics reset");
+        });
+    }
+
+    fn get_last_activity() -> u64 {
+        STATE.with(|state| state.borrow().lifecycle.last_activity_ms().unwrap_or(0))
+    }
+}
+
+impl health_monitoring::Guest for Component {
+    fn get_health() -> HealthReport {
+        STATE.with(|state| {
+            let s = state.borrow();
+            let lifecycle_status = match s.lifecycle.state() {
+                LifecycleState::Active => HealthStatus::Ok,
+                LifecycleState::Error => HealthStatus::Error,
+                LifecycleState::Uninitialized | LifecycleState::Inactive => HealthStatus::Offline,
+            };
+            let overall_health = if lifecycle_status == HealthStatus::Ok {
+                s.health
+            } else {
+                lifecycle_status
+            };
+
+            HealthReport {
+                component_id: "lane-detection".to_string(),
+                overall_health,
+                subsystem_health: vec![
+                    SubsystemHealth {
+                        subsystem_name: "lane-fitting".to_string(),
+                        status: s.health,
+                        details: format!(
+                            "{} of {} frames with both lane lines",
+                            s.frames_with_lane, s.frames_processed
+                        ),
+                    },
+                    SubsystemHealth {
+                        subsystem_name: "lifecycle".to_string(),
+                        status: lifecycle_status,
+                        details: format!("{:?}", s.lifecycle.state()),
+                    },
+                ],
+                last_diagnostic: s.last_diagnostic.clone(),
+                timestamp: get_timestamp_ms(),
+            }
+        })
+    }
+
+    fn run_diagnostic() -> Result<DiagnosticResult, String> {
+        STATE.with(|state| {
+            let mut s = state.borrow_mut();
+            let mut test_results = Vec::new();
+
+            // Fit a synthetic frame with straight markings left and right of center
+            let (width, height) = (160u32, 80u32);
+            let mut luma = vec![40u8; (width * height) as usize];
+            for row in luma.chunks_mut(width as usize) {
+                row[40..44].fill(230);
+                row[116..120].fill(230);
+            }
+            let started = get_timestamp_ms();
+            let fit = lanes::detect_lanes(&luma, width, height, &lane_params(&s.config));
+            test_results.push(TestExecution {
+                test_name: "lane-fitting".to_string(),
+                test_result: if fit.left.is_some() && fit.right.is_some() {
+                    TestResult::Passed
+                } else {
+                    TestResult::Failed
+                },
+                details: "Fit straight lane lines in a synthetic frame".to_string(),
+                execution_time_ms: elapsed_ms(get_timestamp_ms(), started) as f32,
+            });
+
+            test_results.push(TestExecution {
+                test_name: "lifecycle".to_string(),
+                test_result: if s.lifecycle.is_active() {
+                    TestResult::Passed
+                } else {
+                    TestResult::NotRun
+                },
+                details: format!("Component is {:?}", s.lifecycle.state()),
+                execution_time_ms: 0.0,
+            });
+
+            let passed = test_results
+                .iter()
+                .filter(|test| test.test_result == TestResult::Passed)
+                .count();
+            let overall_score = passed as f32 / test_results.len() as f32 * 100.0;
+            let mut recommendations = Vec::new();
+            if fit.left.is_none() || fit.right.is_none() {
+                recommendations.push("Check marking threshold and width range".to_string());
+            }
+            if !s.lifecycle.is_active() {
+                recommendations.push("Start the component before processing frames".to_string());
+            }
+
+            let result = DiagnosticResult {
+                test_results,
+                overall_score,
+                recommendations,
+                timestamp: get_timestamp_ms(),
+            };
+            s.last_diagnostic = Some(result.clone());
+            Ok(result)
+        })
+    }
+
+    fn get_last_diagnostic() -> Option<DiagnosticResult> {
+        STATE.with(|state| state.borrow().last_diagnostic.clone())
+    }
+}
+
+impl performance_monitoring::Guest for Component {
+    fn get_performance() -> ExtendedPerformance {
+        STATE.with(|state| performance(&state.borrow(), get_timestamp_ms()))
+    }
+
+    fn get_performance_history(duration_seconds: u32) -> Vec<ExtendedPerformance> {
+        STATE.with(|state| {
+            state
+                .borrow()
+                .performance_history
+                .window(get_timestamp_ms(), duration_seconds as u64 * 1000)
+        })
+    }
+
+    fn reset_counters() {
+        <Component as lane_detection::Guest>::reset_stats();
+    }
+}
+
+lane_detection_bindings::export!(Component with_types_in lane_detection_bindings);
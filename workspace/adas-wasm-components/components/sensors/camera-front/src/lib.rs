@@ -4,13 +4,14 @@ use camera_front_ecu_bindings::exports::adas::camera_front::{
     diagnostics::{self, Health, TestResult},
 };
 
+use adas_lifecycle::{Lifecycle, LifecycleEvent, LifecycleState, Transition};
+use adas_timing::{elapsed_ms, elapsed_secs, monotonic_now_ms};
 use std::cell::RefCell;
-use std::time::{SystemTime, UNIX_EPOCH};
 
 // Component state
 struct CameraState {
     config: Config,
-    status: Status,
+    lifecycle: Lifecycle,
     frames_processed: u64,
     frames_dropped: u64,
     start_time: u64,
@@ -29,7 +30,7 @@ impl Default for CameraState {
                 auto_exposure: true,
                 auto_white_balance: true,
             },
-            status: Status::Inactive,
+            lifecycle: Lifecycle::new("Camera"),
             frames_processed: 0,
             frames_dropped: 0,
             start_time: 0,
@@ -43,12 +44,18 @@ thread_local! {
     static STATE: RefCell<CameraState> = RefCell::new(CameraState::default());
 }
 
-// Helper to get current timestamp in milliseconds
+// Helper to get current timestamp in milliseconds (never goes backwards)
 fn get_timestamp_ms() -> u64 {
-    SystemTime::now()
-        .duration_since(UNIX_EPOCH)
-        .unwrap_or_default()
-        .as_millis() as u64
+    monotonic_now_ms()
+}
+
+// Map the shared lifecycle state onto the WIT status enum
+fn to_status(state: LifecycleState) -> Status {
+    match state {
+        LifecycleState::Uninitialized | LifecycleState::Inactive => Status::Inactive,
+        LifecycleState::Active => Status::Active,
+        LifecycleState::Error => Status::Error,
+    }
 }
 
 // Component implementation with unified interface
@@ -72,13 +79,14 @@ impl camera_sensor::Guest for Component {
             println!("Camera Front: Initializing {}x{} @ {} FPS, format: {}", 
                 cfg.width, cfg.height, cfg.fps, cfg.format);
             
+            s.lifecycle.check(LifecycleEvent::Initialize)?;
+            
             s.config = cfg;
-            s.status = Status::Initializing;
             s.frames_processed = 0;
             s.frames_dropped = 0;
             
             // Simulate initialization delay
-            s.status = Status::Inactive;
+            s.lifecycle.apply(LifecycleEvent::Initialize)?;
             s.health = Health::Healthy;
             
             Ok(())
@@ -89,12 +97,12 @@ impl camera_sensor::Guest for Component {
         STATE.with(|state| {
             let mut s = state.borrow_mut();
             
-            if matches!(s.status, Status::Active) {
-                return Err("Camera already active".to_string());
+            if s.lifecycle.check(LifecycleEvent::Start)? == Transition::Unchanged {
+                return Ok(());
             }
             
             println!("Camera Front: Starting capture");
-            s.status = Status::Active;
+            s.lifecycle.apply(LifecycleEvent::Start)?;
             s.start_time = get_timestamp_ms();
             s.last_frame_time = s.start_time;
             
@@ -106,12 +114,11 @@ impl camera_sensor::Guest for Component {
         STATE.with(|state| {
             let mut s = state.borrow_mut();
             
-            if !matches!(s.status, Status::Active) {
-                return Err("Camera not active".to_string());
+            if s.lifecycle.apply(LifecycleEvent::Stop)? == Transition::Unchanged {
+                return Ok(());
             }
             
             println!("Camera Front: Stopping capture");
-            s.status = Status::Inactive;
             
             Ok(())
         })
@@ -121,15 +128,16 @@ impl camera_sensor::Guest for Component {
         STATE.with(|state| {
             let mut s = state.borrow_mut();
             
-            if !matches!(s.status, Status::Active) {
+            if !s.lifecycle.is_active() {
                 return Err("Camera not active".to_string());
             }
             
             let now = get_timestamp_ms();
+            s.lifecycle.record_activity(now);
             let frame_interval = 1000 / s.config.fps as u64;
             
             // Check if we're keeping up with frame rate
-            if now - s.last_frame_time > frame_interval * 2 {
+            if elapsed_ms(now, s.last_frame_time) > frame_interval * 2 {
                 s.frames_dropped += 1;
                 s.health = Health::Degraded;
             }
@@ -152,14 +160,14 @@ impl camera_sensor::Guest for Component {
     }
 
     fn get_status() -> Status {
-        STATE.with(|state| state.borrow().status.clone())
+        STATE.with(|state| to_status(state.borrow().lifecycle.state()))
     }
 
     fn get_stats() -> Stats {
         STATE.with(|state| {
             let s = state.borrow();
             let elapsed_sec = if s.start_time > 0 {
-                ((get_timestamp_ms() - s.start_time) as f32) / 1000.0
+                elapsed_secs(get_timestamp_ms(), s.start_time)
             } else {
                 0.0
             };
@@ -247,6 +255,10 @@ impl diagnostics::Guest for Component {
         results
     }
 
+    fn get_last_activity() -> u64 {
+        STATE.with(|state| state.borrow().lifecycle.last_activity_ms().unwrap_or(0))
+    }
+
     fn get_report() -> String {
         STATE.with(|state| {
             let s = state.borrow();
@@ -277,7 +289,7 @@ Sensor Info:
   MIPI CSI-2: Active
   ISP Pipeline: Operational
 "#,
-                s.status,
+                s.lifecycle.state(),
                 s.health,
                 s.config.width, s.config.height, s.config.fps,
                 s.config.format,
@@ -4,13 +4,14 @@ use camera_surround_ecu_bindings::exports::adas::camera_surround::{
     diagnostics::{self, Health, TestResult},
 };
 
+use adas_lifecycle::{Lifecycle, LifecycleEvent, LifecycleState, Transition};
+use adas_timing::{elapsed_secs, monotonic_now_ms};
 use std::cell::RefCell;
-use std::time::{SystemTime, UNIX_EPOCH};
 
 // Component state
 struct CameraSurroundState {
     config: Config,
-    status: Status,
+    lifecycle: Lifecycle,
     frames_processed: u64,
     frames_dropped: u64,
     start_time: u64,
@@ -32,7 +33,7 @@ impl Default for CameraSurroundState {
                 stitching_enabled: true,
                 overlap_degrees: 10.0,
             },
-            status: Status::Inactive,
+            lifecycle: Lifecycle::new("Camera surround"),
             frames_processed: 0,
             frames_dropped: 0,
             start_time: 0,
@@ -48,12 +49,18 @@ thread_local! {
     static STATE: RefCell<CameraSurroundState> = RefCell::new(CameraSurroundState::default());
 }
 
-// Helper to get current timestamp in milliseconds
+// Helper to get current timestamp in milliseconds (never goes backwards)
 fn get_timestamp_ms() -> u64 {
-    SystemTime::now()
-        .duration_since(UNIX_EPOCH)
-        .unwrap_or_default()
-        .as_millis() as u64
+    monotonic_now_ms()
+}
+
+// Map the shared lifecycle state onto the WIT status enum
+fn to_status(state: LifecycleState) -> Status {
+    match state {
+        LifecycleState::Uninitialized | LifecycleState::Inactive => Status::Inactive,
+        LifecycleState::Active => Status::Active,
+        LifecycleState::Error => Status::Error,
+    }
 }
 
 // Component implementation
@@ -75,15 +82,16 @@ impl camera_sensor::Guest for Component {
             println!("Camera Surround: Initializing {} cameras, {}x{} @ {} fps", 
                 cfg.camera_count, cfg.resolution_width, cfg.resolution_height, cfg.fps);
             
+            s.lifecycle.check(LifecycleEvent::Initialize)?;
+            
             s.config = cfg;
-            s.status = Status::Initializing;
             s.frames_processed = 0;
             s.frames_dropped = 0;
             s.stitching_failures = 0;
             s.current_view = None;
             
             // Simulate initialization
-            s.status = Status::Inactive;
+            s.lifecycle.apply(LifecycleEvent::Initialize)?;
             s.health = Health::Healthy;
             
             Ok(())
@@ -94,12 +102,12 @@ impl camera_sensor::Guest for Component {
         STATE.with(|state| {
             let mut s = state.borrow_mut();
             
-            if matches!(s.status, Status::Active) {
-                return Err("Camera surround already active".to_string());
+            if s.lifecycle.check(LifecycleEvent::Start)? == Transition::Unchanged {
+                return Ok(());
             }
             
             println!("Camera Surround: Starting capture");
-            s.status = Status::Active;
+            s.lifecycle.apply(LifecycleEvent::Start)?;
             s.start_time = get_timestamp_ms();
             s.last_frame_time = s.start_time;
             
@@ -111,12 +119,11 @@ impl camera_sensor::Guest for Component {
         STATE.with(|state| {
             let mut s = state.borrow_mut();
             
-            if !matches!(s.status, Status::Active) {
-                return Err("Camera surround not active".to_string());
+            if s.lifecycle.apply(LifecycleEvent::Stop)? == Transition::Unchanged {
+                return Ok(());
             }
             
             println!("Camera Surround: Stopping capture");
-            s.status = Status::Inactive;
             s.current_view = None;
             
             Ok(())
@@ -127,11 +134,12 @@ impl camera_sensor::Guest for Component {
         STATE.with(|state| {
             let mut s = state.borrow_mut();
             
-            if !matches!(s.status, Status::Active) {
+            if !s.lifecycle.is_active() {
                 return Err("Camera surround not active".to_string());
             }
             
             let now = get_timestamp_ms();
+            s.lifecycle.record_activity(now);
             s.frames_processed += 1;
             s.last_frame_time = now;
             
@@ -181,14 +189,14 @@ impl camera_sensor::Guest for Component {
     }
 
     fn get_status() -> Status {
-        STATE.with(|state| state.borrow().status.clone())
+        STATE.with(|state| to_status(state.borrow().lifecycle.state()))
     }
 
     fn get_stats() -> Stats {
         STATE.with(|state| {
             let s = state.borrow();
             let elapsed_sec = if s.start_time > 0 {
-                ((get_timestamp_ms() - s.start_time) as f32) / 1000.0
+                elapsed_secs(get_timestamp_ms(), s.start_time)
             } else {
                 0.0
             };
@@ -279,6 +287,10 @@ impl diagnostics::Guest for Component {
         results
     }
 
+    fn get_last_activity() -> u64 {
+        STATE.with(|state| state.borrow().lifecycle.last_activity_ms().unwrap_or(0))
+    }
+
     fn get_report() -> String {
         STATE.with(|state| {
             let s = state.borrow();
@@ -313,7 +325,7 @@ Camera Info:
   Real-time image stitching
   360° coverage capability
 "#,
-                s.status,
+                s.lifecycle.state(),
                 s.health,
                 s.config.camera_count,
                 s.config.resolution_width,
@@ -4,19 +4,23 @@ use lidar_ecu_bindings::exports::adas::lidar::{
     diagnostics::{self, Health, TestResult},
 };
 
+use adas_lifecycle::{Lifecycle, LifecycleEvent, LifecycleState, Transition};
+use adas_simulation::{SimRng, DEFAULT_SIMULATION_SEED};
+use adas_timing::{elapsed_secs, monotonic_now_ms};
 use std::cell::RefCell;
-use std::time::{SystemTime, UNIX_EPOCH};
 
 // Component state
 struct LidarState {
     config: Config,
-    status: Status,
+    lifecycle: Lifecycle,
     scans_processed: u64,
     points_processed: u64,
     start_time: u64,
     last_frame_time: u64,
     health: Health,
     current_scan: Option<Scan>,
+    // Source of all simulated variation, seeded from the config
+    rng: SimRng,
 }
 
 impl Default for LidarState {
@@ -28,14 +32,16 @@ impl Default for LidarState {
                 field_of_view_degrees: 360.0,
                 scan_rate_hz: 10.0,
                 detection_threshold: 0.1,
+                simulation_seed: DEFAULT_SIMULATION_SEED,
             },
-            status: Status::Inactive,
+            lifecycle: Lifecycle::new("Lidar"),
             scans_processed: 0,
             points_processed: 0,
             start_time: 0,
             last_frame_time: 0,
             health: Health::Healthy,
             current_scan: None,
+            rng: SimRng::default(),
         }
     }
 }
@@ -44,12 +50,18 @@ thread_local! {
     static STATE: RefCell<LidarState> = RefCell::new(LidarState::default());
 }
 
-// Helper to get current timestamp in milliseconds
+// Helper to get current timestamp in milliseconds (never goes backwards)
 fn get_timestamp_ms() -> u64 {
-    SystemTime::now()
-        .duration_since(UNIX_EPOCH)
-        .unwrap_or_default()
-        .as_millis() as u64
+    monotonic_now_ms()
+}
+
+// Map the shared lifecycle state onto the WIT status enum
+fn to_status(state: LifecycleState) -> Status {
+    match state {
+        LifecycleState::Uninitialized | LifecycleState::Inactive => Status::Inactive,
+        LifecycleState::Active => Status::Active,
+        LifecycleState::Error => Status::Error,
+    }
 }
 
 // Component implementation
@@ -71,14 +83,16 @@ impl lidar_sensor::Guest for Component {
             println!("Lidar: Initializing {:.1}m range, {:.1}° FOV, {:.1} Hz", 
                 cfg.range_meters, cfg.field_of_view_degrees, cfg.scan_rate_hz);
             
+            s.lifecycle.check(LifecycleEvent::Initialize)?;
+            
+            s.rng = SimRng::new(cfg.simulation_seed);
             s.config = cfg;
-            s.status = Status::Initializing;
             s.scans_processed = 0;
             s.points_processed = 0;
             s.current_scan = None;
             
             // Simulate initialization
-            s.status = Status::Inactive;
+            s.lifecycle.apply(LifecycleEvent::Initialize)?;
             s.health = Health::Healthy;
             
             Ok(())
@@ -89,12 +103,12 @@ impl lidar_sensor::Guest for Component {
         STATE.with(|state| {
             let mut s = state.borrow_mut();
             
-            if matches!(s.status, Status::Active) {
-                return Err("Lidar already active".to_string());
+            if s.lifecycle.check(LifecycleEvent::Start)? == Transition::Unchanged {
+                return Ok(());
             }
             
             println!("Lidar: Starting scanning");
-            s.status = Status::Active;
+            s.lifecycle.apply(LifecycleEvent::Start)?;
             s.start_time = get_timestamp_ms();
             s.last_frame_time = s.start_time;
             
@@ -106,12 +120,11 @@ impl lidar_sensor::Guest for Component {
         STATE.with(|state| {
             let mut s = state.borrow_mut();
             
-            if !matches!(s.status, Status::Active) {
-                return Err("Lidar not active".to_string());
+            if s.lifecycle.apply(LifecycleEvent::Stop)? == Transition::Unchanged {
+                return Ok(());
             }
             
             println!("Lidar: Stopping scanning");
-            s.status = Status::Inactive;
             s.current_scan = None;
             
             Ok(())
@@ -122,32 +135,36 @@ impl lidar_sensor::Guest for Component {
         STATE.with(|state| {
             let mut s = state.borrow_mut();
             
-            if !matches!(s.status, Status::Active) {
+            if !s.lifecycle.is_active() {
                 return Err("Lidar not active".to_string());
             }
             
             let now = get_timestamp_ms();
+            s.lifecycle.record_activity(now);
             s.scans_processed += 1;
             s.last_frame_time = now;
             
-            // Simulate lidar point cloud generation
+            // Simulate lidar point cloud generation; the scene moves smoothly
+            // with the scan count and every random variation comes from the seeded rng
             let mut points = Vec::new();
-            let point_count = 100 + (s.scans_processed % 50) as usize; // Varying point count
+            let scan = s.scans_processed as f32;
+            let point_count = 100 + s.rng.below(50) as usize; // Varying point count
             
             for i in 0..point_count {
                 let angle = (i as f32 / point_count as f32) * 2.0 * 3.14159; // Full circle
-                let range = 10.0 + (i as f32 * 0.1 + s.scans_processed as f32 * 0.01).sin() * 30.0;
+                let range = 10.0 + (i as f32 * 0.1 + scan * 0.01).sin() * 30.0 + s.rng.jitter(0.0, 0.05);
+                let z = (scan * 0.02).sin() * 2.0 + s.rng.jitter(0.0, 0.02); // Slight height variation
+                let intensity = s.rng.jitter(0.5, 0.3);
                 
                 if range <= s.config.range_meters {
                     let x = range * angle.cos();
                     let y = range * angle.sin();
-                    let z = (s.scans_processed as f32 * 0.02).sin() * 2.0; // Slight height variation
                     
                     points.push(Point {
                         x,
                         y,
                         z,
-                        intensity: 0.5 + (i as f32 * 0.1).sin() * 0.3,
+                        intensity,
                         timestamp: now,
                     });
                 }
@@ -168,14 +185,14 @@ impl lidar_sensor::Guest for Component {
     }
 
     fn get_status() -> Status {
-        STATE.with(|state| state.borrow().status.clone())
+        STATE.with(|state| to_status(state.borrow().lifecycle.state()))
     }
 
     fn get_stats() -> Stats {
         STATE.with(|state| {
             let s = state.borrow();
             let elapsed_sec = if s.start_time > 0 {
-                ((get_timestamp_ms() - s.start_time) as f32) / 1000.0
+                elapsed_secs(get_timestamp_ms(), s.start_time)
             } else {
                 0.0
             };
@@ -205,6 +222,7 @@ impl lidar_sensor::Guest for Component {
             s.start_time = get_timestamp_ms();
             s.health = Health::Healthy;
             s.current_scan = None;
+            s.rng = SimRng::new(s.config.simulation_seed);
             println!("Lidar: Statistics reset");
         });
     }
@@ -262,6 +280,10 @@ impl diagnostics::Guest for Component {
         results
     }
 
+    fn get_last_activity() -> u64 {
+        STATE.with(|state| state.borrow().lifecycle.last_activity_ms().unwrap_or(0))
+    }
+
     fn get_report() -> String {
         STATE.with(|state| {
             let s = state.borrow();
@@ -295,7 +317,7 @@ Lidar Info:
   Rotation: Mechanical
   Point Cloud: Active
 "#,
-                s.status,
+                s.lifecycle.state(),
                 s.health,
                 s.config.range_meters,
                 s.config.resolution_cm,
@@ -4,19 +4,23 @@ use radar_corner_ecu_bindings::exports::adas::radar_corner::{
     diagnostics::{self, Health, TestResult},
 };
 
+use adas_lifecycle::{Lifecycle, LifecycleEvent, LifecycleState, Transition};
+use adas_simulation::{SimRng, DEFAULT_SIMULATION_SEED};
+use adas_timing::{elapsed_secs, monotonic_now_ms};
 use std::cell::RefCell;
-use std::time::{SystemTime, UNIX_EPOCH};
 
 // Component state
 struct RadarCornerState {
     config: Config,
-    status: Status,
+    lifecycle: Lifecycle,
     detections_processed: u64,
     false_positives: u64,
     start_time: u64,
     last_frame_time: u64,
     health: Health,
     current_targets: Vec<Detection>,
+    // Source of all simulated variation, seeded from the config
+    rng: SimRng,
 }
 
 impl Default for RadarCornerState {
@@ -29,14 +33,16 @@ impl Default for RadarCornerState {
                 frequency_ghz: 24.0,
                 detection_threshold: 0.2,
                 corner_position: "front-left".to_string(),
+                simulation_seed: DEFAULT_SIMULATION_SEED,
             },
-            status: Status::Inactive,
+            lifecycle: Lifecycle::new("Radar corner"),
             detections_processed: 0,
             false_positives: 0,
             start_time: 0,
             last_frame_time: 0,
             health: Health::Healthy,
             current_targets: Vec::new(),
+            rng: SimRng::default(),
         }
     }
 }
@@ -45,12 +51,18 @@ thread_local! {
     static STATE: RefCell<RadarCornerState> = RefCell::new(RadarCornerState::default());
 }
 
-// Helper to get current timestamp in milliseconds
+// Helper to get current timestamp in milliseconds (never goes backwards)
 fn get_timestamp_ms() -> u64 {
-    SystemTime::now()
-        .duration_since(UNIX_EPOCH)
-        .unwrap_or_default()
-        .as_millis() as u64
+    monotonic_now_ms()
+}
+
+// Map the shared lifecycle state onto the WIT status enum
+fn to_status(state: LifecycleState) -> Status {
+    match state {
+        LifecycleState::Uninitialized | LifecycleState::Inactive => Status::Inactive,
+        LifecycleState::Active => Status::Active,
+        LifecycleState::Error => Status::Error,
+    }
 }
 
 // Component implementation
@@ -72,14 +84,16 @@ impl radar_sensor::Guest for Component {
             println!("Radar Corner {}: Initializing {:.1}m range, {:.1}° FOV, {:.1} GHz", 
                 cfg.corner_position, cfg.range_meters, cfg.field_of_view_degrees, cfg.frequency_ghz);
             
+            s.lifecycle.check(LifecycleEvent::Initialize)?;
+            
+            s.rng = SimRng::new(cfg.simulation_seed);
             s.config = cfg;
-            s.status = Status::Initializing;
             s.detections_processed = 0;
             s.false_positives = 0;
             s.current_targets.clear();
             
             // Simulate initialization
-            s.status = Status::Inactive;
+            s.lifecycle.apply(LifecycleEvent::Initialize)?;
             s.health = Health::Healthy;
             
             Ok(())
@@ -90,12 +104,12 @@ impl radar_sensor::Guest for Component {
         STATE.with(|state| {
             let mut s = state.borrow_mut();
             
-            if matches!(s.status, Status::Active) {
-                return Err("Radar corner already active".to_string());
+            if s.lifecycle.check(LifecycleEvent::Start)? == Transition::Unchanged {
+                return Ok(());
             }
             
             println!("Radar Corner {}: Starting detection", s.config.corner_position);
-            s.status = Status::Active;
+            s.lifecycle.apply(LifecycleEvent::Start)?;
             s.start_time = get_timestamp_ms();
             s.last_frame_time = s.start_time;
             
@@ -107,12 +121,11 @@ impl radar_sensor::Guest for Component {
         STATE.with(|state| {
             let mut s = state.borrow_mut();
             
-            if !matches!(s.status, Status::Active) {
-                return Err("Radar corner not active".to_string());
+            if s.lifecycle.apply(LifecycleEvent::Stop)? == Transition::Unchanged {
+                return Ok(());
             }
             
             println!("Radar Corner {}: Stopping detection", s.config.corner_position);
-            s.status = Status::Inactive;
             s.current_targets.clear();
             
             Ok(())
@@ -123,24 +136,29 @@ impl radar_sensor::Guest for Component {
         STATE.with(|state| {
             let mut s = state.borrow_mut();
             
-            if !matches!(s.status, Status::Active) {
+            if !s.lifecycle.is_active() {
                 return Err("Radar corner not active".to_string());
             }
             
             let now = get_timestamp_ms();
+            s.lifecycle.record_activity(now);
             s.detections_processed += 1;
             s.last_frame_time = now;
             
-            // Simulate corner radar detections (typically for parking/blind spot)
+            // Simulate corner radar detections (typically for parking/blind spot);
+            // every random variation comes from the seeded rng
             let mut detections = Vec::new();
+            let frame = s.detections_processed as f32;
             
             // Corner radars typically detect closer objects with wider angles
-            let target_count = ((s.detections_processed % 4) + 1) as usize;
+            let target_count = (s.rng.below(4) + 1) as usize;
             
             for i in 0..target_count {
-                let range = 5.0 + (i as f32 * 15.0) + (s.detections_processed as f32 * 0.08).sin() * 8.0;
-                let angle = -60.0 + (i as f32 * 30.0) + (s.detections_processed as f32 * 0.04).cos() * 10.0;
-                let velocity = 2.0 + (s.detections_processed as f32 * 0.03).sin() * 5.0;
+                let range = 5.0 + (i as f32 * 15.0) + (frame * 0.08).sin() * 8.0 + s.rng.jitter(0.0, 0.3);
+                let angle = -60.0 + (i as f32 * 30.0) + (frame * 0.04).cos() * 10.0 + s.rng.jitter(0.0, 0.5);
+                let velocity = 2.0 + (frame * 0.03).sin() * 5.0 + s.rng.jitter(0.0, 0.2);
+                let signal_strength = s.rng.jitter(0.6, 0.3);
+                let confidence = s.rng.jitter(0.7, 0.25);
                 
                 if range <= s.config.range_meters && angle.abs() <= s.config.field_of_view_degrees / 2.0 {
                     let target_type = match i % 4 {
@@ -154,15 +172,15 @@ impl radar_sensor::Guest for Component {
                         range_meters: range,
                         angle_degrees: angle,
                         velocity_ms: velocity,
-                        signal_strength: 0.6 + (s.detections_processed as f32 * 0.04).cos() * 0.3,
+                        signal_strength,
                         target_type: target_type.to_string(),
-                        confidence: 0.7 + (s.detections_processed as f32 * 0.02).sin() * 0.25,
+                        confidence,
                     });
                 }
             }
             
             // Simulate occasional false positives (more common in corner radars)
-            if s.detections_processed % 15 == 0 {
+            if s.rng.chance(1.0 / 15.0) {
                 s.false_positives += 1;
                 s.health = Health::Degraded;
             }
@@ -174,14 +192,14 @@ impl radar_sensor::Guest for Component {
     }
 
     fn get_status() -> Status {
-        STATE.with(|state| state.borrow().status.clone())
+        STATE.with(|state| to_status(state.borrow().lifecycle.state()))
     }
 
     fn get_stats() -> Stats {
         STATE.with(|state| {
             let s = state.borrow();
             let elapsed_sec = if s.start_time > 0 {
-                ((get_timestamp_ms() - s.start_time) as f32) / 1000.0
+                elapsed_secs(get_timestamp_ms(), s.start_time)
             } else {
                 0.0
             };
@@ -211,6 +229,7 @@ impl radar_sensor::Guest for Component {
             s.start_time = get_timestamp_ms();
             s.health = Health::Healthy;
             s.current_targets.clear();
+            s.rng = SimRng::new(s.config.simulation_seed);
             println!("Radar Corner {}: Statistics reset", s.config.corner_position);
         });
     }
@@ -268,6 +287,10 @@ impl diagnostics::Guest for Component {
         results
     }
 
+    fn get_last_activity() -> u64 {
+        STATE.with(|state| state.borrow().lifecycle.last_activity_ms().unwrap_or(0))
+    }
+
     fn get_report() -> String {
         STATE.with(|state| {
             let s = state.borrow();
@@ -301,7 +324,7 @@ Radar Info:
   Wide angle coverage
   Short to medium range detection
 "#,
-                s.status,
+                s.lifecycle.state(),
                 s.health,
                 s.config.corner_position,
                 s.config.range_meters,
@@ -4,19 +4,23 @@ use radar_front_ecu_bindings::exports::adas::radar_front::{
     diagnostics::{self, Health, TestResult},
 };
 
+use adas_lifecycle::{Lifecycle, LifecycleEvent, LifecycleState, Transition};
+use adas_simulation::{SimRng, DEFAULT_SIMULATION_SEED};
+use adas_timing::{elapsed_secs, monotonic_now_ms};
 use std::cell::RefCell;
-use std::time::{SystemTime, UNIX_EPOCH};
 
 // Component state
 struct RadarState {
     config: Config,
-    status: Status,
+    lifecycle: Lifecycle,
     detections_processed: u64,
     false_positives: u64,
     start_time: u64,
     last_frame_time: u64,
     health: Health,
     current_targets: Vec<Detection>,
+    // Source of all simulated variation, seeded from the config
+    rng: SimRng,
 }
 
 impl Default for RadarState {
@@ -28,14 +32,16 @@ impl Default for RadarState {
                 field_of_view_degrees: 60.0,
                 frequency_ghz: 77.0,
                 detection_threshold: 0.3,
+                simulation_seed: DEFAULT_SIMULATION_SEED,
             },
-            status: Status::Inactive,
+            lifecycle: Lifecycle::new("Radar"),
             detections_processed: 0,
             false_positives: 0,
             start_time: 0,
             last_frame_time: 0,
             health: Health::Healthy,
             current_targets: Vec::new(),
+            rng: SimRng::default(),
         }
     }
 }
@@ -44,12 +50,18 @@ thread_local! {
     static STATE: RefCell<RadarState> = RefCell::new(RadarState::default());
 }
 
-// Helper to get current timestamp in milliseconds
+// Helper to get current timestamp in milliseconds (never goes backwards)
 fn get_timestamp_ms() -> u64 {
-    SystemTime::now()
-        .duration_since(UNIX_EPOCH)
-        .unwrap_or_default()
-        .as_millis() as u64
+    monotonic_now_ms()
+}
+
+// Map the shared lifecycle state onto the WIT status enum
+fn to_status(state: LifecycleState) -> Status {
+    match state {
+        LifecycleState::Uninitialized | LifecycleState::Inactive => Status::Inactive,
+        LifecycleState::Active => Status::Active,
+        LifecycleState::Error => Status::Error,
+    }
 }
 
 // Component implementation
@@ -71,14 +83,16 @@ impl radar_sensor::Guest for Component {
             println!("Radar Front: Initializing {:.1}m range, {:.1}° FOV, {:.1} GHz", 
                 cfg.range_meters, cfg.field_of_view_degrees, cfg.frequency_ghz);
             
+            s.lifecycle.check(LifecycleEvent::Initialize)?;
+            
+            s.rng = SimRng::new(cfg.simulation_seed);
             s.config = cfg;
-            s.status = Status::Initializing;
             s.detections_processed = 0;
             s.false_positives = 0;
             s.current_targets.clear();
             
             // Simulate initialization
-            s.status = Status::Inactive;
+            s.lifecycle.apply(LifecycleEvent::Initialize)?;
             s.health = Health::Healthy;
             
             Ok(())
@@ -89,12 +103,12 @@ impl radar_sensor::Guest for Component {
         STATE.with(|state| {
             let mut s = state.borrow_mut();
             
-            if matches!(s.status, Status::Active) {
-                return Err("Radar already active".to_string());
+            if s.lifecycle.check(LifecycleEvent::Start)? == Transition::Unchanged {
+                return Ok(());
             }
             
             println!("Radar Front: Starting detection");
-            s.status = Status::Active;
+            s.lifecycle.apply(LifecycleEvent::Start)?;
             s.start_time = get_timestamp_ms();
             s.last_frame_time = s.start_time;
             
@@ -106,12 +120,11 @@ impl radar_sensor::Guest for Component {
         STATE.with(|state| {
             let mut s = state.borrow_mut();
             
-            if !matches!(s.status, Status::Active) {
-                return Err("Radar not active".to_string());
+            if s.lifecycle.apply(LifecycleEvent::Stop)? == Transition::Unchanged {
+                return Ok(());
             }
             
             println!("Radar Front: Stopping detection");
-            s.status = Status::Inactive;
             s.current_targets.clear();
             
             Ok(())
@@ -122,39 +135,44 @@ impl radar_sensor::Guest for Component {
         STATE.with(|state| {
             let mut s = state.borrow_mut();
             
-            if !matches!(s.status, Status::Active) {
+            if !s.lifecycle.is_active() {
                 return Err("Radar not active".to_string());
             }
             
             let now = get_timestamp_ms();
+            s.lifecycle.record_activity(now);
             s.detections_processed += 1;
             s.last_frame_time = now;
             
-            // Simulate radar detections with some variation
+            // Simulate radar detections; targets follow smooth paths over the
+            // frame count and every random variation comes from the seeded rng
             let mut detections = Vec::new();
+            let frame = s.detections_processed as f32;
             
             // Simulate a few targets at different ranges and angles
-            let target_count = ((s.detections_processed % 5) + 1) as usize;
+            let target_count = (s.rng.below(5) + 1) as usize;
             
             for i in 0..target_count {
-                let range = 50.0 + (i as f32 * 30.0) + (s.detections_processed as f32 * 0.1).sin() * 10.0;
-                let angle = -20.0 + (i as f32 * 10.0) + (s.detections_processed as f32 * 0.05).cos() * 5.0;
-                let velocity = 15.0 + (s.detections_processed as f32 * 0.02).sin() * 10.0;
+                let range = 50.0 + (i as f32 * 30.0) + (frame * 0.1).sin() * 10.0 + s.rng.jitter(0.0, 0.5);
+                let angle = -20.0 + (i as f32 * 10.0) + (frame * 0.05).cos() * 5.0 + s.rng.jitter(0.0, 0.2);
+                let velocity = 15.0 + (frame * 0.02).sin() * 10.0 + s.rng.jitter(0.0, 0.3);
+                let signal_strength = s.rng.jitter(0.8, 0.2);
+                let confidence = s.rng.jitter(0.75, 0.2);
                 
                 if range <= s.config.range_meters {
                     detections.push(Detection {
                         range_meters: range,
                         angle_degrees: angle,
                         velocity_ms: velocity,
-                        signal_strength: 0.8 + (s.detections_processed as f32 * 0.03).cos() * 0.2,
+                        signal_strength,
                         target_type: if i % 2 == 0 { "vehicle".to_string() } else { "pedestrian".to_string() },
-                        confidence: 0.75 + (s.detections_processed as f32 * 0.01).sin() * 0.2,
+                        confidence,
                     });
                 }
             }
             
             // Simulate occasional false positives
-            if s.detections_processed % 20 == 0 {
+            if s.rng.chance(0.05) {
                 s.false_positives += 1;
                 s.health = Health::Degraded;
             }
@@ -166,14 +184,14 @@ impl radar_sensor::Guest for Component {
     }
 
     fn get_status() -> Status {
-        STATE.with(|state| state.borrow().status.clone())
+        STATE.with(|state| to_status(state.borrow().lifecycle.state()))
     }
 
     fn get_stats() -> Stats {
         STATE.with(|state| {
             let s = state.borrow();
             let elapsed_sec = if s.start_time > 0 {
-                ((get_timestamp_ms() - s.start_time) as f32) / 1000.0
+                elapsed_secs(get_timestamp_ms(), s.start_time)
             } else {
                 0.0
             };
@@ -203,6 +221,7 @@ impl radar_sensor::Guest for Component {
             s.start_time = get_timestamp_ms();
             s.health = Health::Healthy;
             s.current_targets.clear();
+            s.rng = SimRng::new(s.config.simulation_seed);
             println!("Radar Front: Statistics reset");
         });
     }
@@ -260,6 +279,10 @@ impl diagnostics::Guest for Component {
         results
     }
 
+    fn get_last_activity() -> u64 {
+        STATE.with(|state| state.borrow().lifecycle.last_activity_ms().unwrap_or(0))
+    }
+
     fn get_report() -> String {
         STATE.with(|state| {
             let s = state.borrow();
@@ -292,7 +315,7 @@ Radar Info:
   Signal Processing: Operational
   Target Tracking: Enabled
 "#,
-                s.status,
+                s.lifecycle.state(),
                 s.health,
                 s.config.range_meters,
                 s.config.resolution_cm,
@@ -4,19 +4,23 @@ use ultrasonic_ecu_bindings::exports::adas::ultrasonic::{
     diagnostics::{self, Health, TestResult},
 };
 
+use adas_lifecycle::{Lifecycle, LifecycleEvent, LifecycleState, Transition};
+use adas_simulation::{SimRng, DEFAULT_SIMULATION_SEED};
+use adas_timing::{elapsed_secs, monotonic_now_ms};
 use std::cell::RefCell;
-use std::time::{SystemTime, UNIX_EPOCH};
 
 // Component state
 struct UltrasonicState {
     config: Config,
-    status: Status,
+    lifecycle: Lifecycle,
     measurements_processed: u64,
     out_of_range_detections: u64,
     start_time: u64,
     last_frame_time: u64,
     health: Health,
     current_readings: Vec<DistanceReading>,
+    // Source of all simulated variation, seeded from the config
+    rng: SimRng,
 }
 
 impl Default for UltrasonicState {
@@ -69,14 +73,16 @@ impl Default for UltrasonicState {
                 frequency_khz: 40,
                 detection_threshold: 0.3,
                 position_mapping: default_positions,
+                simulation_seed: DEFAULT_SIMULATION_SEED,
             },
-            status: Status::Inactive,
+            lifecycle: Lifecycle::new("Ultrasonic sensors"),
             measurements_processed: 0,
             out_of_range_detections: 0,
             start_time: 0,
             last_frame_time: 0,
             health: Health::Healthy,
             current_readings: Vec::new(),
+            rng: SimRng::default(),
         }
     }
 }
@@ -85,12 +91,18 @@ thread_local! {
     static STATE: RefCell<UltrasonicState> = RefCell::new(UltrasonicState::default());
 }
 
-// Helper to get current timestamp in milliseconds
+// Helper to get current timestamp in milliseconds (never goes backwards)
 fn get_timestamp_ms() -> u64 {
-    SystemTime::now()
-        .duration_since(UNIX_EPOCH)
-        .unwrap_or_default()
-        .as_millis() as u64
+    monotonic_now_ms()
+}
+
+// Map the shared lifecycle state onto the WIT status enum
+fn to_status(state: LifecycleState) -> Status {
+    match state {
+        LifecycleState::Uninitialized | LifecycleState::Inactive => Status::Inactive,
+        LifecycleState::Active => Status::Active,
+        LifecycleState::Error => Status::Error,
+    }
 }
 
 // Component implementation
@@ -115,14 +127,16 @@ impl ultrasonic_sensor::Guest for Component {
             println!("Ultrasonic: Initializing {} sensors, max range: {} cm, frequency: {} kHz", 
                 cfg.sensor_count, cfg.max_range_cm, cfg.frequency_khz);
             
+            s.lifecycle.check(LifecycleEvent::Initialize)?;
+            
+            s.rng = SimRng::new(cfg.simulation_seed);
             s.config = cfg;
-            s.status = Status::Initializing;
             s.measurements_processed = 0;
             s.out_of_range_detections = 0;
             s.current_readings.clear();
             
             // Simulate initialization
-            s.status = Status::Inactive;
+            s.lifecycle.apply(LifecycleEvent::Initialize)?;
             s.health = Health::Healthy;
             
             Ok(())
@@ -133,12 +147,12 @@ impl ultrasonic_sensor::Guest for Component {
         STATE.with(|state| {
             let mut s = state.borrow_mut();
             
-            if matches!(s.status, Status::Active) {
-                return Err("Ultrasonic sensors already active".to_string());
+            if s.lifecycle.check(LifecycleEvent::Start)? == Transition::Unchanged {
+                return Ok(());
             }
             
             println!("Ultrasonic: Starting measurement");
-            s.status = Status::Active;
+            s.lifecycle.apply(LifecycleEvent::Start)?;
             s.start_time = get_timestamp_ms();
             s.last_frame_time = s.start_time;
             
@@ -150,12 +164,11 @@ impl ultrasonic_sensor::Guest for Component {
         STATE.with(|state| {
             let mut s = state.borrow_mut();
             
-            if !matches!(s.status, Status::Active) {
-                return Err("Ultrasonic sensors not active".to_string());
+            if s.lifecycle.apply(LifecycleEvent::Stop)? == Transition::Unchanged {
+                return Ok(());
             }
             
             println!("Ultrasonic: Stopping measurement");
-            s.status = Status::Inactive;
             s.current_readings.clear();
             
             Ok(())
@@ -166,21 +179,25 @@ impl ultrasonic_sensor::Guest for Component {
         STATE.with(|state| {
             let mut s = state.borrow_mut();
             
-            if !matches!(s.status, Status::Active) {
+            if !s.lifecycle.is_active() {
                 return Err("Ultrasonic sensors not active".to_string());
             }
             
             let now = get_timestamp_ms();
+            s.lifecycle.record_activity(now);
             s.measurements_processed += 1;
             s.last_frame_time = now;
             
-            // Simulate ultrasonic sensor readings
+            // Simulate ultrasonic sensor readings; obstacles move smoothly with
+            // the measurement count and echo noise comes from the seeded rng
             let mut readings = Vec::new();
+            let measurement = s.measurements_processed as f32;
             
             for i in 0..s.config.sensor_count {
                 let base_distance = 80.0 + (i as f32 * 20.0);
-                let time_variation = (s.measurements_processed as f32 * 0.05 + i as f32).sin() * 30.0;
-                let distance_cm = (base_distance + time_variation).max(10.0) as u32;
+                let time_variation = (measurement * 0.05 + i as f32).sin() * 30.0;
+                let echo_noise = s.rng.jitter(0.0, 2.0);
+                let distance_cm = (base_distance + time_variation + echo_noise).max(10.0) as u32;
                 
                 // Check if reading is out of range
                 let is_out_of_range = distance_cm >= s.config.max_range_cm;
@@ -205,7 +222,7 @@ impl ultrasonic_sensor::Guest for Component {
             }
             
             // Simulate occasional sensor degradation
-            if s.measurements_processed % 200 == 0 {
+            if s.rng.chance(0.005) {
                 s.health = Health::Degraded;
             }
             
@@ -222,14 +239,14 @@ impl ultrasonic_sensor::Guest for Component {
     }
 
     fn get_status() -> Status {
-        STATE.with(|state| state.borrow().status.clone())
+        STATE.with(|state| to_status(state.borrow().lifecycle.state()))
     }
 
     fn get_stats() -> Stats {
         STATE.with(|state| {
             let s = state.borrow();
             let elapsed_sec = if s.start_time > 0 {
-                ((get_timestamp_ms() - s.start_time) as f32) / 1000.0
+                elapsed_secs(get_timestamp_ms(), s.start_time)
             } else {
                 0.0
             };
@@ -259,6 +276,7 @@ impl ultrasonic_sensor::Guest for Component {
             s.start_time = get_timestamp_ms();
             s.health = Health::Healthy;
             s.current_readings.clear();
+            s.rng = SimRng::new(s.config.simulation_seed);
             println!("Ultrasonic: Statistics reset");
         });
     }
@@ -323,6 +341,10 @@ impl diagnostics::Guest for Component {
         results
     }
 
+    fn get_last_activity() -> u64 {
+        STATE.with(|state| state.borrow().lifecycle.last_activity_ms().unwrap_or(0))
+    }
+
     fn get_report() -> String {
         STATE.with(|state| {
             let s = state.borrow();
@@ -363,7 +385,7 @@ Ultrasonic Info:
   360° proximity detection
   High precision distance measurement
 "#,
-                s.status,
+                s.lifecycle.state(),
                 s.health,
                 s.config.sensor_count,
                 s.config.max_range_cm,
@@ -0,0 +1,269 @@
+// DBC-style signal packing for classic CAN frames
+// A message layout places scaled signals at fixed bit positions; encoding turns
+// physical values into raw integers and packs them into the frame payload
+
+/// Largest payload of a classic CAN frame
+pub const MAX_DLC: u8 = 8;
+
+/// Highest 11-bit and 29-bit identifiers
+const MAX_STANDARD_ID: u32 = 0x7FF;
+const MAX_EXTENDED_ID: u32 = 0x1FFF_FFFF;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ByteOrder {
+    /// Intel: `start_bit` is the least significant bit
+    LittleEndian,
+    /// Motorola: `start_bit` is the most significant bit, DBC numbering
+    BigEndian,
+}
+
+/// Decision quantity a signal carries
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SignalSource {
+    Intervention,
+    Maneuver,
+    TargetSpeed,
+    BrakeDemand,
+}
+
+/// Placement and scaling of one signal; physical = raw * factor + offset
+#[derive(Debug, Clone, PartialEq)]
+pub struct SignalLayout {
+    pub name: String,
+    pub source: SignalSource,
+    pub start_bit: u32,
+    pub length: u32,
+    pub byte_order: ByteOrder,
+    pub signed: bool,
+    pub factor: f64,
+    pub offset: f64,
+}
+
+/// One CAN message and the signals packed into it
+#[derive(Debug, Clone, PartialEq)]
+pub struct MessageLayout {
+    pub name: String,
+    pub can_id: u32,
+    pub extended: bool,
+    pub dlc: u8,
+    pub signals: Vec<SignalLayout>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Frame {
+    pub id: u32,
+    pub extended: bool,
+    pub data: Vec<u8>,
+}
+
+impl SignalLayout {
+    /// Absolute payload bit of each raw bit, least significant first
+    fn bit_positions(&self, dlc: u8) -> Result<Vec<u32>, String> {
+        if self.length == 0 || self.length > 64 {
+            return Err(format!("Signal '{}' has invalid length {} (must be 1-64)", self.name, self.length));
+        }
+
+        let mut positions = Vec::with_capacity(self.length as usize);
+        let mut position = self.start_bit;
+        for _ in 0..self.length {
+            if position >= u32::from(dlc) * 8 {
+                return Err(format!("Signal '{}' does not fit in {} data bytes", self.name, dlc));
+            }
+            positions.push(position);
+            position = match (self.byte_order, position % 8) {
+                (ByteOrder::LittleEndian, _) => position + 1,
+                // Motorola walks from bit 7 of one byte to bit 0, then on to bit 7 of the next
+                (ByteOrder::BigEndian, 0) => position + 15,
+                (ByteOrder::BigEndian, _) => position - 1,
+            };
+        }
+
+        if self.byte_order == ByteOrder::BigEndian {
+            positions.reverse();
+        }
+        Ok(positions)
+    }
+
+    /// Raw value of a physical value, rounded and saturated to the signal range
+    pub fn raw_value(&self, physical: f64) -> u64 {
+        let (min, max) = if self.signed {
+            (-(1i128 << (self.length - 1)), (1i128 << (self.length - 1)) - 1)
+        } else {
+            (0, (1i128 << self.length) - 1)
+        };
+
+        let scaled = ((physical - self.offset) / self.factor).round();
+        let raw = if scaled.is_nan() {
+            0
+        } else {
+            (scaled.clamp(min as f64, max as f64) as i128).clamp(min, max)
+        };
+
+        // Two's complement, truncated to the signal length
+        let mask = if self.length == 64 { u64::MAX } else { (1u64 << self.length) - 1 };
+        (raw as u64) & mask
+    }
+}
+
+impl MessageLayout {
+    /// Check the identifier, payload size and that no two signals share a bit
+    pub fn validate(&self) -> Result<(), String> {
+        let max_id = if self.extended { MAX_EXTENDED_ID } else { MAX_STANDARD_ID };
+        if self.can_id > max_id {
+            return Err(format!("Message '{}' has CAN ID {:#X} above {:#X}", self.name, self.can_id, max_id));
+        }
+        if self.dlc == 0 || self.dlc > MAX_DLC {
+            return Err(format!("Message '{}' has invalid DLC {} (must be 1-{})", self.name, self.dlc, MAX_DLC));
+        }
+
+        let mut used = 0u64;
+        for signal in &self.signals {
+            if !signal.factor.is_finite() || signal.factor == 0.0 || !signal.offset.is_finite() {
+                return Err(format!("Signal '{}' needs a finite, non-zero factor and a finite offset", signal.name));
+            }
+            for position in signal.bit_positions(self.dlc)? {
+                if used & (1 << position) != 0 {
+                    return Err(format!("Signal '{}' overlaps another signal at bit {}", signal.name, position));
+                }
+                used |= 1 << position;
+            }
+        }
+        Ok(())
+    }
+
+    /// Encode one frame, reading each signal's physical value from `value_of`
+    ///
+    /// The layout must have passed [`MessageLayout::validate`].
+    pub fn encode(&self, value_of: impl Fn(SignalSource) -> f64) -> Result<Frame, String> {
+        let mut data = vec![0u8; self.dlc as usize];
+        for signal in &self.signals {
+            let raw = signal.raw_value(value_of(signal.source));
+            for (bit, position) in signal.bit_positions(self.dlc)?.into_iter().enumerate() {
+                if (raw >> bit) & 1 == 1 {
+                    data[(position / 8) as usize] |= 1 << (position % 8);
+                }
+            }
+        }
+        Ok(Frame { id: self.can_id, extended: self.extended, data })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn signal(source: SignalSource, start_bit: u32, length: u32, byte_order: ByteOrder) -> SignalLayout {
+        SignalLayout {
+            name: format!("{:?}", source),
+            source,
+            start_bit,
+            length,
+            byte_order,
+            signed: false,
+            factor: 1.0,
+            offset: 0.0,
+        }
+    }
+
+    fn aeb_command() -> MessageLayout {
+        MessageLayout {
+            name: "AEB_Command".to_string(),
+            can_id: 0x1A0,
+            extended: false,
+            dlc: 8,
+            signals: vec![
+                signal(SignalSource::Intervention, 0, 4, ByteOrder::LittleEndian),
+                signal(SignalSource::Maneuver, 4, 4, ByteOrder::LittleEndian),
+                SignalLayout { factor: 0.005, ..signal(SignalSource::BrakeDemand, 8, 8, ByteOrder::LittleEndian) },
+                SignalLayout {
+                    factor: 0.01,
+                    offset: -50.0,
+                    ..signal(SignalSource::TargetSpeed, 23, 16, ByteOrder::BigEndian)
+                },
+            ],
+        }
+    }
+
+    #[test]
+    fn test_emergency_brake_decision_frame() {
+        let layout = aeb_command();
+        layout.validate().unwrap();
+
+        // Emergency brake (3), stop (4), full brake demand, target 0 m/s
+        let frame = layout
+            .encode(|source| match source {
+                SignalSource::Intervention => 3.0,
+                SignalSource::Maneuver => 4.0,
+                SignalSource::BrakeDemand => 1.0,
+                SignalSource::TargetSpeed => 0.0,
+            })
+            .unwrap();
+
+        assert_eq!(frame.id, 0x1A0);
+        assert!(!frame.extended);
+        // Brake demand 1.0 / 0.005 = 200; target speed (0 + 50) / 0.01 = 5000, big-endian
+        assert_eq!(frame.data, [0x43, 0xC8, 0x13, 0x88, 0x00, 0x00, 0x00, 0x00]);
+    }
+
+    #[test]
+    fn test_raw_values_are_scaled_and_saturated() {
+        let speed = SignalLayout { factor: 0.1, ..signal(SignalSource::TargetSpeed, 0, 8, ByteOrder::LittleEndian) };
+        assert_eq!(speed.raw_value(13.87), 139);
+        assert_eq!(speed.raw_value(100.0), 255);
+        assert_eq!(speed.raw_value(-3.0), 0);
+        assert_eq!(speed.raw_value(f64::NAN), 0);
+
+        let signed = SignalLayout { signed: true, ..signal(SignalSource::TargetSpeed, 0, 12, ByteOrder::LittleEndian) };
+        assert_eq!(signed.raw_value(-1.0), 0xFFF);
+        assert_eq!(signed.raw_value(-5000.0), 0x800);
+        assert_eq!(signed.raw_value(5000.0), 0x7FF);
+
+        let wide = signal(SignalSource::TargetSpeed, 0, 64, ByteOrder::LittleEndian);
+        assert_eq!(wide.raw_value(1.0), 1);
+    }
+
+    #[test]
+    fn test_signed_little_endian_across_bytes() {
+        let layout = MessageLayout {
+            name: "Decel".to_string(),
+            can_id: 0x18FF_1000,
+            extended: true,
+            dlc: 2,
+            signals: vec![SignalLayout {
+                signed: true,
+                factor: 0.01,
+                ..signal(SignalSource::BrakeDemand, 4, 12, ByteOrder::LittleEndian)
+            }],
+        };
+        layout.validate().unwrap();
+
+        // -0.5 / 0.01 = -50 = 0xFCE in 12 bits, starting at bit 4
+        let frame = layout.encode(|_| -0.5).unwrap();
+        assert_eq!(frame.data, [0xE0, 0xFC]);
+    }
+
+    #[test]
+    fn test_invalid_layouts_are_rejected() {
+        let mut layout = aeb_command();
+        layout.signals.push(signal(SignalSource::TargetSpeed, 14, 4, ByteOrder::LittleEndian));
+        assert!(layout.validate().unwrap_err().contains("overlaps"));
+
+        let mut layout = aeb_command();
+        layout.signals[3].start_bit = 63;
+        assert!(layout.validate().unwrap_err().contains("does not fit"));
+
+        let layout = MessageLayout { can_id: 0x800, ..aeb_command() };
+        assert!(layout.validate().is_err());
+        assert!(MessageLayout { extended: true, ..layout }.validate().is_ok());
+
+        assert!(MessageLayout { dlc: 9, ..aeb_command() }.validate().is_err());
+
+        let mut layout = aeb_command();
+        layout.signals[0].factor = 0.0;
+        assert!(layout.validate().is_err());
+
+        let mut layout = aeb_command();
+        layout.signals[0].length = 0;
+        assert!(layout.validate().is_err());
+    }
+}
@@ -2,6 +2,17 @@
 
 // The bindings are generated as a separate crate based on the BUILD target name
 use can_gateway_ecu_bindings::Guest;
+use can_gateway_ecu_bindings::exports::adas::can_gateway::can_export::{
+    self, AutomotiveDecision, ByteOrder, CanFrame, GatewayConfig, MessageLayout, SignalLayout, SignalSource,
+};
+use std::cell::RefCell;
+
+mod dbc;
+
+thread_local! {
+    // Validated message layouts, in configuration order
+    static MESSAGES: RefCell<Vec<dbc::MessageLayout>> = const { RefCell::new(Vec::new()) };
+}
 
 struct Component;
 
@@ -11,5 +22,79 @@ impl Guest for Component {
     }
 }
 
+fn to_signal_layout(signal: SignalLayout) -> dbc::SignalLayout {
+    dbc::SignalLayout {
+        name: signal.name,
+        source: match signal.source {
+            SignalSource::Intervention => dbc::SignalSource::Intervention,
+            SignalSource::Maneuver => dbc::SignalSource::Maneuver,
+            SignalSource::TargetSpeed => dbc::SignalSource::TargetSpeed,
+            SignalSource::BrakeDemand => dbc::SignalSource::BrakeDemand,
+        },
+        start_bit: signal.start_bit,
+        length: signal.length,
+        byte_order: match signal.byte_order {
+            ByteOrder::LittleEndian => dbc::ByteOrder::LittleEndian,
+            ByteOrder::BigEndian => dbc::ByteOrder::BigEndian,
+        },
+        signed: signal.signed,
+        factor: signal.factor,
+        offset: signal.offset,
+    }
+}
+
+fn to_message_layout(message: MessageLayout) -> dbc::MessageLayout {
+    dbc::MessageLayout {
+        name: message.name,
+        can_id: message.can_id,
+        extended: message.extended,
+        dlc: message.dlc,
+        signals: message.signals.into_iter().map(to_signal_layout).collect(),
+    }
+}
+
+// Physical value of a signal source; enums encode as their index
+fn signal_value(decision: &AutomotiveDecision, source: dbc::SignalSource) -> f64 {
+    match source {
+        dbc::SignalSource::Intervention => decision.intervention as u8 as f64,
+        dbc::SignalSource::Maneuver => decision.maneuver as u8 as f64,
+        dbc::SignalSource::TargetSpeed => decision.target_speed as f64,
+        dbc::SignalSource::BrakeDemand => decision.brake_demand as f64,
+    }
+}
+
+impl can_export::Guest for Component {
+    fn configure(config: GatewayConfig) -> Result<(), String> {
+        let messages: Vec<dbc::MessageLayout> = config.messages.into_iter().map(to_message_layout).collect();
+        for (i, message) in messages.iter().enumerate() {
+            message.validate()?;
+            if messages[..i].iter().any(|other| other.can_id == message.can_id && other.extended == message.extended) {
+                return Err(format!("Message '{}' reuses CAN ID {:#X}", message.name, message.can_id));
+            }
+        }
+
+        println!("CAN Gateway: {} message layouts configured", messages.len());
+        MESSAGES.with(|state| *state.borrow_mut() = messages);
+        Ok(())
+    }
+
+    fn encode_decision(decision: AutomotiveDecision) -> Result<Vec<CanFrame>, String> {
+        MESSAGES.with(|state| {
+            let messages = state.borrow();
+            if messages.is_empty() {
+                return Err("CAN gateway has no message layouts configured".to_string());
+            }
+
+            messages
+                .iter()
+                .map(|message| {
+                    let frame = message.encode(|source| signal_value(&decision, source))?;
+                    Ok(CanFrame { id: frame.id, extended: frame.extended, data: frame.data })
+                })
+                .collect()
+        })
+    }
+}
+
 // Export the component using the generated macro with proper path
 can_gateway_ecu_bindings::export!(Component with_types_in can_gateway_ecu_bindings);
@@ -9,6 +9,9 @@ use tracing::{info, warn, error, debug};
 use wasmtime::{Config, Engine, Store, Component, Linker};
 use wasmtime_wasi::{WasiCtx, WasiCtxBuilder};
 
+mod metrics;
+mod selftest;
+
 /// ADAS Wasmtime Host Application
 /// 
 /// This application loads and executes the composed ADAS WebAssembly component
@@ -184,22 +187,7 @@ impl AdasHost {
     pub async fn new(component_path: PathBuf, config: AdasConfig) -> Result<Self> {
         info!("🚀 Initializing ADAS Wasmtime Host");
         
-        // Configure wasmtime engine
-        let mut wasmtime_config = Config::new();
-        wasmtime_config.wasm_component_model(true);
-        wasmtime_config.async_support(true);
-        wasmtime_config.consume_fuel(true);
-        
-        // Enable WASI-NN for AI components
-        wasmtime_config.wasm_simd(true);
-        wasmtime_config.wasm_relaxed_simd(true);
-        
-        // Memory and resource limits
-        wasmtime_config.memory_init_cow(true);
-        wasmtime_config.memory_guaranteed_dense_image_size(1024 * 1024); // 1MB
-        
-        let engine = Engine::new(&wasmtime_config)
-            .context("Failed to create wasmtime engine")?;
+        let engine = create_engine()?;
         
         // Load the composed ADAS component
         info!("📦 Loading ADAS component from: {}", component_path.display());
@@ -455,6 +443,26 @@ fn init_logging(config: &LoggingConfig) -> Result<()> {
     Ok(())
 }
 
+/// Create the wasmtime engine shared by the host and self-test mode
+fn create_engine() -> Result<Engine> {
+    // Configure wasmtime engine
+    let mut wasmtime_config = Config::new();
+    wasmtime_config.wasm_component_model(true);
+    wasmtime_config.async_support(true);
+    wasmtime_config.consume_fuel(true);
+    
+    // Enable WASI-NN for AI components
+    wasmtime_config.wasm_simd(true);
+    wasmtime_config.wasm_relaxed_simd(true);
+    
+    // Memory and resource limits
+    wasmtime_config.memory_init_cow(true);
+    wasmtime_config.memory_guaranteed_dense_image_size(1024 * 1024); // 1MB
+    
+    Engine::new(&wasmtime_config)
+        .context("Failed to create wasmtime engine")
+}
+
 #[tokio::main]
 async fn main() -> Result<()> {
     // Parse command line arguments
@@ -475,6 +483,14 @@ async fn main() -> Result<()> {
             .long("verbose")
             .action(clap::ArgAction::SetTrue)
             .help("Enable verbose logging"))
+        .arg(Arg::new("selftest")
+            .long("selftest")
+            .action(clap::ArgAction::SetTrue)
+            .help("Exercise each component in isolation and print a JSON report; COMPONENT may be a directory"))
+        .arg(Arg::new("metrics")
+            .long("metrics")
+            .action(clap::ArgAction::SetTrue)
+            .help("Print each component's performance metrics in Prometheus text format; COMPONENT may be a directory"))
         .get_matches();
     
     let component_path = PathBuf::from(matches.get_one::<String>("component").unwrap());
@@ -500,6 +516,25 @@ async fn main() -> Result<()> {
         return Err(anyhow::anyhow!("Component file not found"));
     }
     
+    // Self-test mode: exercise every component in isolation and report
+    if matches.get_flag("selftest") {
+        let engine = create_engine()?;
+        let report = selftest::run(&engine, &component_path).await?;
+        println!("{}", serde_json::to_string_pretty(&report)?);
+        
+        if report.components_failed > 0 {
+            anyhow::bail!("{} of {} components failed self-test", report.components_failed, report.components_tested);
+        }
+        return Ok(());
+    }
+    
+    // Metrics mode: render every component's performance metrics for scraping
+    if matches.get_flag("metrics") {
+        let engine = create_engine()?;
+        print!("{}", metrics::run(&engine, &component_path).await?);
+        return Ok(());
+    }
+    
     // Create and run ADAS host
     let mut host = AdasHost::new(component_path, config).await?;
     
@@ -0,0 +1,378 @@
+//! Prometheus metrics mode
+//!
+//! Instantiates every built component, calls `get-performance` on those that
+//! export `adas:diagnostics/performance-monitoring` and renders the returned
+//! `extended-performance` records in the Prometheus text exposition format.
+//! Every sample carries the component's file stem as its `component_id`
+//! label, so one scrape covers the whole system.
+//!
+//! Components are instantiated as in self-test mode, with imports the host
+//! does not provide stubbed, and each one gets an `adas_up` gauge: 1 when it
+//! instantiated and reported its metrics (or has none), 0 when it failed.
+
+use anyhow::Result;
+use std::collections::BTreeMap;
+use std::fmt::Write;
+use std::path::Path;
+use tracing::{info, warn};
+use wasmtime::component::Val;
+use wasmtime::Engine;
+
+use crate::selftest;
+
+/// Interface providing `get-performance`
+const PERFORMANCE_INTERFACE: &str = "adas:diagnostics/performance-monitoring";
+
+/// Prefix of every exported metric name
+const METRIC_PREFIX: &str = "adas_";
+
+/// `base-metrics` fields: field, metric name, help
+const BASE_METRICS: &[(&str, &str, &str)] = &[
+    ("latency-avg-ms", "latency_avg_ms", "Average processing latency in milliseconds"),
+    ("latency-max-ms", "latency_max_ms", "Maximum processing latency in milliseconds"),
+    ("cpu-utilization", "cpu_utilization_ratio", "CPU utilization from 0 to 1"),
+    ("memory-usage-mb", "memory_usage_mb", "Memory usage in megabytes"),
+    ("throughput-hz", "throughput_hz", "Processing throughput in hertz"),
+    ("error-rate", "error_rate_ratio", "Fraction of failed operations from 0 to 1"),
+];
+
+/// `resource-usage` fields: field, metric name, help
+const RESOURCE_METRICS: &[(&str, &str, &str)] = &[
+    ("cpu-cores-used", "resource_cpu_cores_used", "CPU cores in use"),
+    ("memory-allocated-mb", "resource_memory_allocated_mb", "Allocated memory in megabytes"),
+    ("memory-peak-mb", "resource_memory_peak_mb", "Peak memory in megabytes"),
+    ("disk-io-mb", "resource_disk_io_mb", "Disk I/O in megabytes"),
+    ("network-io-mb", "resource_network_io_mb", "Network I/O in megabytes"),
+    ("gpu-utilization", "resource_gpu_utilization_ratio", "GPU utilization from 0 to 1"),
+    ("gpu-memory-mb", "resource_gpu_memory_mb", "GPU memory in megabytes"),
+];
+
+/// Prometheus metric type
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MetricKind {
+    Counter,
+    Gauge,
+}
+
+impl MetricKind {
+    fn as_str(self) -> &'static str {
+        match self {
+            MetricKind::Counter => "counter",
+            MetricKind::Gauge => "gauge",
+        }
+    }
+}
+
+/// First sample of a metric family and the value each component reported
+type Family<'a> = (&'a Sample, Vec<(&'a str, f64)>);
+
+/// One metric value reported by a component
+#[derive(Debug, Clone, PartialEq)]
+pub struct Sample {
+    pub name: String,
+    pub help: String,
+    pub kind: MetricKind,
+    pub value: f64,
+}
+
+/// Collect and render the metrics of a component file or a directory of components
+pub async fn run(engine: &Engine, target: &Path) -> Result<String> {
+    let mut components = Vec::new();
+    for path in selftest::collect_components(target)? {
+        let component_id = selftest::component_name(&path);
+        let (up, mut component_samples) = match performance(engine, &path).await {
+            Ok(Some(value)) => (true, samples(&value)),
+            Ok(None) => {
+                info!("{} does not export {}", component_id, PERFORMANCE_INTERFACE);
+                (true, Vec::new())
+            }
+            Err(e) => {
+                warn!("❌ Failed to collect metrics from {}: {:#}", component_id, e);
+                (false, Vec::new())
+            }
+        };
+        component_samples.insert(0, up_sample(up));
+        components.push((component_id, component_samples));
+    }
+
+    Ok(render(&components))
+}
+
+/// Call `get-performance` on a fresh instance; `None` if it is not exported
+async fn performance(engine: &Engine, path: &Path) -> Result<Option<Val>> {
+    let (mut store, component, instance) = selftest::instantiate(engine, path).await?;
+
+    let interface = selftest::exported_functions(engine, &component)
+        .into_iter()
+        .find(|(interface, function)| {
+            function == "get-performance"
+                && interface.as_deref().and_then(|name| name.split('@').next()) == Some(PERFORMANCE_INTERFACE)
+        })
+        .and_then(|(interface, _)| interface);
+    let Some(interface) = interface else {
+        return Ok(None);
+    };
+    let Some(func) = selftest::lookup_func(&mut store, &instance, Some(&interface), "get-performance") else {
+        return Ok(None);
+    };
+
+    let mut results = [Val::Bool(false)];
+    func.call_async(&mut store, &[], &mut results).await?;
+    func.post_return_async(&mut store).await?;
+    let [value] = results;
+    Ok(Some(value))
+}
+
+/// `adas_up` sample: whether a component could be instantiated and scraped
+pub fn up_sample(up: bool) -> Sample {
+    Sample {
+        name: format!("{}up", METRIC_PREFIX),
+        help: "Whether the component instantiated and reported its metrics (1) or failed (0)".to_string(),
+        kind: MetricKind::Gauge,
+        value: if up { 1.0 } else { 0.0 },
+    }
+}
+
+/// Flatten an `extended-performance` record into samples
+pub fn samples(performance: &Val) -> Vec<Sample> {
+    let mut samples = Vec::new();
+
+    for (record, fields) in [("base-metrics", BASE_METRICS), ("resource-usage", RESOURCE_METRICS)] {
+        let Some(record) = field(performance, record) else {
+            continue;
+        };
+        for (wit_name, name, help) in fields {
+            if let Some(value) = field(record, wit_name).and_then(number) {
+                samples.push(Sample {
+                    name: format!("{}{}", METRIC_PREFIX, name),
+                    help: help.to_string(),
+                    kind: MetricKind::Gauge,
+                    value,
+                });
+            }
+        }
+    }
+
+    if let Some(Val::List(metrics)) = field(performance, "component-specific") {
+        for metric in metrics {
+            let (Some(Val::String(name)), Some(value)) = (field(metric, "name"), field(metric, "value").and_then(number))
+            else {
+                continue;
+            };
+            let unit = match field(metric, "unit") {
+                Some(Val::String(unit)) => unit.as_str(),
+                _ => "",
+            };
+            let description = match field(metric, "description") {
+                Some(Val::String(description)) => description.as_str(),
+                _ => "",
+            };
+            samples.push(component_metric(name, value, unit, description));
+        }
+    }
+
+    samples
+}
+
+/// Sample for a component-specific metric
+///
+/// Components report cumulative values as `count` metrics described as a
+/// total ("Total frames rendered") or named `*_total`; everything else is a
+/// point-in-time gauge. Counters get the conventional `_total` suffix.
+fn component_metric(name: &str, value: f64, unit: &str, description: &str) -> Sample {
+    let mut name = format!("{}{}", METRIC_PREFIX, sanitize_name(name));
+    let cumulative = name.ends_with("_total")
+        || (unit.eq_ignore_ascii_case("count") && description.trim_start().to_lowercase().starts_with("total"));
+    let kind = if cumulative { MetricKind::Counter } else { MetricKind::Gauge };
+    if kind == MetricKind::Counter && !name.ends_with("_total") {
+        name.push_str("_total");
+    }
+
+    let help = match (description.is_empty(), unit.is_empty()) {
+        (true, _) => name.clone(),
+        (false, true) => description.to_string(),
+        (false, false) => format!("{} ({})", description, unit),
+    };
+    Sample { name, help, kind, value }
+}
+
+/// Render samples in the Prometheus text exposition format
+///
+/// Samples are grouped into one family per metric name, with a single
+/// `# HELP` and `# TYPE` line taken from the first component reporting it.
+pub fn render(components: &[(String, Vec<Sample>)]) -> String {
+    let mut families: BTreeMap<&str, Family> = BTreeMap::new();
+    for (component_id, samples) in components {
+        for sample in samples {
+            families
+                .entry(sample.name.as_str())
+                .or_insert_with(|| (sample, Vec::new()))
+                .1
+                .push((component_id.as_str(), sample.value));
+        }
+    }
+
+    let mut out = String::new();
+    for (name, (first, values)) in families {
+        let _ = writeln!(out, "# HELP {} {}", name, escape_help(&first.help));
+        let _ = writeln!(out, "# TYPE {} {}", name, first.kind.as_str());
+        for (component_id, value) in values {
+            let _ = writeln!(
+                out,
+                "{}{{component_id=\"{}\"}} {}",
+                name,
+                escape_label_value(component_id),
+                format_value(value)
+            );
+        }
+    }
+    out
+}
+
+/// Make a metric name match `[a-zA-Z_:][a-zA-Z0-9_:]*`
+pub fn sanitize_name(name: &str) -> String {
+    let mut sanitized: String = name
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() || c == '_' || c == ':' { c } else { '_' })
+        .collect();
+    if sanitized.is_empty() || sanitized.starts_with(|c: char| c.is_ascii_digit()) {
+        sanitized.insert(0, '_');
+    }
+    sanitized
+}
+
+fn escape_label_value(value: &str) -> String {
+    value.replace('\\', "\\\\").replace('"', "\\\"").replace('\n', "\\n")
+}
+
+fn escape_help(help: &str) -> String {
+    help.replace('\\', "\\\\").replace('\n', "\\n")
+}
+
+fn format_value(value: f64) -> String {
+    if value.is_nan() {
+        "NaN".to_string()
+    } else if value.is_infinite() {
+        if value > 0.0 { "+Inf" } else { "-Inf" }.to_string()
+    } else {
+        value.to_string()
+    }
+}
+
+fn field<'a>(record: &'a Val, name: &str) -> Option<&'a Val> {
+    match record {
+        Val::Record(fields) => fields.iter().find(|(field, _)| field == name).map(|(_, value)| value),
+        _ => None,
+    }
+}
+
+fn number(value: &Val) -> Option<f64> {
+    match value {
+        Val::Float32(v) => Some(*v as f64),
+        Val::Float64(v) => Some(*v),
+        Val::U8(v) => Some(*v as f64),
+        Val::U16(v) => Some(*v as f64),
+        Val::U32(v) => Some(*v as f64),
+        Val::U64(v) => Some(*v as f64),
+        Val::S8(v) => Some(*v as f64),
+        Val::S16(v) => Some(*v as f64),
+        Val::S32(v) => Some(*v as f64),
+        Val::S64(v) => Some(*v as f64),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn record(fields: &[(&str, Val)]) -> Val {
+        Val::Record(fields.iter().map(|(name, value)| (name.to_string(), value.clone())).collect())
+    }
+
+    fn metric(name: &str, value: f64, unit: &str, description: &str) -> Val {
+        record(&[
+            ("name", Val::String(name.to_string())),
+            ("value", Val::Float64(value)),
+            ("unit", Val::String(unit.to_string())),
+            ("description", Val::String(description.to_string())),
+        ])
+    }
+
+    #[test]
+    fn test_renders_extended_performance() {
+        let performance = record(&[
+            (
+                "base-metrics",
+                record(&[("latency-avg-ms", Val::Float32(12.5)), ("memory-usage-mb", Val::U32(64))]),
+            ),
+            (
+                "component-specific",
+                Val::List(vec![
+                    metric("messages_processed", 1200.0, "count", "Total messages processed"),
+                    metric("overlay_objects", 3.0, "count", "Objects in current overlay"),
+                    metric("render time (ms)", 4.0, "milliseconds", "Average render time per frame"),
+                ]),
+            ),
+            ("resource-usage", record(&[("gpu-utilization", Val::Float32(0.5))])),
+            ("timestamp", Val::U64(1)),
+        ]);
+
+        let text = render(&[
+            ("orchestrator".to_string(), samples(&performance)),
+            ("lidar\"x".to_string(), samples(&record(&[("base-metrics", record(&[("latency-avg-ms", Val::Float32(2.0))]))]))),
+        ]);
+
+        assert!(text.contains(
+            "# HELP adas_latency_avg_ms Average processing latency in milliseconds\n\
+             # TYPE adas_latency_avg_ms gauge\n\
+             adas_latency_avg_ms{component_id=\"orchestrator\"} 12.5\n\
+             adas_latency_avg_ms{component_id=\"lidar\\\"x\"} 2\n"
+        ));
+        assert!(text.contains("adas_memory_usage_mb{component_id=\"orchestrator\"} 64\n"));
+        assert!(text.contains("# TYPE adas_messages_processed_total counter\n"));
+        assert!(text.contains("adas_messages_processed_total{component_id=\"orchestrator\"} 1200\n"));
+        assert!(text.contains("# TYPE adas_overlay_objects gauge\n"));
+        assert!(text.contains("# HELP adas_render_time__ms_ Average render time per frame (milliseconds)\n"));
+        assert!(text.contains("adas_resource_gpu_utilization_ratio{component_id=\"orchestrator\"} 0.5\n"));
+        assert_eq!(text.matches("# TYPE adas_latency_avg_ms").count(), 1);
+    }
+
+    #[test]
+    fn test_up_gauge_per_component() {
+        let performance = record(&[("base-metrics", record(&[("latency-avg-ms", Val::Float32(2.0))]))]);
+        let mut lidar = samples(&performance);
+        lidar.insert(0, up_sample(true));
+
+        let text = render(&[
+            ("lidar".to_string(), lidar),
+            ("hmi-interface".to_string(), vec![up_sample(true)]),
+            ("object-detection".to_string(), vec![up_sample(false)]),
+        ]);
+
+        assert!(text.contains(
+            "# TYPE adas_up gauge\n\
+             adas_up{component_id=\"lidar\"} 1\n\
+             adas_up{component_id=\"hmi-interface\"} 1\n\
+             adas_up{component_id=\"object-detection\"} 0\n"
+        ));
+        assert!(text.contains("adas_latency_avg_ms{component_id=\"lidar\"} 2\n"));
+        assert!(!text.contains("adas_latency_avg_ms{component_id=\"object-detection\"}"));
+    }
+
+    #[test]
+    fn test_sanitizes_names_and_values() {
+        assert_eq!(sanitize_name("frames.rendered-per/s"), "frames_rendered_per_s");
+        assert_eq!(sanitize_name("3d_boxes"), "_3d_boxes");
+        assert_eq!(sanitize_name("ns:metric"), "ns:metric");
+        assert_eq!(sanitize_name(""), "_");
+
+        assert_eq!(format_value(f64::NAN), "NaN");
+        assert_eq!(format_value(f64::NEG_INFINITY), "-Inf");
+        assert_eq!(format_value(0.25), "0.25");
+
+        let counter = component_metric("retries_total", 2.0, "", "");
+        assert_eq!((counter.name.as_str(), counter.kind), ("adas_retries_total", MetricKind::Counter));
+        assert_eq!(counter.help, "adas_retries_total");
+    }
+}
@@ -0,0 +1,775 @@
+//! Component self-test mode
+//!
+//! Instantiates every built component in isolation and exercises its
+//! lifecycle: initialize and start with a default configuration, feed one
+//! synthetic input, and run its health diagnostic. Unlike static validation
+//! this catches problems that only show up at instantiation time, such as a
+//! missing WASI-NN backend or a model that fails to load.
+//!
+//! Imports the host does not provide are linked as traps, so a component
+//! still instantiates and only the calls that reach them fail. Arguments
+//! come from `selftest-defaults.json`, which lists each component's default
+//! configuration; unlisted parameters get zero values.
+
+use anyhow::{Context, Result};
+use serde::Serialize;
+use serde_json::Value as Json;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::time::Instant;
+use tracing::{info, warn};
+use wasmtime::component::types::{ComponentItem, Type};
+use wasmtime::component::{Component, Func, Instance, Linker, Val};
+use wasmtime::{Engine, Store};
+use wasmtime_wasi::{WasiCtx, WasiCtxBuilder, WasiView, ResourceTable};
+
+/// Lifecycle phases exercised by the self-test, in execution order
+const PHASES: &[(&str, &[&str])] = &[
+    ("initialize", &["initialize-system", "initialize"]),
+    ("start", &["start-system", "start"]),
+    ("synthetic-input", &["process-frame", "fuse-sensor-data", "predict-trajectories", "process-sensor-frame"]),
+    ("diagnostic", &["run-diagnostic", "run-diagnostics"]),
+];
+
+/// Arguments lifecycle functions are called with, keyed by
+/// `package:name/interface#function` without the package version
+const DEFAULT_ARGUMENTS: &str = include_str!("../selftest-defaults.json");
+
+/// Interfaces AI components import to run inference
+const WASI_NN_PREFIX: &str = "wasi:nn/";
+
+/// Whether the host links a WASI-NN backend
+const WASI_NN_AVAILABLE: bool = cfg!(feature = "wasi-nn");
+
+/// Self-test report for all components
+#[derive(Debug, Serialize)]
+pub struct SelfTestReport {
+    pub components_tested: usize,
+    pub components_passed: usize,
+    pub components_failed: usize,
+    pub components: Vec<ComponentSelfTest>,
+}
+
+/// Self-test outcome for one component
+#[derive(Debug, Serialize)]
+pub struct ComponentSelfTest {
+    pub component: String,
+    pub path: PathBuf,
+    pub passed: bool,
+    pub steps: Vec<SelfTestStep>,
+}
+
+/// A single step of a component self-test
+#[derive(Debug, Serialize)]
+pub struct SelfTestStep {
+    pub phase: String,
+    pub function: Option<String>,
+    pub passed: bool,
+    pub detail: String,
+    pub duration_ms: f32,
+}
+
+/// Store state for self-test instances
+pub(crate) struct SelfTestState {
+    wasi: WasiCtx,
+    #[cfg(feature = "wasi-nn")]
+    nn: wasmtime_wasi_nn::WasiNnCtx,
+    table: ResourceTable,
+}
+
+impl WasiView for SelfTestState {
+    fn ctx(&mut self) -> &mut WasiCtx {
+        &mut self.wasi
+    }
+
+    fn table(&mut self) -> &mut ResourceTable {
+        &mut self.table
+    }
+}
+
+/// Arguments to call lifecycle functions with instead of zero values
+pub(crate) struct DefaultArguments(HashMap<String, Vec<Json>>);
+
+impl DefaultArguments {
+    /// The table shipped with the host
+    pub(crate) fn builtin() -> Result<Self> {
+        Self::parse(DEFAULT_ARGUMENTS)
+    }
+
+    pub(crate) fn parse(source: &str) -> Result<Self> {
+        serde_json::from_str(source)
+            .map(Self)
+            .context("Failed to parse self-test default arguments")
+    }
+
+    /// Arguments listed for a function, if any
+    fn get(&self, interface: Option<&str>, function: &str) -> Option<&[Json]> {
+        let key = match interface {
+            Some(interface) => format!("{}#{}", strip_version(interface), function),
+            None => function.to_string(),
+        };
+        self.0.get(&key).map(Vec::as_slice)
+    }
+}
+
+/// Interface name without its `@version` suffix
+fn strip_version(interface: &str) -> &str {
+    interface.split('@').next().unwrap_or(interface)
+}
+
+/// Run the self-test over a component file or a directory of components
+pub async fn run(engine: &Engine, target: &Path) -> Result<SelfTestReport> {
+    let paths = collect_components(target)?;
+    let defaults = DefaultArguments::builtin()?;
+    info!("🧪 Running self-test on {} components", paths.len());
+
+    let mut components = Vec::new();
+    for path in paths {
+        let result = test_component(engine, &path, &defaults).await;
+        if result.passed {
+            info!("✅ {} passed self-test", result.component);
+        } else {
+            warn!("❌ {} failed self-test", result.component);
+        }
+        components.push(result);
+    }
+
+    let components_passed = components.iter().filter(|c| c.passed).count();
+    Ok(SelfTestReport {
+        components_tested: components.len(),
+        components_passed,
+        components_failed: components.len() - components_passed,
+        components,
+    })
+}
+
+/// Collect `.wasm` files to test
+pub(crate) fn collect_components(target: &Path) -> Result<Vec<PathBuf>> {
+    if target.is_file() {
+        return Ok(vec![target.to_path_buf()]);
+    }
+
+    let mut paths: Vec<PathBuf> = std::fs::read_dir(target)
+        .with_context(|| format!("Failed to read component directory: {}", target.display()))?
+        .filter_map(|entry| entry.ok().map(|e| e.path()))
+        .filter(|path| path.extension().map_or(false, |ext| ext == "wasm"))
+        .collect();
+    paths.sort();
+
+    Ok(paths)
+}
+
+/// Component name reported for a component file: its file stem
+pub(crate) fn component_name(path: &Path) -> String {
+    path.file_stem()
+        .map(|s| s.to_string_lossy().to_string())
+        .unwrap_or_else(|| path.display().to_string())
+}
+
+/// Instantiate one component and run every lifecycle phase it exports
+async fn test_component(engine: &Engine, path: &Path, defaults: &DefaultArguments) -> ComponentSelfTest {
+    let name = component_name(path);
+
+    let mut steps = Vec::new();
+    let start = Instant::now();
+
+    let instantiated = instantiate(engine, path).await;
+    let (mut store, component, instance) = match instantiated {
+        Ok(parts) => {
+            steps.push(step("instantiate", None, true, "Component instantiated".to_string(), start));
+            parts
+        }
+        Err(e) => {
+            steps.push(step("instantiate", None, false, format!("{:#}", e), start));
+            return ComponentSelfTest { component: name, path: path.to_path_buf(), passed: false, steps };
+        }
+    };
+
+    // Inference calls trap without a backend; report that once instead of per phase
+    let nn_imports = wasi_nn_imports(engine, &component);
+    if !nn_imports.is_empty() && !WASI_NN_AVAILABLE {
+        steps.push(SelfTestStep {
+            phase: "wasi-nn".to_string(),
+            function: None,
+            passed: false,
+            detail: format!(
+                "Imports {} but the host has no WASI-NN backend (build with --features wasi-nn)",
+                nn_imports.join(", ")
+            ),
+            duration_ms: 0.0,
+        });
+    }
+
+    let exports = exported_functions(engine, &component);
+
+    for (phase, candidates) in PHASES {
+        let found = candidates.iter().find_map(|candidate| {
+            exports.iter().find(|(_, function)| function == candidate)
+        });
+
+        let Some((interface, function)) = found else {
+            continue;
+        };
+
+        let phase_start = Instant::now();
+        let qualified = match interface {
+            Some(interface) => format!("{}#{}", interface, function),
+            None => function.clone(),
+        };
+
+        match lookup_func(&mut store, &instance, interface.as_deref(), function) {
+            Some(func) => {
+                let arguments = defaults.get(interface.as_deref(), function);
+                let (passed, detail) = call_phase(&mut store, func, phase, arguments).await;
+                steps.push(step(phase, Some(qualified), passed, detail, phase_start));
+            }
+            None => steps.push(step(phase, Some(qualified), false, "Export not callable".to_string(), phase_start)),
+        }
+    }
+
+    if !steps.iter().any(|s| s.phase == "diagnostic") {
+        steps.push(SelfTestStep {
+            phase: "diagnostic".to_string(),
+            function: None,
+            passed: false,
+            detail: "Component does not export a diagnostic function".to_string(),
+            duration_ms: 0.0,
+        });
+    }
+
+    let passed = steps.iter().all(|s| s.passed);
+    ComponentSelfTest { component: name, path: path.to_path_buf(), passed, steps }
+}
+
+/// Create a fresh store and instance for a component
+pub(crate) async fn instantiate(engine: &Engine, path: &Path) -> Result<(Store<SelfTestState>, Component, Instance)> {
+    let component = Component::from_file(engine, path)
+        .with_context(|| format!("Failed to load component: {}", path.display()))?;
+
+    let state = SelfTestState {
+        wasi: WasiCtxBuilder::new().inherit_stdio().build(),
+        #[cfg(feature = "wasi-nn")]
+        nn: wasi_nn_ctx()?,
+        table: ResourceTable::new(),
+    };
+    let mut store = Store::new(engine, state);
+    store.set_fuel(u64::MAX).context("Failed to set fuel")?;
+
+    let mut linker = Linker::new(engine);
+    wasmtime_wasi::add_to_linker_async(&mut linker)
+        .context("Failed to add WASI to linker")?;
+    #[cfg(feature = "wasi-nn")]
+    wasmtime_wasi_nn::wit::add_to_linker(&mut linker, |state: &mut SelfTestState| {
+        wasmtime_wasi_nn::wit::WasiNnView::new(&mut state.table, &mut state.nn)
+    })
+    .context("Failed to add WASI-NN to linker")?;
+    // Anything else (other components' interfaces, host services) traps when called
+    linker.define_unknown_imports_as_traps(&component)
+        .context("Failed to stub unknown imports")?;
+
+    let instance = linker.instantiate_async(&mut store, &component).await
+        .context("Failed to instantiate component")?;
+
+    Ok((store, component, instance))
+}
+
+/// WASI-NN context with every backend the host was built with
+#[cfg(feature = "wasi-nn")]
+fn wasi_nn_ctx() -> Result<wasmtime_wasi_nn::WasiNnCtx> {
+    let (backends, registry) = wasmtime_wasi_nn::preload(&[])
+        .context("Failed to load WASI-NN backends")?;
+    Ok(wasmtime_wasi_nn::WasiNnCtx::new(backends, registry))
+}
+
+/// WASI-NN interfaces a component imports
+pub(crate) fn wasi_nn_imports(engine: &Engine, component: &Component) -> Vec<String> {
+    component
+        .component_type()
+        .imports(engine)
+        .filter(|(name, _)| name.starts_with(WASI_NN_PREFIX))
+        .map(|(name, _)| name.to_string())
+        .collect()
+}
+
+/// List exported functions as (interface, function) pairs
+pub(crate) fn exported_functions(engine: &Engine, component: &Component) -> Vec<(Option<String>, String)> {
+    let mut functions = Vec::new();
+
+    for (name, item) in component.component_type().exports(engine) {
+        match item {
+            ComponentItem::ComponentFunc(_) => functions.push((None, name.to_string())),
+            ComponentItem::ComponentInstance(instance) => {
+                for (function, item) in instance.exports(engine) {
+                    if matches!(item, ComponentItem::ComponentFunc(_)) {
+                        functions.push((Some(name.to_string()), function.to_string()));
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+
+    functions
+}
+
+/// Resolve an exported function, optionally nested in an exported interface
+pub(crate) fn lookup_func(
+    store: &mut Store<SelfTestState>,
+    instance: &Instance,
+    interface: Option<&str>,
+    function: &str,
+) -> Option<Func> {
+    let parent = match interface {
+        Some(interface) => Some(instance.get_export(&mut *store, None, interface)?),
+        None => None,
+    };
+    let index = instance.get_export(&mut *store, parent.as_ref(), function)?;
+    instance.get_func(&mut *store, index)
+}
+
+/// Call a lifecycle function and check that its output is well-formed
+///
+/// `arguments` are the function's entry in the default-argument table;
+/// without one every parameter is default-constructed.
+async fn call_phase(
+    store: &mut Store<SelfTestState>,
+    func: Func,
+    phase: &str,
+    arguments: Option<&[Json]>,
+) -> (bool, String) {
+    let param_types = func.params(&*store);
+    let params: Vec<Val> = match arguments {
+        Some(arguments) if arguments.len() != param_types.len() => {
+            return (false, format!(
+                "Default arguments list {} values for {} parameters",
+                arguments.len(),
+                param_types.len()
+            ));
+        }
+        Some(arguments) => {
+            let converted = param_types.iter().zip(arguments).map(|(ty, json)| val_from_json(ty, json));
+            match converted.collect::<Result<_, _>>() {
+                Ok(params) => params,
+                Err(e) => return (false, format!("Invalid default arguments: {}", e)),
+            }
+        }
+        None => param_types.iter().map(default_val).collect(),
+    };
+    let mut results = vec![Val::Bool(false); func.results(&*store).len()];
+
+    if let Err(e) = func.call_async(&mut *store, &params, &mut results).await {
+        return (false, format!("Call trapped: {:#}", e));
+    }
+    if let Err(e) = func.post_return_async(&mut *store).await {
+        return (false, format!("Post-return failed: {:#}", e));
+    }
+
+    match results.first() {
+        Some(value) => match check_output(phase, value) {
+            Ok(()) => (true, format!("Returned {}", describe(value))),
+            Err(problem) => (false, problem),
+        },
+        None => (true, "Completed".to_string()),
+    }
+}
+
+/// Check that a phase's return value is well-formed
+///
+/// No error or non-finite number may appear anywhere in it, a returned
+/// string must not be empty, and a diagnostic must report at least one
+/// check with none of them failing.
+fn check_output(phase: &str, value: &Val) -> Result<(), String> {
+    if let Val::Result(Err(error)) = value {
+        return Err(format!("Returned error: {:?}", error));
+    }
+    if matches!(value, Val::String(s) if s.trim().is_empty()) {
+        return Err("Returned an empty string".to_string());
+    }
+    check_value(value, "result")?;
+    if phase == "diagnostic" {
+        check_diagnostic(value)?;
+    }
+    Ok(())
+}
+
+/// Reject errors and non-finite numbers nested anywhere in a value
+fn check_value(value: &Val, path: &str) -> Result<(), String> {
+    match value {
+        Val::Float32(f) if !f.is_finite() => Err(format!("Non-finite number at {}", path)),
+        Val::Float64(f) if !f.is_finite() => Err(format!("Non-finite number at {}", path)),
+        Val::Result(Err(error)) => Err(format!("Error at {}: {:?}", path, error)),
+        Val::Result(Ok(Some(inner))) | Val::Option(Some(inner)) | Val::Variant(_, Some(inner)) => {
+            check_value(inner, path)
+        }
+        Val::Record(fields) => fields
+            .iter()
+            .try_for_each(|(name, field)| check_value(field, &format!("{}.{}", path, name))),
+        Val::List(items) | Val::Tuple(items) => items
+            .iter()
+            .enumerate()
+            .try_for_each(|(index, item)| check_value(item, &format!("{}[{}]", path, index))),
+        _ => Ok(()),
+    }
+}
+
+/// A diagnostic must report checks, and every check with a `passed` field must pass
+fn check_diagnostic(value: &Val) -> Result<(), String> {
+    let checks = match value {
+        Val::Result(Ok(Some(inner))) => return check_diagnostic(inner),
+        Val::List(checks) => checks.as_slice(),
+        other => std::slice::from_ref(other),
+    };
+    if checks.is_empty() {
+        return Err("Diagnostic reported no checks".to_string());
+    }
+
+    for check in checks {
+        let Val::Record(fields) = check else {
+            continue;
+        };
+        let field = |name: &str| fields.iter().find(|(field, _)| field == name).map(|(_, value)| value);
+        if matches!(field("passed"), Some(Val::Bool(false))) {
+            let text = |name: &str| match field(name) {
+                Some(Val::String(s)) => s.as_str(),
+                _ => "",
+            };
+            return Err(format!("Diagnostic check '{}' failed: {}", text("name"), text("message")));
+        }
+    }
+    Ok(())
+}
+
+/// Build a value of a component-model type from its JSON form
+///
+/// Records are objects keyed by field name, enums and flags use case names,
+/// variants are `{"case": payload}` or a bare case name, results are
+/// `{"ok": ..}` or `{"err": ..}`, and `null` is an absent option.
+fn val_from_json(ty: &Type, json: &Json) -> Result<Val, String> {
+    let mismatch = || format!("{} is not a valid {}", json, kind(ty));
+    let value = match ty {
+        Type::Bool => json.as_bool().map(Val::Bool),
+        Type::S8 => integer(json).map(Val::S8),
+        Type::U8 => integer(json).map(Val::U8),
+        Type::S16 => integer(json).map(Val::S16),
+        Type::U16 => integer(json).map(Val::U16),
+        Type::S32 => integer(json).map(Val::S32),
+        Type::U32 => integer(json).map(Val::U32),
+        Type::S64 => json.as_i64().map(Val::S64),
+        Type::U64 => json.as_u64().map(Val::U64),
+        Type::Float32 => json.as_f64().map(|f| Val::Float32(f as f32)),
+        Type::Float64 => json.as_f64().map(Val::Float64),
+        Type::Char => {
+            let mut chars = json.as_str().unwrap_or_default().chars();
+            match (chars.next(), chars.next()) {
+                (Some(c), None) => Some(Val::Char(c)),
+                _ => None,
+            }
+        }
+        Type::String => json.as_str().map(|s| Val::String(s.to_string())),
+        Type::List(list) => {
+            let items = json.as_array().ok_or_else(mismatch)?;
+            let element = list.ty();
+            return items.iter().map(|item| val_from_json(&element, item)).collect::<Result<_, _>>().map(Val::List);
+        }
+        Type::Record(record) => {
+            let object = json.as_object().ok_or_else(mismatch)?;
+            if let Some(unknown) = object.keys().find(|key| !record.fields().any(|field| field.name == key.as_str())) {
+                return Err(format!("record has no field '{}'", unknown));
+            }
+            let fields = record.fields().map(|field| {
+                let json = object.get(field.name).ok_or_else(|| format!("missing record field '{}'", field.name))?;
+                let value = val_from_json(&field.ty, json).map_err(|e| format!("{}: {}", field.name, e))?;
+                Ok((field.name.to_string(), value))
+            });
+            return fields.collect::<Result<_, String>>().map(Val::Record);
+        }
+        Type::Tuple(tuple) => {
+            let items = json.as_array().filter(|items| items.len() == tuple.types().len()).ok_or_else(mismatch)?;
+            let values = tuple.types().zip(items).map(|(ty, item)| val_from_json(&ty, item));
+            return values.collect::<Result<_, _>>().map(Val::Tuple);
+        }
+        Type::Variant(variant) => {
+            let (name, payload) = single_case(json).ok_or_else(mismatch)?;
+            let case = variant.cases().find(|case| case.name == name)
+                .ok_or_else(|| format!("variant has no case '{}'", name))?;
+            let payload = match (case.ty, payload) {
+                (Some(ty), Some(payload)) => Some(Box::new(val_from_json(&ty, payload)?)),
+                (None, None) => None,
+                _ => return Err(mismatch()),
+            };
+            Some(Val::Variant(name.to_string(), payload))
+        }
+        Type::Enum(enum_ty) => json
+            .as_str()
+            .filter(|name| enum_ty.names().any(|case| case == *name))
+            .map(|name| Val::Enum(name.to_string())),
+        Type::Option(option) => {
+            if json.is_null() {
+                return Ok(Val::Option(None));
+            }
+            return val_from_json(&option.ty(), json).map(|value| Val::Option(Some(Box::new(value))));
+        }
+        Type::Result(result) => {
+            let (case, payload) = single_case(json).ok_or_else(mismatch)?;
+            let ty = match case {
+                "ok" => result.ok(),
+                "err" => result.err(),
+                _ => return Err(mismatch()),
+            };
+            let payload = match (ty, payload) {
+                (Some(ty), Some(payload)) => Some(Box::new(val_from_json(&ty, payload)?)),
+                (None, None) => None,
+                _ => return Err(mismatch()),
+            };
+            Some(Val::Result(if case == "ok" { Ok(payload) } else { Err(payload) }))
+        }
+        Type::Flags(flags) => json.as_array().and_then(|names| {
+            names
+                .iter()
+                .map(|name| name.as_str().filter(|name| flags.names().any(|flag| flag == *name)).map(str::to_string))
+                .collect::<Option<Vec<_>>>()
+                .map(Val::Flags)
+        }),
+        Type::Own(_) | Type::Borrow(_) => return Err("resources cannot be given as arguments".to_string()),
+    };
+    value.ok_or_else(mismatch)
+}
+
+/// Integer of any width that fits the JSON number
+fn integer<T: TryFrom<i64>>(json: &Json) -> Option<T> {
+    json.as_i64().and_then(|n| T::try_from(n).ok())
+}
+
+/// Case name and payload of `"case"` or `{"case": payload}`
+fn single_case(json: &Json) -> Option<(&str, Option<&Json>)> {
+    match json {
+        Json::String(name) => Some((name.as_str(), None)),
+        Json::Object(object) if object.len() == 1 => {
+            object.iter().next().map(|(name, payload)| (name.as_str(), Some(payload)))
+        }
+        _ => None,
+    }
+}
+
+/// Name of a type's kind, for argument errors
+fn kind(ty: &Type) -> &'static str {
+    match ty {
+        Type::Bool => "bool",
+        Type::S8 | Type::U8 | Type::S16 | Type::U16 | Type::S32 | Type::U32 | Type::S64 | Type::U64 => "integer",
+        Type::Float32 | Type::Float64 => "number",
+        Type::Char => "char",
+        Type::String => "string",
+        Type::List(_) => "list",
+        Type::Record(_) => "record",
+        Type::Tuple(_) => "tuple",
+        Type::Variant(_) => "variant",
+        Type::Enum(_) => "enum",
+        Type::Option(_) => "option",
+        Type::Result(_) => "result",
+        Type::Flags(_) => "flags",
+        Type::Own(_) | Type::Borrow(_) => "resource",
+    }
+}
+
+/// Build a default value for a component-model type
+fn default_val(ty: &Type) -> Val {
+    match ty {
+        Type::Bool => Val::Bool(false),
+        Type::S8 => Val::S8(0),
+        Type::U8 => Val::U8(0),
+        Type::S16 => Val::S16(0),
+        Type::U16 => Val::U16(0),
+        Type::S32 => Val::S32(0),
+        Type::U32 => Val::U32(0),
+        Type::S64 => Val::S64(0),
+        Type::U64 => Val::U64(0),
+        Type::Float32 => Val::Float32(0.0),
+        Type::Float64 => Val::Float64(0.0),
+        Type::Char => Val::Char('\0'),
+        Type::String => Val::String(String::new()),
+        Type::List(_) => Val::List(Vec::new()),
+        Type::Record(record) => Val::Record(
+            record.fields().map(|field| (field.name.to_string(), default_val(&field.ty))).collect(),
+        ),
+        Type::Tuple(tuple) => Val::Tuple(tuple.types().map(|ty| default_val(&ty)).collect()),
+        Type::Variant(variant) => {
+            let case = variant.cases().next().expect("variant has at least one case");
+            Val::Variant(case.name.to_string(), case.ty.map(|ty| Box::new(default_val(&ty))))
+        }
+        Type::Enum(enum_ty) => Val::Enum(enum_ty.names().next().unwrap_or_default().to_string()),
+        Type::Option(_) => Val::Option(None),
+        Type::Result(result) => Val::Result(Ok(result.ok().map(|ty| Box::new(default_val(&ty))))),
+        Type::Flags(_) => Val::Flags(Vec::new()),
+        // Resources cannot be fabricated; the call will trap and be reported
+        Type::Own(_) | Type::Borrow(_) => Val::Bool(false),
+    }
+}
+
+/// Short human-readable description of a returned value
+fn describe(value: &Val) -> String {
+    match value {
+        Val::String(s) => format!("string ({} bytes)", s.len()),
+        Val::List(items) => format!("list ({} items)", items.len()),
+        Val::Record(fields) => format!("record ({} fields)", fields.len()),
+        Val::Result(Ok(_)) => "ok".to_string(),
+        other => format!("{:?}", other),
+    }
+}
+
+fn step(phase: &str, function: Option<String>, passed: bool, detail: String, start: Instant) -> SelfTestStep {
+    SelfTestStep {
+        phase: phase.to_string(),
+        function,
+        passed,
+        detail,
+        duration_ms: start.elapsed().as_secs_f32() * 1000.0,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Component importing a host interface and WASI-NN, whose `initialize`
+    /// fails unless its threshold is set
+    const PROBE_COMPONENT: &str = r#"
+(component
+  (import "adas:test/host" (instance
+    (export "now" (func (result u64)))
+  ))
+  (import "wasi:nn/graph@0.2.0-rc-2024-10-28" (instance
+    (export "load-by-name" (func (param "name" u32) (result u32)))
+  ))
+  (core module $m
+    (func (export "initialize") (param f32 i32) (result i32)
+      (if (result i32) (f32.gt (local.get 0) (f32.const 0))
+        (then (i32.const 0))
+        (else (i32.const 1))))
+    (func (export "run-diagnostic") (result i32) (i32.const 1))
+  )
+  (core instance $i (instantiate $m))
+  (type $mode-def (enum "fast" "slow"))
+  (export $mode "mode" (type $mode-def))
+  (type $config-def (record (field "threshold" f32) (field "mode" $mode)))
+  (export $config "config" (type $config-def))
+  (func (export "initialize") (param "cfg" $config) (result (result))
+    (canon lift (core func $i "initialize")))
+  (func (export "run-diagnostic") (result u32)
+    (canon lift (core func $i "run-diagnostic")))
+)
+"#;
+
+    fn write_probe(dir: &Path) -> PathBuf {
+        let path = dir.join("probe.wat");
+        std::fs::write(&path, PROBE_COMPONENT).unwrap();
+        path
+    }
+
+    fn config_type(engine: &Engine, path: &Path) -> Type {
+        let component = Component::from_file(engine, path).unwrap();
+        component
+            .component_type()
+            .exports(engine)
+            .find_map(|(name, item)| match item {
+                ComponentItem::ComponentFunc(func) if name == "initialize" => func.params().next(),
+                _ => None,
+            })
+            .unwrap()
+    }
+
+    fn test_result(passed: bool, name: &str) -> Val {
+        Val::Record(vec![
+            ("name".to_string(), Val::String(name.to_string())),
+            ("passed".to_string(), Val::Bool(passed)),
+            ("message".to_string(), Val::String("sensor offline".to_string())),
+            ("duration-ms".to_string(), Val::Float32(0.5)),
+        ])
+    }
+
+    #[test]
+    fn test_builtin_defaults_parse() {
+        let defaults = DefaultArguments::builtin().unwrap();
+        let arguments = defaults.get(Some("adas:object-detection/detection-engine@0.2.0"), "initialize").unwrap();
+        assert_eq!(arguments.len(), 1);
+        assert_eq!(arguments[0]["confidence-threshold"], 0.5);
+        assert!(defaults.get(Some("adas:object-detection/detection-engine@0.2.0"), "start").is_none());
+    }
+
+    #[tokio::test]
+    async fn test_probe_runs_on_default_configuration() {
+        let engine = crate::create_engine().unwrap();
+        let dir = tempfile::tempdir().unwrap();
+        let path = write_probe(dir.path());
+        let defaults = DefaultArguments::parse(r#"{"initialize": [{"threshold": 0.5, "mode": "slow"}]}"#).unwrap();
+
+        let result = test_component(&engine, &path, &defaults).await;
+        let phase = |name: &str| result.steps.iter().find(|s| s.phase == name).unwrap();
+
+        // The unknown host interface is stubbed instead of failing instantiation
+        assert!(phase("instantiate").passed);
+        assert!(phase("initialize").passed, "{}", phase("initialize").detail);
+        assert!(phase("diagnostic").passed);
+        if WASI_NN_AVAILABLE {
+            assert!(result.passed);
+        } else {
+            assert!(phase("wasi-nn").detail.contains("wasi:nn/graph"));
+            assert!(!result.passed);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_zero_configuration_is_rejected() {
+        let engine = crate::create_engine().unwrap();
+        let dir = tempfile::tempdir().unwrap();
+        let path = write_probe(dir.path());
+        let defaults = DefaultArguments::parse("{}").unwrap();
+
+        let result = test_component(&engine, &path, &defaults).await;
+        let initialize = result.steps.iter().find(|s| s.phase == "initialize").unwrap();
+        assert!(!initialize.passed);
+        assert!(initialize.detail.starts_with("Returned error"));
+    }
+
+    #[test]
+    fn test_val_from_json() {
+        let engine = crate::create_engine().unwrap();
+        let dir = tempfile::tempdir().unwrap();
+        let ty = config_type(&engine, &write_probe(dir.path()));
+
+        let value = val_from_json(&ty, &serde_json::json!({"threshold": 0.25, "mode": "fast"})).unwrap();
+        assert_eq!(
+            value,
+            Val::Record(vec![
+                ("threshold".to_string(), Val::Float32(0.25)),
+                ("mode".to_string(), Val::Enum("fast".to_string())),
+            ])
+        );
+
+        let missing = val_from_json(&ty, &serde_json::json!({"threshold": 0.25})).unwrap_err();
+        assert!(missing.contains("missing record field 'mode'"));
+        let unknown = serde_json::json!({"threshold": 0.25, "mode": "fast", "gain": 2});
+        assert!(val_from_json(&ty, &unknown).unwrap_err().contains("no field 'gain'"));
+        let bad_case = val_from_json(&ty, &serde_json::json!({"threshold": 0.25, "mode": "turbo"})).unwrap_err();
+        assert!(bad_case.starts_with("mode:"));
+    }
+
+    #[test]
+    fn test_output_checks() {
+        assert!(check_output("start", &Val::Result(Ok(None))).is_ok());
+        assert!(check_output("start", &Val::Result(Err(Some(Box::new(Val::String("busy".into())))))).is_err());
+        assert!(check_output("synthetic-input", &Val::String(String::new())).is_err());
+        assert!(check_output("synthetic-input", &Val::String("Frame processed".into())).is_ok());
+
+        let nan = Val::Result(Ok(Some(Box::new(Val::Record(vec![("speed".to_string(), Val::Float32(f32::NAN))])))));
+        assert_eq!(check_output("synthetic-input", &nan).unwrap_err(), "Non-finite number at result.speed");
+
+        let passing = Val::List(vec![test_result(true, "model-loaded"), test_result(true, "latency")]);
+        assert!(check_output("diagnostic", &passing).is_ok());
+        let failing = Val::List(vec![test_result(true, "model-loaded"), test_result(false, "camera-link")]);
+        assert_eq!(
+            check_output("diagnostic", &failing).unwrap_err(),
+            "Diagnostic check 'camera-link' failed: sensor offline"
+        );
+        assert!(check_output("diagnostic", &Val::List(Vec::new())).is_err());
+    }
+}